@@ -0,0 +1,363 @@
+//! Standard scenes used to measure the performances of the engine.
+//!
+//! They are shared by the benchmarks and the `bench_scenes` example, which displays them.
+
+use std::sync::Arc;
+use std::num::FloatMath;
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use na::{Pnt3, Vec3, Iso3, Translation, Transform, Rotate};
+use na;
+use ncollide::shape::{Shape, Plane, Cuboid, Ball, Ball3, Mesh, Mesh3};
+use ncollide::geometry::Contact;
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::detection::ContactGenerator;
+use nphysics::detection::joint::{Anchor, BallInSocket};
+
+/// A scene to simulate.
+pub struct Scene {
+    /// The name of the scene.
+    pub name:         &'static str,
+    /// Builds the world of the scene.
+    pub build:        fn() -> World,
+    /// The number of steps to perform before the scene is in the state to measure.
+    pub settle_steps: uint,
+    /// Called before each step, e.g., to move the kinematic bodies.
+    pub update:       Option<fn(&mut World)>
+}
+
+/// Every standard scene.
+pub fn scenes() -> Vec<Scene> {
+    vec!(
+        Scene { name: "box_pile",         build: box_pile,         settle_steps: 300, update: None },
+        Scene { name: "ball_rain",        build: ball_rain,        settle_steps: 0,   update: None },
+        Scene { name: "mesh_and_boxes",   build: mesh_and_boxes,   settle_steps: 60,  update: None },
+        Scene { name: "joint_chain",      build: joint_chain,      settle_steps: 0,   update: None },
+        Scene { name: "pyramid",          build: pyramid,          settle_steps: 60,  update: None },
+        Scene { name: "platforms",        build: platforms,        settle_steps: 0,   update: Some(slide_platforms) },
+        Scene { name: "platforms_cached", build: platforms_cached, settle_steps: 0,   update: Some(slide_platforms) }
+    )
+}
+
+/// The scene named `name`, if any.
+pub fn find(name: &str) -> Option<Scene> {
+    scenes().into_iter().find(|s| s.name == name)
+}
+
+fn new_world() -> World {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    world
+}
+
+/// 1000 boxes stacked in 10 by 10 columns on a plane, left to settle and fall asleep.
+pub fn box_pile() -> World {
+    let mut world = new_world();
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    let rad   = 0.5;
+    let shift = rad * 2.0 + 0.1;
+
+    for i in range(0u, 10) {
+        for j in range(0u, 10) {
+            for k in range(0u, 10) {
+                let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(rad, rad, rad)), 1.0, 0.3, 0.6);
+
+                rb.append_translation(&Vec3::new(i as f32 * shift, rad + j as f32 * shift, k as f32 * shift));
+
+                world.add_body(rb);
+            }
+        }
+    }
+
+    world
+}
+
+/// 1500 balls falling on a plane, and kept awake.
+pub fn ball_rain() -> World {
+    let mut world = new_world();
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    let rad   = 0.5;
+    let shift = rad * 2.0 + 0.5;
+
+    for i in range(0u, 15) {
+        for j in range(0u, 10) {
+            for k in range(0u, 10) {
+                let mut rb = RigidBody::new_dynamic(Ball::new(rad), 1.0, 0.3, 0.6);
+
+                // Every other layer is shifted so that the balls do not fall on top of each other.
+                let offset = if j % 2 == 0 { 0.0 } else { rad };
+
+                rb.append_translation(&Vec3::new(i as f32 * shift + offset,
+                                                 5.0 + j as f32 * shift,
+                                                 k as f32 * shift + offset));
+                rb.set_deactivation_threshold(None);
+
+                world.add_body(rb);
+            }
+        }
+    }
+
+    world
+}
+
+// The height of the wavy mesh at `(x, z)`.
+fn wavy_height(x: f32, z: f32) -> f32 {
+    (x * 0.3).sin() * (z * 0.3).cos()
+}
+
+// A wavy mesh of 20,000 triangles, 50 wide, centered on the origin.
+fn wavy_mesh() -> Mesh3<f32> {
+    let n    = 100u;
+    let size = 50.0f32;
+    let step = size / (n as f32 - 1.0);
+
+    let mut vertices = Vec::new();
+    let mut indices  = Vec::new();
+
+    for i in range(0u, n) {
+        for j in range(0u, n) {
+            let x = i as f32 * step - size / 2.0;
+            let z = j as f32 * step - size / 2.0;
+
+            vertices.push(Pnt3::new(x, wavy_height(x, z), z));
+        }
+    }
+
+    for i in range(0u, n - 1) {
+        for j in range(0u, n - 1) {
+            let a = i * n + j;
+
+            indices.push_all(&[ a, a + 1, a + n, a + 1, a + n + 1, a + n ]);
+        }
+    }
+
+    Mesh::new(Arc::new(vertices), Arc::new(indices), None, None)
+}
+
+/// 50 boxes falling on a wavy static mesh of 20,000 triangles.
+pub fn mesh_and_boxes() -> World {
+    let mut world = new_world();
+
+    world.add_body(RigidBody::new_static(wavy_mesh(), 0.3, 0.6));
+
+    for i in range(0u, 5) {
+        for j in range(0u, 10) {
+            let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+
+            rb.append_translation(&Vec3::new(i as f32 * 3.0 - 6.0, 5.0, j as f32 * 3.0 - 13.5));
+
+            world.add_body(rb);
+        }
+    }
+
+    world
+}
+
+/// A chain of 200 balls linked by ball-in-socket joints, attached to the ground by one end and
+/// released horizontally.
+pub fn joint_chain() -> World {
+    let mut world = new_world();
+
+    let rad    = 0.2;
+    let length = rad * 2.5;
+    let mut prev: Option<RigidBodyHandle> = None;
+
+    for i in range(0u, 200) {
+        let mut rb = RigidBody::new_dynamic(Ball::new(rad), 1.0, 0.3, 0.6);
+
+        rb.append_translation(&Vec3::new(i as f32 * length, 50.0, 0.0));
+
+        let body = world.add_body(rb);
+
+        let anchor1 = match prev {
+            Some(ref p) => Anchor::new(Some(p.clone()), Pnt3::new(length / 2.0, 0.0, 0.0)),
+            None        => Anchor::new(None, Pnt3::new(-length / 2.0, 50.0, 0.0))
+        };
+
+        world.add_ball_in_socket(BallInSocket::new(anchor1, Anchor::new(Some(body.clone()), Pnt3::new(-length / 2.0, 0.0, 0.0))));
+
+        prev = Some(body);
+    }
+
+    world
+}
+
+/// A pyramid of 210 boxes, 20 boxes wide at its base, standing on a plane.
+pub fn pyramid() -> World {
+    let mut world = new_world();
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    let num     = 20u;
+    let rad     = 0.5;
+    let shift   = rad * 2.0;
+    let centerx = shift * (num as f32) / 2.0;
+    let centery = shift / 2.0 + 0.04;
+
+    for i in range(0u, num) {
+        for j in range(i, num) {
+            let x = (i as f32 * shift / 2.0) + (j - i) as f32 * shift - centerx;
+            let y = i as f32 * shift + centery;
+
+            let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(rad - 0.04, rad - 0.04, rad - 0.04)), 1.0, 0.3, 0.6);
+
+            rb.append_translation(&Vec3::new(x, y, 0.0));
+
+            world.add_body(rb);
+        }
+    }
+
+    world
+}
+
+/// 100 static balls, standing for kinematic platforms, slid slowly over the wavy mesh of
+/// `mesh_and_boxes`.
+///
+/// The default narrow phase cannot reuse the contacts of a pair, so those of the balls and the
+/// mesh are computed by a contact generator.
+pub fn platforms() -> World {
+    platforms_over_mesh(false)
+}
+
+/// The `platforms` scene with the contact caching enabled.
+pub fn platforms_cached() -> World {
+    platforms_over_mesh(true)
+}
+
+fn platforms_over_mesh(caching: bool) -> World {
+    let mut world = new_world();
+
+    world.register_contact_generator(TypeId::of::<Mesh3<f32>>(), TypeId::of::<Ball3<f32>>(), box MeshBallGenerator);
+
+    if caching {
+        world.set_contact_caching(Some((0.01, 0.01)));
+    }
+
+    world.add_body(RigidBody::new_static(wavy_mesh(), 0.3, 0.6));
+
+    for i in range(0u, 10) {
+        for j in range(0u, 10) {
+            let x = i as f32 * 4.0 - 18.0;
+            let z = j as f32 * 4.0 - 18.0;
+
+            let mut rb = RigidBody::new_static(Ball::new(0.5f32), 0.3, 0.6);
+
+            // Slightly sunk into the mesh so that each platform has contacts.
+            rb.append_translation(&Vec3::new(x, wavy_height(x, z) + 0.45, z));
+
+            world.add_body(rb);
+        }
+    }
+
+    world
+}
+
+// Moves the platforms of the `platforms` scenes by 1 millimeter along `x`.
+fn slide_platforms(world: &mut World) {
+    for b in world.bodies() {
+        let mut rb = b.borrow_mut();
+
+        if rb.shape_ref().is::<Ball3<f32>>() {
+            rb.append_translation(&Vec3::new(0.001, 0.0, 0.0));
+        }
+    }
+}
+
+// Computes the contacts between a mesh and a ball from the point of each triangle closest to the
+// center of the ball.
+struct MeshBallGenerator;
+
+impl ContactGenerator for MeshBallGenerator {
+    fn generate(&mut self,
+                m1:         &Iso3<f32>,
+                s1:         &Shape<f32, Pnt3<f32>, Vec3<f32>, Iso3<f32>>,
+                m2:         &Iso3<f32>,
+                s2:         &Shape<f32, Pnt3<f32>, Vec3<f32>, Iso3<f32>>,
+                prediction: f32,
+                out:        &mut Vec<Contact<f32, Pnt3<f32>, Vec3<f32>>>) {
+        let mesh     = s1.downcast_ref::<Mesh3<f32>>().unwrap();
+        let ball     = s2.downcast_ref::<Ball3<f32>>().unwrap();
+        let vertices = mesh.vertices().as_slice();
+        let world2   = na::orig::<Pnt3<f32>>() + m2.translation();
+        // The center of the ball in the local frame of the mesh.
+        let center   = m1.inv_transform(&world2);
+        let max_dist = ball.radius() + prediction;
+
+        for t in mesh.indices().as_slice().chunks(3) {
+            let (a, b, c) = (vertices[t[0]], vertices[t[1]], vertices[t[2]]);
+
+            // Most triangles are far away.
+            if a.x.min(b.x).min(c.x) > center.x + max_dist || a.x.max(b.x).max(c.x) < center.x - max_dist ||
+               a.z.min(b.z).min(c.z) > center.z + max_dist || a.z.max(b.z).max(c.z) < center.z - max_dist {
+                continue;
+            }
+
+            let closest = closest_point_on_triangle(&center, &a, &b, &c);
+            let dist    = na::norm(&(center - closest));
+
+            if dist < max_dist && dist > 0.0 {
+                let normal = m1.rotate(&((center - closest) / dist));
+
+                out.push(Contact::new(m1.transform(&closest), world2 + normal * -ball.radius(), normal, ball.radius() - dist))
+            }
+        }
+    }
+}
+
+// The point of the triangle `abc` closest to `p`.
+fn closest_point_on_triangle(p: &Pnt3<f32>, a: &Pnt3<f32>, b: &Pnt3<f32>, c: &Pnt3<f32>) -> Pnt3<f32> {
+    let ab = *b - *a;
+    let ac = *c - *a;
+    let ap = *p - *a;
+    let d1 = na::dot(&ab, &ap);
+    let d2 = na::dot(&ac, &ap);
+
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return *a
+    }
+
+    let bp = *p - *b;
+    let d3 = na::dot(&ab, &bp);
+    let d4 = na::dot(&ac, &bp);
+
+    if d3 >= 0.0 && d4 <= d3 {
+        return *b
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return *a + ab * (d1 / (d1 - d3))
+    }
+
+    let cp = *p - *c;
+    let d5 = na::dot(&ab, &cp);
+    let d6 = na::dot(&ac, &cp);
+
+    if d6 >= 0.0 && d5 <= d6 {
+        return *c
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return *a + ac * (d2 / (d2 - d6))
+    }
+
+    let va = d3 * d6 - d5 * d4;
+
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return *b + (*c - *b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)))
+    }
+
+    // The projection of `p` lies inside the triangle.
+    let denom = 1.0 / (va + vb + vc);
+
+    *a + ab * (vb * denom) + ac * (vc * denom)
+}
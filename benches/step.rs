@@ -0,0 +1,102 @@
+/*!
+ * Measures the duration of the steps of the standard scenes.
+ *
+ * Each scene is built, stepped until it reaches the state to measure, then stepped a fixed number
+ * of times. The mean, median, and extreme durations of those steps are reported in milliseconds,
+ * together with the mean duration of each of their phases. Run with:
+ *
+ *     cargo bench --features "3df32 step_stats" [scene names]
+ *
+ * Without the `step_stats` feature, only the total duration of the steps is measured. When both
+ * `platforms` scenes are run, the speedup brought by the contact caching is reported as well.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate time;
+
+use std::os;
+use nphysics::world::{World, StepStatistics};
+use scenes::Scene;
+
+mod scenes;
+
+static NUM_STEPS: uint = 200;
+
+fn main() {
+    let args: Vec<String> = os::args().into_iter().skip(1).filter(|a| !a.as_slice().starts_with("-")).collect();
+
+    println!("{:<16} {:>8} {:>8} {:>8} {:>8} | {:>8} {:>8} {:>8} {:>8} {:>8} {:>9} {:>7}",
+             "scene", "mean", "median", "min", "max",
+             "integr.", "coll.", "activ.", "contacts", "solver", "#contacts", "#cached");
+
+    let mut means = Vec::new();
+
+    for scene in scenes::scenes().into_iter() {
+        if args.is_empty() || args.iter().any(|a| a.as_slice() == scene.name) {
+            means.push((scene.name, run(&scene)))
+        }
+    }
+
+    let mean_of = |name: &str| means.iter().find(|&&(n, _)| n == name).map(|&(_, m)| m);
+
+    match (mean_of("platforms"), mean_of("platforms_cached")) {
+        (Some(plain), Some(cached)) => println!("contact caching speedup: {:.2}", plain / cached),
+        _ => { }
+    }
+}
+
+fn step(scene: &Scene, world: &mut World) {
+    match scene.update {
+        Some(update) => update(world),
+        None         => { }
+    }
+
+    world.step(0.016);
+}
+
+// Gives the mean duration of the measured steps.
+fn run(scene: &Scene) -> f64 {
+    let mut world: World = (scene.build)();
+
+    for _ in range(0, scene.settle_steps) {
+        step(scene, &mut world);
+    }
+
+    let mut durations = Vec::with_capacity(NUM_STEPS);
+    let mut phases    = StepStatistics::new();
+
+    for _ in range(0, NUM_STEPS) {
+        let before = time::precise_time_s();
+
+        step(scene, &mut world);
+
+        durations.push((time::precise_time_s() - before) * 1000.0);
+
+        let stats = world.step_statistics();
+
+        phases.integration         = phases.integration + stats.integration;
+        phases.collision_detection = phases.collision_detection + stats.collision_detection;
+        phases.activation          = phases.activation + stats.activation;
+        phases.contacts            = phases.contacts + stats.contacts;
+        phases.resolution          = phases.resolution + stats.resolution;
+        phases.num_contacts        = phases.num_contacts + stats.num_contacts;
+        phases.num_cached_pairs    = phases.num_cached_pairs + stats.num_cached_pairs;
+    }
+
+    durations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let n    = NUM_STEPS as f64;
+    let mean = durations.iter().fold(0.0, |s, d| s + *d) / n;
+    // The phases are in seconds.
+    let ms   = 1000.0 / n;
+
+    println!("{:<16} {:>8.3} {:>8.3} {:>8.3} {:>8.3} | {:>8.3} {:>8.3} {:>8.3} {:>8.3} {:>8.3} {:>9} {:>7}",
+             scene.name, mean, durations[NUM_STEPS / 2], durations[0], durations[NUM_STEPS - 1],
+             phases.integration * ms, phases.collision_detection * ms, phases.activation * ms,
+             phases.contacts * ms, phases.resolution * ms, phases.num_contacts / NUM_STEPS,
+             phases.num_cached_pairs / NUM_STEPS);
+
+    mean
+}
@@ -0,0 +1,78 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed2d;
+
+use na::{Pnt2, Vec2, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::detection::joint::{Anchor, Distance, DistanceMode};
+use nphysics_testbed2d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec2::new(0.0, 9.81));
+
+    /*
+     * A plane for the ground
+     */
+    let ground_geom = Plane::new(Vec2::new(0.0, -1.0));
+
+    world.add_body(RigidBody::new_static(ground_geom, 0.3, 0.6));
+
+    /*
+     * The bridge: planks attached to each other, and to the ground at both ends, by ropes.
+     */
+    let num_planks = 20u;
+    let half_width = 0.9f32;
+    let gap        = 0.2f32;
+    let height     = -15.0f32;
+    let start      = -(num_planks as f32 * (half_width * 2.0 + gap) + gap) / 2.0;
+
+    let mut prev: Option<Anchor<Pnt2<f32>>> = Some(Anchor::new(None, Pnt2::new(start, height)));
+
+    for i in range(0u, num_planks) {
+        let x = start + gap + half_width + i as f32 * (half_width * 2.0 + gap);
+
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec2::new(half_width, 0.2)), 1.0, 0.3, 0.6);
+        rb.append_translation(&Vec2::new(x, height));
+
+        let plank = world.add_body(rb);
+
+        add_rope(&mut world, prev.take().unwrap(), Anchor::new(Some(plank.clone()), Pnt2::new(-half_width, 0.0)), gap);
+
+        prev = Some(Anchor::new(Some(plank), Pnt2::new(half_width, 0.0)));
+    }
+
+    let end = -start;
+    add_rope(&mut world, prev.take().unwrap(), Anchor::new(None, Pnt2::new(end, height)), gap);
+
+    /*
+     * Boxes dropped on the bridge.
+     */
+    for i in range(0u, 10) {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec2::new(0.5f32, 0.5)), 1.0, 0.3, 0.6);
+        rb.append_translation(&Vec2::new(start + 4.0 + i as f32 * 3.5, height - 10.0 - (i % 3) as f32 * 2.0));
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Run the simulation.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.run();
+}
+
+fn add_rope(world: &mut World, anchor1: Anchor<Pnt2<f32>>, anchor2: Anchor<Pnt2<f32>>, length: f32) {
+    let mut rope = Distance::new(anchor1, anchor2, length);
+
+    rope.set_mode(DistanceMode::MaxDistance);
+
+    world.add_distance(rope);
+}
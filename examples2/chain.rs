@@ -0,0 +1,66 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed2d;
+
+use na::{Pnt2, Vec2, Translation};
+use ncollide::shape::{Plane, Cuboid, Ball};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::detection::joint::{Anchor, BallInSocket};
+use nphysics_testbed2d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec2::new(0.0, 9.81));
+
+    /*
+     * A plane for the ground
+     */
+    world.add_body(RigidBody::new_static(Plane::new(Vec2::new(0.0, -1.0)), 0.3, 0.6));
+
+    /*
+     * Chains of links hanging horizontally from the ground by their first link, so that they
+     * swing down.
+     */
+    let num_links   = 15u;
+    let half_length = 0.5f32;
+    let height      = -20.0f32;
+
+    for c in range(0u, 3) {
+        let start = -15.0 + c as f32 * 12.0;
+
+        let mut prev = Anchor::new(None, Pnt2::new(start, height));
+
+        for i in range(0u, num_links) {
+            let x = start + half_length + i as f32 * half_length * 2.0;
+
+            let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec2::new(half_length, 0.1)), 1.0, 0.3, 0.6);
+            rb.append_translation(&Vec2::new(x, height));
+
+            let link = world.add_body(rb);
+
+            world.add_ball_in_socket(BallInSocket::new(prev, Anchor::new(Some(link.clone()), Pnt2::new(-half_length, 0.0))));
+
+            prev = Anchor::new(Some(link), Pnt2::new(half_length, 0.0));
+        }
+    }
+
+    /*
+     * A heavy ball dropped on the swinging chains.
+     */
+    let mut rb = RigidBody::new_dynamic(Ball::new(1.0f32), 5.0, 0.3, 0.6);
+    rb.append_translation(&Vec2::new(5.0, height - 10.0));
+
+    world.add_body(rb);
+
+    /*
+     * Run the simulation.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.run();
+}
@@ -1,61 +1,112 @@
+use std::num::Float;
 use rsfml::graphics;
-use rsfml::graphics::{Vertex, VertexArray, Color, RenderTarget};
+use rsfml::graphics::{Vertex, VertexArray, Color, RenderTarget, CircleShape};
 use rsfml::system::vector2::Vector2f;
 use na::Translate;
 use na::Pnt2;
 use na;
+use na::Pnt3;
 use nphysics::world::World;
+use nphysics::object::RigidBody;
 use nphysics::detection::constraint::Constraint;
 use nphysics::detection::joint::Joint;
 
 pub static DRAW_SCALE: f32 = 20.0;
 
-pub fn draw_colls(window:  &mut graphics::RenderWindow,
-                  physics: &mut World) {
-
-    let mut collisions = Vec::new();
-
-    physics.interferences(&mut collisions);
-
-    for c in collisions.iter() {
-        match *c {
-            Constraint::RBRB(_, _, c) => {
-                draw_line(
-                    window,
-                    &c.world1,
-                    &c.world2,
-                    &Color::new_RGB(255, 255, 255));
-
-                let center = na::center(&c.world1, &c.world2);
-                draw_line(
-                    window,
-                    &center,
-                    &(center + c.normal * c.depth),
-                    &Color::new_RGB(255, 0, 0));
-
-                draw_line(
-                    window,
-                    &center,
-                    &(center + c.normal),
-                    &Color::new_RGB(0, 0, 255));
-            },
-            Constraint::BallInSocket(ref bis) => {
-                draw_line(
-                    window,
-                    &bis.borrow().anchor1_pos(),
-                    &bis.borrow().anchor2_pos(),
-                    &Color::new_RGB(255, 0, 0)
-                );
+// The penetration depth displayed with the warmest color.
+static MAX_DISPLAYED_DEPTH: f32 = 0.2;
+// The radius of the circles drawn at the contact points, in pixels.
+static CONTACT_RADIUS: f32 = 3.0;
+
+/// Draws the contacts solved during the last step, and the joint anchors.
+///
+/// Each contact point is a circle with a segment along its normal. They are colored by
+/// penetration depth, or by the normal impulse they received relatively to the largest one if
+/// `by_impulse` is `true`. This shows which contacts carry the load.
+pub fn draw_contacts(window:     &mut graphics::RenderWindow,
+                     physics:    &World,
+                     by_impulse: bool) {
+    let mut circle = CircleShape::new().unwrap();
+
+    circle.set_radius(CONTACT_RADIUS);
+    circle.set_origin(&Vector2f::new(CONTACT_RADIUS, CONTACT_RADIUS));
+
+    let max_impulse = physics.contacts().iter().fold(0.0f32, |m, c| m.max(c.normal_impulse));
+
+    for info in physics.contacts().iter() {
+        let c      = &info.contact;
+        let center = na::center(&c.world1, &c.world2);
+        let t      = if by_impulse {
+            if max_impulse > 0.0 { info.normal_impulse / max_impulse } else { 0.0 }
+        }
+        else {
+            c.depth / MAX_DISPLAYED_DEPTH
+        };
+        let color  = ramp(t);
+
+        circle.set_fill_color(&color);
+        circle.set_position(&Vector2f::new(center.x * DRAW_SCALE, center.y * DRAW_SCALE));
+        window.draw(&circle);
+
+        draw_line(window, &center, &(center + c.normal * 0.5f32), &color);
+    }
+
+    for joint in physics.joints() {
+        let (p1, p2) = match *joint {
+            Constraint::BallInSocket(ref bis) => (bis.borrow().anchor1_pos(), bis.borrow().anchor2_pos()),
+            Constraint::Fixed(ref f) => {
+                (na::translation(&f.borrow().anchor1_pos()).translate(&na::orig()),
+                 na::translation(&f.borrow().anchor2_pos()).translate(&na::orig()))
             },
-            Constraint::Fixed(ref bis) => {
-                draw_line(
-                    window,
-                    &na::translation(&bis.borrow().anchor1_pos()).translate(&na::orig()),
-                    &na::translation(&bis.borrow().anchor2_pos()).translate(&na::orig()),
-                    &Color::new_RGB(255, 0, 0)
-                );
-            }
+            Constraint::Distance(ref d) => (d.borrow().anchor1_pos(), d.borrow().anchor2_pos()),
+            Constraint::Grab(ref g) => (g.borrow().anchor1_pos(), g.borrow().anchor2_pos()),
+            Constraint::RBRB(_, _, _) => continue
+        };
+
+        circle.set_fill_color(&Color::new_RGB(255, 255, 0));
+
+        for p in [ p1, p2 ].iter() {
+            circle.set_position(&Vector2f::new(p.x * DRAW_SCALE, p.y * DRAW_SCALE));
+            window.draw(&circle);
         }
+
+        draw_line(window, &p1, &p2, &Color::new_RGB(255, 255, 0));
+    }
+}
+
+// The opacity of the fill color of the sensors.
+static SENSOR_ALPHA: u8 = 64;
+// The thickness of the outline of the sensors, in pixels.
+static SENSOR_OUTLINE: f32 = 2.0;
+
+/// The fill color, outline color, and outline thickness of a shape of `body`.
+///
+/// Sleeping bodies are darkened. Sensors are semi-transparent and outlined with their opaque color.
+/// Bodies which last step was altered by a safety mechanism of the world, see
+/// `RigidBody::last_step_flags`, are tinted in yellow.
+pub fn body_colors(color: &Pnt3<u8>, body: &RigidBody) -> (Color, Color, f32) {
+    let color = if body.last_step_flags().is_empty() { *color } else { Pnt3::new(255, 255, 0) };
+    let c     = if body.is_active() { color } else { Pnt3::new(color.x / 4, color.y / 4, color.z / 4) };
+
+    if body.is_sensor() {
+        (Color::new_RGBA(c.x, c.y, c.z, SENSOR_ALPHA), Color::new_RGB(c.x, c.y, c.z), SENSOR_OUTLINE)
+    }
+    else {
+        (Color::new_RGB(c.x, c.y, c.z), Color::new_RGB(c.x, c.y, c.z), 0.0)
+    }
+}
+
+// Maps `t` in `[0, 1]` from blue to green to red.
+fn ramp(t: f32) -> Color {
+    let t = t.max(0.0).min(1.0);
+
+    if t < 0.5 {
+        let a = t * 2.0;
+        Color::new_RGB(0, (a * 255.0) as u8, ((1.0 - a) * 255.0) as u8)
+    }
+    else {
+        let a = (t - 0.5) * 2.0;
+        Color::new_RGB((a * 255.0) as u8, ((1.0 - a) * 255.0) as u8, 0)
     }
 }
 
@@ -174,6 +174,21 @@ impl<'a> GraphicsManager<'a> {
     }
 
 
+    pub fn remove(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        let key = body.deref() as *const RefCell<RigidBody> as uint;
+
+        self.rb2sn.remove(&key);
+    }
+
+    /// Updates the scene nodes of a body scaled with `World::scale_body`, or whose shape was
+    /// replaced.
+    ///
+    /// The nodes are simply rebuilt since they are cheap to create. The body keeps its color.
+    pub fn rescale_body(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        self.remove(body);
+        self.add(body.clone());
+    }
+
     pub fn clear(&mut self) {
         self.rb2sn.clear();
     }
@@ -9,7 +9,7 @@ extern crate ncollide;
 extern crate nphysics;
 
 
-pub use testbed::Testbed;
+pub use testbed::{Testbed, EventHandler};
 
 mod testbed;
 mod engine;
@@ -7,6 +7,7 @@ use rsfml::system::vector2;
 use na::{Pnt3, Iso2};
 use na;
 use nphysics::object::RigidBody;
+use draw_helper;
 use draw_helper::DRAW_SCALE;
 
 pub struct Box<'a> {
@@ -55,14 +56,11 @@ impl<'a> Box<'a> {
         });
         self.gfx.set_rotation(rot.x.to_degrees() as f32);
 
-        if body.is_active() {
-            self.gfx.set_fill_color(
-                &Color::new_RGB(self.color.x, self.color.y, self.color.z));
-        }
-        else {
-            self.gfx.set_fill_color(
-                &Color::new_RGB(self.color.x / 4, self.color.y / 4, self.color.z / 4));
-        }
+        let (fill, outline, thickness) = draw_helper::body_colors(&self.color, body.deref());
+
+        self.gfx.set_fill_color(&fill);
+        self.gfx.set_outline_color(&outline);
+        self.gfx.set_outline_thickness(thickness);
     }
 
     pub fn draw(&self, rw: &mut graphics::RenderWindow) {
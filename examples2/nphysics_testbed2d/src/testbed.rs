@@ -1,70 +1,73 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::os;
-use rsfml::graphics::{RenderWindow, RenderTarget, Font};
+use std::num::Float;
+use rsfml::graphics::{RenderWindow, RenderTarget, Font, Text};
 use rsfml::window::{ContextSettings, VideoMode, Close};
 use rsfml::window::event;
 use rsfml::window::{keyboard, mouse};
 use rsfml::graphics::Color;
 use rsfml::system::vector2::Vector2i;
-use na::{Pnt2, Pnt3, Iso2};
+use na::{Pnt2, Pnt3, Transform, Translation};
 use na;
-use nphysics::world::World;
+use nphysics::world::{World, WorldEvent, GroupHandle};
+use nphysics::io;
 use nphysics::object::RigidBody;
-use nphysics::detection::joint::{Fixed, Anchor};
+use nphysics::detection::joint::{Distance, Anchor};
 use camera::Camera;
 use fps::Fps;
 use engine::GraphicsManager;
 use draw_helper;
 
+// The fraction of the distance to the cursor left uncorrected by each step while dragging a body.
+static GRAB_SOFTNESS: f32 = 0.3;
+// The largest acceleration the cursor can give to a dragged body.
+static GRAB_MAX_ACCELERATION: f32 = 1000.0;
+// The relative growth of the energy of the world tolerated by the headless mode.
+static ENERGY_TOLERANCE: f32 = 0.1;
+
 fn usage(exe_name: &str) {
     println!("Usage: {} [OPTION] ", exe_name);
     println!("");
     println!("Options:");
     println!("    --help  - prints this help message and exits.");
     println!("    --pause - do not start the simulation right away.");
+    println!("    --scene FILE - replace the world by the scene described by the JSON file FILE.");
+    println!("    --timestep DT - set the timestep of the world to DT seconds.");
+    println!("    --headless STEPS - without any window, run STEPS steps and exit with an error if the");
+    println!("                       simulation diverged.");
     println!("");
     println!("The following keyboard commands are supported:");
     println!("    t     - pause/continue the simulation.");
     println!("    s     - pause then execute only one simulation step.");
-    println!("    space - display/hide contacts.");
+    println!("    space - display/hide contacts and joint anchors.");
+    println!("    i     - color the contacts by normal impulse instead of penetration depth.");
+    println!("");
+    println!("The bodies which last step was altered by the continuous collision detection, a velocity clamp");
+    println!("or a capped penetration correction are tinted in yellow until the next step.");
 }
 
 
-#[deriving(PartialEq)]
-enum RunMode {
-    Running,
-    Stop,
-    Step
+/// Trait implemented by the objects notified of the events emitted by the world.
+pub trait EventHandler {
+    /// Called with each event, once per frame, in the order they were emitted.
+    fn handle_event(&mut self, event: &WorldEvent);
 }
 
 pub struct Testbed<'a> {
-    world:    World,
-    window:   RenderWindow,
-    graphics: GraphicsManager<'a>
+    world:         World,
+    graphics:      GraphicsManager<'a>,
+    event_handler: Option<Box<EventHandler + 'static>>
 }
 
 impl<'a> Testbed<'a> {
     pub fn new_empty() -> Testbed<'a> {
-        let mode    = VideoMode::new_init(800, 600, 32);
-        let setting = ContextSettings {
-            depth_bits:         10,
-            stencil_bits:       10,
-            antialiasing_level: 2,
-            major_version:      0,
-            minor_version:      1
-        };
-        let window =
-            match RenderWindow::new(mode, "nphysics 2d demo", Close, &setting) {
-                Some(rwindow) => rwindow,
-                None          => panic!("Error on creating the sfml window.")
-            };
         let graphics = GraphicsManager::new();
 
         Testbed {
-            world:    World::new(),
-            window:   window,
-            graphics: graphics
+            world:         World::new(),
+            graphics:      graphics,
+            event_handler: None
         }
     }
 
@@ -77,12 +80,50 @@ impl<'a> Testbed<'a> {
     }
 
     pub fn set_world(&mut self, world: World) {
+        // A paused testbed stays paused when its world is replaced, e.g., by switching scenes.
+        let paused = self.world.is_paused() || world.is_paused();
+
         self.world = world;
+        self.world.set_paused(paused);
+        // The bodies moved by hand during a pause stay pickable.
+        self.world.set_refresh_when_paused(true);
         self.graphics.clear();
 
         for rb in self.world.bodies() {
             self.graphics.add(rb.clone());
         }
+
+        // The bodies already present have just been added.
+        self.world.drain_events(&mut Vec::new());
+    }
+
+    fn sync_graphics(&mut self) {
+        let mut events = Vec::new();
+
+        self.world.drain_events(&mut events);
+
+        for event in events.iter() {
+            match *event {
+                WorldEvent::BodyAdded(ref b) => {
+                    if self.graphics.body_to_scene_node(b).is_none() {
+                        self.graphics.add(b.clone())
+                    }
+                },
+                WorldEvent::BodyRemoved(ref b) => self.graphics.remove(b),
+                WorldEvent::BodyScaled(ref b, _) | WorldEvent::BodyShapeUpdated(ref b) => self.graphics.rescale_body(b),
+                _ => { }
+            }
+
+            match self.event_handler {
+                Some(ref mut handler) => handler.handle_event(event),
+                None                  => { }
+            }
+        }
+    }
+
+    /// Sets the object notified of the events emitted by the world.
+    pub fn set_event_handler<H: EventHandler + 'static>(&mut self, handler: H) {
+        self.event_handler = Some(box handler as Box<EventHandler + 'static>)
     }
 
     pub fn set_color(&mut self, body: &Rc<RefCell<RigidBody>>, color: Pnt3<f32>) {
@@ -95,50 +136,168 @@ impl<'a> Testbed<'a> {
         self.graphics.set_color(body, color);
     }
 
-    pub fn run(&mut self) {
-        let args        = os::args();
-        let mut running = RunMode::Running;
+    /// Sets the color of every body of `group`, including those already displayed.
+    pub fn set_group_color(&mut self, group: &GroupHandle, color: Pnt3<f32>) {
+        let bodies = self.world.group_bodies(group).to_vec();
 
-        if args.len() > 1 {
-            if args.len() > 2 || args[1].as_slice() != "--pause" {
-                usage(args[0].as_slice());
+        for b in bodies.iter() {
+            self.set_color(b, color);
+
+            if self.graphics.body_to_scene_node(b).is_some() {
+                self.graphics.remove(b);
+                self.graphics.add(b.clone());
+            }
+        }
+    }
+
+    pub fn load_scene(&mut self, path: &Path) {
+        match io::load_scene(path) {
+            Ok((world, bodies)) => {
+                for b in bodies.iter() {
+                    match b.color {
+                        Some(color) => self.set_color(&b.body, color),
+                        None        => { }
+                    }
+                }
+
+                self.set_world(world);
+            },
+            Err(e) => panic!("Unable to load the scene `{}`: {}", path.display(), e)
+        }
+    }
+
+    // Steps the world `num_steps` times without rendering it, and fails if a body got a non-finite
+    // position or if the energy of the world grew significantly.
+    fn run_headless(&mut self, num_steps: uint) {
+        let initial = energy(&self.world);
+        let bound   = initial + ENERGY_TOLERANCE * (initial.abs() + 1.0);
+        let mut max = initial;
+
+        // Nothing would be simulated if `--pause` was given.
+        self.world.set_paused(false);
+
+        for _ in range(0u, num_steps) {
+            self.world.step_fixed();
+            // Nothing is displayed: the events are only drained to not accumulate.
+            self.world.drain_events(&mut Vec::new());
+
+            let diverged = self.world.bodies().any(|b| {
+                let t = b.borrow().position().translation();
+                !t.x.is_finite() || !t.y.is_finite()
+            });
+
+            if diverged {
+                println!("Non-finite position after {} steps.", self.world.step_count());
                 os::set_exit_status(1);
                 return;
             }
+
+            max = max.max(energy(&self.world));
+        }
+
+        println!("{} steps, energy: {} initially, {} at most.", num_steps, initial, max);
+
+        if max > bound {
+            println!("The energy grew above {}.", bound);
+            os::set_exit_status(1);
+        }
+    }
+
+    pub fn run(&mut self) {
+        let args          = os::args();
+        let mut step_once = false;
+        let mut timestep  = None;
+        let mut headless  = None;
+
+        let mut i = 1;
+
+        while i < args.len() {
+            let arg = args[i].as_slice();
+
+            if arg == "--pause" {
+                self.world.set_paused(true);
+            }
+            else if arg == "--scene" && i + 1 < args.len() {
+                i = i + 1;
+                self.load_scene(&Path::new(args[i].as_slice()));
+            }
+            else if arg == "--timestep" && i + 1 < args.len() {
+                i = i + 1;
+                match from_str::<f32>(args[i].as_slice()) {
+                    Some(dt) if dt > 0.0 => timestep = Some(dt),
+                    _ => {
+                        println!("Invalid timestep: {}", args[i]);
+                        os::set_exit_status(1);
+                        return;
+                    }
+                }
+            }
+            else if arg == "--headless" && i + 1 < args.len() {
+                i = i + 1;
+                match from_str::<uint>(args[i].as_slice()) {
+                    Some(n) => headless = Some(n),
+                    None    => {
+                        println!("Invalid number of steps: {}", args[i]);
+                        os::set_exit_status(1);
+                        return;
+                    }
+                }
+            }
             else {
-                running = RunMode::Stop;
+                usage(args[0].as_slice());
+                os::set_exit_status(1);
+                return;
             }
+
+            i = i + 1;
+        }
+
+        // Applied last since `--scene` replaces the world.
+        match timestep {
+            Some(dt) => self.world.set_timestep(dt),
+            None     => { }
+        }
+
+        match headless {
+            Some(num_steps) => {
+                self.run_headless(num_steps);
+                return;
+            },
+            None => { }
         }
 
         let mut draw_colls = false;
+        let mut by_impulse = false;
 
 
         let mut camera = Camera::new();
+        // Only created now so that the headless mode does not need a display.
+        let mut window = create_window();
 
-        self.window.set_framerate_limit(60);
+        window.set_framerate_limit(60);
 
 
         let font_mem = include_bin!("Inconsolata.otf");
         let     fnt  = Font::new_from_memory(font_mem).unwrap();
         let mut fps  = Fps::new(&fnt);
+        let mut sim_time = Text::new().unwrap();
+        sim_time.set_font(&fnt);
+        sim_time.set_color(&Color::new_RGB(255, 255, 255));
         let mut grabbed_object: Option<Rc<RefCell<RigidBody>>> = None;
-        let mut grabbed_object_joint: Option<Rc<RefCell<Fixed>>> = None;
+        let mut grabbed_object_joint: Option<Rc<RefCell<Distance>>> = None;
 
-        while self.window.is_open() {
+        while window.is_open() {
             loop {
-                match self.window.poll_event() {
+                match window.poll_event() {
                     event::KeyPressed{code, ..} => {
                         match code {
-                            keyboard::Escape => self.window.close(),
-                            keyboard::S      => running = RunMode::Step,
+                            keyboard::Escape => window.close(),
+                            keyboard::S      => step_once = true,
                             keyboard::Space  => draw_colls = !draw_colls,
+                            keyboard::I      => by_impulse = !by_impulse,
                             keyboard::T      => {
-                                if running == RunMode::Stop {
-                                    running = RunMode::Running;
-                                }
-                                else {
-                                    running = RunMode::Stop;
-                                }
+                                let paused = self.world.is_paused();
+                                self.world.set_paused(!paused);
                             },
                             _                => { }
                         }
@@ -158,17 +317,21 @@ impl<'a> Testbed<'a> {
                                     Some(ref b) => {
                                         for node in self.graphics.body_to_scene_node(b).unwrap().iter_mut() {
                                             match grabbed_object_joint {
-                                                Some(ref j) => self.world.remove_fixed(j),
+                                                Some(ref j) => self.world.remove_distance(j),
                                                 None        => { }
                                             }
 
-                                            let _1: Iso2<f32> = na::one();
-                                            let attach2 = na::append_translation(&_1, mapped_point.as_vec());
-                                            let attach1 = na::inv(&na::transformation(b.borrow().position())).unwrap() * attach2;
-                                            let anchor1 = Anchor::new(Some(grabbed_object.as_ref().unwrap().clone()), attach1);
-                                            let anchor2 = Anchor::new(None, attach2);
-                                            let joint = Fixed::new(anchor1, anchor2);
-                                            grabbed_object_joint = Some(self.world.add_fixed(joint));
+                                            // The grabbed point is pulled toward the cursor by a
+                                            // soft joint so that the body does not tunnel through
+                                            // the others when the mouse moves quickly.
+                                            let attach1 = b.borrow().position().inv_transform(&mapped_point);
+                                            let anchor1 = Anchor::new(Some(b.clone()), attach1);
+                                            let anchor2 = Anchor::new(None, mapped_point);
+                                            let mass    = 1.0 / b.borrow().inv_mass();
+                                            let mut joint = Distance::new(anchor1, anchor2, 0.0);
+                                            joint.set_softness(GRAB_SOFTNESS);
+                                            joint.set_max_force(Some(mass * GRAB_MAX_ACCELERATION));
+                                            grabbed_object_joint = Some(self.world.add_distance(joint));
                                             node.select()
                                         }
                                     },
@@ -193,7 +356,7 @@ impl<'a> Testbed<'a> {
                                 }
 
                                 match grabbed_object_joint {
-                                    Some(ref j) => self.world.remove_fixed(j),
+                                    Some(ref j) => self.world.remove_distance(j),
                                     None => { }
                                 }
 
@@ -208,47 +371,100 @@ impl<'a> Testbed<'a> {
                     event::MouseMoved{x, y} => {
                         let mapped_coords = camera.map_pixel_to_coords(Vector2i::new(x, y));
                         let mapped_point = Pnt2::new(mapped_coords.x, mapped_coords.y);
-                        let _1: Iso2<f32> = na::one();
-                        let attach2 = na::append_translation(&_1, (mapped_point).as_vec());
                         match grabbed_object {
                             Some(_) => {
                                 let joint = grabbed_object_joint.as_ref().unwrap();
-                                joint.borrow_mut().set_local2(attach2);
+                                joint.borrow_mut().set_local2(mapped_point);
                             },
                             None => camera.handle_event(&event::MouseMoved{x: x, y: y})
                         };
                     },
-                    event::Closed  => self.window.close(),
+                    event::Closed  => window.close(),
                     event::NoEvent => break,
                     e              => camera.handle_event(&e)
                 }
             }
 
-            self.window.clear(&Color::black());
+            window.clear(&Color::black());
 
             fps.reset();
 
-            if running != RunMode::Stop {
-                self.world.step(0.016);
+            // A single step is simulated by resuming the world for it. Paused, stepping only keeps
+            // the bodies moved by hand pickable.
+            if step_once {
+                self.world.set_paused(false);
             }
 
-            if running == RunMode::Step {
-                running = RunMode::Stop;
+            self.world.step_fixed();
+            self.sync_graphics();
+
+            if step_once {
+                self.world.set_paused(true);
+                step_once = false;
             }
             fps.register_delta();
-            self.graphics.draw(&mut self.window, &camera);
+            self.graphics.draw(&mut window, &camera);
 
-            camera.activate_scene(&mut self.window);
+            camera.activate_scene(&mut window);
             if draw_colls {
-                draw_helper::draw_colls(&mut self.window, &mut self.world);
+                draw_helper::draw_contacts(&mut window, &self.world, by_impulse);
             }
 
-            camera.activate_ui(&mut self.window);
-            fps.draw_registered(&mut self.window);
+            camera.activate_ui(&mut window);
+            fps.draw_registered(&mut window);
+
+            let view = window.get_view();
+            sim_time.set_position(&window.map_pixel_to_coords(&Vector2i::new(0, 30), &view));
+            sim_time.set_string(format!("t = {:.2}s, step {}", self.world.time(), self.world.step_count()).as_slice());
+            window.draw(&sim_time);
 
-            self.window.display();
+            window.display();
         }
 
-        self.window.close();
+        window.close();
     }
 }
+
+fn create_window() -> RenderWindow {
+    let mode    = VideoMode::new_init(800, 600, 32);
+    let setting = ContextSettings {
+        depth_bits:         10,
+        stencil_bits:       10,
+        antialiasing_level: 2,
+        major_version:      0,
+        minor_version:      1
+    };
+
+    match RenderWindow::new(mode, "nphysics 2d demo", Close, &setting) {
+        Some(rwindow) => rwindow,
+        None          => panic!("Error on creating the sfml window.")
+    }
+}
+
+// The kinetic energy of the dynamic bodies of `world`, plus their potential energy due to the
+// constant gravity.
+fn energy(world: &World) -> f32 {
+    let gravity    = world.gravity();
+    let mut energy = 0.0;
+
+    for body in world.bodies() {
+        let rb = body.borrow();
+
+        if !rb.can_move() {
+            continue;
+        }
+
+        let mass = 1.0 / rb.inv_mass();
+        let av   = rb.ang_vel();
+
+        energy = energy + 0.5 * mass * na::sqnorm(&rb.lin_vel()) -
+                 mass * na::dot(&gravity, rb.center_of_mass().as_vec());
+
+        match na::inv(rb.inv_inertia()) {
+            Some(inertia) => energy = energy + 0.5 * na::dot(&av, &(inertia * av)),
+            None          => { }
+        }
+    }
+
+    energy
+}
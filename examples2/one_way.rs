@@ -0,0 +1,59 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed2d;
+
+use na::{Vec2, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics_testbed2d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec2::new(0.0, 9.81));
+
+    /*
+     * Ground
+     */
+    let rb = RigidBody::new_static(Plane::new(Vec2::new(0.0, -1.0)), 0.3, 0.6);
+
+    world.add_body(rb);
+
+    /*
+     * One-way ledge: boxes pass through it from below and land on it from above.
+     */
+    let mut rb = RigidBody::new_static(Cuboid::new(Vec2::new(10.0, 0.2)), 0.3, 0.6);
+
+    rb.append_translation(&Vec2::new(0.0, -8.0));
+    rb.set_one_way(Vec2::new(0.0, -1.0));
+
+    world.add_body(rb);
+
+    /*
+     * Create the boxes tossed upward.
+     */
+    let num = 10;
+    let rad = 0.5;
+
+    for i in range(0u, num) {
+        let x = (i as f32 - num as f32 / 2.0) * 2.0 * rad;
+
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec2::new(rad - 0.04, rad - 0.04)), 1.0, 0.3, 0.6);
+
+        rb.append_translation(&Vec2::new(x, -rad));
+        rb.set_lin_vel(Vec2::new(0.0, -15.0 - i as f32));
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Run the simulation.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.run();
+}
@@ -0,0 +1,76 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed2d;
+
+use na::{Vec2, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::RigidBody;
+use nphysics_testbed2d::{Testbed, EventHandler};
+
+// Prints the bodies entering and leaving the trigger strip.
+struct SensorPrinter;
+
+impl EventHandler for SensorPrinter {
+    fn handle_event(&mut self, event: &WorldEvent) {
+        match *event {
+            WorldEvent::SensorEntered(_, ref b) => {
+                println!("Box at x = {:.1} entered the trigger strip.", b.borrow().position().translation().x)
+            },
+            WorldEvent::SensorLeft(_, ref b) => {
+                println!("Box at x = {:.1} left the trigger strip.", b.borrow().position().translation().x)
+            },
+            _ => { }
+        }
+    }
+}
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec2::new(0.0, 9.81));
+
+    /*
+     * Ground
+     */
+    let rb = RigidBody::new_static(Plane::new(Vec2::new(0.0, -1.0)), 0.3, 0.6);
+
+    world.add_body(rb);
+
+    /*
+     * Trigger strip: the boxes fall through it.
+     */
+    let mut rb = RigidBody::new_static(Cuboid::new(Vec2::new(10.0, 0.5)), 0.3, 0.6);
+
+    rb.append_translation(&Vec2::new(0.0, -5.0));
+    rb.set_sensor(true);
+
+    world.add_body(rb);
+
+    /*
+     * Create the boxes falling at different heights, so that they cross the strip one by one.
+     */
+    let num = 10;
+    let rad = 0.5;
+
+    for i in range(0u, num) {
+        let x = (i as f32 - num as f32 / 2.0) * 2.0 * rad;
+
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec2::new(rad - 0.04, rad - 0.04)), 1.0, 0.3, 0.6);
+
+        rb.append_translation(&Vec2::new(x, -10.0 - i as f32 * 2.0));
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Run the simulation.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.set_event_handler(SensorPrinter);
+    testbed.run();
+}
@@ -0,0 +1,74 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed2d;
+
+use std::f32;
+use std::num::Float;
+use na::{Vec1, Vec2, Translation};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::integration::GroundFriction;
+use nphysics_testbed2d::Testbed;
+
+fn main() {
+    /*
+     * World seen from above: the pucks slide on a table, and stop spinning quickly.
+     */
+    let mut world = World::new();
+    world.set_top_down_defaults(0.1, 2.0);
+    world.add_force_generator(GroundFriction::new(0.15, 9.81));
+
+    /*
+     * Walls around the table.
+     */
+    let width  = 30.0f32;
+    let height = 20.0f32;
+    let walls  = [
+        (Vec2::new(width / 2.0, 0.5), Vec2::new(0.0, -height / 2.0)),
+        (Vec2::new(width / 2.0, 0.5), Vec2::new(0.0, height / 2.0)),
+        (Vec2::new(0.5, height / 2.0), Vec2::new(-width / 2.0, 0.0)),
+        (Vec2::new(0.5, height / 2.0), Vec2::new(width / 2.0, 0.0))
+    ];
+
+    for &(half_extents, pos) in walls.iter() {
+        let mut rb = RigidBody::new_static(Cuboid::new(half_extents), 0.8, 0.1);
+        rb.append_translation(&pos);
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Pucks thrown in every direction.
+     */
+    let num = 12u;
+
+    for i in range(0u, num) {
+        let angle = i as f32 * 2.0 * f32::consts::PI / num as f32;
+        let dir   = Vec2::new(angle.cos(), angle.sin());
+
+        let mut rb = RigidBody::new_dynamic(Ball::new(0.8f32), 1.0, 0.8, 0.1);
+        rb.append_translation(&(dir * 3.0));
+        rb.set_lin_vel(dir * (10.0 + i as f32));
+        rb.set_ang_vel(Vec1::new(5.0));
+
+        world.add_body(rb);
+    }
+
+    /*
+     * A heavier mallet in a corner, thrown toward the pucks.
+     */
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec2::new(1.0f32, 1.0)), 4.0, 0.5, 0.1);
+    rb.append_translation(&Vec2::new(-width / 2.0 + 3.0, -height / 2.0 + 3.0));
+    rb.set_lin_vel(Vec2::new(15.0, 10.0));
+
+    world.add_body(rb);
+
+    /*
+     * Run the simulation.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.run();
+}
@@ -0,0 +1,71 @@
+/*!
+ * Checks that the bodies wrapped around the world bounds keep their motion.
+ *
+ * A box slides without friction on the ground, in a world wrapping the bodies around `x = -5` and
+ * `x = 5`. Each time it goes past the right bound, it must appear at the left one, with the same
+ * velocity and no spike due to the contacts of its previous position. The program exits with an
+ * error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Vec2, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::RigidBody;
+use harness::check;
+
+#[path = "../examples3/harness.rs"]
+mod harness;
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec2::new(0.0, 9.81));
+    world.set_wrap_bounds(-5.0, 5.0);
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec2::new(0.0, -1.0)), 0.0, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec2::new(0.5f32, 0.5)), 1.0, 0.0, 0.0);
+    rb.append_translation(&Vec2::new(0.0, -0.5));
+    rb.set_lin_vel(Vec2::new(3.0, 0.0));
+    rb.set_deactivation_threshold(None);
+
+    let body = world.add_body(rb);
+
+    let mut events    = Vec::new();
+    let mut teleports = 0u;
+    let mut max_dvx   = 0.0f32;
+    let mut max_vy    = 0.0f32;
+
+    for _ in range(0u, 500) {
+        world.step(0.016);
+        world.drain_events(&mut events);
+
+        for e in events.iter() {
+            match *e {
+                WorldEvent::BodyTeleported(_) => {
+                    teleports = teleports + 1;
+                    check("teleported to the left bound", body.borrow().position().translation().x < -4.9);
+                },
+                _ => { }
+            }
+        }
+
+        events.clear();
+
+        let v = body.borrow().lin_vel();
+
+        max_dvx = max_dvx.max((v.x - 3.0).abs());
+        max_vy  = max_vy.max(v.y.abs());
+
+        check("within the bounds", body.borrow().position().translation().x.abs() <= 5.0);
+    }
+
+    check("wrapped around", teleports >= 2);
+    check("same horizontal velocity", max_dvx < 1.0e-3);
+    check("no vertical velocity spike", max_vy < 0.2);
+    check("still on the ground", body.borrow().position().translation().y > -0.6);
+}
@@ -0,0 +1,63 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::num::Float;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::Ball;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::integration::Attractor;
+use nphysics_testbed3d::Testbed;
+
+fn main() {
+    /*
+     * World, without global gravity.
+     */
+    let mut world = World::new();
+
+    /*
+     * The heavy central body.
+     */
+    let strength = 200.0f32;
+    let center   = world.add_body(RigidBody::new_static(Ball::new(2.0f32), 0.3, 0.6));
+
+    let mut attractor = Attractor::new(na::orig(), strength);
+    attractor.set_min_radius(2.0);
+    attractor.set_body(Some(center));
+
+    world.add_force_generator(attractor);
+
+    /*
+     * Create the orbiting balls.
+     */
+    let num = 20u;
+    let rad = 0.3;
+
+    for i in range(0u, num) {
+        let angle = i as f32 * 2.0 * Float::pi() / num as f32;
+        let dist  = 6.0 + (i % 4) as f32 * 2.0;
+        let pos   = Vec3::new(angle.cos() * dist, (i % 3) as f32 - 1.0, angle.sin() * dist);
+
+        let mut rb = RigidBody::new_dynamic(Ball::new(rad), 1.0, 0.3, 0.6);
+
+        // Circular orbit velocity.
+        let speed   = (strength / dist).sqrt();
+        let tangent  = Vec3::new(-angle.sin(), 0.0, angle.cos());
+
+        rb.append_translation(&pos);
+        rb.set_lin_vel(tangent * speed);
+        rb.set_deactivation_threshold(None);
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.look_at(Pnt3::new(0.0, 20.0, -20.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
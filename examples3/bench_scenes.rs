@@ -0,0 +1,29 @@
+/*!
+ * Displays the scenes used by the benchmarks. Start with one of them with, e.g.,
+ * `bench_scenes --scene box_pile`, and switch to the others with the page up and page down keys.
+ * The bodies moved before each step by the benchmarks, e.g., the platforms, are left in place.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use na::Pnt3;
+use nphysics_testbed3d::Testbed;
+
+#[path = "../benches/scenes.rs"]
+mod scenes;
+
+fn main() {
+    let mut testbed = Testbed::new_empty();
+
+    for scene in scenes::scenes().into_iter() {
+        let build = scene.build;
+
+        testbed.register_scene(scene.name, box move |&:| build());
+    }
+
+    testbed.look_at(Pnt3::new(-30.0, 30.0, -30.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
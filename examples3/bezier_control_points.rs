@@ -0,0 +1,77 @@
+/*!
+ * Checks the update of the control points of a Bezier surface.
+ *
+ * A ball falls asleep on a flat Bezier patch, which is then lifted. The ball must be woken up,
+ * pushed upward by the new surface, and a single `BodyShapeUpdated` event must be emitted.
+ * Changing the number of control points must panic. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::task;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Ball, BezierSurface};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::{check, steps};
+
+mod harness;
+
+// A flat 6x6 patch at the height `y`.
+fn control_points(y: f32) -> Vec<Pnt3<f32>> {
+    let mut res = Vec::new();
+
+    for i in range(0u, 6) {
+        for j in range(0u, 6) {
+            res.push(Pnt3::new(i as f32 * 2.0 - 5.0, y, j as f32 * 2.0 - 5.0));
+        }
+    }
+
+    res
+}
+
+fn world_with_patch() -> (World, RigidBodyHandle) {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    let patch = world.add_body(RigidBody::new_static(BezierSurface::new(control_points(0.0), 6, 6), 0.3, 0.6));
+
+    (world, patch)
+}
+
+fn main() {
+    let (mut world, patch) = world_with_patch();
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    rb.append_translation(&Vec3::new(0.0, 1.0, 0.0));
+    let ball = world.add_body(rb);
+
+    steps(&mut world, 300);
+
+    check("ball asleep on the patch", !ball.borrow().is_active());
+    check("ball on the patch", ball.borrow().position().translation().y < 1.0);
+
+    let mut events = Vec::new();
+    world.drain_events(&mut events);
+    events.clear();
+
+    world.update_bezier_control_points(&patch, control_points(1.0).as_slice());
+
+    steps(&mut world, 120);
+
+    world.drain_events(&mut events);
+
+    let updates = events.iter().filter(|e| match **e { WorldEvent::BodyShapeUpdated(_) => true, _ => false }).count();
+
+    check("one shape update event", updates == 1);
+    check("ball woken up", events.iter().any(|e| match *e { WorldEvent::BodyActivated(_) => true, _ => false }));
+    check("ball lifted", ball.borrow().position().translation().y > 1.3);
+
+    check("panic on a different number of control points", task::try(proc() {
+        let (mut world, patch) = world_with_patch();
+
+        world.update_bezier_control_points(&patch, control_points(1.0).slice_to(30));
+    }).is_err());
+}
@@ -0,0 +1,80 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::num::Float;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Ball, BezierSurface};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics_testbed3d::{Testbed, StepHandler};
+
+static NUM_POINTS: uint = 6;
+
+// The control points of the surface waving along `x` at the time `t`.
+fn control_points(t: f32) -> Vec<Pnt3<f32>> {
+    let mut res = Vec::new();
+
+    for i in range(0u, NUM_POINTS) {
+        for j in range(0u, NUM_POINTS) {
+            let x = i as f32 * 4.0 - 10.0;
+            let z = j as f32 * 4.0 - 10.0;
+
+            res.push(Pnt3::new(x, (x * 0.4 + t * 2.0).sin() * 3.0, z));
+        }
+    }
+
+    res
+}
+
+struct Waver {
+    surface: RigidBodyHandle,
+    time:    f32
+}
+
+impl StepHandler for Waver {
+    fn handle_step(&mut self, world: &mut World) {
+        self.time = self.time + world.timestep();
+
+        world.update_bezier_control_points(&self.surface, control_points(self.time).as_slice());
+    }
+}
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    /*
+     * Waving ground
+     */
+    let surface = BezierSurface::new(control_points(0.0), NUM_POINTS, NUM_POINTS);
+    let surface = world.add_body(RigidBody::new_static(surface, 0.3, 0.6));
+
+    /*
+     * Create the balls
+     */
+    let rad = 0.5;
+
+    for i in range(0u, 8) {
+        for k in range(0u, 8) {
+            let mut rb = RigidBody::new_dynamic(Ball::new(rad), 1.0, 0.3, 0.6);
+
+            rb.append_translation(&Vec3::new(i as f32 * 2.0 - 7.0, 10.0, k as f32 * 2.0 - 7.0));
+
+            world.add_body(rb);
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.set_step_handler(Waver { surface: surface, time: 0.0 });
+    testbed.look_at(Pnt3::new(-25.0, 20.0, -25.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
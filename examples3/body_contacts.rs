@@ -0,0 +1,78 @@
+/*!
+ * Checks the enumeration of the contacts of a single body.
+ *
+ * A box rests on the ground with a second box on top of it, and a ball rests on the ground
+ * elsewhere. The contacts of each body must be exactly those of the global list involving it,
+ * whichever body of the pair it is, and the bodies it touches must be listed once each. Removing
+ * a body or moving it away must update them. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::cell::RefCell;
+use na::{Vec3, Iso3, Translation};
+use ncollide::shape::{Plane, Cuboid, Ball};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::{check, steps};
+
+mod harness;
+
+fn same(a: &RigidBodyHandle, b: &RigidBodyHandle) -> bool {
+    a.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>
+}
+
+fn add(world: &mut World, mut rb: RigidBody, pos: Vec3<f32>) -> RigidBodyHandle {
+    rb.append_translation(&pos);
+    rb.set_deactivation_threshold(None);
+
+    world.add_body(rb)
+}
+
+// Tests that `bodies` are the bodies in contact with `body`, in any order.
+fn touches(world: &World, body: &RigidBodyHandle, bodies: &[&RigidBodyHandle]) -> bool {
+    let others = world.bodies_in_contact_with(body);
+
+    others.len() == bodies.len() && bodies.iter().all(|b| others.iter().any(|o| same(o, *b)))
+}
+
+// Tests that the contacts of `body` are those of the global list involving it.
+fn consistent(world: &World, body: &RigidBodyHandle) -> bool {
+    let expected = world.contacts().iter().filter(|c| same(&c.body1, body) || same(&c.body2, body)).count();
+
+    world.contacts_with(body).count() == expected &&
+    world.contacts_with(body).all(|c| same(&c.body1, body) || same(&c.body2, body))
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    let ground = world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0f32, 1.0, 0.0)), 0.0, 0.5));
+    let lower  = add(&mut world, RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5), Vec3::new(0.0, 0.5, 0.0));
+    let upper  = add(&mut world, RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5), Vec3::new(0.0, 1.5, 0.0));
+    let ball   = add(&mut world, RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5), Vec3::new(5.0, 0.5, 0.0));
+
+    steps(&mut world, 100);
+
+    for b in [ &ground, &lower, &upper, &ball ].iter() {
+        check("consistency with the global list", consistent(&world, *b));
+    }
+
+    check("ground", touches(&world, &ground, &[ &lower, &ball ]));
+    check("lower box", touches(&world, &lower, &[ &ground, &upper ]));
+    check("upper box", touches(&world, &upper, &[ &lower ]));
+    check("ball", touches(&world, &ball, &[ &ground ]));
+
+    world.remove_body(&upper);
+
+    check("removed body", touches(&world, &lower, &[ &ground ]) && consistent(&world, &lower));
+    check("no contacts for the removed body", world.contacts_with(&upper).count() == 0);
+
+    ball.borrow_mut().set_transformation(Iso3::new(Vec3::new(5.0, 20.0, 0.0), na::zero()));
+    world.step(0.016);
+
+    check("separated ball", world.contacts_with(&ball).count() == 0 && touches(&world, &ground, &[ &lower ]));
+}
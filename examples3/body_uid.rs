@@ -0,0 +1,100 @@
+/*!
+ * Checks the unique identifiers of the bodies.
+ *
+ * The bodies must be given distinct identifiers, never reused after a removal, and kept by the
+ * bodies cloned or added back unless another body already has them. The scenes must give the
+ * bodies the identifiers they specify, and the following ones to the others. The program exits
+ * with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::os;
+use std::io::File;
+use std::cell::RefCell;
+use ncollide::shape::Ball;
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::RigidBody;
+use nphysics::io::load_scene;
+use harness::check;
+
+mod harness;
+
+fn ball() -> RigidBody {
+    RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.5)
+}
+
+fn load(content: &str) -> Result<Vec<u64>, String> {
+    let path = os::tmpdir().join("nphysics_body_uid.json");
+    let _    = File::create(&path).write_str(content);
+
+    load_scene(&path).map(|(_, bodies)| bodies.iter().map(|b| b.body.borrow().uid()).collect())
+}
+
+fn main() {
+    /*
+     * Identifiers given by the world.
+     */
+    let mut world = World::new();
+
+    check("unassigned", ball().uid() == 0);
+
+    let a = world.add_body(ball());
+    let b = world.add_body(ball());
+    let c = world.add_body(ball());
+
+    let (ua, ub, uc) = (a.borrow().uid(), b.borrow().uid(), c.borrow().uid());
+
+    check("distinct", ua != 0 && ua != ub && ub != uc && ua != uc);
+
+    let mut events = Vec::new();
+    world.drain_events(&mut events);
+    world.remove_body(&b);
+    world.drain_events(&mut events);
+
+    check("removed event", events.iter().any(|e| match *e {
+        WorldEvent::BodyRemoved(ref r) => r.borrow().uid() == ub,
+        _ => false
+    }));
+    check("removed", world.body_with_uid(ub).is_none());
+
+    let d = world.add_body(ball());
+    let ud = d.borrow().uid();
+
+    check("not reused", ud != ua && ud != ub && ud != uc);
+
+    let e = world.add_body(b.borrow().clone());
+    check("added back", e.borrow().uid() == ub);
+
+    let f = world.add_body(a.borrow().clone());
+    let uf = f.borrow().uid();
+    check("clone of a body in the world", uf != ua && uf != ub && uf != uc && uf != ud);
+
+    match world.body_with_uid(uc) {
+        Some(h) => check("lookup", h.deref() as *const RefCell<RigidBody> == c.deref() as *const RefCell<RigidBody>),
+        None    => check("lookup", false)
+    }
+
+    let mut restored = ball();
+    restored.set_uid(100);
+    let _ = world.add_body(restored);
+
+    check("after a restored body", world.add_body(ball()).borrow().uid() == 101);
+
+    /*
+     * Identifiers of the scenes.
+     */
+    let shape = r#""shape": { "type": "ball", "radius": 1.0 }"#;
+
+    match load(format!(r#"{{ "bodies": [ {{ {0} }}, {{ {0}, "uid": 7 }}, {{ {0}, "uid": 3 }}, {{ {0} }} ] }}"#,
+                       shape).as_slice()) {
+        Ok(uids) => check("scene", uids == vec!(8, 7, 3, 9)),
+        Err(e)   => check(e.as_slice(), false)
+    }
+
+    check("duplicate", load(format!(r#"{{ "bodies": [ {{ {0}, "uid": 2 }}, {{ {0}, "uid": 2 }} ] }}"#,
+                                    shape).as_slice()).is_err());
+    check("zero", load(format!(r#"{{ "bodies": [ {{ {0}, "uid": 0 }} ] }}"#, shape).as_slice()).is_err());
+}
@@ -0,0 +1,89 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::sync::Arc;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::volumetric::Volumetric;
+use ncollide::shape::{Plane, Cuboid, Compound, CompoundData, Shape3};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::utils::{build_bridge, Bridge};
+use nphysics_testbed3d::{Testbed, KeyHandler, Key};
+
+// Cuts the bridge in its middle when the `x` key is released.
+struct Severer {
+    bridge: Bridge
+}
+
+impl KeyHandler for Severer {
+    fn handle_key(&mut self, key: Key, world: &mut World) {
+        if key == Key::X {
+            let middle = self.bridge.middle_hinge();
+
+            self.bridge.sever(world, middle);
+        }
+    }
+}
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    /*
+     * Planes
+     */
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    /*
+     * The bridge, hanging between two points 30 units apart.
+     */
+    let num_planks = 20u;
+    let plank      = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.65f32, 0.1, 2.0)), 1.0, 0.3, 0.8);
+    let bridge     = build_bridge(&mut world,
+                                  Pnt3::new(-15.0, 15.0, 0.0),
+                                  Pnt3::new(15.0, 15.0, 0.0),
+                                  num_planks,
+                                  &plank,
+                                  0.1);
+
+    /*
+     * Cross shaped geometry
+     */
+    let mut cross_geoms = CompoundData::new();
+    cross_geoms.push_shape(na::one(), Cuboid::new(Vec3::new(1.96, 0.21, 0.21)), 1.0);
+    cross_geoms.push_shape(na::one(), Cuboid::new(Vec3::new(0.21, 1.96, 0.21)), 1.0);
+    cross_geoms.push_shape(na::one(), Cuboid::new(Vec3::new(0.21, 0.21, 1.96)), 1.0);
+
+    let compound = Compound::new(cross_geoms);
+    let mass     = compound.mass_properties(1.0);
+    let cross    = Arc::new(box compound as Box<Shape3<f32>>);
+
+    /*
+     * Create the crosses above the bridge.
+     */
+    for i in range(0u, 6) {
+        for j in range(0u, 3) {
+            let mut rb = RigidBody::new(cross.clone(), Some(mass), 0.3, 0.5);
+
+            rb.append_translation(&Vec3::new(i as f32 * 4.5 - 11.25, 20.0 + j as f32 * 4.5, 0.0));
+
+            world.add_body(rb);
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    println!("Release the x key to cut the bridge in its middle.");
+
+    let mut testbed = Testbed::new(world);
+
+    testbed.set_key_handler(Severer { bridge: bridge });
+    testbed.look_at(Pnt3::new(-20.0, 25.0, -35.0), Pnt3::new(0.0, 10.0, 0.0));
+    testbed.run();
+}
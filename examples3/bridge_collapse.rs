@@ -0,0 +1,74 @@
+/*!
+ * Checks the bridges built by `build_bridge`.
+ *
+ * A bridge of ten planks must barely move during its first steps, and its middle must then hang
+ * about as low as it was built. Once its middle hinge is severed, the planks next to it must fall
+ * while hanging from the anchors, and the bodies resting on it must fall with them. The program
+ * exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::utils::build_bridge;
+use harness::{check, steps};
+
+mod harness;
+
+fn position(body: &RigidBodyHandle) -> Vec3<f32> {
+    body.borrow().position().translation()
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    // The planks hang between two anchors 10 units apart, the middle 1 unit lower.
+    let plank  = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.45f32, 0.1, 1.0)), 1.0, 0.3, 0.8);
+    let bridge = build_bridge(&mut world, Pnt3::new(-5.0, 10.0, 0.0), Pnt3::new(5.0, 10.0, 0.0), 10, &plank, 0.1);
+
+    check("number of planks", bridge.planks().len() == 10);
+    check("number of hinges", bridge.hinges().len() == 11);
+    check("middle hinge", bridge.middle_hinge() == 5);
+
+    let before: Vec<Vec3<f32>> = bridge.planks().iter().map(position).collect();
+
+    steps(&mut world, 5);
+
+    let drop = bridge.planks().iter().zip(before.iter())
+                     .map(|(p, b)| na::norm(&(position(p) - *b)))
+                     .fold(0.0f32, |a, b| a.max(b));
+
+    check("first steps", drop < 0.05);
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.3f32), 1.0, 0.0, 0.8);
+    rb.append_translation(&Vec3::new(0.0, 10.0, 0.0));
+    let ball = world.add_body(rb);
+
+    steps(&mut world, 300);
+
+    let middle = &bridge.planks()[5];
+
+    check("sag", position(middle).y > 8.5 && position(middle).y < 9.5);
+    check("ball on the bridge", position(&ball).y > 8.5);
+
+    /*
+     * Severing the middle hinge. The two halves swing down from the anchors.
+     */
+    let i = bridge.middle_hinge();
+
+    bridge.sever(&mut world, i);
+    bridge.sever(&mut world, i);
+
+    steps(&mut world, 300);
+
+    check("collapse", position(middle).y < 6.0);
+    check("hanging half", position(&bridge.planks()[0]).y > 9.0);
+    check("ball fallen", position(&ball).y < 5.0);
+}
@@ -0,0 +1,87 @@
+/*!
+ * Checks that the margins and the broad phase padding can be tuned independently.
+ *
+ * Two balls two units apart must form a broad phase pair once the world padding is large enough,
+ * without any contact, and keep it when their margins are removed. Overriding their padding must
+ * remove the pair. Cubes dropped on the ground must rest at the same height whatever the padding,
+ * and lower once their margin is removed. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Vec3, Translation};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::{check, steps};
+
+mod harness;
+
+// The height at which a cube dropped on the ground comes to rest.
+fn rest_height(padding: f32, margin: f32) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.set_broad_phase_padding(padding);
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.0, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+    rb.set_margin(margin);
+    rb.append_translation(&Vec3::new(0.0, 2.0, 0.0));
+    let cube = world.add_body(rb);
+
+    steps(&mut world, 300);
+
+    let y = cube.borrow().position().translation().y;
+
+    y
+}
+
+fn main() {
+    /*
+     * Pairs.
+     */
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    rb.append_translation(&Vec3::new(-1.5, 0.0, 0.0));
+    let b1 = world.add_body(rb);
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    rb.append_translation(&Vec3::new(1.5, 0.0, 0.0));
+    let b2 = world.add_body(rb);
+
+    world.step(0.016);
+    check("no pair without padding", !world.pair_exists(&b1, &b2));
+
+    world.set_broad_phase_padding(1.5);
+    world.step(0.016);
+    check("pair with the world padding", world.pair_exists(&b1, &b2));
+    check("no contact with the world padding", world.contacts().is_empty());
+
+    b1.borrow_mut().set_margin(0.0);
+    b2.borrow_mut().set_margin(0.0);
+    world.step(0.016);
+    check("pair kept without margins", world.pair_exists(&b1, &b2));
+
+    b1.borrow_mut().set_broad_phase_padding(Some(0.0));
+    b2.borrow_mut().set_broad_phase_padding(Some(0.0));
+    world.step(0.016);
+    check("pair removed by the overriden paddings", !world.pair_exists(&b1, &b2));
+    check("overriden padding", b1.borrow().broad_phase_padding() == 0.0);
+
+    /*
+     * Contacts.
+     */
+    let default = rest_height(0.0, 0.04);
+    let padded  = rest_height(2.0, 0.04);
+    let thin    = rest_height(2.0, 0.0);
+
+    check(format!("rest height changed by the padding: {} instead of {}", padded, default).as_slice(),
+          (padded - default).abs() < 1.0e-3);
+    check(format!("rest height not changed by the margin: {} instead of {}", thin, default - 0.04).as_slice(),
+          (thin - (default - 0.04)).abs() < 1.0e-2);
+}
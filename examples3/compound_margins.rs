@@ -0,0 +1,69 @@
+/*!
+ * Checks that the margin is applied exactly once to each part of a compound shape.
+ *
+ * A cuboid and a compound made of two cuboids of half its width, covering the same volume, are
+ * dropped on a plane. Once at rest, they must be at the same height. A third compound, identical
+ * except that its parts have no margin, must rest lower by the body margin. The program exits
+ * with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use std::sync::Arc;
+use na::{Vec3, Iso3, Translation};
+use ncollide::shape::{Plane, Cuboid, Shape3};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::utils::CompoundBuilder;
+use harness::{check, steps};
+
+mod harness;
+
+fn compound(world: &mut World, x: f32, part_margin: Option<f32>) -> RigidBodyHandle {
+    let mut builder = CompoundBuilder::new();
+
+    builder.push_shape(Iso3::new(Vec3::new(-0.5, 0.0, 0.0), na::zero()), Cuboid::new(Vec3::new(0.5f32, 0.5, 1.0)), 1.0);
+    builder.push_shape(Iso3::new(Vec3::new(0.5, 0.0, 0.0), na::zero()), Cuboid::new(Vec3::new(0.5f32, 0.5, 1.0)), 1.0);
+    builder.set_margin(0, part_margin);
+    builder.set_margin(1, part_margin);
+
+    let margins        = builder.margins().to_vec();
+    let (shape, props) = builder.build();
+    let mut rb         = RigidBody::new(Arc::new(box shape as Box<Shape3<f32>>), Some(props), 0.0, 0.6);
+
+    rb.set_part_margins(margins);
+    rb.append_translation(&Vec3::new(x, 2.0, 0.0));
+
+    world.add_body(rb)
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0f32, 1.0, 0.0)), 0.0, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(1.0f32, 0.5, 1.0)), 1.0, 0.0, 0.6);
+    rb.append_translation(&Vec3::new(-5.0, 2.0, 0.0));
+
+    let margin  = rb.margin();
+    let cuboid  = world.add_body(rb);
+    let regular = compound(&mut world, 0.0, None);
+    let thin    = compound(&mut world, 5.0, Some(0.0));
+
+    steps(&mut world, 500);
+
+    let y_cuboid  = cuboid.borrow().position().translation().y;
+    let y_regular = regular.borrow().position().translation().y;
+    let y_thin    = thin.borrow().position().translation().y;
+    let slop      = 0.01;
+
+    println!("Resting heights: cuboid {}, compound {}, compound without part margins {}.",
+             y_cuboid, y_regular, y_thin);
+
+    check("compound height", (y_cuboid - y_regular).abs() <= slop);
+    check("height without part margins", (y_cuboid - margin - y_thin).abs() <= slop);
+}
@@ -0,0 +1,51 @@
+/*!
+ * Checks the mass properties of a compound made of parts with different densities.
+ *
+ * The compound is made of a rotated light cuboid and of a heavy cube given by its mass. The mass,
+ * center of mass and angular inertia computed by `CompoundBuilder` are compared to their values
+ * computed by hand with the parallel axis theorem. The program exits with an error if they
+ * differ.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::f32::consts::FRAC_PI_2;
+use na::{Pnt3, Vec3, Mat3, Iso3};
+use ncollide::shape::Cuboid;
+use nphysics::utils::CompoundBuilder;
+use harness::check;
+
+mod harness;
+
+fn main() {
+    let mut builder = CompoundBuilder::new();
+
+    // A 2x1x1 cuboid with a unit density, rotated such that its long side is along the z axis:
+    // its mass is 2.
+    builder.push_shape(Iso3::new(na::zero(), Vec3::new(0.0, FRAC_PI_2, 0.0)),
+                       Cuboid::new(Vec3::new(1.0f32, 0.5, 0.5)),
+                       1.0);
+    // A unit cube with a mass of 6.
+    builder.push_shape_with_mass(Iso3::new(Vec3::new(1.5, 1.0, 0.0), na::zero()),
+                                 Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)),
+                                 6.0);
+
+    let (mass, com, inertia) = builder.mass_properties();
+
+    let expected_mass    = 8.0f32;
+    let expected_com     = Pnt3::new(1.125f32, 0.75, 0.0);
+    let expected_inertia = Mat3::new(
+        10.0 / 3.0, -2.25,        0.0,
+        -2.25,      125.0 / 24.0, 0.0,
+        0.0,        0.0,          149.0 / 24.0);
+
+    println!("mass: {} (expected {})", mass, expected_mass);
+    println!("center of mass: {} (expected {})", com, expected_com);
+    println!("angular inertia: {} (expected {})", inertia, expected_inertia);
+
+    check("mass", na::approx_eq_eps(&mass, &expected_mass, &1.0e-4));
+    check("center of mass", na::approx_eq_eps(&com, &expected_com, &1.0e-4));
+    check("angular inertia", na::approx_eq_eps(&inertia, &expected_inertia, &1.0e-4));
+}
@@ -0,0 +1,97 @@
+/*!
+ * Checks that the parts of a compound hit by rays and contacts are identified.
+ *
+ * A compound made of three cuboids along the `x` axis is hit by a vertical ray above each of its
+ * parts. A ball is then dropped on its last part. The part hit must be identified each time, and
+ * mapped back to its local transform. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::sync::Arc;
+use std::cell::RefCell;
+use na::{Pnt3, Vec3, Iso3, Translation};
+use ncollide::shape::{Ball, Cuboid, Shape3};
+use ncollide::ray::Ray;
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::RigidBody;
+use nphysics::utils::CompoundBuilder;
+use harness::check;
+
+mod harness;
+
+fn main() {
+    let mut world   = World::new();
+    let mut builder = CompoundBuilder::new();
+
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    for i in range(0u, 3) {
+        let delta = Iso3::new(Vec3::new(i as f32 * 3.0 - 3.0, 0.0, 0.0), na::zero());
+
+        builder.push_shape(delta, Cuboid::new(Vec3::new(1.0f32, 0.5, 1.0)), 1.0);
+    }
+
+    let (shape, _) = builder.build();
+    let compound   = world.add_body(RigidBody::new(Arc::new(box shape as Box<Shape3<f32>>), None, 0.0, 0.6));
+
+    for i in range(0u, 3) {
+        let x   = i as f32 * 3.0 - 3.0;
+        let ray = Ray::new(Pnt3::new(x, 10.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let rb  = compound.borrow();
+        let id  = rb.sub_shape_hit_by_ray(&ray, 9.5);
+
+        if !check(format!("part {} hit by the ray above the part {}", id, i).as_slice(), id == vec![i]) {
+            return;
+        }
+
+        match rb.sub_shape(id.as_slice()) {
+            Some((delta, _)) if na::approx_eq(&delta.translation().x, &x) => { },
+            _ => {
+                check(format!("transform of the part {}", i).as_slice(), false);
+                return;
+            }
+        }
+    }
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.6);
+    rb.append_translation(&Vec3::new(3.0, 3.0, 0.0));
+
+    let ball = world.add_body(rb);
+
+    world.set_impact_event_threshold(0.0);
+
+    let mut events       = Vec::new();
+    let mut found_impact = false;
+
+    for _ in range(0u, 100) {
+        world.step(0.016);
+        world.drain_events(&mut events);
+
+        for event in events.iter() {
+            match *event {
+                WorldEvent::Impact(ref impact) => {
+                    let part = if impact.body1.deref() as *const RefCell<RigidBody> == ball.deref() as *const RefCell<RigidBody> {
+                        &impact.sub_shape2
+                    }
+                    else {
+                        &impact.sub_shape1
+                    };
+
+                    if !check(format!("part {} hit by the ball instead of the part 2", part).as_slice(), *part == vec![2u]) {
+                        return;
+                    }
+
+                    found_impact = true;
+                },
+                _ => { }
+            }
+        }
+
+        events.clear();
+    }
+
+    check("impact with the compound", found_impact);
+}
@@ -0,0 +1,97 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::sync::Arc;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Plane, Shape3};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::utils;
+use nphysics_testbed3d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    /*
+     * Plane
+     */
+    let geom = Plane::new(Vec3::new(0.0, 1.0, 0.0));
+
+    world.add_body(RigidBody::new_static(geom, 0.3, 0.6));
+
+    /*
+     * Decompose an L-shaped concave mesh.
+     */
+    let (vertices, indices) = l_shaped_mesh();
+    let (compound, mass)    = utils::convex_decomposition(vertices.as_slice(), indices.as_slice(), 0.03, 1, 1.0);
+    let compound            = Arc::new(box compound as Box<Shape3<f32>>);
+
+    /*
+     * Create the L-shaped bodies.
+     */
+    let num   = 5;
+    let shift = 4.0;
+
+    for i in range(0u, num) {
+        for j in range(0u, num) {
+            let x = i as f32 * shift - shift * (num as f32) / 2.0;
+            let y = j as f32 * shift + 5.0;
+            let z = (i + j) as f32 * 0.5;
+
+            let mut rb = RigidBody::new(compound.clone(), Some(mass), 0.3, 0.5);
+
+            rb.append_translation(&Vec3::new(x, y, z));
+
+            world.add_body(rb);
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.look_at(Pnt3::new(-30.0, 30.0, -30.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
+
+// An extruded L-shaped polygon.
+fn l_shaped_mesh() -> (Vec<Pnt3<f32>>, Vec<uint>) {
+    let profile = [
+        (0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 3.0), (0.0, 3.0)
+    ];
+    let n = profile.len();
+
+    let mut vertices = Vec::new();
+    let mut indices  = Vec::new();
+
+    for &(x, y) in profile.iter() {
+        vertices.push(Pnt3::new(x, y, -0.5));
+    }
+
+    for &(x, y) in profile.iter() {
+        vertices.push(Pnt3::new(x, y, 0.5));
+    }
+
+    // The first vertex sees the whole polygon: both caps can be triangulated as fans.
+    for i in range(1u, n - 1) {
+        indices.push_all(&[0, i + 1, i]);
+        indices.push_all(&[n, n + i, n + i + 1]);
+    }
+
+    // Sides.
+    for i in range(0u, n) {
+        let j = (i + 1) % n;
+
+        indices.push_all(&[i, j, j + n]);
+        indices.push_all(&[i, j + n, i + n]);
+    }
+
+    (vertices, indices)
+}
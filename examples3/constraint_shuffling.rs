@@ -0,0 +1,98 @@
+/*!
+ * Checks the shuffling of the constraints solved by the world.
+ *
+ * Boxes dropped at mirrored positions onto a pyramid must stay mirrored far better with the
+ * shuffling than without it, since the fixed ordering of the constraints favors one side of the
+ * scene. Two simulations with the same seed must stay identical. The program exits with an error
+ * otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Vec3, Translation};
+use ncollide::shape::{Cuboid, Plane};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::{check, steps};
+
+mod harness;
+
+fn add_box(world: &mut World, x: f32, y: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+    rb.append_translation(&Vec3::new(x, y, 0.0));
+
+    world.add_body(rb)
+}
+
+// A pyramid of boxes, and boxes dropped onto it. Each box comes with its mirror wrt. the `x = 0`
+// plane, the boxes on that plane being their own mirror.
+fn mirrored_drop(shuffling: Option<u64>) -> Vec<(RigidBodyHandle, RigidBodyHandle)> {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.0, 0.5));
+
+    match shuffling {
+        Some(seed) => world.set_constraint_shuffling(true, seed),
+        None       => { }
+    }
+
+    let mut pairs = Vec::new();
+
+    for layer in range(0u, 4) {
+        let num = 4 - layer;
+        let y   = 0.5 + layer as f32;
+
+        for i in range(0u, num / 2) {
+            let x = (num - 1 - 2 * i) as f32 * 0.5;
+
+            pairs.push((add_box(&mut world, x, y), add_box(&mut world, -x, y)));
+        }
+
+        if num % 2 == 1 {
+            let b = add_box(&mut world, 0.0, y);
+            pairs.push((b.clone(), b));
+        }
+    }
+
+    for i in range(0u, 3) {
+        let x = 0.3 + i as f32 * 0.4;
+        let y = 6.0 + i as f32 * 2.0;
+
+        pairs.push((add_box(&mut world, x, y), add_box(&mut world, -x, y)));
+    }
+
+    steps(&mut world, 400);
+
+    pairs
+}
+
+// The sum of the distances between the positions of the boxes and the mirror of the positions of
+// their mirrors.
+fn asymmetry(pairs: &[(RigidBodyHandle, RigidBodyHandle)]) -> f32 {
+    pairs.iter().fold(0.0, |acc, &(ref a, ref b)| {
+        let pa = a.borrow().position().translation();
+        let pb = b.borrow().position().translation();
+
+        acc + (pa.x + pb.x).abs() + (pa.y - pb.y).abs() + (pa.z - pb.z).abs()
+    })
+}
+
+fn positions(pairs: &[(RigidBodyHandle, RigidBodyHandle)]) -> Vec<Vec3<f32>> {
+    pairs.iter().map(|&(ref a, _)| a.borrow().position().translation()).collect()
+}
+
+fn main() {
+    let fixed    = asymmetry(mirrored_drop(None).as_slice());
+    let shuffled = asymmetry(mirrored_drop(Some(42)).as_slice());
+
+    check("asymmetry of the fixed ordering", fixed > 1.0e-3);
+    check("asymmetry of the shuffled ordering", shuffled * 4.0 < fixed);
+
+    let a = mirrored_drop(Some(7));
+    let b = mirrored_drop(Some(7));
+
+    check("determinism", positions(a.as_slice()) == positions(b.as_slice()));
+}
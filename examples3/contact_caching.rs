@@ -0,0 +1,160 @@
+/*!
+ * Checks the caching of the contacts computed by a user-defined contact generator.
+ *
+ * Balls falling on a plane must come to rest at the same positions with and without the caching,
+ * while the generator is called far less once they rest. Static balls, standing for kinematic
+ * platforms, slid slowly along the plane must also call the generator far less. A platform moved
+ * farther than the tolerances must have its contacts generated again. The program exits with an
+ * error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::rc::Rc;
+use std::cell::Cell;
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use na::{Pnt3, Vec3, Iso3, Translation};
+use ncollide::shape::{Shape, Plane3, Ball3};
+use ncollide::geometry::Contact;
+use nphysics::world::World;
+use nphysics::detection::ContactGenerator;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::check;
+
+mod harness;
+
+struct PlaneBallGenerator {
+    calls: Rc<Cell<uint>>
+}
+
+impl ContactGenerator for PlaneBallGenerator {
+    fn generate(&mut self,
+                m1:         &Iso3<f32>,
+                s1:         &Shape<f32, Pnt3<f32>, Vec3<f32>, Iso3<f32>>,
+                m2:         &Iso3<f32>,
+                s2:         &Shape<f32, Pnt3<f32>, Vec3<f32>, Iso3<f32>>,
+                prediction: f32,
+                out:        &mut Vec<Contact<f32, Pnt3<f32>, Vec3<f32>>>) {
+        self.calls.set(self.calls.get() + 1);
+
+        let plane  = s1.downcast_ref::<Plane3<f32>>().unwrap();
+        let ball   = s2.downcast_ref::<Ball3<f32>>().unwrap();
+        let normal = na::rotate(m1, plane.normal());
+        let center = na::orig::<Pnt3<f32>>() + m2.translation();
+        let dist   = na::dot(&(center - (na::orig::<Pnt3<f32>>() + m1.translation())), &normal);
+        let depth  = ball.radius() - dist;
+
+        if depth > -prediction {
+            out.push(Contact::new(center + normal * -dist, center + normal * -ball.radius(), normal, depth))
+        }
+    }
+}
+
+// A world with a plane handled by a counting generator, and the counter of its calls.
+fn new_world(caching: bool) -> (World, Rc<Cell<uint>>) {
+    let calls     = Rc::new(Cell::new(0u));
+    let mut world = World::new();
+
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+    world.register_contact_generator(TypeId::of::<Plane3<f32>>(), TypeId::of::<Ball3<f32>>(),
+                                     box PlaneBallGenerator { calls: calls.clone() });
+    world.add_body(RigidBody::new_static(Plane3::new(Vec3::new(0.0f32, 1.0, 0.0)), 0.3, 0.6));
+
+    if caching {
+        world.set_contact_caching(Some((0.01, 0.01)));
+    }
+
+    (world, calls)
+}
+
+fn add_balls(world: &mut World, dynamic: bool, y: f32) -> Vec<RigidBodyHandle> {
+    let mut res = Vec::new();
+
+    for i in range(0u, 10) {
+        for j in range(0u, 10) {
+            let mut rb = if dynamic {
+                RigidBody::new_dynamic(Ball3::new(0.5f32), 1.0, 0.3, 0.6)
+            }
+            else {
+                RigidBody::new_static(Ball3::new(0.5f32), 0.3, 0.6)
+            };
+
+            rb.append_translation(&Vec3::new(i as f32 * 2.0, y + (i + j) as f32 * 0.1, j as f32 * 2.0));
+            res.push(world.add_body(rb));
+        }
+    }
+
+    res
+}
+
+// Steps `world` `num` times, moving the static `platforms` along `x` by `dx` before each step,
+// and gives the number of generator calls.
+fn steps(world: &mut World, calls: &Rc<Cell<uint>>, platforms: &[RigidBodyHandle], dx: f32, num: uint) -> uint {
+    let before = calls.get();
+
+    for _ in range(0u, num) {
+        for p in platforms.iter() {
+            p.borrow_mut().append_translation(&Vec3::new(dx, 0.0, 0.0));
+        }
+
+        world.step(0.016);
+    }
+
+    calls.get() - before
+}
+
+fn main() {
+    /*
+     * Balls coming to rest.
+     */
+    let (mut plain, plain_calls)   = new_world(false);
+    let (mut cached, cached_calls) = new_world(true);
+
+    let plain_balls  = add_balls(&mut plain, true, 1.0);
+    let cached_balls = add_balls(&mut cached, true, 1.0);
+
+    let _ = steps(&mut plain, &plain_calls, [].as_slice(), 0.0, 300);
+    let _ = steps(&mut cached, &cached_calls, [].as_slice(), 0.0, 300);
+
+    let num_plain  = steps(&mut plain, &plain_calls, [].as_slice(), 0.0, 100);
+    let num_cached = steps(&mut cached, &cached_calls, [].as_slice(), 0.0, 100);
+
+    check("calls at rest", num_cached * 5 < num_plain);
+    check("positions", plain_balls.iter().zip(cached_balls.iter()).all(|(a, b)| {
+        na::norm(&(a.borrow().position().translation() - b.borrow().position().translation())) < 0.02
+    }));
+
+    /*
+     * Kinematic platforms sliding along the plane.
+     */
+    let (mut plain, plain_calls)   = new_world(false);
+    let (mut cached, cached_calls) = new_world(true);
+
+    let plain_platforms  = add_balls(&mut plain, false, 0.5);
+    let cached_platforms = add_balls(&mut cached, false, 0.5);
+
+    let num_plain  = steps(&mut plain, &plain_calls, plain_platforms.as_slice(), 0.001, 200);
+    let num_cached = steps(&mut cached, &cached_calls, cached_platforms.as_slice(), 0.001, 200);
+
+    check("calls of the sliding platforms", num_cached > 0 && num_cached * 5 < num_plain);
+    check("cached pairs", cached.step_statistics().num_cached_pairs <= cached_platforms.len());
+
+    /*
+     * A platform moved farther than the tolerances.
+     */
+    let (mut world, calls) = new_world(true);
+    let platforms = add_balls(&mut world, false, 0.5);
+
+    let _ = steps(&mut world, &calls, [].as_slice(), 0.0, 5);
+    check("still platforms", steps(&mut world, &calls, [].as_slice(), 0.0, 5) == 0);
+    check("reused pairs", world.step_statistics().num_cached_pairs == platforms.len());
+
+    platforms[0].borrow_mut().append_translation(&Vec3::new(0.5, 0.0, 0.0));
+    check("moved platform", steps(&mut world, &calls, [].as_slice(), 0.0, 1) == 1);
+
+    world.set_contact_caching(None);
+    check("caching disabled", steps(&mut world, &calls, [].as_slice(), 0.0, 1) == platforms.len());
+}
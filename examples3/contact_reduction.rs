@@ -0,0 +1,108 @@
+/*!
+ * Checks that the number of contacts between two bodies is capped.
+ *
+ * A box resting on a finely tessellated flat mesh must touch it with exactly four contacts, and
+ * rest at the same height as a box lying on a mesh made of a single quad. Many boxes are then
+ * dropped on a big mesh, with and without the cap, and the average duration of a step is
+ * displayed for both. The program exits with an error otherwise.
+ */
+
+extern crate time;
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::uint;
+use std::sync::Arc;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Mesh, Mesh3, Cuboid};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::{check, steps};
+
+mod harness;
+
+// A flat square mesh of side `size`, centered at the origin, made of `n * n` quads.
+fn grid(size: f32, n: uint) -> Mesh3<f32> {
+    let mut vertices = Vec::new();
+    let mut indices  = Vec::new();
+
+    for i in range(0u, n + 1) {
+        for k in range(0u, n + 1) {
+            vertices.push(Pnt3::new((i as f32 / n as f32 - 0.5) * size, 0.0, (k as f32 / n as f32 - 0.5) * size));
+        }
+    }
+
+    for i in range(0u, n) {
+        for k in range(0u, n) {
+            let a = i * (n + 1) + k;
+            let b = a + n + 1;
+
+            indices.push_all(&[ a, a + 1, b, b, a + 1, b + 1 ]);
+        }
+    }
+
+    Mesh::new(Arc::new(vertices), Arc::new(indices), None, None)
+}
+
+fn add_box(world: &mut World, pos: Vec3<f32>) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.6);
+
+    rb.append_translation(&pos);
+    rb.set_deactivation_threshold(None);
+
+    world.add_body(rb)
+}
+
+// The resting height of a box on the mesh, and the number of contacts it ends up with.
+fn rest(mesh: Mesh3<f32>) -> (f32, uint) {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    world.add_body(RigidBody::new_static(mesh, 0.0, 0.6));
+
+    let body = add_box(&mut world, Vec3::new(0.13, 0.5, 0.07));
+
+    steps(&mut world, 200);
+
+    let y = body.borrow().position().translation().y;
+
+    (y, world.contacts().len())
+}
+
+// Steps a world with many boxes on a big mesh and returns the average duration of a step, in
+// milliseconds.
+fn run(max_contacts: uint) -> f64 {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+    world.set_max_contacts_per_pair(max_contacts);
+
+    world.add_body(RigidBody::new_static(grid(40.0, 200), 0.0, 0.6));
+
+    for i in range(0u, 10) {
+        for k in range(0u, 10) {
+            add_box(&mut world, Vec3::new(i as f32 * 2.0 - 10.0, 0.5, k as f32 * 2.0 - 10.0));
+        }
+    }
+
+    let num_steps = 100u;
+    let before    = time::precise_time_s();
+
+    steps(&mut world, num_steps);
+
+    (time::precise_time_s() - before) * 1000.0 / num_steps as f64
+}
+
+fn main() {
+    let (fine_y, fine_contacts) = rest(grid(10.0, 40));
+    let (coarse_y, _)           = rest(grid(10.0, 1));
+
+    check(format!("{} contacts on the fine mesh instead of 4", fine_contacts).as_slice(), fine_contacts == 4);
+    check(format!("resting height {} on the fine mesh instead of {}", fine_y, coarse_y).as_slice(),
+          na::approx_eq_eps(&fine_y, &coarse_y, &1.0e-2));
+
+    let capped   = run(4);
+    let uncapped = run(uint::MAX);
+
+    println!("Average step duration: {}ms with at most 4 contacts per pair, {}ms without limit.", capped, uncapped);
+}
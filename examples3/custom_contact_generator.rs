@@ -0,0 +1,107 @@
+/*!
+ * Checks that a user-defined contact generator replaces the default narrow phase.
+ *
+ * An analytic plane-ball contact generator is registered for the `(Plane, Ball)` shape types. The
+ * same balls are dropped on a plane in a world using it and in a world using the default narrow
+ * phase. The planes are added after the balls, so that the generator is given the shapes swapped.
+ * The generator must be called, and the balls must end up at the same positions in both worlds.
+ * The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::rc::Rc;
+use std::cell::Cell;
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use na::{Pnt3, Vec3, Iso3, Translation};
+use ncollide::shape::{Shape, Plane3, Ball3};
+use ncollide::geometry::Contact;
+use nphysics::world::World;
+use nphysics::detection::ContactGenerator;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::check;
+
+mod harness;
+
+struct PlaneBallGenerator {
+    calls: Rc<Cell<uint>>
+}
+
+impl ContactGenerator for PlaneBallGenerator {
+    fn generate(&mut self,
+                m1:         &Iso3<f32>,
+                s1:         &Shape<f32, Pnt3<f32>, Vec3<f32>, Iso3<f32>>,
+                m2:         &Iso3<f32>,
+                s2:         &Shape<f32, Pnt3<f32>, Vec3<f32>, Iso3<f32>>,
+                prediction: f32,
+                out:        &mut Vec<Contact<f32, Pnt3<f32>, Vec3<f32>>>) {
+        self.calls.set(self.calls.get() + 1);
+
+        let plane  = s1.downcast_ref::<Plane3<f32>>().unwrap();
+        let ball   = s2.downcast_ref::<Ball3<f32>>().unwrap();
+        let normal = na::rotate(m1, plane.normal());
+        let center = na::orig::<Pnt3<f32>>() + m2.translation();
+        let dist   = na::dot(&(center - (na::orig::<Pnt3<f32>>() + m1.translation())), &normal);
+        let depth  = ball.radius() - dist;
+
+        if depth > -prediction {
+            out.push(Contact::new(center + normal * -dist, center + normal * -ball.radius(), normal, depth))
+        }
+    }
+}
+
+fn add_scene(world: &mut World) -> Vec<RigidBodyHandle> {
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    let mut balls = Vec::new();
+
+    for i in range(0u, 5) {
+        let mut rb = RigidBody::new_dynamic(Ball3::new(0.5f32), 1.0, 0.3, 0.6);
+
+        rb.append_translation(&Vec3::new(i as f32 * 2.0, 1.0 + i as f32, 0.0));
+        rb.set_lin_vel(Vec3::new(1.0, 0.0, 0.0));
+
+        balls.push(world.add_body(rb));
+    }
+
+    world.add_body(RigidBody::new_static(Plane3::new(Vec3::new(0.0f32, 1.0, 0.0)), 0.3, 0.6));
+
+    balls
+}
+
+fn main() {
+    let calls = Rc::new(Cell::new(0u));
+
+    let mut custom = World::new();
+    custom.register_contact_generator(TypeId::of::<Plane3<f32>>(), TypeId::of::<Ball3<f32>>(),
+                                      box PlaneBallGenerator { calls: calls.clone() });
+
+    let mut default = World::new();
+
+    let balls1 = add_scene(&mut custom);
+    let balls2 = add_scene(&mut default);
+
+    for _ in range(0u, 300) {
+        custom.step(0.016);
+        default.step(0.016);
+    }
+
+    if !check("custom contact generator never called", calls.get() != 0) {
+        return;
+    }
+
+    for (b1, b2) in balls1.iter().zip(balls2.iter()) {
+        let p1 = b1.borrow().position().translation();
+        let p2 = b2.borrow().position().translation();
+
+        if !check(format!("ball at {} with the custom contact generator instead of {}", p1, p2).as_slice(),
+                  na::approx_eq_eps(&p1, &p2, &1.0e-2)) {
+            return;
+        }
+    }
+
+    println!("The custom contact generator was called {} times.", calls.get());
+}
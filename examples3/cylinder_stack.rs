@@ -0,0 +1,63 @@
+/*!
+ * Checks that a stack of flat cylinders remains at rest.
+ *
+ * Deactivation is disabled so that the bodies are simulated during the whole run. The program
+ * exits with an error if any cylinder drifts sideways or tilts noticeably.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Vec3, Translation};
+use ncollide::shape::{Plane, Cylinder};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::{check, steps};
+
+mod harness;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    /*
+     * Plane
+     */
+    let geom = Plane::new(Vec3::new(0.0f32, 1.0, 0.0));
+
+    world.add_body(RigidBody::new_static(geom, 0.3, 0.6));
+
+    /*
+     * Three flat cylinders.
+     */
+    let mut bodies = Vec::new();
+
+    for i in range(0u, 3) {
+        let mut rb = RigidBody::new_dynamic(Cylinder::new(0.25f32, 0.5), 1.0, 0.3, 0.6);
+
+        rb.append_translation(&Vec3::new(0.0, 0.25 + 0.5 * i as f32, 0.0));
+        rb.set_deactivation_threshold(None);
+
+        bodies.push(world.add_body(rb));
+    }
+
+    steps(&mut world, 5000);
+
+    for (i, body) in bodies.iter().enumerate() {
+        let rb    = body.borrow();
+        let pos   = rb.position().translation();
+        let up    = na::rotate(rb.position(), &Vec3::new(0.0f32, 1.0, 0.0));
+        let drift = (pos.x * pos.x + pos.z * pos.z).sqrt();
+        let tilt  = up.y.min(1.0).acos();
+
+        println!("Cylinder {}: drift = {}, tilt = {} rad.", i, drift, tilt);
+
+        check("cylinder drift", drift <= 0.05);
+        check("cylinder tilt", tilt <= 0.05);
+    }
+}
@@ -0,0 +1,107 @@
+/*!
+ * Checks the changes of the world recorded from a signal handler.
+ *
+ * A ball falls on a static crate. When they get close, a proximity handler removes the crate and
+ * spawns four smaller boxes in its place. The crate must be removed exactly once, and the four
+ * pieces must be added exactly once and fall on the ground. The program exits with an error
+ * otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vec3, Translation};
+use ncollide::shape::{Plane, Ball, Cuboid};
+use ncollide::broad_phase::ProximitySignalHandler;
+use nphysics::world::{World, WorldEvent, WorldCommands};
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::check;
+
+mod harness;
+
+fn is(a: &RigidBodyHandle, b: &RigidBodyHandle) -> bool {
+    a.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>
+}
+
+struct CrateBreaker {
+    commands: Rc<RefCell<WorldCommands>>,
+    krate:    RigidBodyHandle,
+    pieces:   Rc<RefCell<Vec<RigidBodyHandle>>>
+}
+
+impl ProximitySignalHandler<RigidBodyHandle> for CrateBreaker {
+    fn handle_proximity(&mut self, b1: &RigidBodyHandle, b2: &RigidBodyHandle, started: bool) {
+        if !started || !self.pieces.borrow().is_empty() || !(is(b1, &self.krate) || is(b2, &self.krate)) {
+            return;
+        }
+
+        let center       = self.krate.borrow().position().translation();
+        let mut commands = self.commands.borrow_mut();
+
+        commands.remove_body(&self.krate);
+
+        for &(dx, dz) in [ (-0.5f32, -0.5f32), (-0.5, 0.5), (0.5, -0.5), (0.5, 0.5) ].iter() {
+            let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.4f32, 0.4, 0.4)), 1.0, 0.0, 0.5);
+
+            rb.append_translation(&(center + Vec3::new(dx, 0.0, dz)));
+
+            self.pieces.borrow_mut().push(commands.add_body(rb));
+        }
+    }
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.0, 0.5));
+
+    let mut krate = RigidBody::new_static(Cuboid::new(Vec3::new(1.0f32, 1.0, 1.0)), 0.0, 0.5);
+    krate.append_translation(&Vec3::new(0.0, 3.0, 0.0));
+    let krate = world.add_body(krate);
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+    ball.append_translation(&Vec3::new(0.0, 8.0, 0.0));
+    world.add_body(ball);
+
+    let pieces  = Rc::new(RefCell::new(Vec::new()));
+    let breaker = CrateBreaker {
+        commands: world.commands(),
+        krate:    krate.clone(),
+        pieces:   pieces.clone()
+    };
+
+    world.register_proximity_signal_handler("crate breaker", breaker);
+
+    let mut events = Vec::new();
+
+    for _ in range(0u, 300) {
+        world.step(0.016);
+        world.drain_events(&mut events);
+    }
+
+    check("crate hit", pieces.borrow().len() == 4);
+    check("no pending command", world.commands().borrow().pending().is_empty());
+    check("crate removed", !world.bodies().any(|b| is(b, &krate)));
+
+    let removed = events.iter().filter(|e| match **e {
+        WorldEvent::BodyRemoved(ref b) => is(b, &krate),
+        _                              => false
+    }).count();
+
+    check("crate removed once", removed == 1);
+
+    for piece in pieces.borrow().iter() {
+        let added = events.iter().filter(|e| match **e {
+            WorldEvent::BodyAdded(ref b) => is(b, piece),
+            _                            => false
+        }).count();
+
+        check("piece added once", added == 1);
+        check("piece in the world", world.bodies().any(|b| is(b, piece)));
+        check("piece on the ground", piece.borrow().position().translation().y < 0.5);
+    }
+}
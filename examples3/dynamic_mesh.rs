@@ -0,0 +1,95 @@
+/*!
+ * Checks that a mesh cannot be the shape of a dynamic body, and that its decomposition can.
+ *
+ * Adding a dynamic body with a `Mesh` shape must fail. The same L-shaped mesh decomposed into
+ * convex pieces is then dropped on a static mesh: it must come to rest on it instead of falling
+ * through. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::task;
+use std::sync::Arc;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Mesh, Mesh3, Shape3};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::utils;
+use harness::{check, steps};
+
+mod harness;
+
+// An extruded L-shaped polygon.
+fn l_shaped_mesh() -> Mesh3<f32> {
+    let profile = [
+        (0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 3.0), (0.0, 3.0)
+    ];
+    let n = profile.len();
+
+    let mut vertices = Vec::new();
+    let mut indices  = Vec::new();
+
+    for &(x, y) in profile.iter() {
+        vertices.push(Pnt3::new(x, y, -0.5));
+    }
+
+    for &(x, y) in profile.iter() {
+        vertices.push(Pnt3::new(x, y, 0.5));
+    }
+
+    for i in range(1u, n - 1) {
+        indices.push_all(&[0, i + 1, i]);
+        indices.push_all(&[n, n + i, n + i + 1]);
+    }
+
+    for i in range(0u, n) {
+        let j = (i + 1) % n;
+
+        indices.push_all(&[i, j, j + n]);
+        indices.push_all(&[i, j + n, i + n]);
+    }
+
+    Mesh::new(Arc::new(vertices), Arc::new(indices), None, None)
+}
+
+// A flat square made of two triangles.
+fn ground() -> Mesh3<f32> {
+    let vertices = vec!(Pnt3::new(-10.0, 0.0, -10.0), Pnt3::new(10.0, 0.0, -10.0),
+                        Pnt3::new(10.0, 0.0, 10.0),   Pnt3::new(-10.0, 0.0, 10.0));
+    let indices  = vec!(0, 2, 1, 0, 3, 2);
+
+    Mesh::new(Arc::new(vertices), Arc::new(indices), None, None)
+}
+
+fn main() {
+    let rejected = task::try(proc() {
+        let mut world = World::new();
+        let mesh      = Arc::new(box l_shaped_mesh() as Box<Shape3<f32>>);
+
+        world.add_body(RigidBody::new(mesh, Some((1.0, na::orig(), na::one())), 0.3, 0.6));
+    }).is_err();
+
+    check("dynamic body with a mesh shape accepted", rejected);
+
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    world.add_body(RigidBody::new_static(ground(), 0.0, 0.6));
+
+    let (compound, props) = utils::mesh_convex_decomposition(&l_shaped_mesh(), 0.03, 1, 1.0);
+    let mut rb            = RigidBody::new(Arc::new(box compound as Box<Shape3<f32>>), Some(props), 0.0, 0.6);
+
+    rb.append_translation(&Vec3::new(0.0, 2.0, 0.0));
+    rb.set_deactivation_threshold(None);
+
+    let body = world.add_body(rb);
+
+    steps(&mut world, 300);
+
+    let y = body.borrow().position().translation().y;
+
+    check(format!("decomposed mesh not resting on the ground, at {}", y).as_slice(),
+          y >= -0.2 && !world.contacts().is_empty());
+}
@@ -0,0 +1,68 @@
+/*!
+ * Checks that the orientation of a quickly spinning body does not drift.
+ *
+ * A cuboid spins at 200 rad/s around the `z` axis for 100000 steps, without gravity. Its rotation
+ * matrix must stay orthonormal, and its shape must not be scaled: the `z` extent of its AABB must
+ * stay constant, and the `xy` extents must remain in the range allowed by a rotation around `z`.
+ * The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::Vec3;
+use ncollide::shape::Cuboid;
+use ncollide::bounding_volume::HasAABB;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::check;
+
+mod harness;
+
+fn main() {
+    let mut world = World::new();
+
+    let half   = Vec3::new(1.0f32, 0.5, 0.25);
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(half), 1.0, 0.3, 0.6);
+
+    rb.set_ang_vel(Vec3::new(0.0, 0.0, 200.0));
+    rb.set_deactivation_threshold(None);
+
+    let body = world.add_body(rb);
+
+    // Bounds of the `xy` extents of the AABB of the cuboid rotated around `z`.
+    let min_xy = 2.0 * half.y;
+    let max_xy = 2.0 * (half.x * half.x + half.y * half.y).sqrt();
+
+    for i in range(0u, 100000) {
+        world.step(0.016);
+
+        let rb   = body.borrow();
+        let aabb = rb.shape_ref().aabb(rb.position());
+        let ext  = *aabb.maxs() - *aabb.mins();
+
+        // The images of the canonical basis must be orthonormal.
+        let x = na::rotate(rb.position(), &Vec3::x());
+        let y = na::rotate(rb.position(), &Vec3::y());
+        let z = na::rotate(rb.position(), &Vec3::z());
+
+        let orthonormal = (na::norm(&x) - 1.0).abs() < 1.0e-5 &&
+                          (na::norm(&y) - 1.0).abs() < 1.0e-5 &&
+                          (na::norm(&z) - 1.0).abs() < 1.0e-5 &&
+                          na::dot(&x, &y).abs() < 1.0e-5 &&
+                          na::dot(&y, &z).abs() < 1.0e-5 &&
+                          na::dot(&z, &x).abs() < 1.0e-5;
+        let unscaled    = (ext.z - 2.0 * half.z).abs() < 1.0e-4 &&
+                          ext.x > min_xy - 1.0e-4 && ext.x < max_xy + 1.0e-4 &&
+                          ext.y > min_xy - 1.0e-4 && ext.y < max_xy + 1.0e-4;
+
+        if !check(format!("orientation at step {}: basis {}, {}, {}, AABB extents {}", i, x, y, z, ext).as_slice(),
+                  orthonormal && unscaled) {
+            return;
+        }
+    }
+
+    println!("The orientation stayed orthonormal over 100000 steps.");
+}
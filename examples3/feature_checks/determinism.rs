@@ -0,0 +1,145 @@
+/*!
+ * Checks that the simulation gives the same results on every platform.
+ *
+ * A ball spinning quickly is thrown on a pyramid of boxes, next to a pair of boxes welded by a
+ * fixed joint. After a fixed number of steps, the positions of the bodies are hashed bit by bit.
+ * The hash must not depend on the addresses of the bodies, and must match the reference recorded
+ * in `determinism.hash`, which is computed with the `deterministic` feature and is the same on
+ * every platform. Run `determinism --record` to print the line to add to this file. The program
+ * exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::os;
+use std::mem;
+use na::{Vec3, Iso3, Translation};
+use ncollide::shape::{Plane, Ball, Cuboid};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::detection::joint::{Anchor, Fixed};
+use harness::check;
+
+#[path = "../harness.rs"]
+mod harness;
+
+static NUM_STEPS: uint = 500;
+
+static REFERENCE: &'static str = include_str!("determinism.hash");
+
+// Builds the scene. `garbage` live allocations are made between the bodies so that their
+// addresses change from one call to another.
+fn build(garbage: uint) -> (World, Vec<RigidBodyHandle>, Vec<Box<[u8, ..64]>>) {
+    let mut world  = World::new();
+    let mut bodies = Vec::new();
+    let mut heap   = Vec::new();
+
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    for level in range(0u, 4) {
+        for i in range(0u, 4 - level) {
+            let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+
+            rb.append_translation(&Vec3::new(i as f32 * 1.1 + level as f32 * 0.55, 0.5 + level as f32, 0.0));
+
+            for _ in range(0, garbage) {
+                heap.push(box [ 0u8, ..64 ]);
+            }
+
+            bodies.push(world.add_body(rb));
+        }
+    }
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 3.0, 0.5, 0.3);
+
+    ball.append_translation(&Vec3::new(-6.0, 2.0, 0.0));
+    ball.set_lin_vel(Vec3::new(12.0, 3.0, 0.0));
+    ball.set_ang_vel(Vec3::new(5.0, 20.0, -35.0));
+
+    bodies.push(world.add_body(ball));
+
+    let mut welded = Vec::new();
+
+    for i in range(0u, 2) {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.25, 0.25)), 1.0, 0.3, 0.6);
+
+        rb.append_translation(&Vec3::new(i as f32, 4.0, 4.0));
+        rb.append_rotation(&Vec3::new(0.3, 0.0, 0.2));
+        rb.set_ang_vel(Vec3::new(0.0, 4.0, 0.0));
+
+        welded.push(world.add_body(rb));
+    }
+
+    world.add_fixed(Fixed::new(Anchor::new(Some(welded[0].clone()), Iso3::new(Vec3::new(0.5, 0.0, 0.0), na::zero())),
+                               Anchor::new(Some(welded[1].clone()), Iso3::new(Vec3::new(-0.5, 0.0, 0.0), na::zero()))));
+
+    bodies.push_all(welded.as_slice());
+
+    (world, bodies, heap)
+}
+
+// FNV-1a hash of the bits of the positions of `bodies`.
+fn hash(bodies: &[RigidBodyHandle]) -> u64 {
+    let mut res = 0xcbf29ce484222325u64;
+
+    for b in bodies.iter() {
+        let rb = b.borrow();
+        let t  = rb.position().translation();
+        let r  = &rb.position().rotation;
+        // The third column of the rotation is the cross product of the two others.
+        let x  = *r * Vec3::new(1.0f32, 0.0, 0.0);
+        let y  = *r * Vec3::new(0.0f32, 1.0, 0.0);
+
+        for v in [ t.x, t.y, t.z, x.x, x.y, x.z, y.x, y.y, y.z ].iter() {
+            let bits: u32 = unsafe { mem::transmute(*v) };
+
+            for i in range(0u, 4) {
+                res = (res ^ ((bits >> (8 * i)) & 0xff) as u64) * 0x100000001b3;
+            }
+        }
+    }
+
+    res
+}
+
+fn simulate(garbage: uint) -> u64 {
+    let (mut world, bodies, _heap) = build(garbage);
+
+    for _ in range(0, NUM_STEPS) {
+        world.step(0.016);
+    }
+
+    hash(bodies.as_slice())
+}
+
+// The reference hash for `steps` steps, read from lines formatted as `<steps> <hexadecimal hash>`.
+fn reference(steps: uint) -> Option<u64> {
+    for line in REFERENCE.lines() {
+        let words: Vec<&str> = line.words().collect();
+
+        if words.len() == 2 && !words[0].starts_with("#") && from_str::<uint>(words[0]) == Some(steps) {
+            return std::num::from_str_radix::<u64>(words[1], 16);
+        }
+    }
+
+    None
+}
+
+fn main() {
+    let res = simulate(0);
+
+    if os::args().iter().any(|a| a.as_slice() == "--record") {
+        println!("{} {:x}", NUM_STEPS, res);
+        return;
+    }
+
+    check("independent of the addresses", simulate(7) == res);
+
+    match reference(NUM_STEPS) {
+        Some(expected) => check(format!("hash {:x} instead of {:x}", res, expected).as_slice(), res == expected),
+        None           => check(format!("no reference hash recorded for {} steps, got {:x}", NUM_STEPS, res).as_slice(), false)
+    }
+}
@@ -0,0 +1,91 @@
+/*!
+ * Checks that the steps of a settled scene do not reallocate the buffers of the world.
+ *
+ * The balls are kept awake so that every step does the full work. After a warm-up, the capacity
+ * of the buffers reused by the world from one step to the next must not grow anymore. The program
+ * exits with an error otherwise.
+ *
+ * This requires the `alloc_stats` feature of nphysics, enabled for the programs of this crate only.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Translation};
+use ncollide::shape::{Plane, Ball};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::{check, steps};
+
+#[path = "../harness.rs"]
+mod harness;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    /*
+     * Planes
+     */
+    let normals = [
+        Vec3::new(0.0f32, 1.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(-1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec3::new(0.0, 0.0, -1.0)
+    ];
+
+    for n in normals.iter() {
+        let mut rb = RigidBody::new_static(Plane::new(*n), 0.3, 0.6);
+
+        rb.append_translation(&(-*n * 8.0 + Vec3::new(0.0, 8.0, 0.0)));
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Balls
+     */
+    let num   = 15u;
+    let rad   = 0.5f32;
+    let shift = 2.0 * rad;
+
+    for i in range(0u, num) {
+        for j in range(0u, 10) {
+            for k in range(0u, num) {
+                let x = i as f32 * shift - 7.0;
+                let y = j as f32 * shift + rad;
+                let z = k as f32 * shift - 7.0;
+
+                let mut rb = RigidBody::new_dynamic(Ball::new(rad), 1.0, 0.3, 0.6);
+
+                rb.append_translation(&Vec3::new(x, y, z));
+                rb.set_deactivation_threshold(None);
+
+                world.add_body(rb);
+            }
+        }
+    }
+
+    /*
+     * Let the balls settle.
+     */
+    steps(&mut world, 1000);
+
+    let capacity = world.buffers_capacity();
+
+    for i in range(0u, 500) {
+        world.step(0.016);
+
+        if !check(format!("buffer growth at step {}: {} -> {}", i, capacity, world.buffers_capacity()).as_slice(),
+                  world.buffers_capacity() == capacity) {
+            return;
+        }
+    }
+
+    println!("No buffer growth over 500 steps (total capacity: {}).", capacity);
+}
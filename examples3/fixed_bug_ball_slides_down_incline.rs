@@ -0,0 +1,80 @@
+/*!
+ * # Expected behaviour:
+ * A solid ball released on a 30° incline with a friction coefficient of 0.5 rolls without
+ * slipping. Its acceleration along the slope is then `5/7·g·sin(30°)`. The acceleration measured
+ * over 2 seconds must be within 5% of this value. The program exits with an error otherwise.
+ *
+ * # Symptoms:
+ * The ball slides down instead of rolling, with an acceleration close to the one of a frictionless
+ * body.
+ *
+ * # Cause:
+ * The friction was not solved during the iterations where the normal impulse of the contact was
+ * zero. The friction impulse used for warm-starting was then left applied without being bounded,
+ * and the tangential velocity of the contact point was not corrected.
+ *
+ * # Solution:
+ * The friction is solved at each iteration, with bounds set to zero when there is no normal
+ * impulse.
+ *
+ * # Limitations of the solution:
+ * The ball still slips during the few steps it takes to settle on the incline, so the acceleration
+ * is only measured afterward.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::f32;
+use std::num::{Float, FloatMath};
+use na::{Vec3, Translation};
+use ncollide::shape::{Plane, Ball};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::check;
+
+mod harness;
+
+fn main() {
+    let g        = 9.81f32;
+    let dt       = 0.016f32;
+    let angle    = f32::consts::PI / 6.0;
+    let normal   = Vec3::new(-angle.sin(), angle.cos(), 0.0);
+    let gravity  = Vec3::new(0.0, -g, 0.0);
+    let downhill = na::normalize(&(gravity - normal * na::dot(&gravity, &normal)));
+    let expected = 5.0 / 7.0 * g * angle.sin();
+
+    let mut world = World::new();
+    world.set_gravity(gravity);
+
+    // The friction coefficient of a contact is the product of those of its bodies.
+    world.add_body(RigidBody::new_static(Plane::new(normal), 0.0, 0.5));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 1.0);
+
+    rb.append_translation(&(normal * (0.5 + rb.margin() * 2.0)));
+    rb.set_deactivation_threshold(None);
+
+    let ball = world.add_body(rb);
+
+    // Let the ball settle on the incline.
+    for _ in range(0u, 30) {
+        world.step(dt);
+    }
+
+    let v0 = na::dot(&ball.borrow().lin_vel(), &downhill);
+
+    let num_steps = (2.0 / dt) as uint;
+
+    for _ in range(0u, num_steps) {
+        world.step(dt);
+    }
+
+    let v1     = na::dot(&ball.borrow().lin_vel(), &downhill);
+    let actual = (v1 - v0) / (num_steps as f32 * dt);
+
+    println!("Acceleration along the slope: {}, expected: {}.", actual, expected);
+
+    check("rolling without slipping", (actual - expected).abs() <= expected * 0.05);
+}
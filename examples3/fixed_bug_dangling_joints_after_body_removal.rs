@@ -0,0 +1,95 @@
+/*!
+ * # Expected behaviour:
+ * A chain of balls hangs from a fixed point and swings. The middle link has been removed after
+ * one second of simulation: the upper half of the chain keeps swinging and the lower half falls on
+ * the ground.
+ *
+ * # Symptoms:
+ * The lower half of the chain stays attached to an invisible body, or the simulation panics.
+ *
+ * # Cause:
+ * Removing a body did not remove its joints from the joint manager, so the solver kept using
+ * joints attached to a deleted body.
+ *
+ * # Solution:
+ * The joints attached to a removed body are now removed as well, together with their references
+ * on the other attached body.
+ *
+ * # Limitations of the solution:
+ * None.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Ball, Plane};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::detection::joint::{Anchor, BallInSocket};
+use nphysics_testbed3d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    /*
+     * Plane
+     */
+    let geom = Plane::new(Vec3::new(0.0, 1.0, 0.0));
+
+    world.add_body(RigidBody::new_static(geom, 0.3, 0.6));
+
+    /*
+     * Create the chain
+     */
+    let num    = 10u;
+    let rad    = 0.2;
+    let height = 10.0;
+    let mut links = Vec::new();
+
+    for i in range(0u, num) {
+        let mut rb = RigidBody::new_dynamic(Ball::new(rad), 1.0, 0.3, 0.6);
+
+        rb.append_translation(&Vec3::new((i + 1) as f32 * rad * 2.0, height, 0.0));
+
+        links.push(world.add_body(rb));
+    }
+
+    let ground_anchor = Anchor::new(None, Pnt3::new(0.0, height, 0.0));
+    let first_anchor  = Anchor::new(Some(links[0].clone()), Pnt3::new(-rad * 2.0, 0.0, 0.0));
+    world.add_ball_in_socket(BallInSocket::new(ground_anchor, first_anchor));
+
+    for i in range(1u, num) {
+        let anchor1 = Anchor::new(Some(links[i - 1].clone()), Pnt3::new(rad, 0.0, 0.0));
+        let anchor2 = Anchor::new(Some(links[i].clone()), Pnt3::new(-rad, 0.0, 0.0));
+
+        world.add_ball_in_socket(BallInSocket::new(anchor1, anchor2));
+    }
+
+    /*
+     * Let the chain swing, then remove the middle link.
+     */
+    for _ in range(0u, 60) {
+        world.step(0.016);
+    }
+
+    world.remove_body(&links[num / 2]);
+
+    assert!(world.joints_attached_to(&links[num / 2]).is_empty());
+    assert!(world.joints_attached_to(&links[num / 2 - 1]).len() == 1);
+    assert!(world.joints_attached_to(&links[num / 2 + 1]).len() == 1);
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.look_at(Pnt3::new(10.0, 10.0, 10.0), Pnt3::new(2.0, 5.0, 0.0));
+    testbed.run();
+}
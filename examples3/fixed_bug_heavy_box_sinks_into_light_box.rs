@@ -0,0 +1,93 @@
+/*!
+ * # Expected behaviour:
+ * A box resting on a box one thousand times lighter stays on top of it. During ten seconds, the
+ * penetration between both boxes remains smaller than the penetration tolerated by the solver. The
+ * program exits with an error otherwise.
+ *
+ * # Symptoms:
+ * The heavy box slowly sinks through the light one within a few seconds. Increasing the number of
+ * solver iterations barely helps.
+ *
+ * # Cause:
+ * The projected Gauss-Seidel solver converges very slowly when the masses of the bodies in contact
+ * are very different: the impulses needed to support the heavy box are never reached.
+ *
+ * # Solution:
+ * The world can perform a shock propagation, enabled with `set_shock_propagation`. The contacts are
+ * solved a last time from the bottom to the top of the stack, each lower body being considered
+ * immovable.
+ *
+ * # Limitations of the solution:
+ * The shock propagation is disabled by default since it alters the behaviour of stacks hit from
+ * the side or from below.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::detection::constraint::Constraint;
+use harness::check;
+
+mod harness;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+    world.set_shock_propagation(true);
+
+    /*
+     * Plane
+     */
+    let geom = Plane::new(Vec3::new(0.0f32, 1.0, 0.0));
+
+    world.add_body(RigidBody::new_static(geom, 0.3, 0.6));
+
+    /*
+     * The light pallet and the heavy crate.
+     */
+    let rad = 0.5f32;
+
+    let mut light = RigidBody::new_dynamic(Cuboid::new(Vec3::new(rad, rad, rad)), 1.0, 0.3, 0.6);
+    light.append_translation(&Vec3::new(0.0, rad, 0.0));
+    world.add_body(light);
+
+    let mut heavy = RigidBody::new_dynamic(Cuboid::new(Vec3::new(rad, rad, rad)), 1000.0, 0.3, 0.6);
+    heavy.append_translation(&Vec3::new(0.0, 3.0 * rad, 0.0));
+    world.add_body(heavy);
+
+    let slop          = 0.08f32;
+    let mut max_depth = 0.0f32;
+    let mut contacts  = Vec::new();
+
+    for _ in range(0u, 625) {
+        world.step(0.016);
+
+        world.interferences(&mut contacts);
+
+        for c in contacts.iter() {
+            match *c {
+                Constraint::RBRB(ref b1, ref b2, ref c) => {
+                    // The only contact between two dynamic bodies is the one between the boxes.
+                    if b1.borrow().can_move() && b2.borrow().can_move() {
+                        max_depth = max_depth.max(c.depth)
+                    }
+                },
+                _ => { }
+            }
+        }
+
+        contacts.clear();
+    }
+
+    println!("Maximum penetration: {} (tolerance: {}).", max_depth, slop);
+
+    check("penetration", max_depth <= slop);
+}
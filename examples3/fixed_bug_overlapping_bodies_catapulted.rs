@@ -0,0 +1,70 @@
+/*!
+ * # Expected behaviour:
+ * The two cubes start half-overlapped. They should separate gently over a few steps and end up
+ * resting side by side, with a velocity close to zero.
+ *
+ * # Symptoms:
+ * On the first step, the two cubes are violently pushed apart and fly away.
+ *
+ * # Cause:
+ * The penetration depth correction was converted into a separating velocity proportional to the
+ * depth, without any limit. Deep initial overlaps thus injected a huge velocity into the bodies.
+ *
+ * # Solution:
+ * The velocity injected by the penetration correction can now be bounded with
+ * `World::set_max_correction_velocity`. The remaining penetration is resolved by the position
+ * correction over the next steps.
+ *
+ * # Limitations of the solution:
+ * A very deep overlap may take a lot of steps to be resolved if the maximum correction velocity is
+ * too small.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics_testbed3d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.set_max_correction_velocity(1.0);
+
+    /*
+     * Plane
+     */
+    let geom = Plane::new(Vec3::new(0.0, 1.0, 0.0));
+
+    world.add_body(RigidBody::new_static(geom, 0.3, 0.6));
+
+    /*
+     * Create the overlapping cubes
+     */
+    let rad = 0.5;
+
+    for i in range(0u, 2) {
+        let geom   = Cuboid::new(Vec3::new(rad - 0.04, rad - 0.04, rad - 0.04));
+        let mut rb = RigidBody::new_dynamic(geom, 1.0, 0.3, 0.5);
+
+        rb.append_translation(&Vec3::new(i as f32 * rad, rad, 0.0));
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.look_at(Pnt3::new(-5.0, 5.0, -5.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
@@ -0,0 +1,125 @@
+/*!
+ * # Expected behaviour:
+ * A pyramid of bouncy frictionless balls enclosed in a box never gains energy: the total energy
+ * after each step is at most the one of the previous step. The program exits with an error
+ * otherwise.
+ *
+ * # Symptoms:
+ * The total kinetic energy slowly increases until the pyramid flies apart, without any external
+ * input.
+ *
+ * # Cause:
+ * The restitution was applied on top of the velocity used to correct penetrations. Thus, each
+ * bounce of a slightly penetrating ball was faster than its approach.
+ *
+ * # Solution:
+ * The separating velocity due to the restitution is now computed from the approach velocity only
+ * and never exceeds it. The penetration correction is not added to it but replaces it when it is
+ * larger.
+ *
+ * # Limitations of the solution:
+ * The position-based penetration correction may still slightly increase the potential energy. This
+ * is accounted for by a small tolerance.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Translation};
+use ncollide::shape::{Plane, Ball};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::check;
+
+mod harness;
+
+fn energy(bodies: &[RigidBodyHandle], gravity: f32) -> f32 {
+    let mut energy = 0.0;
+
+    for body in bodies.iter() {
+        let rb      = body.borrow();
+        let mass    = 1.0 / rb.inv_mass();
+        let inertia = na::inv(rb.inv_inertia()).unwrap();
+        let ang_vel = rb.ang_vel();
+
+        energy = energy + 0.5 * mass * na::sqnorm(&rb.lin_vel());
+        energy = energy + 0.5 * na::dot(&ang_vel, &(inertia * ang_vel));
+        energy = energy + mass * gravity * rb.center_of_mass().y;
+    }
+
+    energy
+}
+
+fn main() {
+    let gravity = 9.81f32;
+
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -gravity, 0.0));
+
+    /*
+     * A closed box.
+     */
+    let normals = [
+        Vec3::new(0.0f32, 1.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(-1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec3::new(0.0, 0.0, -1.0),
+        Vec3::new(0.0, -1.0, 0.0)
+    ];
+
+    for n in normals.iter() {
+        let mut rb = RigidBody::new_static(Plane::new(*n), 0.9, 0.0);
+
+        rb.append_translation(&(-*n * 5.0 + Vec3::new(0.0, 5.0, 0.0)));
+
+        world.add_body(rb);
+    }
+
+    /*
+     * The pyramid of balls.
+     */
+    let rad       = 0.5f32;
+    let num       = 4u;
+    let mut balls = Vec::new();
+
+    for i in range(0u, num) {
+        for j in range(i, num) {
+            for k in range(i, num) {
+                let fi = i as f32;
+                let fj = j as f32;
+                let fk = k as f32;
+
+                let mut rb = RigidBody::new_dynamic(Ball::new(rad), 1.0, 0.9, 0.0);
+
+                rb.append_translation(&Vec3::new((fj - 0.5 * fi - 1.5) * 2.0 * rad,
+                                                 rad + fi * 2.0 * rad,
+                                                 (fk - 0.5 * fi - 1.5) * 2.0 * rad));
+                rb.set_deactivation_threshold(None);
+
+                balls.push(world.add_body(rb));
+            }
+        }
+    }
+
+    let tolerance = 1.0e-3 * energy(balls.as_slice(), gravity).abs();
+    let mut prev  = energy(balls.as_slice(), gravity);
+
+    for i in range(0u, 5000) {
+        world.step(0.016);
+
+        let curr = energy(balls.as_slice(), gravity);
+
+        if !check(format!("energy gain at step {}: {} -> {}", i, prev, curr).as_slice(), curr <= prev + tolerance) {
+            return;
+        }
+
+        prev = curr;
+    }
+
+    println!("No energy gain over 5000 steps (final energy: {}).", prev);
+}
@@ -0,0 +1,72 @@
+/*!
+ * Checks that a box resting on a slope does not creep downhill.
+ *
+ * A box is left on a 20° incline with a friction coefficient of 0.6, which is enough to hold it.
+ * It is kept awake so that it is simulated at each step. Once it has settled, it must move by
+ * less than 1 mm over 10,000 steps. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::f32;
+use std::num::FloatMath;
+use na::{Vec3, Translation, Rotation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::check;
+
+mod harness;
+
+fn main() {
+    let dt     = 0.016f32;
+    let angle  = 20.0 * f32::consts::PI / 180.0;
+    let normal = Vec3::new(-angle.sin(), angle.cos(), 0.0);
+
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.set_friction_anchor_max_drift(Some(0.005));
+
+    // The friction coefficient of a contact is the product of those of its bodies.
+    world.add_body(RigidBody::new_static(Plane::new(normal), 0.0, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 1.0);
+
+    rb.append_rotation(&Vec3::new(0.0, 0.0, angle));
+    rb.append_translation(&(normal * (0.5 + rb.margin() * 2.0)));
+    rb.set_deactivation_threshold(None);
+
+    let body = world.add_body(rb);
+
+    // Let the box settle on the incline.
+    for _ in range(0u, 100) {
+        world.step(dt);
+    }
+
+    let start = body.borrow().position().translation();
+
+    for _ in range(0u, 10000) {
+        world.step(dt);
+    }
+
+    let creep = na::norm(&(body.borrow().position().translation() - start));
+
+    println!("Distance crept over 10,000 steps: {}.", creep);
+
+    check("awake box", body.borrow().is_active());
+    check("creep", creep < 1.0e-3);
+
+    // Without the anchors, only the velocity is cancelled at each step.
+    world.set_friction_anchor_max_drift(None);
+
+    let start = body.borrow().position().translation();
+
+    for _ in range(0u, 10000) {
+        world.step(dt);
+    }
+
+    println!("Distance crept without the friction anchors: {}.",
+             na::norm(&(body.borrow().position().translation() - start)));
+}
@@ -0,0 +1,81 @@
+/*!
+ * Checks the friction impulses reported for each contact.
+ *
+ * A box rests on a 20° incline where friction holds it. Once it has settled, the friction impulses
+ * of its contacts must balance the part of its weight along the slope, and the normal impulses the
+ * part orthogonal to it. The friction impulses must be orthogonal to the contact normals, and their
+ * components along the reported tangents must add up to them. The program exits with an error
+ * otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::f32;
+use std::num::{Float, FloatMath};
+use std::cell::RefCell;
+use na::{Vec3, Translation, Rotation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::check;
+
+mod harness;
+
+fn main() {
+    let g      = 9.81f32;
+    let dt     = 0.016f32;
+    let angle  = 20.0 * f32::consts::PI / 180.0;
+    let normal = Vec3::new(-angle.sin(), angle.cos(), 0.0);
+
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -g, 0.0));
+
+    world.add_body(RigidBody::new_static(Plane::new(normal), 0.0, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 1.0);
+
+    rb.append_rotation(&Vec3::new(0.0, 0.0, angle));
+    rb.append_translation(&(normal * (0.5 + rb.margin() * 2.0)));
+    rb.set_deactivation_threshold(None);
+
+    let body = world.add_body(rb);
+    let mass = 1.0 / body.borrow().inv_mass();
+
+    for _ in range(0u, 200) {
+        world.step(dt);
+    }
+
+    let mut total_normal: f32         = 0.0;
+    let mut total_friction: Vec3<f32> = na::zero();
+
+    for c in world.contacts_with(&body) {
+        // The impulses are applied to the second body, the box is the first one here.
+        let is_body2 = c.body2.deref() as *const RefCell<RigidBody> == body.deref() as *const RefCell<RigidBody>;
+        let sign     = if is_body2 { 1.0f32 } else { -1.0 };
+
+        total_normal   = total_normal + c.normal_impulse;
+        total_friction = total_friction + c.friction_impulse * sign;
+
+        let components = c.friction_impulses();
+        let tangents   = c.tangents();
+        let mut sum: Vec3<f32> = na::zero();
+
+        for (t, i) in tangents.iter().zip(components.iter()) {
+            sum = sum + *t * *i;
+        }
+
+        check("orthogonal to the normal",
+              na::dot(&c.friction_impulse, &c.contact.normal).abs() < 1.0e-4);
+        check("tangent components", na::approx_eq_eps(&sum, &c.friction_impulse, &1.0e-4));
+    }
+
+    let uphill   = na::normalize(&Vec3::new(angle.cos(), angle.sin(), 0.0));
+    let expected = mass * g * angle.sin() * dt;
+
+    println!("Friction impulse along the slope: {}, expected: {}.", na::dot(&total_friction, &uphill), expected);
+
+    check("friction balances the weight", (na::dot(&total_friction, &uphill) - expected).abs() < expected * 0.05);
+    check("normal balances the weight", (total_normal - mass * g * angle.cos() * dt).abs() < mass * g * dt * 0.05);
+}
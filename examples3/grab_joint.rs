@@ -0,0 +1,107 @@
+/*!
+ * Checks the grab joint.
+ *
+ * A ball grabbed by a point of its surface must follow a target moving in circle, and come to rest
+ * at the target once it stops. A box grabbed with a small maximum force must not be lifted off the
+ * ground, and must be dropped once its joint is removed. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::detection::joint::{Grab, Joint};
+use harness::check;
+
+mod harness;
+
+static DT: f32 = 0.016;
+
+fn main() {
+    /*
+     * Target moving in circle.
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.append_translation(&Vec3::new(2.0, 5.0, 0.0));
+    let ball = world.add_body(rb);
+
+    let target = |t: f32| Pnt3::new(2.0 * t.cos(), 5.0, 2.0 * t.sin());
+    let grab   = world.add_grab(Grab::new(ball.clone(), Pnt3::new(0.0, 0.5, 0.0), target(0.0)));
+
+    let mut max_error = 0.0f32;
+
+    for i in range(0u, 500) {
+        grab.borrow_mut().set_target(target(i as f32 * DT));
+        world.step(DT);
+
+        if i > 50 {
+            let g = grab.borrow();
+            max_error = max_error.max(na::dist(&g.anchor1_pos(), &g.anchor2_pos()));
+        }
+    }
+
+    check(format!("circle: the ball lags {} behind the target", max_error).as_slice(), max_error < 0.3);
+
+    for _ in range(0u, 200) {
+        world.step(DT);
+    }
+
+    {
+        let g = grab.borrow();
+        check("rest: the ball reaches the target", na::dist(&g.anchor1_pos(), &g.anchor2_pos()) < 0.05);
+    }
+
+    check("rest: the ball hangs below its grabbed point", ball.borrow().center_of_mass().y < 5.0);
+
+    world.remove_grab(&grab);
+    check("removed joint", world.joints().count() == 0);
+
+    /*
+     * Bounded force.
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.append_translation(&Vec3::new(0.0, 0.5, 0.0));
+    let cube = world.add_body(rb);
+
+    let mass = 1.0 / cube.borrow().inv_mass();
+    let mut joint = Grab::new(cube.clone(), na::orig(), Pnt3::new(0.0, 10.0, 0.0));
+
+    // Half of the weight: the cube must not leave the ground.
+    joint.set_max_force(Some(mass * 9.81 * 0.5));
+
+    let grab = world.add_grab(joint);
+
+    for _ in range(0u, 200) {
+        world.step(DT);
+    }
+
+    check("bounded force: the cube is lifted", cube.borrow().center_of_mass().y < 0.6);
+
+    grab.borrow_mut().set_max_force(None);
+
+    for _ in range(0u, 200) {
+        world.step(DT);
+    }
+
+    check("unbounded force: the cube is not lifted", cube.borrow().center_of_mass().y > 9.0);
+
+    world.remove_grab(&grab);
+
+    for _ in range(0u, 200) {
+        world.step(DT);
+    }
+
+    check("released: the cube does not fall", cube.borrow().center_of_mass().y < 0.6);
+}
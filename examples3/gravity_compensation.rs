@@ -0,0 +1,116 @@
+/*!
+ * Checks the gravity compensation of the joints of an articulation.
+ *
+ * A three-link arm hanging from the ground, driven by servos with near-zero gains, must hold any
+ * pose when its joints compensate gravity, and must fall otherwise. The program exits with an
+ * error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Pnt3, Vec3, Iso3, Rotation, Translation};
+use ncollide::shape::Cuboid;
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::detection::joint::{Anchor, BallInSocket, Articulation};
+use nphysics::integration::TorsionSpring;
+use harness::{check, steps};
+
+mod harness;
+
+// Builds an arm which links have the orientations `pose`, and returns them.
+fn arm(world: &mut World, pose: &[Vec3<f32>], compensated: bool) -> Vec<RigidBodyHandle> {
+    let articulation = world.add_articulation(Articulation::new());
+    let mut links: Vec<RigidBodyHandle> = Vec::new();
+    let mut pivot = Pnt3::new(0.0f32, 5.0, 0.0);
+
+    for rot in pose.iter() {
+        let dir    = na::rotate(&Iso3::new(na::zero(), *rot), &Vec3::x());
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.1, 0.1)), 1.0, 0.3, 0.5);
+        rb.set_transformation(Iso3::new(*(pivot + dir * 0.5f32).as_vec(), *rot));
+        rb.set_deactivation_threshold(None);
+
+        let link   = world.add_body(rb);
+        let start  = Pnt3::new(-0.5f32, 0.0, 0.0);
+        let (parent, parent_anchor, frame) = match links.last() {
+            Some(parent) => {
+                let pos   = parent.borrow().position().clone();
+                let frame = na::inv(&pos).unwrap() * *link.borrow().position();
+
+                (Some(parent.clone()), Pnt3::new(0.5f32, 0.0, 0.0), frame)
+            },
+            None => (None, pivot, link.borrow().position().clone())
+        };
+
+        let mut joint = BallInSocket::new(Anchor::new(parent.clone(), parent_anchor), Anchor::new(Some(link.clone()), start));
+        joint.set_gravity_compensation(compensated);
+        world.add_ball_in_socket_to(&articulation, joint);
+
+        // The servo holding the pose, too weak to fight gravity.
+        world.add_force_generator(TorsionSpring::new(
+            Anchor::new(parent, frame),
+            Anchor::new(Some(link.clone()), na::one()),
+            0.01, 0.01, 100.0));
+
+        pivot = pivot + dir;
+        links.push(link);
+    }
+
+    links
+}
+
+// The largest displacement and rotation of `links` since the positions `initial`.
+fn drift(links: &[RigidBodyHandle], initial: &[Iso3<f32>]) -> (f32, f32) {
+    let mut res = (0.0f32, 0.0f32);
+
+    for (link, init) in links.iter().zip(initial.iter()) {
+        let pos   = link.borrow().position().clone();
+        let shift = na::norm(&(pos.translation() - init.translation()));
+        let angle = na::norm(&(na::inv(init).unwrap() * pos).rotation());
+
+        res = (res.val0().max(shift), res.val1().max(angle));
+    }
+
+    res
+}
+
+fn main() {
+    let poses = [
+        [ Vec3::new(0.0f32, 0.0, 0.3), Vec3::new(0.0, 0.0, -0.8), Vec3::new(0.0, 0.0, 1.2) ],
+        [ Vec3::new(0.0f32, 0.7, -0.2), Vec3::new(0.4, -0.5, 0.9), Vec3::new(0.0, 1.5, 0.0) ]
+    ];
+
+    for pose in poses.iter() {
+        /*
+         * The compensated arm holds its pose.
+         */
+        let mut world = World::new();
+        world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+        let links   = arm(&mut world, pose.as_slice(), true);
+        let initial: Vec<Iso3<f32>> = links.iter().map(|l| l.borrow().position().clone()).collect();
+
+        steps(&mut world, 300);
+
+        let (shift, angle) = drift(links.as_slice(), initial.as_slice());
+
+        check("compensated arm moved", shift < 0.05);
+        check("compensated arm rotated", angle < 0.05);
+
+        /*
+         * The same arm falls without compensation.
+         */
+        let mut world = World::new();
+        world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+        let links = arm(&mut world, pose.as_slice(), false);
+
+        steps(&mut world, 300);
+
+        let (shift, _) = drift(links.as_slice(), initial.as_slice());
+
+        check("uncompensated arm held", shift > 0.5);
+    }
+}
@@ -0,0 +1,92 @@
+/*!
+ * Checks the detection of the bodies not supported by the ground.
+ *
+ * A bridge deck made of three spans of welded boxes rests on three static pillars, with a loose box
+ * lying on two of the spans. Everything is grounded until the middle pillar is removed: then, the
+ * middle span and the box lying on it must be the only ungrounded island. The program exits with
+ * an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::cell::RefCell;
+use na::{Vec3, Iso3, Translation};
+use ncollide::shape::Cuboid;
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::detection::joint::{Anchor, Fixed};
+use harness::check;
+
+mod harness;
+
+fn is(a: &RigidBodyHandle, b: &RigidBodyHandle) -> bool {
+    a.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>
+}
+
+fn add_box(world: &mut World, x: f32, y: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(x, y, 0.0));
+
+    world.add_body(rb)
+}
+
+// Adds three boxes welded side by side, the middle one lying on the pillar at `x`.
+fn add_span(world: &mut World, x: f32) -> Vec<RigidBodyHandle> {
+    let span: Vec<RigidBodyHandle> = range(0u, 3).map(|i| add_box(world, x + i as f32 - 1.0, 2.49)).collect();
+
+    for i in range(1u, 3) {
+        world.add_fixed(Fixed::new(Anchor::new(Some(span[i - 1].clone()), Iso3::new(Vec3::new(0.5, 0.0, 0.0), na::zero())),
+                                   Anchor::new(Some(span[i].clone()), Iso3::new(Vec3::new(-0.5, 0.0, 0.0), na::zero()))));
+    }
+
+    span
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    let mut pillars = Vec::new();
+
+    for x in [ -4.0f32, 0.0, 4.0 ].iter() {
+        let mut pillar = RigidBody::new_static(Cuboid::new(Vec3::new(0.5f32, 1.0, 0.5)), 0.0, 0.5);
+        pillar.append_translation(&Vec3::new(*x, 1.0, 0.0));
+        pillars.push(world.add_body(pillar));
+    }
+
+    let left     = add_span(&mut world, -4.0);
+    let middle   = add_span(&mut world, 0.0);
+    let right    = add_span(&mut world, 4.0);
+    let left_top = add_box(&mut world, -4.0, 3.48);
+    let top      = add_box(&mut world, 0.0, 3.48);
+
+    world.step(0.016);
+
+    check("nothing ungrounded on the pillars", world.ungrounded_islands().is_empty());
+    check("middle span grounded", middle.iter().all(|b| world.is_grounded(b)));
+    check("pillar grounded", world.is_grounded(&pillars[1]));
+
+    world.remove_body(&pillars[1]);
+    world.step(0.016);
+
+    let islands = world.ungrounded_islands();
+
+    check("one ungrounded island", islands.len() == 1);
+
+    if islands.len() == 1 {
+        let island = &islands[0];
+
+        check("island size", island.len() == 4);
+        check("middle span in the island", middle.iter().all(|b| island.iter().any(|e| is(e, b))));
+        check("top box in the island", island.iter().any(|e| is(e, &top)));
+    }
+
+    check("middle span ungrounded", middle.iter().all(|b| !world.is_grounded(b)));
+    check("top box ungrounded", !world.is_grounded(&top));
+    check("left span grounded", left.iter().all(|b| world.is_grounded(b)));
+    check("right span grounded", right.iter().all(|b| world.is_grounded(b)));
+    check("box on the left span grounded", world.is_grounded(&left_top));
+}
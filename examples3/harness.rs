@@ -0,0 +1,39 @@
+//! The helpers shared by the console programs checking the behavior of the physics world.
+//!
+//! Include it with `mod harness;`, or with a `#[path]` attribute from another directory.
+
+#![allow(dead_code)]
+
+use std::os;
+use nphysics::world::World;
+
+/// Reports the failure of the check `name` unless `ok`, and makes the program exit with an error.
+///
+/// The message names the program, e.g., `ray_hits` reports `Wrong ray hits: <name>.`. Returns
+/// `ok`.
+pub fn check(name: &str, ok: bool) -> bool {
+    if !ok {
+        println!("Wrong {}: {}.", subject(), name);
+        os::set_exit_status(1);
+    }
+
+    ok
+}
+
+/// Performs `num` steps of 0.016 seconds.
+pub fn steps(world: &mut World, num: uint) {
+    for _ in range(0u, num) {
+        world.step(0.016);
+    }
+}
+
+// The name of the program, with spaces instead of underscores.
+fn subject() -> String {
+    let args = os::args();
+    let name = match args.as_slice().head() {
+        Some(arg) => Path::new(arg.as_slice()).filestem_str().unwrap_or("check").to_string(),
+        None      => "check".to_string()
+    };
+
+    name.replace("_", " ")
+}
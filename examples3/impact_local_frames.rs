@@ -0,0 +1,144 @@
+/*!
+ * Checks the local coordinates of the contact points and normals of the impact events.
+ *
+ * A ball thrown at a rotated box must hit it at a point of one of its faces, with a normal along
+ * the axis of that face, in the local coordinates of the box. They must map back to the global
+ * contact point and normal, and the relative velocity must be the one of the ball before the
+ * impact. The same must hold for the part of a compound body, in the coordinates of that part. The
+ * program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::sync::Arc;
+use std::cell::RefCell;
+use na::{Pnt3, Vec3, Iso3, Translation, Rotation};
+use ncollide::volumetric::Volumetric;
+use ncollide::shape::{Ball, Cuboid, Compound, CompoundData, Shape3};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::resolution::Impact;
+use harness::check;
+
+mod harness;
+
+fn same(a: &RigidBodyHandle, b: &RigidBodyHandle) -> bool {
+    a.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>
+}
+
+// Whether `p` lies on a face of the cuboid with the half extents `half`, up to its margin.
+fn on_face(p: &Pnt3<f32>, half: &Vec3<f32>) -> bool {
+    let ratios = [ p.x.abs() / half.x, p.y.abs() / half.y, p.z.abs() / half.z ];
+    let max    = ratios.iter().fold(0.0f32, |a, b| a.max(*b));
+
+    max > 0.9 && max < 1.1
+}
+
+// Whether `n` is along one of the coordinate axes.
+fn face_normal(n: &Vec3<f32>) -> bool {
+    n.x.abs() > 0.99 || n.y.abs() > 0.99 || n.z.abs() > 0.99
+}
+
+// Throws a ball at `target` with the velocity `vel`, and returns the first impact between them,
+// the position of `target` just after it, and whether `target` is its first body.
+fn throw(world: &mut World, target: &RigidBodyHandle, from: Vec3<f32>, vel: Vec3<f32>)
+         -> Option<(Impact, Iso3<f32>, bool)> {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.5, 0.5);
+    rb.append_translation(&from);
+    rb.set_lin_vel(vel);
+
+    let ball       = world.add_body(rb);
+    let mut events = Vec::new();
+
+    for _ in range(0u, 200) {
+        world.step(0.016);
+        world.drain_events(&mut events);
+
+        for event in events.iter() {
+            match *event {
+                WorldEvent::Impact(ref impact) => {
+                    let first = same(&impact.body1, target) && same(&impact.body2, &ball);
+
+                    if first || (same(&impact.body1, &ball) && same(&impact.body2, target)) {
+                        return Some((impact.clone(), target.borrow().position().clone(), first))
+                    }
+                },
+                _ => { }
+            }
+        }
+
+        events.clear();
+    }
+
+    None
+}
+
+fn main() {
+    /*
+     * A ball thrown at a rotated box.
+     */
+    let mut world = World::new();
+    world.set_impact_event_threshold(0.1);
+
+    let half   = Vec3::new(1.0f32, 1.5, 0.8);
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(half), 1.0, 0.5, 0.5);
+    rb.append_rotation(&Vec3::new(0.0, 0.2, 0.3));
+    let cube = world.add_body(rb);
+    let vel  = Vec3::new(10.0, 0.0, 0.0);
+
+    match throw(&mut world, &cube, Vec3::new(-5.0, 0.4, 0.0), vel) {
+        Some((impact, pos, first)) => {
+            let (point, normal) = if first { (impact.local_point1, impact.local_normal1) }
+                                  else     { (impact.local_point2, impact.local_normal2) };
+
+            check("point on the box", on_face(&point, &half));
+            check("normal of a face", face_normal(&normal));
+            check("point in global coordinates", na::norm(&(na::transform(&pos, &point) - impact.point)) < 0.05);
+            check("normal in global coordinates", na::norm(&(na::rotate(&pos, &normal) - impact.normal)) < 0.05);
+
+            // The box is at rest, and the ball does not spin before the impact.
+            let expected = if first { vel } else { -vel };
+
+            check("relative velocity", na::norm(&(impact.relative_velocity - expected)) < 1.0e-3);
+            check("approach velocity",
+                  (na::dot(&impact.relative_velocity, &impact.normal) + impact.approach_velocity).abs() < 0.1);
+        },
+        None => check("impact with the box", false)
+    }
+
+    /*
+     * A ball thrown at the rotated and shifted part of a compound body.
+     */
+    let mut world = World::new();
+    world.set_impact_event_threshold(0.1);
+
+    let part_half  = Vec3::new(0.5f32, 0.5, 0.5);
+    let delta      = Iso3::new(Vec3::new(0.0f32, 3.0, 0.0), Vec3::new(0.0, 0.0, 0.6));
+    let mut shapes = CompoundData::new();
+    shapes.push_shape(na::one(), Cuboid::new(Vec3::new(1.0f32, 1.0, 1.0)), 1.0);
+    shapes.push_shape(delta, Cuboid::new(part_half), 1.0);
+
+    let compound = Compound::new(shapes);
+    let mass     = compound.mass_properties(1.0);
+    let shape    = Arc::new(box compound as Box<Shape3<f32>>);
+    let body     = world.add_body(RigidBody::new(shape, Some(mass), 0.5, 0.5));
+
+    match throw(&mut world, &body, Vec3::new(-5.0, 3.0, 0.0), vel) {
+        Some((impact, pos, first)) => {
+            let (sub_shape, point, normal) =
+                if first { (impact.sub_shape1.clone(), impact.part_point1, impact.part_normal1) }
+                else     { (impact.sub_shape2.clone(), impact.part_point2, impact.part_normal2) };
+            let local = if first { impact.local_point1 } else { impact.local_point2 };
+
+            check("part hit", sub_shape == vec![1u]);
+            check("point on the part", on_face(&point, &part_half));
+            check("normal of a face of the part", face_normal(&normal));
+            check("point in body coordinates", na::norm(&(na::transform(&delta, &point) - local)) < 1.0e-3);
+            check("point on the part in global coordinates",
+                  na::norm(&(na::transform(&(pos * delta), &point) - impact.point)) < 0.05);
+        },
+        None => check("impact with the compound", false)
+    }
+}
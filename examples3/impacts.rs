@@ -0,0 +1,62 @@
+/*!
+ * Prints the impacts of a ball bouncing on the ground until it settles.
+ *
+ * Only the impacts with an impulse larger than the threshold are reported: the small bounces of
+ * the ball before it comes to rest do not generate any event.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Translation};
+use ncollide::shape::{Ball, Plane};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::RigidBody;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.set_impact_event_threshold(0.5);
+
+    /*
+     * Plane
+     */
+    let geom = Plane::new(Vec3::new(0.0, 1.0, 0.0));
+
+    world.add_body(RigidBody::new_static(geom, 0.8, 0.6));
+
+    /*
+     * Ball
+     */
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.8, 0.6);
+
+    rb.append_translation(&Vec3::new(0.0, 5.0, 0.0));
+
+    world.add_body(rb);
+
+    /*
+     * Run the simulation and print the impacts.
+     */
+    let mut events = Vec::new();
+
+    for i in range(0u, 600) {
+        world.step(0.016);
+        world.drain_events(&mut events);
+
+        for event in events.iter() {
+            match *event {
+                WorldEvent::Impact(ref impact) => {
+                    println!("Step {}: impact at {} with speed {} and impulse {}.",
+                             i, impact.point, impact.approach_velocity, impact.impulse)
+                },
+                _ => { }
+            }
+        }
+
+        events.clear();
+    }
+}
@@ -0,0 +1,76 @@
+/*!
+ * Checks that the bodies attached to a joint do not collide, unless it is enabled.
+ *
+ * Two overlapping boxes are attached by a ball-in-socket joint. They must not have any contact
+ * with each other until the collisions of the joint are enabled. A second joint between the same
+ * boxes, with its collisions disabled, must disable them again. A third box overlapping them but
+ * not attached to them must keep its contacts. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::cell::RefCell;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::Cuboid;
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::detection::joint::{Anchor, BallInSocket};
+use harness::check;
+
+mod harness;
+
+fn add_box(world: &mut World, x: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(x, 0.0, 0.0));
+    rb.set_deactivation_threshold(None);
+
+    world.add_body(rb)
+}
+
+fn same(a: &RigidBodyHandle, b: &RigidBodyHandle) -> bool {
+    a.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>
+}
+
+fn in_contact(world: &World, b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> bool {
+    world.contacts().iter().any(|c| {
+        (same(&c.body1, b1) && same(&c.body2, b2)) || (same(&c.body1, b2) && same(&c.body2, b1))
+    })
+}
+
+fn main() {
+    let mut world = World::new();
+
+    let box1 = add_box(&mut world, 0.0);
+    let box2 = add_box(&mut world, 0.8);
+
+    let joint = world.add_ball_in_socket(BallInSocket::new(
+        Anchor::new(Some(box1.clone()), Pnt3::new(0.4, 0.0, 0.0)),
+        Anchor::new(Some(box2.clone()), Pnt3::new(-0.4, 0.0, 0.0))));
+
+    world.step(0.016);
+    check("disabled by default", !in_contact(&world, &box1, &box2));
+
+    joint.borrow_mut().set_collision_enabled(true);
+    world.step(0.016);
+    check("enabled", in_contact(&world, &box1, &box2));
+
+    let other = world.add_ball_in_socket(BallInSocket::new(
+        Anchor::new(Some(box2.clone()), Pnt3::new(0.0, 0.4, 0.0)),
+        Anchor::new(Some(box1.clone()), Pnt3::new(0.8, 0.4, 0.0))));
+
+    world.step(0.016);
+    check("disabled by another joint", !in_contact(&world, &box1, &box2));
+
+    // A body attached to neither box, but overlapping the second one.
+    let box3 = add_box(&mut world, 1.6);
+
+    world.step(0.016);
+    check("unrelated body", in_contact(&world, &box2, &box3));
+
+    world.remove_ball_in_socket(&other);
+    world.step(0.016);
+    check("removed joint", in_contact(&world, &box1, &box2));
+}
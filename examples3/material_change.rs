@@ -0,0 +1,94 @@
+/*!
+ * Checks that changing the friction or the restitution of a body applies right away.
+ *
+ * A box is left to fall asleep on a 20° incline with enough friction to hold it. The friction of
+ * the static incline is then set to zero: the box must be woken up at the next step and slide
+ * down. A ball is then dropped on a flat ground, and the restitution of both is set to one: it must
+ * bounce back near its initial height. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::f32;
+use std::num::{Float, FloatMath};
+use na::{Vec3, Translation, Rotation};
+use ncollide::shape::{Plane, Cuboid, Ball};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::check;
+
+mod harness;
+
+fn main() {
+    let dt     = 0.016f32;
+    let angle  = 20.0 * f32::consts::PI / 180.0;
+    let normal = Vec3::new(-angle.sin(), angle.cos(), 0.0);
+
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    let incline = world.add_body(RigidBody::new_static(Plane::new(normal), 0.0, 1.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 1.0);
+
+    rb.append_rotation(&Vec3::new(0.0, 0.0, angle));
+    rb.append_translation(&(normal * (0.5 + rb.margin() * 2.0)));
+
+    let body = world.add_body(rb);
+
+    for _ in range(0u, 1000) {
+        world.step(dt);
+    }
+
+    check("box asleep", !body.borrow().is_active());
+
+    incline.borrow_mut().set_friction(0.0);
+    check("friction getter", incline.borrow().friction() == 0.0);
+
+    let start = body.borrow().position().translation();
+
+    world.step(dt);
+
+    check("box woken up", body.borrow().is_active());
+
+    for _ in range(0u, 60) {
+        world.step(dt);
+    }
+
+    check("box slides", na::norm(&(body.borrow().position().translation() - start)) > 0.5);
+
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    let ground = world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.0, 0.5));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(0.0, 5.0, 0.0));
+
+    let ball = world.add_body(rb);
+
+    ground.borrow_mut().set_restitution(1.0);
+    ball.borrow_mut().set_restitution(1.0);
+    check("restitution getter", ball.borrow().restitution() == 1.0);
+
+    let mut max_after_bounce = 0.0f32;
+    let mut bounced          = false;
+
+    for _ in range(0u, 150) {
+        world.step(dt);
+
+        let vy = ball.borrow().lin_vel().y;
+        let y  = ball.borrow().position().translation().y;
+
+        bounced = bounced || vy > 0.0;
+
+        if bounced {
+            max_after_bounce = max_after_bounce.max(y);
+        }
+    }
+
+    check("ball bounces back", max_after_bounce > 4.0);
+}
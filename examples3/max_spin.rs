@@ -0,0 +1,96 @@
+/*!
+ * Checks the maximum angular velocity of the bodies and the stall events.
+ *
+ * A ball driven by a motor must spin at its maximum angular velocity, and faster once the maximum is
+ * cleared. A rotor starting up must not be reported as stalled. Braking it while the motor drives it
+ * must emit a single `BodyStalled` event, and releasing it a single `BodyUnstalled` event. Braking it
+ * once the motor is off must not emit anything. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::Vec3;
+use ncollide::shape::{Ball, Cuboid};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::RigidBody;
+use nphysics::integration::Motor;
+use harness::{check, steps};
+
+mod harness;
+
+// The stall events of the last steps, `true` for those of a body which stalled.
+fn stall_events(world: &mut World) -> Vec<bool> {
+    let mut events = Vec::new();
+    let mut res    = Vec::new();
+
+    world.drain_events(&mut events);
+
+    for e in events.iter() {
+        match *e {
+            WorldEvent::BodyStalled(_)   => res.push(true),
+            WorldEvent::BodyUnstalled(_) => res.push(false),
+            _ => { }
+        }
+    }
+
+    res
+}
+
+fn main() {
+    /*
+     * Spin clamp.
+     */
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.5);
+    rb.set_deactivation_threshold(None);
+    rb.set_max_angular_velocity(Some(2.0));
+    let ball = world.add_body(rb);
+
+    let _ = world.add_force_generator(Motor::new(&ball, Vec3::new(0.0, 5.0, 0.0)));
+
+    steps(&mut world, 100);
+
+    let spin = na::norm(&ball.borrow().ang_vel());
+    check("clamped spin", spin <= 2.0 + 1.0e-4 && spin > 1.99);
+
+    ball.borrow_mut().set_max_angular_velocity(None);
+    steps(&mut world, 10);
+
+    check("spin without maximum", na::norm(&ball.borrow().ang_vel()) > 2.1);
+
+    /*
+     * Rotor stall.
+     */
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(2.0f32, 0.2, 0.2)), 1.0, 0.3, 0.5);
+    rb.set_deactivation_threshold(None);
+    rb.set_max_angular_velocity(Some(3.0));
+    rb.set_stall_speed(Some(1.0));
+    let rotor = world.add_body(rb);
+
+    let motor = world.add_force_generator(Motor::new(&rotor, Vec3::new(0.0, 20.0, 0.0)));
+
+    steps(&mut world, 100);
+    check("start-up", stall_events(&mut world).is_empty());
+
+    // The damping stands for a body wedged against the rotor.
+    rotor.borrow_mut().set_angular_damping(100.0);
+    steps(&mut world, 20);
+    check("stalled", stall_events(&mut world) == vec!(true));
+    check("braked spin", na::norm(&rotor.borrow().ang_vel()) < 1.0);
+
+    rotor.borrow_mut().set_angular_damping(0.0);
+    steps(&mut world, 20);
+    check("unstalled", stall_events(&mut world) == vec!(false));
+
+    motor.borrow_mut().set_enabled(false);
+    rotor.borrow_mut().set_angular_damping(100.0);
+    steps(&mut world, 20);
+    check("braked without motor", stall_events(&mut world).is_empty());
+}
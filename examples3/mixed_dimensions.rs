@@ -0,0 +1,54 @@
+/*!
+ * Uses a 3d world and a 2d world from the same program.
+ *
+ * The 3d physics is provided by the `nphysics` crate compiled with the `3df32` feature, and the 2d
+ * physics by the `nphysics2df32` crate. Both crates are distinct, so their types can be used
+ * together without any conflict.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics2df32;
+
+use na::{Vec2, Vec3, Translation};
+use ncollide::shape::{Ball, Plane};
+use nphysics::world::World as World3;
+use nphysics::object::RigidBody as RigidBody3;
+use nphysics2df32::world::World as World2;
+use nphysics2df32::object::RigidBody as RigidBody2;
+
+fn main() {
+    /*
+     * The 3d world.
+     */
+    let mut world3 = World3::new();
+    world3.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world3.add_body(RigidBody3::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    let mut rb = RigidBody3::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.append_translation(&Vec3::new(0.0, 10.0, 0.0));
+    let ball3 = world3.add_body(rb);
+
+    /*
+     * The 2d world.
+     */
+    let mut world2 = World2::new();
+    world2.set_gravity(Vec2::new(0.0, 9.81));
+    world2.add_body(RigidBody2::new_static(Plane::new(Vec2::new(0.0, -1.0)), 0.3, 0.6));
+
+    let mut rb = RigidBody2::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.append_translation(&Vec2::new(0.0, -10.0));
+    let ball2 = world2.add_body(rb);
+
+    /*
+     * Step both worlds.
+     */
+    for _ in range(0u, 300) {
+        world3.step(0.016);
+        world2.step(0.016);
+    }
+
+    println!("3d ball: {}", ball3.borrow().position().translation());
+    println!("2d ball: {}", ball2.borrow().position().translation());
+}
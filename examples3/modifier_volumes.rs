@@ -0,0 +1,117 @@
+/*!
+ * Checks the modifier volumes.
+ *
+ * A ball floats at rest inside a zero-gravity room while another one falls through it. The first
+ * ball must stay in place and fall asleep, the second one must not accelerate inside the room and
+ * must enter and leave it exactly once. Gravity is then restored inside the room: the sleeping
+ * ball must be woken up and fall. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::cell::RefCell;
+use std::num::Float;
+use na::{Vec3, Iso3, Translation};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics::world::{World, WorldEvent};
+use harness::{check, steps};
+
+mod harness;
+use nphysics::object::{RigidBody, RigidBodyHandle, ModifierVolume, ModifierVolumeHandle,
+                       Containment, Modifier};
+
+fn add_ball(world: &mut World, x: f32, y: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(x, y, 0.0));
+
+    world.add_body(rb)
+}
+
+fn height(body: &RigidBodyHandle) -> f32 {
+    body.borrow().position().translation().y
+}
+
+fn is(a: &RigidBodyHandle, b: &RigidBodyHandle) -> bool {
+    a.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>
+}
+
+// The number of times `body` entered and left `volume` in `events`.
+fn crossings(events: &[WorldEvent], volume: &ModifierVolumeHandle, body: &RigidBodyHandle) -> (uint, uint) {
+    let mut entered = 0;
+    let mut left    = 0;
+
+    for e in events.iter() {
+        match *e {
+            WorldEvent::VolumeEntered(ref v, ref b) if v.deref() as *const RefCell<ModifierVolume> == volume.deref() as *const RefCell<ModifierVolume> && is(b, body) => {
+                entered = entered + 1
+            },
+            WorldEvent::VolumeLeft(ref v, ref b) if v.deref() as *const RefCell<ModifierVolume> == volume.deref() as *const RefCell<ModifierVolume> && is(b, body) => {
+                left = left + 1
+            },
+            _ => { }
+        }
+    }
+
+    (entered, left)
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    // The room spans the heights from 5 to 15.
+    let room = world.add_modifier_volume(ModifierVolume::new(
+            Cuboid::new(Vec3::new(5.0f32, 5.0, 5.0)),
+            Iso3::new(Vec3::new(0.0, 10.0, 0.0), na::zero()),
+            Containment::CenterOfMass,
+            Modifier::GravityScale(0.0)));
+
+    let floating = add_ball(&mut world, -2.0, 10.0);
+    let falling  = add_ball(&mut world, 2.0, 20.0);
+
+    let mut events    = Vec::new();
+    let mut entry_vel = None;
+    let mut exit_vel  = None;
+
+    for _ in range(0u, 300) {
+        world.step(0.016);
+
+        let y  = height(&falling);
+        let vy = falling.borrow().lin_vel().y;
+
+        if y < 14.0 && entry_vel.is_none() {
+            entry_vel = Some(vy);
+        }
+
+        if y < 6.0 && exit_vel.is_none() {
+            exit_vel = Some(vy);
+        }
+
+        world.drain_events(&mut events);
+    }
+
+    check("floating ball at rest", (height(&floating) - 10.0).abs() < 1.0e-3);
+    check("floating ball asleep", !floating.borrow().is_active());
+
+    match (entry_vel, exit_vel) {
+        (Some(entry), Some(exit)) => check("no acceleration in the room", (entry - exit).abs() < 0.2),
+        _                         => check("falling ball crossed the room", false)
+    }
+
+    check("falling ball crossings", crossings(events.as_slice(), &room, &falling) == (1, 1));
+    check("floating ball crossings", crossings(events.as_slice(), &room, &floating) == (1, 0));
+    check("room contents", room.borrow().contains(&floating) && !room.borrow().contains(&falling));
+
+    room.borrow_mut().set_modifier(Modifier::GravityScale(1.0));
+
+    world.step(0.016);
+
+    check("floating ball woken up", floating.borrow().is_active());
+
+    steps(&mut world, 60);
+
+    check("floating ball falls", height(&floating) < 9.0);
+}
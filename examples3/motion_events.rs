@@ -0,0 +1,103 @@
+/*!
+ * Checks the events emitted when the bodies start and stop moving.
+ *
+ * A body which speed oscillates between the two motion thresholds must start moving once and never
+ * stop. A ball bouncing on the ground with a decaying height must alternate between starting and
+ * stopping a bounded number of times, and end at rest. No event must be emitted once the thresholds
+ * are cleared. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Vec3, Translation};
+use ncollide::shape::{Ball, Plane};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::RigidBody;
+use harness::check;
+
+mod harness;
+
+// The motion events of the last step, `true` for those of a body starting to move.
+fn motion_events(world: &mut World) -> Vec<bool> {
+    let mut events = Vec::new();
+    let mut res    = Vec::new();
+
+    world.drain_events(&mut events);
+
+    for e in events.iter() {
+        match *e {
+            WorldEvent::BodyStartedMoving(_) => res.push(true),
+            WorldEvent::BodyStoppedMoving(_) => res.push(false),
+            _ => { }
+        }
+    }
+
+    res
+}
+
+fn main() {
+    /*
+     * Speed hovering around the start speed.
+     */
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+    world.set_motion_thresholds(0.5, 1.1);
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+    rb.set_deactivation_threshold(None);
+    let body = world.add_body(rb);
+
+    let mut transitions = Vec::new();
+
+    for i in range(0u, 1000) {
+        body.borrow_mut().set_lin_vel(Vec3::new(1.0 + 0.2 * (i as f32 * 0.5).sin(), 0.0, 0.0));
+        world.step(0.016);
+        transitions.push_all(motion_events(&mut world).as_slice());
+    }
+
+    check(format!("hovering: {} transitions instead of one", transitions.len()).as_slice(),
+          transitions.len() == 1 && transitions[0]);
+
+    /*
+     * Decaying bounce.
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.set_motion_thresholds(0.05, 0.3);
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.8, 0.5));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.8, 0.5);
+    rb.append_translation(&Vec3::new(0.0, 3.0, 0.0));
+    world.add_body(rb);
+
+    let mut transitions = Vec::new();
+
+    for _ in range(0u, 1500) {
+        world.step(0.016);
+        transitions.push_all(motion_events(&mut world).as_slice());
+    }
+
+    check("bounce: started moving", transitions.len() >= 2);
+    check(format!("bounce: bounded number of transitions, got {}", transitions.len()).as_slice(),
+          transitions.len() <= 40);
+    check("bounce: alternating transitions", transitions.iter().enumerate().all(|(i, t)| *t == (i % 2 == 0)));
+    check("bounce: at rest at the end", transitions.last() == Some(&false));
+
+    /*
+     * Disabled events.
+     */
+    world.clear_motion_thresholds();
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.8, 0.5);
+    rb.append_translation(&Vec3::new(5.0, 3.0, 0.0));
+    world.add_body(rb);
+
+    for _ in range(0u, 100) {
+        world.step(0.016);
+        check("no event when disabled", motion_events(&mut world).is_empty());
+    }
+}
@@ -0,0 +1,32 @@
+use na::Pnt3;
+use kiss3d::window::Window;
+
+/// Buffer collecting the debug segments drawn during one frame.
+///
+/// The overlays push their segments here instead of calling `Window::draw_line` one by one. They
+/// are all submitted at once by `flush`, and the buffer memory is reused from one frame to the next.
+pub struct DebugLines {
+    lines: Vec<(Pnt3<f32>, Pnt3<f32>, Pnt3<f32>)>
+}
+
+impl DebugLines {
+    pub fn new() -> DebugLines {
+        DebugLines {
+            lines: Vec::new()
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, a: &Pnt3<f32>, b: &Pnt3<f32>, color: &Pnt3<f32>) {
+        self.lines.push((*a, *b, *color))
+    }
+
+    /// Sends every segment of this frame to the window, and empties the buffer.
+    pub fn flush(&mut self, window: &mut Window) {
+        for &(ref a, ref b, ref color) in self.lines.iter() {
+            window.draw_line(a, b, color);
+        }
+
+        self.lines.clear();
+    }
+}
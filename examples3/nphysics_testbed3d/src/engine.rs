@@ -1,18 +1,20 @@
 use std::intrinsics::TypeId;
-use std::any::AnyRefExt;
+use std::num::Float;
 use std::rc::Rc;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::fs::PathExtensions;
 use rand::{SeedableRng, XorShiftRng, Rng};
-use na::{Pnt3, Vec3, Iso3, Col, Translate};
+use na::{Pnt3, Vec3, Iso3, Col, Translation};
 use na;
 use kiss3d::window::Window;
 use kiss3d::scene::SceneNode;
 use kiss3d::camera::{Camera, ArcBall, FirstPerson};
+use kiss3d::loader::obj;
 use ncollide::shape::Shape3;
 use ncollide::shape;
 use ncollide::procedural;
-use nphysics::object::RigidBody;
+use nphysics::object::{RigidBody, ShapeDesc, shape_desc};
 use objects::bezier_surface::BezierSurface;
 use objects::ball::Ball;
 use objects::box_node::Box;
@@ -21,6 +23,8 @@ use objects::cone::Cone;
 use objects::mesh::Mesh;
 use objects::plane::Plane;
 use objects::convex::Convex;
+use objects::custom::Custom;
+use debug_lines::DebugLines;
 
 
 pub enum Node {
@@ -31,7 +35,8 @@ pub enum Node {
     Mesh(Mesh),
     Plane(Plane),
     BezierSurface(BezierSurface),
-    Convex(Convex)
+    Convex(Convex),
+    Custom(Custom)
 }
 
 impl Node {
@@ -44,7 +49,8 @@ impl Node {
             Node::Cone(ref mut n)              => n.select(),
             Node::Mesh(ref mut n)              => n.select(),
             Node::BezierSurface(ref mut n)     => n.select(),
-            Node::Convex(ref mut n)            => n.select()
+            Node::Convex(ref mut n)            => n.select(),
+            Node::Custom(ref mut n)            => n.select()
         }
     }
 
@@ -57,7 +63,8 @@ impl Node {
             Node::Cone(ref mut n)              => n.unselect(),
             Node::Mesh(ref mut n)              => n.unselect(),
             Node::BezierSurface(ref mut n)     => n.unselect(),
-            Node::Convex(ref mut n)            => n.unselect()
+            Node::Convex(ref mut n)            => n.unselect(),
+            Node::Custom(ref mut n)            => n.unselect()
         }
     }
 
@@ -70,7 +77,22 @@ impl Node {
             Node::Cone(ref mut n)              => n.update(),
             Node::Mesh(ref mut n)              => n.update(),
             Node::BezierSurface(ref mut n)     => n.update(),
-            Node::Convex(ref mut n)            => n.update()
+            Node::Convex(ref mut n)            => n.update(),
+            Node::Custom(ref mut n)            => n.update()
+        }
+    }
+
+    pub fn rescale(&mut self, s: f32) {
+        match *self {
+            Node::Plane(ref mut n)             => n.rescale(s),
+            Node::Ball(ref mut n)              => n.rescale(s),
+            Node::Box(ref mut n)               => n.rescale(s),
+            Node::Cylinder(ref mut n)          => n.rescale(s),
+            Node::Cone(ref mut n)              => n.rescale(s),
+            Node::Mesh(ref mut n)              => n.rescale(s),
+            Node::BezierSurface(ref mut n)     => n.rescale(s),
+            Node::Convex(ref mut n)            => n.rescale(s),
+            Node::Custom(ref mut n)            => n.rescale(s)
         }
     }
 
@@ -83,7 +105,8 @@ impl Node {
             Node::Cone(ref n)              => n.object(),
             Node::Mesh(ref n)              => n.object(),
             Node::BezierSurface(ref n)     => n.object(),
-            Node::Convex(ref n)            => n.object()
+            Node::Convex(ref n)            => n.object(),
+            Node::Custom(ref n)            => n.object()
         }
     }
 
@@ -96,19 +119,84 @@ impl Node {
             Node::Cone(ref n)              => n.body(),
             Node::Mesh(ref n)              => n.body(),
             Node::BezierSurface(ref n)     => n.body(),
-            Node::Convex(ref n)            => n.body()
+            Node::Convex(ref n)            => n.body(),
+            Node::Custom(ref n)            => n.body()
         }
     }
 }
 
+/// Describes `shape`, the shape or a part of the shape of `body`, for the testbed to render it.
+///
+/// If the shape cannot be rendered, this panics with a description of the shape and of the body
+/// when `strict` is `true`. Otherwise, the description is pushed to `warnings`, and `None` is
+/// returned so that the body is simulated without being displayed. The unsupported parts of a
+/// compound shape are reported the same way when the nodes of the body are built.
+pub fn dispatch_shape<'a>(shape:    &'a Shape3<f32>,
+                          body:     &Rc<RefCell<RigidBody>>,
+                          strict:   bool,
+                          warnings: &mut Vec<String>)
+                          -> Option<ShapeDesc<'a>> {
+    match shape_desc(shape) {
+        ShapeDesc::Other(other) => {
+            report_unsupported_shape(other, body, strict, warnings);
+
+            None
+        },
+        desc => Some(desc)
+    }
+}
+
+// Panics with a description of `shape` and `body` if `strict` is `true`, or pushes it to
+// `warnings` otherwise.
+fn report_unsupported_shape(shape:    &Shape3<f32>,
+                            body:     &Rc<RefCell<RigidBody>>,
+                            strict:   bool,
+                            warnings: &mut Vec<String>) {
+    let message = format!("unable to render the shape {} of the body {:x} at {}.",
+                          shape_type_name(shape),
+                          body.deref() as *const RefCell<RigidBody> as uint,
+                          body.borrow().position().translation());
+
+    if strict {
+        panic!("The testbed is {}", message)
+    }
+
+    println!("Warning: {} The body is not displayed.", message);
+    warnings.push(message);
+}
+
+// The name of the type of `shape`, for the shapes of ncollide the testbed cannot render.
+fn shape_type_name(shape: &Shape3<f32>) -> String {
+    let id = shape.get_type_id();
+
+    let name =
+        if id == TypeId::of::<shape::Capsule3<f32>>() { "Capsule3" }
+        else if id == TypeId::of::<shape::Segment3<f32>>() { "Segment3" }
+        else if id == TypeId::of::<shape::Triangle3<f32>>() { "Triangle3" }
+        else { return format!("of unknown type {}", id) };
+
+    name.to_string()
+}
+
+// The seed of the random colors used when none is set with `set_color_seed`.
+static DEFAULT_COLOR_SEED: u64 = 0x0804_0200;
+
 pub struct GraphicsManager {
     rand:             XorShiftRng,
+    palette:          Vec<Pnt3<f32>>,
+    num_colored:      uint,
     rb2sn:            HashMap<uint, Vec<Node>>,
     rb2color:         HashMap<uint, Pnt3<f32>>,
+    generated:        HashSet<uint>,
+    rb2mesh:          HashMap<uint, (Path, f32)>,
+    rb2texture:       HashMap<uint, Path>,
     arc_ball:         ArcBall,
     first_person:     FirstPerson,
     curr_is_arc_ball: bool,
-    aabbs:            Vec<SceneNode>
+    aabbs:            Vec<SceneNode>,
+    render_margins:   bool,
+    strict_rendering: bool,
+    warnings:         Vec<String>
 }
 
 impl GraphicsManager {
@@ -116,21 +204,22 @@ impl GraphicsManager {
         let arc_ball     = ArcBall::new(Pnt3::new(10.0, 10.0, 10.0), Pnt3::new(0.0, 0.0, 0.0));
         let first_person = FirstPerson::new(Pnt3::new(10.0, 10.0, 10.0), Pnt3::new(0.0, 0.0, 0.0));
 
-        let mut rng: XorShiftRng = SeedableRng::from_seed([0, 2, 4, 8]);
-
-        // the first colors are boring.
-        for _ in range(0u, 100) {
-            let _: Pnt3<f32> = rng.gen();
-        }
-
         GraphicsManager {
             arc_ball:         arc_ball,
             first_person:     first_person,
             curr_is_arc_ball: true,
-            rand:             rng,
+            rand:             color_rng(DEFAULT_COLOR_SEED),
+            palette:          Vec::new(),
+            num_colored:      0,
             rb2sn:            HashMap::new(),
             rb2color:         HashMap::new(),
-            aabbs:            Vec::new()
+            generated:        HashSet::new(),
+            rb2mesh:          HashMap::new(),
+            rb2texture:       HashMap::new(),
+            aabbs:            Vec::new(),
+            render_margins:   true,
+            strict_rendering: false,
+            warnings:         Vec::new()
         }
     }
 
@@ -164,18 +253,117 @@ impl GraphicsManager {
         self.rb2sn.remove(&key);
     }
 
+    /// Whether the shapes are rendered dilated by the body margins, i.e., as seen by the collision
+    /// detection.
+    pub fn render_margins(&self) -> bool {
+        self.render_margins
+    }
+
+    /// Sets whether the shapes are rendered dilated by the body margins.
+    ///
+    /// This only affects the bodies added afterward.
+    pub fn set_render_margins(&mut self, render_margins: bool) {
+        self.render_margins = render_margins
+    }
+
+    fn margin(&self, body: &Rc<RefCell<RigidBody>>) -> f32 {
+        if self.render_margins {
+            body.borrow().margin()
+        }
+        else {
+            0.0
+        }
+    }
+
+    // The margin of the `i`-th part of the compound shape of `body`, itself rendered with the
+    // margin `parent`.
+    fn part_margin(&self, body: &Rc<RefCell<RigidBody>>, i: uint, parent: f32) -> f32 {
+        if self.render_margins {
+            body.borrow().part_margin(i)
+        }
+        else {
+            parent
+        }
+    }
+
     pub fn set_color(&mut self, body: &Rc<RefCell<RigidBody>>, color: Pnt3<f32>) {
-        self.rb2color.insert(body.deref() as *const RefCell<RigidBody> as uint, color);
+        let key = body.deref() as *const RefCell<RigidBody> as uint;
+
+        self.rb2color.insert(key, color);
+        self.generated.remove(&key);
+    }
+
+    /// Restarts the generation of the colors of the dynamic bodies from `seed`.
+    ///
+    /// This only affects the bodies colored afterward, see `forget_generated_color`.
+    pub fn set_color_seed(&mut self, seed: u64) {
+        self.rand        = color_rng(seed);
+        self.num_colored = 0;
+    }
+
+    /// Sets the colors given in turn to the dynamic bodies, instead of random ones.
+    ///
+    /// An empty palette restores the random colors. This only affects the bodies colored
+    /// afterward, see `forget_generated_color`.
+    pub fn set_palette(&mut self, palette: Vec<Pnt3<f32>>) {
+        self.palette     = palette;
+        self.num_colored = 0;
+    }
+
+    /// Forgets the color of `body` if it was not set with `set_color`.
+    ///
+    /// A new one is generated the next time its nodes are built.
+    pub fn forget_generated_color(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        let key = body.deref() as *const RefCell<RigidBody> as uint;
+
+        if self.generated.remove(&key) {
+            self.rb2color.remove(&key);
+        }
+    }
+
+    // The color of the next dynamic body without a color.
+    fn next_color(&mut self) -> Pnt3<f32> {
+        let i = self.num_colored;
+
+        self.num_colored = self.num_colored + 1;
+
+        if !self.palette.is_empty() {
+            self.palette[i % self.palette.len()]
+        }
+        else {
+            // Saturated but not too dark nor too light, to be readable against the background.
+            let h = self.rand.gen_range(0.0f32, 1.0);
+            let s = self.rand.gen_range(0.45f32, 0.8);
+            let v = self.rand.gen_range(0.6f32, 0.95);
+
+            hsv_to_rgb(h, s, v)
+        }
+    }
+
+    /// Sets the OBJ file, and its scale, used to render `body` instead of its shape.
+    ///
+    /// This only affects the nodes built afterward.
+    pub fn set_mesh(&mut self, body: &Rc<RefCell<RigidBody>>, path: Path, scale: f32) {
+        self.rb2mesh.insert(body.deref() as *const RefCell<RigidBody> as uint, (path, scale));
+    }
+
+    /// Sets the image file used to texture the nodes of `body`.
+    ///
+    /// This only affects the nodes built afterward.
+    pub fn set_texture(&mut self, body: &Rc<RefCell<RigidBody>>, path: Path) {
+        self.rb2texture.insert(body.deref() as *const RefCell<RigidBody> as uint, path);
     }
 
     pub fn add(&mut self, window: &mut Window, body: Rc<RefCell<RigidBody>>) {
+        let key = body.deref() as *const RefCell<RigidBody> as uint;
         let color;
 
-        match self.rb2color.get(&(body.deref() as *const RefCell<RigidBody> as uint)) {
+        match self.rb2color.get(&key) {
             Some(c) => color = *c,
             None    => {
                 if body.borrow().can_move() {
-                    color = self.rand.gen();
+                    color = self.next_color();
+                    self.generated.insert(key);
                 }
                 else {
                     color = Pnt3::new(0.5, 0.5, 0.5);
@@ -183,9 +371,17 @@ impl GraphicsManager {
             }
         }
 
+        // Remember the color so that the body keeps it when its nodes are rebuilt.
+        self.rb2color.insert(key, color);
+
         self.add_with_color(window, body, color)
     }
 
+    pub fn rebuild(&mut self, window: &mut Window, body: Rc<RefCell<RigidBody>>) {
+        self.remove(window, &body);
+        self.add(window, body);
+    }
+
     pub fn add_with_color(&mut self,
                           window: &mut Window,
                           body:   Rc<RefCell<RigidBody>>,
@@ -194,7 +390,39 @@ impl GraphicsManager {
             let rb        = body.borrow();
             let mut nodes = Vec::new();
 
-            self.add_shape(window, body.clone(), na::one(), rb.shape_ref(), color, &mut nodes);
+            let margin = self.margin(&body);
+            let key    = body.deref() as *const RefCell<RigidBody> as uint;
+
+            let custom = match self.rb2mesh.get(&key) {
+                Some(&(ref path, scale)) => load_mesh(window, path, scale),
+                None                     => None
+            };
+
+            match custom {
+                Some(gfx) => nodes.push(Node::Custom(Custom::new(body.clone(), gfx, color))),
+                None      => {
+                    let strict = self.strict_rendering;
+
+                    match dispatch_shape(rb.shape_ref(), &body, strict, &mut self.warnings) {
+                        Some(desc) => self.add_shape(window, body.clone(), na::one(), margin, &desc, true, color, &mut nodes),
+                        None       => { }
+                    }
+                }
+            }
+
+            match self.rb2texture.get(&key) {
+                Some(path) => {
+                    if path.exists() {
+                        for n in nodes.iter() {
+                            n.object().clone().set_texture_from_file(path, path.as_str().unwrap_or(""));
+                        }
+                    }
+                    else {
+                        println!("Warning: unable to find the texture `{}`.", path.display());
+                    }
+                },
+                None => { }
+            }
 
             nodes
         };
@@ -202,162 +430,121 @@ impl GraphicsManager {
         self.rb2sn.insert(body.deref() as *const RefCell<RigidBody> as uint, nodes);
     }
 
+    /// Whether the testbed panics when a body has a shape it cannot render.
+    pub fn strict_rendering(&self) -> bool {
+        self.strict_rendering
+    }
+
+    /// Sets whether the testbed panics when a body has a shape it cannot render, instead of
+    /// simulating it without displaying it.
+    pub fn set_strict_rendering(&mut self, strict: bool) {
+        self.strict_rendering = strict
+    }
+
+    /// The descriptions of the shapes which could not be rendered.
+    pub fn warnings(&self) -> &[String] {
+        self.warnings.as_slice()
+    }
+
     fn add_shape(&mut self,
                  window: &mut Window,
                  body:   Rc<RefCell<RigidBody>>,
                  delta:  Iso3<f32>,
-                 shape:   &Shape3<f32>,
+                 margin: f32,
+                 desc:   &ShapeDesc,
+                 root:   bool,
                  color:  Pnt3<f32>,
                  out:    &mut Vec<Node>) {
-        type Pl = shape::Plane3<f32>;
-        type Bl = shape::Ball3<f32>;
-        type Bo = shape::Cuboid3<f32>;
-        type Cy = shape::Cylinder3<f32>;
-        type Co = shape::Cone3<f32>;
-        type Cm = shape::Compound3<f32>;
-        type Tm = shape::Mesh3<f32>;
-        type Bs = shape::BezierSurface3<f32>;
-        type Cx = shape::Convex3<f32>;
-
-        let id = shape.get_type_id();
-        if id == TypeId::of::<Pl>(){
-            self.add_plane(window, body, shape.downcast_ref::<Pl>().unwrap(), color, out)
-        }
-        else if id == TypeId::of::<Bl>() {
-            self.add_ball(window, body, delta, shape.downcast_ref::<Bl>().unwrap(), color, out)
-        }
-        else if id == TypeId::of::<Bo>() {
-            self.add_box(window, body, delta, shape.downcast_ref::<Bo>().unwrap(), color, out)
-        }
-        else if id == TypeId::of::<Cx>() {
-            self.add_convex(window, body, delta, shape.downcast_ref::<Cx>().unwrap(), color, out)
-        }
-        else if id == TypeId::of::<Cy>() {
-            self.add_cylinder(window, body, delta, shape.downcast_ref::<Cy>().unwrap(), color, out)
-        }
-        else if id == TypeId::of::<Co>() {
-            self.add_cone(window, body, delta, shape.downcast_ref::<Co>().unwrap(), color, out)
-        }
-        else if id == TypeId::of::<Bs>() {
-            self.add_bezier_surface(window, body, delta, shape.downcast_ref::<Bs>().unwrap(), color, out)
-        }
-        else if id == TypeId::of::<Cm>() {
-            let c = shape.downcast_ref::<Cm>().unwrap();
+        match *desc {
+            ShapeDesc::Plane(ref normal) => {
+                // The node places itself from the pose of the body each time it is updated.
+                out.push(Node::Plane(Plane::new(body, normal, color, window)))
+            },
+            ShapeDesc::Ball(radius) => {
+                out.push(Node::Ball(Ball::new(body, delta, radius + margin, color, window)))
+            },
+            ShapeDesc::Cuboid(ref half_extents) => {
+                let rx = half_extents.x + margin;
+                let ry = half_extents.y + margin;
+                let rz = half_extents.z + margin;
 
-            for &(t, ref s) in c.shapes().iter() {
-                self.add_shape(window, body.clone(), delta * t, &***s, color, out)
-            }
-        }
-        else if id == TypeId::of::<Tm>() {
-            self.add_mesh(window, body, delta, shape.downcast_ref::<Tm>().unwrap(), color, out);
-        }
-        else {
-            panic!("Not yet implemented.")
-        }
+                out.push(Node::Box(Box::new(body, delta, rx, ry, rz, color, window)))
+            },
+            ShapeDesc::Convex(points) => self.add_convex(window, body, delta, margin, points, color, out),
+            ShapeDesc::Cylinder(half_height, radius) => {
+                let r = radius + margin;
+                let h = (half_height + margin) * 2.0;
 
-    }
+                out.push(Node::Cylinder(Cylinder::new(body, delta, r, h, color, window)))
+            },
+            ShapeDesc::Cone(half_height, radius) => {
+                let r = radius + margin;
+                let h = (half_height + margin) * 2.0;
 
-    fn add_plane(&mut self,
-                 window: &mut Window,
-                 body:   Rc<RefCell<RigidBody>>,
-                 shape:   &shape::Plane3<f32>,
-                 color:  Pnt3<f32>,
-                 out:    &mut Vec<Node>) {
-        let position = na::translation(body.borrow().position()).translate(&na::orig());
-        let normal   = na::rotate(body.borrow().position(), shape.normal());
+                out.push(Node::Cone(Cone::new(body, delta, r, h, color, window)))
+            },
+            ShapeDesc::BezierSurface(control_points, nupoints, nvpoints) => {
+                out.push(Node::BezierSurface(BezierSurface::new(body, delta, control_points, nupoints, nvpoints, color, window)))
+            },
+            ShapeDesc::Compound(ref parts) => {
+                for (i, &(t, ref part)) in parts.iter().enumerate() {
+                    // Only the parts of the shape of the body itself have their own margin.
+                    let margin = if root { self.part_margin(&body, i, margin) } else { margin };
 
-        out.push(Node::Plane(Plane::new(body, &position, &normal, color, window)))
+                    self.add_shape(window, body.clone(), delta * t, margin, part, false, color, out)
+                }
+            },
+            ShapeDesc::Mesh(vertices, indices) => {
+                self.add_mesh(window, body, delta, margin, vertices, indices, color, out)
+            },
+            ShapeDesc::Other(shape) => {
+                report_unsupported_shape(shape, &body, self.strict_rendering, &mut self.warnings)
+            }
+        }
     }
 
     fn add_mesh(&mut self,
-                window: &mut Window,
-                body:   Rc<RefCell<RigidBody>>,
-                delta:  Iso3<f32>,
-                shape:   &shape::Mesh3<f32>,
-                color:  Pnt3<f32>,
-                out:    &mut Vec<Node>) {
-        let vertices = shape.vertices().deref();
-        let indices  = shape.indices().deref();
-
-        let vs     = vertices.clone();
+                window:   &mut Window,
+                body:     Rc<RefCell<RigidBody>>,
+                delta:    Iso3<f32>,
+                margin:   f32,
+                vertices: &[Pnt3<f32>],
+                indices:  &[uint],
+                color:    Pnt3<f32>,
+                out:      &mut Vec<Node>) {
+        let mut vs = vertices.to_vec();
         let mut is = Vec::new();
 
-        for i in indices.as_slice().chunks(3) {
+        for i in indices.chunks(3) {
             is.push(Vec3::new(i[0] as u32, i[1] as u32, i[2] as u32))
         }
 
-        out.push(Node::Mesh(Mesh::new(body, delta, vs, is, color, window)))
-    }
-
-    fn add_bezier_surface(&mut self,
-                window: &mut Window,
-                body:   Rc<RefCell<RigidBody>>,
-                delta:  Iso3<f32>,
-                shape:   &shape::BezierSurface3<f32>,
-                color:  Pnt3<f32>,
-                out:    &mut Vec<Node>) {
-        out.push(Node::BezierSurface(BezierSurface::new(body, delta, shape.control_points(), shape.nupoints(), shape.nvpoints(), color, window)))
-    }
-
-    fn add_ball(&mut self,
-                window: &mut Window,
-                body:   Rc<RefCell<RigidBody>>,
-                delta:  Iso3<f32>,
-                shape:   &shape::Ball3<f32>,
-                color:  Pnt3<f32>,
-                out:    &mut Vec<Node>) {
-        let margin = body.borrow().margin();
-        out.push(Node::Ball(Ball::new(body, delta, shape.radius() + margin, color, window)))
-    }
+        dilate(vs.as_mut_slice(), is.as_slice(), margin);
 
-    fn add_box(&mut self,
-               window: &mut Window,
-               body:   Rc<RefCell<RigidBody>>,
-               delta:  Iso3<f32>,
-               shape:   &shape::Cuboid3<f32>,
-               color:  Pnt3<f32>,
-               out:    &mut Vec<Node>) {
-        let rx = shape.half_extents().x + body.borrow().margin();
-        let ry = shape.half_extents().y + body.borrow().margin();
-        let rz = shape.half_extents().z + body.borrow().margin();
-
-        out.push(Node::Box(Box::new(body, delta, rx, ry, rz, color, window)))
+        out.push(Node::Mesh(Mesh::new(body, delta, vs, is, color, window)))
     }
 
     fn add_convex(&mut self,
                   window: &mut Window,
                   body:   Rc<RefCell<RigidBody>>,
                   delta:  Iso3<f32>,
-                  shape:   &shape::Convex3<f32>,
+                  margin: f32,
+                  points: &[Pnt3<f32>],
                   color:  Pnt3<f32>,
                   out:    &mut Vec<Node>) {
-        out.push(Node::Convex(Convex::new(body, delta, &procedural::convex_hull3(shape.points()), color, window)))
-    }
+        let mut hull = procedural::convex_hull3(points);
 
-    fn add_cylinder(&mut self,
-                    window: &mut Window,
-                    body:   Rc<RefCell<RigidBody>>,
-                    delta:  Iso3<f32>,
-                    shape:   &shape::Cylinder3<f32>,
-                    color:  Pnt3<f32>,
-                    out:    &mut Vec<Node>) {
-        let r = shape.radius();
-        let h = shape.half_height() * 2.0;
-
-        out.push(Node::Cylinder(Cylinder::new(body, delta, r, h, color, window)))
-    }
+        match hull.indices {
+            procedural::IndexBuffer::Unified(ref is) => {
+                dilate(hull.coords.as_mut_slice(), is.as_slice(), margin)
+            },
+            procedural::IndexBuffer::Split(_) => { }
+        }
 
-    fn add_cone(&mut self,
-                window: &mut Window,
-                body:   Rc<RefCell<RigidBody>>,
-                delta:  Iso3<f32>,
-                shape:   &shape::Cone3<f32>,
-                color:  Pnt3<f32>,
-                out:    &mut Vec<Node>) {
-        let r = shape.radius();
-        let h = shape.half_height() * 2.0;
+        // The normals of the dilated hull are recomputed by kiss3d.
+        hull.normals = None;
 
-        out.push(Node::Cone(Cone::new(body, delta, r, h, color, window)))
+        out.push(Node::Convex(Convex::new(body, delta, &hull, color, window)))
     }
 
     pub fn draw(&mut self) {
@@ -368,7 +555,71 @@ impl GraphicsManager {
         }
     }
 
-    pub fn draw_positions(&mut self, window: &mut Window) {
+    pub fn draw_bodies(&mut self, bodies: &[Rc<RefCell<RigidBody>>]) {
+        for b in bodies.iter() {
+            self.update_body(b)
+        }
+    }
+
+    pub fn update_body(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        match self.body_to_scene_node(body) {
+            Some(ns) => {
+                for n in ns.iter_mut() {
+                    n.update()
+                }
+            },
+            None => { }
+        }
+    }
+
+    /// Rescales the scene nodes of a body scaled by `s` with `World::scale_body`.
+    ///
+    /// The nodes are scaled instead of being rebuilt, so the rendered margins are scaled too.
+    pub fn rescale_body(&mut self, body: &Rc<RefCell<RigidBody>>, s: f32) {
+        match self.body_to_scene_node(body) {
+            Some(ns) => {
+                for n in ns.iter_mut() {
+                    n.rescale(s)
+                }
+            },
+            None => { }
+        }
+    }
+
+    /// Updates the scene nodes of a body whose shape was replaced by `World::update_mesh_vertices`
+    /// or `World::update_bezier_control_points`.
+    ///
+    /// A Bezier surface is tessellated again in place, the other nodes are rebuilt.
+    pub fn update_shape(&mut self, window: &mut Window, body: &Rc<RefCell<RigidBody>>) {
+        let updated = {
+            let rb = body.borrow();
+
+            match rb.shape_desc() {
+                ShapeDesc::BezierSurface(control_points, nupoints, nvpoints) => {
+                    match self.body_to_scene_node(body) {
+                        Some(ref mut ns) if ns.len() == 1 => {
+                            match *ns.get_mut(0).unwrap() {
+                                Node::BezierSurface(ref mut n) => {
+                                    n.set_control_points(control_points, nupoints, nvpoints, window);
+                                    true
+                                },
+                                _ => false
+                            }
+                        },
+                        Some(_) => false,
+                        None    => true
+                    }
+                },
+                _ => false
+            }
+        };
+
+        if !updated {
+            self.rebuild(window, body.clone())
+        }
+    }
+
+    pub fn draw_positions(&mut self, lines: &mut DebugLines) {
         for (_, ns) in self.rb2sn.iter_mut() {
             for n in ns.iter_mut() {
                 let rb = n.body().borrow();
@@ -380,9 +631,9 @@ impl GraphicsManager {
                 let y = t.rotation.col(1) * 0.25f32;
                 let z = t.rotation.col(2) * 0.25f32;
 
-                window.draw_line(center, &(*center + x), &Pnt3::new(1.0, 0.0, 0.0));
-                window.draw_line(center, &(*center + y), &Pnt3::new(0.0, 1.0, 0.0));
-                window.draw_line(center, &(*center + z), &Pnt3::new(0.0, 0.0, 1.0));
+                lines.push(center, &(*center + x), &Pnt3::new(1.0, 0.0, 0.0));
+                lines.push(center, &(*center + y), &Pnt3::new(0.0, 1.0, 0.0));
+                lines.push(center, &(*center + z), &Pnt3::new(0.0, 0.0, 1.0));
             }
         }
     }
@@ -416,3 +667,81 @@ impl GraphicsManager {
         self.rb2sn.get_mut(&(rb.deref() as *const RefCell<RigidBody> as uint))
     }
 }
+
+// Builds a scene node from the meshes of an OBJ file scaled by `scale`, or returns `None`, with a
+// warning, if the file cannot be loaded.
+fn color_rng(seed: u64) -> XorShiftRng {
+    // The seed of a xorshift generator must not be zero.
+    SeedableRng::from_seed([ seed as u32, (seed >> 32) as u32, 0x9e37_79b9, 0x7f4a_7c15 ])
+}
+
+// Converts a color from HSV to RGB, each component being in [0, 1].
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> Pnt3<f32> {
+    let h6 = h * 6.0;
+    let f  = h6 - h6.floor();
+    let p  = v * (1.0 - s);
+    let q  = v * (1.0 - s * f);
+    let t  = v * (1.0 - s * (1.0 - f));
+
+    match h6.floor() as uint % 6 {
+        0 => Pnt3::new(v, t, p),
+        1 => Pnt3::new(q, v, p),
+        2 => Pnt3::new(p, v, t),
+        3 => Pnt3::new(p, q, v),
+        4 => Pnt3::new(t, p, v),
+        _ => Pnt3::new(v, p, q)
+    }
+}
+
+fn load_mesh(window: &mut Window, path: &Path, scale: f32) -> Option<SceneNode> {
+    // The materials are looked for next to the OBJ file.
+    let mtl_dir = path.dir_path();
+
+    match obj::parse_file(path, &mtl_dir, path.as_str().unwrap_or("")) {
+        Ok(objects) => {
+            let mut gfx = window.add_group();
+
+            for (_, m, _) in objects.into_iter() {
+                let mut part = gfx.add_mesh(Rc::new(RefCell::new(m)), Vec3::new(scale, scale, scale));
+
+                part.enable_backface_culling(false);
+            }
+
+            Some(gfx)
+        },
+        Err(e) => {
+            println!("Warning: unable to load the mesh `{}`, rendering the shape instead: {}.", path.display(), e);
+
+            None
+        }
+    }
+}
+
+// Moves each vertex of a triangle mesh by `margin` along its normal, i.e., the average of the
+// normals of the adjacent faces.
+fn dilate(coords: &mut [Pnt3<f32>], indices: &[Vec3<u32>], margin: f32) {
+    if margin == 0.0 {
+        return;
+    }
+
+    let mut normals: Vec<Vec3<f32>> = Vec::from_elem(coords.len(), na::zero());
+
+    for t in indices.iter() {
+        let (a, b, c) = (t.x as uint, t.y as uint, t.z as uint);
+        let n = na::cross(&(coords[b] - coords[a]), &(coords[c] - coords[a]));
+
+        if !na::is_zero(&n) {
+            let n = na::normalize(&n);
+
+            normals[a] = normals[a] + n;
+            normals[b] = normals[b] + n;
+            normals[c] = normals[c] + n;
+        }
+    }
+
+    for (p, n) in coords.iter_mut().zip(normals.iter()) {
+        if !na::is_zero(n) {
+            *p = *p + na::normalize(n) * margin;
+        }
+    }
+}
@@ -10,8 +10,13 @@ extern crate ncollide;
 extern crate nphysics;
 
 
-pub use testbed::Testbed;
+pub use testbed::{Testbed, SelectionHandler, StepHandler, EventHandler, KeyHandler};
+pub use glfw::Key;
+pub use stress::{StressTool, StressShape, StressSample};
+pub use engine::dispatch_shape;
 
 mod testbed;
 mod engine;
 mod objects;
+mod debug_lines;
+mod stress;
@@ -4,13 +4,17 @@ use kiss3d::window::Window;
 use kiss3d::scene::SceneNode;
 use na::{Pnt3, Iso3};
 use nphysics::object::RigidBody;
+use objects::sync;
+use objects::sync::SyncState;
 
 pub struct Ball {
     color:      Pnt3<f32>,
     base_color: Pnt3<f32>,
     delta:      Iso3<f32>,
     gfx:        SceneNode,
-    body:       Rc<RefCell<RigidBody>>
+    scale:      f32,
+    body:       Rc<RefCell<RigidBody>>,
+    sync:       SyncState
 }
 
 impl Ball {
@@ -26,7 +30,9 @@ impl Ball {
             base_color: color,
             delta:      delta,
             gfx:        window.add_sphere(radius as f32),
-            body:       body
+            scale:      1.0,
+            body:       body,
+            sync:       SyncState::new()
         };
 
         res.gfx.set_color(color.x, color.y, color.z);
@@ -45,17 +51,30 @@ impl Ball {
     }
 
     pub fn update(&mut self) {
-        let rb = self.body.borrow();
+        let rb    = self.body.borrow();
+        let color = sync::display_color(&*rb, &self.color);
+
+        if !self.sync.update(rb.position(), rb.is_active(), &color) {
+            return;
+        }
 
         if rb.is_active() {
             self.gfx.set_local_transformation(*rb.position() * self.delta);
-            self.gfx.set_color(self.color.x, self.color.y, self.color.z);
+            self.gfx.set_color(color.x, color.y, color.z);
         }
         else {
-            self.gfx.set_color(self.color.x * 0.25, self.color.y * 0.25, self.color.z * 0.25);
+            self.gfx.set_color(color.x * 0.25, color.y * 0.25, color.z * 0.25);
         }
     }
 
+    pub fn rescale(&mut self, s: f32) {
+        self.scale             = self.scale * s;
+        self.delta.translation = self.delta.translation * s;
+
+        self.gfx.set_local_scale(self.scale, self.scale, self.scale);
+        self.gfx.set_local_transformation(*self.body.borrow().position() * self.delta);
+    }
+
     pub fn object(&self) -> &SceneNode {
         &self.gfx
     }
@@ -5,6 +5,8 @@ use kiss3d::scene::SceneNode;
 use na::{Pnt3, Iso3};
 use na;
 use nphysics::object::RigidBody;
+use objects::sync;
+use objects::sync::SyncState;
 use ncollide::procedural;
 
 pub struct BezierSurface {
@@ -12,7 +14,9 @@ pub struct BezierSurface {
     base_color: Pnt3<f32>,
     delta:      Iso3<f32>,
     gfx:        SceneNode,
-    body:       Rc<RefCell<RigidBody>>
+    scale:      f32,
+    body:       Rc<RefCell<RigidBody>>,
+    sync:       SyncState
 }
 
 impl BezierSurface {
@@ -31,7 +35,9 @@ impl BezierSurface {
             base_color: color,
             delta:      delta,
             gfx:        window.add_trimesh(bezier, na::one()),
-            body:       body
+            scale:      1.0,
+            body:       body,
+            sync:       SyncState::new()
         };
 
         res.gfx.set_color(color.x, color.y, color.z);
@@ -51,17 +57,51 @@ impl BezierSurface {
     }
 
     pub fn update(&mut self) {
-        let rb = self.body.borrow();
+        let rb    = self.body.borrow();
+        let color = sync::display_color(&*rb, &self.color);
+
+        if !self.sync.update(rb.position(), rb.is_active(), &color) {
+            return;
+        }
 
         if rb.is_active() {
             self.gfx.set_local_transformation(*rb.position() * self.delta);
-            self.gfx.set_color(self.color.x, self.color.y, self.color.z);
+            self.gfx.set_color(color.x, color.y, color.z);
         }
         else {
-            self.gfx.set_color(self.color.x * 0.25, self.color.y * 0.25, self.color.z * 0.25);
+            self.gfx.set_color(color.x * 0.25, color.y * 0.25, color.z * 0.25);
         }
     }
 
+    /// Tessellates again the surface after its control points changed.
+    ///
+    /// The number of control points must be the same as before.
+    pub fn set_control_points(&mut self,
+                              control_points: &[Pnt3<f32>],
+                              nupoints:       uint,
+                              nvpoints:       uint,
+                              window:         &mut Window) {
+        let bezier = procedural::bezier_surface(control_points, nupoints, nvpoints, 100, 100);
+
+        window.remove(&mut self.gfx);
+
+        self.gfx  = window.add_trimesh(bezier, na::one());
+        self.sync = SyncState::new();
+
+        self.gfx.set_local_scale(self.scale, self.scale, self.scale);
+        self.gfx.set_local_transformation(*self.body.borrow().position() * self.delta);
+        self.gfx.enable_backface_culling(false);
+        self.update();
+    }
+
+    pub fn rescale(&mut self, s: f32) {
+        self.scale             = self.scale * s;
+        self.delta.translation = self.delta.translation * s;
+
+        self.gfx.set_local_scale(self.scale, self.scale, self.scale);
+        self.gfx.set_local_transformation(*self.body.borrow().position() * self.delta);
+    }
+
     pub fn object(&self) -> &SceneNode {
         &self.gfx
     }
@@ -6,13 +6,17 @@ use na::{Pnt3, Vec3, Iso3};
 use na;
 use ncollide::procedural::TriMesh;
 use nphysics::object::RigidBody;
+use objects::sync;
+use objects::sync::SyncState;
 
 pub struct Convex {
     color:      Pnt3<f32>,
     base_color: Pnt3<f32>,
     delta:      Iso3<f32>,
     gfx:        SceneNode,
-    body:       Rc<RefCell<RigidBody>>
+    scale:      f32,
+    body:       Rc<RefCell<RigidBody>>,
+    sync:       SyncState
 }
 
 impl Convex {
@@ -28,7 +32,9 @@ impl Convex {
             base_color: color,
             delta:      delta,
             gfx:        window.add_trimesh(convex.clone(), na::one()),
-            body:       body
+            scale:      1.0,
+            body:       body,
+            sync:       SyncState::new()
         };
 
         res.gfx.set_color(color.x, color.y, color.z);
@@ -47,20 +53,33 @@ impl Convex {
     }
 
     pub fn update(&mut self) {
-        let rb = self.body.borrow();
+        let rb    = self.body.borrow();
+        let color = sync::display_color(&*rb, &self.color);
+
+        if !self.sync.update(rb.position(), rb.is_active(), &color) {
+            return;
+        }
 
         if rb.is_active() {
             {
                 self.gfx.set_local_transformation(*rb.position() * self.delta);
             }
 
-            self.gfx.set_color(self.color.x, self.color.y, self.color.z);
+            self.gfx.set_color(color.x, color.y, color.z);
         }
         else {
-            self.gfx.set_color(self.color.x * 0.25, self.color.y * 0.25, self.color.z * 0.25);
+            self.gfx.set_color(color.x * 0.25, color.y * 0.25, color.z * 0.25);
         }
     }
 
+    pub fn rescale(&mut self, s: f32) {
+        self.scale             = self.scale * s;
+        self.delta.translation = self.delta.translation * s;
+
+        self.gfx.set_local_scale(self.scale, self.scale, self.scale);
+        self.gfx.set_local_transformation(*self.body.borrow().position() * self.delta);
+    }
+
     pub fn object(&self) -> &SceneNode {
         &self.gfx
     }
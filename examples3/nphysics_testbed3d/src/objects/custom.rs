@@ -0,0 +1,77 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use kiss3d::scene::SceneNode;
+use na::Pnt3;
+use nphysics::object::RigidBody;
+use objects::sync;
+use objects::sync::SyncState;
+
+/// A body rendered with a user-provided scene node instead of its shape.
+pub struct Custom {
+    color:      Pnt3<f32>,
+    base_color: Pnt3<f32>,
+    gfx:        SceneNode,
+    scale:      f32,
+    body:       Rc<RefCell<RigidBody>>,
+    sync:       SyncState
+}
+
+impl Custom {
+    pub fn new(body: Rc<RefCell<RigidBody>>, gfx: SceneNode, color: Pnt3<f32>) -> Custom {
+        let t = body.borrow().position().clone();
+
+        let mut res = Custom {
+            color:      color,
+            base_color: color,
+            gfx:        gfx,
+            scale:      1.0,
+            body:       body,
+            sync:       SyncState::new()
+        };
+
+        res.gfx.set_color(color.x, color.y, color.z);
+        res.gfx.set_local_transformation(t);
+        res.update();
+
+        res
+    }
+
+    pub fn select(&mut self) {
+        self.color = Pnt3::new(1.0, 0.0, 0.0);
+    }
+
+    pub fn unselect(&mut self) {
+        self.color = self.base_color;
+    }
+
+    pub fn update(&mut self) {
+        let rb    = self.body.borrow();
+        let color = sync::display_color(&*rb, &self.color);
+
+        if !self.sync.update(rb.position(), rb.is_active(), &color) {
+            return;
+        }
+
+        if rb.is_active() {
+            self.gfx.set_local_transformation(*rb.position());
+            self.gfx.set_color(color.x, color.y, color.z);
+        }
+        else {
+            self.gfx.set_color(color.x * 0.25, color.y * 0.25, color.z * 0.25);
+        }
+    }
+
+    pub fn rescale(&mut self, s: f32) {
+        self.scale = self.scale * s;
+
+        self.gfx.set_local_scale(self.scale, self.scale, self.scale);
+    }
+
+    pub fn object(&self) -> &SceneNode {
+        &self.gfx
+    }
+
+    pub fn body(&self) -> &Rc<RefCell<RigidBody>> {
+        &self.body
+    }
+}
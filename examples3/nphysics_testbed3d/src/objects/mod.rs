@@ -6,4 +6,6 @@ pub mod cone;
 pub mod mesh;
 pub mod bezier_surface;
 pub mod convex;
+pub mod custom;
+pub mod sync;
 // pub mod parametric_surface;
@@ -3,38 +3,32 @@ use std::cell::RefCell;
 use na;
 use kiss3d::window;
 use kiss3d::scene::SceneNode;
-use na::{Pnt3, Vec3};
+use na::{Pnt3, Vec3, Translate};
 use nphysics::object::RigidBody;
+use objects::sync::SyncState;
 
 pub struct Plane {
-    gfx:  SceneNode,
-    body: Rc<RefCell<RigidBody>>
+    gfx:    SceneNode,
+    normal: Vec3<f32>,
+    color:  Pnt3<f32>,
+    body:   Rc<RefCell<RigidBody>>,
+    sync:   SyncState
 }
 
 impl Plane {
-    pub fn new(body:         Rc<RefCell<RigidBody>>,
-               world_pos:    &Pnt3<f32>,
-               world_normal: &Vec3<f32>,
-               color:        Pnt3<f32>,
-               window:       &mut window::Window) -> Plane {
+    pub fn new(body:   Rc<RefCell<RigidBody>>,
+               normal: &Vec3<f32>,
+               color:  Pnt3<f32>,
+               window: &mut window::Window) -> Plane {
         let mut res = Plane {
-            gfx:  window.add_quad(100.0, 100.0, 10, 10),
-            body: body
+            gfx:    window.add_quad(100.0, 100.0, 10, 10),
+            normal: *normal,
+            color:  color,
+            body:   body,
+            sync:   SyncState::new()
         };
 
         res.gfx.set_color(color.x, color.y, color.z);
-
-        let up;
-
-        if na::is_zero(&world_normal.z) && na::is_zero(&world_normal.y) {
-            up = Vec3::z();
-        }
-        else {
-            up = Vec3::x();
-        }
-
-        res.gfx.look_at_z(world_pos, &(*world_pos + *world_normal), &up);
-
         res.update();
 
         res
@@ -47,7 +41,30 @@ impl Plane {
     }
 
     pub fn update(&mut self) {
-        // FIXME: atm we assume the plane does not move
+        let rb = self.body.borrow();
+
+        // The activation does not change the rendering of a plane.
+        if !self.sync.update(rb.position(), true, &self.color) {
+            return;
+        }
+
+        let world_pos    = na::translation(rb.position()).translate(&na::orig());
+        let world_normal = na::rotate(rb.position(), &self.normal);
+
+        let up;
+
+        if na::is_zero(&world_normal.z) && na::is_zero(&world_normal.y) {
+            up = Vec3::z();
+        }
+        else {
+            up = Vec3::x();
+        }
+
+        self.gfx.look_at_z(&world_pos, &(world_pos + world_normal), &up);
+    }
+
+    pub fn rescale(&mut self, _: f32) {
+        // Planes are infinite.
     }
 
     pub fn object(&self) -> &SceneNode {
@@ -0,0 +1,52 @@
+use na::{Pnt3, Iso3};
+use na;
+use nphysics::object::RigidBody;
+
+/// The state of a body last copied to its scene node.
+///
+/// Updating a kiss3d scene node is expensive so it is skipped when the body did not change.
+pub struct SyncState {
+    synced:   bool,
+    position: Iso3<f32>,
+    active:   bool,
+    color:    Pnt3<f32>
+}
+
+impl SyncState {
+    pub fn new() -> SyncState {
+        SyncState {
+            synced:   false,
+            position: na::one(),
+            active:   false,
+            color:    na::orig()
+        }
+    }
+
+    /// Records the new state and returns `true` if it differs from the last one recorded.
+    ///
+    /// Changes of position smaller than an epsilon are ignored.
+    pub fn update(&mut self, position: &Iso3<f32>, active: bool, color: &Pnt3<f32>) -> bool {
+        if self.synced && self.active == active && self.color == *color &&
+           na::approx_eq_eps(&self.position, position, &1.0e-5) {
+            return false;
+        }
+
+        self.synced   = true;
+        self.position = position.clone();
+        self.active   = active;
+        self.color    = *color;
+
+        true
+    }
+}
+
+/// The color of a node of `body`: `color`, or yellow if a safety mechanism of the world altered
+/// the last step of `body`, see `RigidBody::last_step_flags`.
+pub fn display_color(body: &RigidBody, color: &Pnt3<f32>) -> Pnt3<f32> {
+    if body.last_step_flags().is_empty() {
+        *color
+    }
+    else {
+        Pnt3::new(1.0, 1.0, 0.0)
+    }
+}
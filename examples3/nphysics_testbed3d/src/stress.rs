@@ -0,0 +1,211 @@
+use std::num::Float;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::RingBuf;
+use na::{Pnt3, Vec3, Translation};
+use na;
+use ncollide::shape::{Ball, Cuboid};
+use nphysics::object::RigidBody;
+use nphysics::world::World;
+
+// Number of measures kept for the overlay, one per simulated second.
+static MAX_SAMPLES: uint = 8;
+// Height above the top of the scene where the bodies are spawned.
+static SPAWN_HEIGHT: f32 = 5.0;
+
+/// The shape of the bodies spawned by the stress tool.
+#[deriving(Clone, PartialEq, Show)]
+pub enum StressShape {
+    /// Balls of the given radius.
+    Ball(f32),
+    /// Cubes of the given half extent.
+    Cube(f32)
+}
+
+/// The timing of a step, with the size of the world it was measured on.
+#[deriving(Clone, Show)]
+pub struct StressSample {
+    /// The simulated time at the end of the step.
+    pub time:         f32,
+    /// The number of bodies in the world.
+    pub num_bodies:   uint,
+    /// The number of contacts given to the solver.
+    pub num_contacts: uint,
+    /// The duration of the step, in seconds.
+    pub step_time:    f64
+}
+
+/// Tool spawning bodies at a fixed rate above the scene, to find out how the step time grows with
+/// the number of bodies.
+pub struct StressTool {
+    rate:      f32,
+    shape:     StressShape,
+    spawning:  bool,
+    // Simulated time not yet converted into spawned bodies.
+    remainder: f32,
+    spawn_pos: Pnt3<f32>,
+    spawned:   Vec<Rc<RefCell<RigidBody>>>,
+    samples:   RingBuf<StressSample>,
+    last_time: f32
+}
+
+impl StressTool {
+    /// Creates a stopped stress tool spawning `rate` bodies per simulated second.
+    pub fn new(rate: f32, shape: StressShape) -> StressTool {
+        StressTool {
+            rate:      rate,
+            shape:     shape,
+            spawning:  false,
+            remainder: 0.0,
+            spawn_pos: na::orig(),
+            spawned:   Vec::new(),
+            samples:   RingBuf::new(),
+            last_time: 0.0
+        }
+    }
+
+    pub fn set_rate(&mut self, rate: f32) {
+        assert!(rate > 0.0, "The spawn rate must be positive.");
+        self.rate = rate
+    }
+
+    pub fn set_shape(&mut self, shape: StressShape) {
+        self.shape = shape
+    }
+
+    pub fn is_spawning(&self) -> bool {
+        self.spawning
+    }
+
+    /// The number of spawned bodies still in the world.
+    pub fn num_spawned(&self) -> uint {
+        self.spawned.len()
+    }
+
+    /// Starts spawning bodies above the current content of `world`.
+    pub fn start(&mut self, world: &World) {
+        self.spawn_pos = match world.aabb() {
+            Some(aabb) => {
+                let center = na::center(aabb.mins(), aabb.maxs());
+                Pnt3::new(center.x, aabb.maxs().y + SPAWN_HEIGHT, center.z)
+            },
+            None => Pnt3::new(0.0, SPAWN_HEIGHT, 0.0)
+        };
+
+        self.spawning  = true;
+        self.remainder = 0.0;
+        self.last_time = world.time();
+    }
+
+    pub fn stop(&mut self) {
+        self.spawning = false
+    }
+
+    /// Stops spawning, and forgets the spawned bodies and the measures, e.g., when the world is
+    /// replaced.
+    pub fn reset(&mut self) {
+        self.spawning = false;
+        self.spawned.clear();
+        self.samples.clear();
+    }
+
+    /// Removes every spawned body from `world`.
+    pub fn clear(&mut self, world: &mut World) {
+        for b in self.spawned.iter() {
+            world.remove_body(b);
+        }
+
+        self.spawned.clear();
+    }
+
+    /// Forgets `body` if it was spawned, e.g., because it has been removed from the world by
+    /// other means.
+    pub fn forget(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        let key = body.deref() as *const RefCell<RigidBody>;
+
+        self.spawned.retain(|b| b.deref() as *const RefCell<RigidBody> != key);
+    }
+
+    /// Spawns the bodies due since the last call, according to the time simulated by `world`.
+    pub fn spawn(&mut self, world: &mut World) {
+        let time  = world.time();
+        let delta = time - self.last_time;
+
+        self.last_time = time;
+
+        if !self.spawning || delta <= 0.0 {
+            return;
+        }
+
+        self.remainder = self.remainder + delta * self.rate;
+
+        while self.remainder >= 1.0 {
+            self.remainder = self.remainder - 1.0;
+
+            let mut rb = match self.shape {
+                StressShape::Ball(radius)      => RigidBody::new_dynamic(Ball::new(radius), 1.0, 0.3, 0.6),
+                StressShape::Cube(half_extent) => {
+                    let geom = Cuboid::new(Vec3::new(half_extent, half_extent, half_extent));
+                    RigidBody::new_dynamic(geom, 1.0, 0.3, 0.6)
+                }
+            };
+
+            // Spread the bodies on a small spiral so that they do not stack perfectly.
+            let i     = self.spawned.len() as f32;
+            let angle = i * 2.4;
+            let dist  = 0.5 * (i % 10.0);
+
+            rb.append_translation(&(self.spawn_pos.to_vec() + Vec3::new(angle.cos() * dist, 0.0, angle.sin() * dist)));
+
+            self.spawned.push(world.add_body(rb));
+        }
+    }
+
+    /// Records the duration of a step of `world`.
+    pub fn record(&mut self, world: &World, step_time: f64) -> StressSample {
+        let sample = StressSample {
+            time:         world.time(),
+            num_bodies:   world.bodies().count(),
+            num_contacts: world.step_statistics().num_contacts,
+            step_time:    step_time
+        };
+
+        let due = match self.samples.back() {
+            Some(last) => sample.time >= last.time + 1.0,
+            None       => true
+        };
+
+        if due {
+            if self.samples.len() == MAX_SAMPLES {
+                let _ = self.samples.pop_front();
+            }
+
+            self.samples.push_back(sample.clone());
+        }
+
+        sample
+    }
+
+    /// The lines describing the latest measures, the most recent first.
+    pub fn describe(&self) -> Vec<String> {
+        let mut res = Vec::new();
+
+        res.push(format!("stress: {}, {} spawned", if self.spawning { "spawning" } else { "stopped" }, self.spawned.len()));
+
+        for s in self.samples.iter().rev() {
+            res.push(format!("{} bodies: {:.3} ms/step", s.num_bodies, s.step_time * 1000.0));
+        }
+
+        res
+    }
+
+    /// The header of the CSV output of `csv_line`.
+    pub fn csv_header() -> &'static str {
+        "time,bodies,contacts,step_ms"
+    }
+
+    /// Formats `sample` as a line of CSV.
+    pub fn csv_line(sample: &StressSample) -> String {
+        format!("{},{},{},{}", sample.time, sample.num_bodies, sample.num_contacts, sample.step_time * 1000.0)
+    }
+}
@@ -1,10 +1,11 @@
 use std::os;
+use std::num::Float;
 use std::rc::Rc;
 use std::cell::RefCell;
 use time;
 use glfw;
 use glfw::{Key, Action};
-use na::{Pnt2, Pnt3, Vec3, Translation, Translate, Iso3, Bounded};
+use na::{Pnt2, Pnt3, Vec3, Translation, Translate, Transform, Iso3, Bounded};
 use na;
 use kiss3d::window::Window;
 use kiss3d::light;
@@ -13,13 +14,19 @@ use kiss3d::loader::obj;
 use ncollide::shape::{Cuboid, Ball};
 use ncollide::ray;
 use ncollide::ray::Ray;
+use ncollide::bounding_volume::HasBoundingVolume;
 use nphysics::detection::Detector;
 use nphysics::detection::constraint::Constraint;
-use nphysics::detection::joint::{Anchor, Fixed, Joint};
+use nphysics::detection::joint::{Grab, Joint, DistanceMode};
 use nphysics::object::RigidBody;
-use nphysics::world::World;
+use nphysics::world::{World, WorldEvent, GroupHandle};
+use nphysics::io;
 use engine::GraphicsManager;
+use debug_lines::DebugLines;
+use stress::{StressTool, StressShape};
 
+// The maximum acceleration given to a body dragged with the mouse.
+static GRAB_MAX_ACCELERATION: f32 = 200.0;
 
 fn usage(exe_name: &str) {
     println!("Usage: {} [OPTION] ", exe_name);
@@ -27,6 +34,13 @@ fn usage(exe_name: &str) {
     println!("Options:");
     println!("    --help  - prints this help message and exits.");
     println!("    --pause - do not start the simulation right away.");
+    println!("    --scene NAME - start with the registered scene NAME, or replace the world by the scene");
+    println!("                   described by the JSON file NAME if no scene has this name.");
+    println!("    --timestep DT - set the timestep of the world to DT seconds.");
+    println!("    --no-margins - render the shapes without their collision margins.");
+    println!("    --color-seed SEED - generate the random colors of the bodies from the integer SEED.");
+    println!("    --stress RATE,DURATION - without rendering, spawn RATE bodies per second during DURATION");
+    println!("                             simulated seconds and print the time of each step as CSV.");
     println!("");
     println!("The following keyboard commands are supported:");
     println!("    t      - pause/continue the simulation.");
@@ -36,17 +50,61 @@ fn usage(exe_name: &str) {
     println!("    3      - launch a fast cube using continuous collision detection.");
     println!("    TAB    - switch camera mode (first-person or arc-ball).");
     println!("    SHIFT + right click - launch a fast cube using continuous collision detection.");
-    println!("    CTRL + left click + drag - select and drag an object using a ball-in-socket joint.");
+    println!("    CTRL + left click + drag - select and drag an object using a grab joint.");
+    println!("    left click - select an object and display its state. Click on empty space to deselect.");
     println!("    SHIFT + left click - remove an object.");
     println!("    arrows - move around when in first-person camera mode.");
     println!("    space  - switch wireframe mode. When ON, the contacts points and normals are displayed.");
     println!("    b      - draw the bounding boxes.");
+    println!("    j      - draw the joints. Joints with a large error are displayed in red.");
+    println!("    n      - start spawning bodies above the scene, and display the time of the steps.");
+    println!("    m      - stop spawning bodies.");
+    println!("    delete - remove every spawned body.");
+    println!("    page up/page down - switch to the previous/next registered scene.");
+    println!("");
+    println!("The bodies which last step was altered by the continuous collision detection, a velocity clamp");
+    println!("or a capped penetration correction are tinted in yellow until the next step.");
+}
+
+/// Trait implemented by the objects notified when the body selected on the testbed changes.
+pub trait SelectionHandler {
+    /// Called with the newly selected body, or `None` if the selection was cleared.
+    fn handle_selection(&mut self, body: Option<&Rc<RefCell<RigidBody>>>);
+}
+
+/// Trait implemented by the objects modifying the world before each step of the testbed.
+pub trait StepHandler {
+    /// Called before `world` is stepped, e.g., to animate its static bodies.
+    fn handle_step(&mut self, world: &mut World);
+}
+
+/// Trait implemented by the objects notified of the keys not used by the testbed itself.
+pub trait KeyHandler {
+    /// Called when `key` is released, e.g., to remove a joint of `world`.
+    fn handle_key(&mut self, key: Key, world: &mut World);
+}
+
+/// Trait implemented by the objects notified of the events emitted by the world of the testbed.
+pub trait EventHandler {
+    /// Called with each event emitted during the last step.
+    ///
+    /// The bodies pushed to `highlights` with `true` are displayed in red, and those pushed with
+    /// `false` get their color back.
+    fn handle_event(&mut self, event: &WorldEvent, highlights: &mut Vec<(Rc<RefCell<RigidBody>>, bool)>);
 }
 
 pub struct Testbed {
-    world:    World,
-    window:   Window,
-    graphics: GraphicsManager,
+    world:             World,
+    window:            Window,
+    graphics:          GraphicsManager,
+    selected:          Option<Rc<RefCell<RigidBody>>>,
+    selection_handler: Option<Box<SelectionHandler + 'static>>,
+    step_handler:      Option<Box<StepHandler + 'static>>,
+    event_handler:     Option<Box<EventHandler + 'static>>,
+    key_handler:       Option<Box<KeyHandler + 'static>>,
+    stress:            StressTool,
+    scenes:            Vec<(String, Box<Fn() -> World + 'static>)>,
+    scene:             Option<uint>
 }
 
 impl Testbed {
@@ -55,9 +113,17 @@ impl Testbed {
         let window   = Window::new("nphysics: 3d demo");
 
         Testbed {
-            world:    World::new(),
-            window:   window,
-            graphics: graphics
+            world:             World::new(),
+            window:            window,
+            graphics:          graphics,
+            selected:          None,
+            selection_handler: None,
+            step_handler:      None,
+            event_handler:     None,
+            key_handler:       None,
+            stress:            StressTool::new(10.0, StressShape::Cube(0.5)),
+            scenes:            Vec::new(),
+            scene:             None
         }
     }
 
@@ -70,23 +136,328 @@ impl Testbed {
     }
 
     pub fn set_world(&mut self, world: World) {
+        self.select(None);
+        self.stress.reset();
+        // A paused testbed stays paused when its world is replaced, e.g., by switching scenes.
+        let paused = self.world.is_paused() || world.is_paused();
+
         self.world = world;
+        self.world.set_paused(paused);
+        // The bodies moved by hand during a pause stay pickable.
+        self.world.set_refresh_when_paused(true);
+        self.scene = None;
 
         self.graphics.clear(&mut self.window);
 
         for rb in self.world.bodies() {
             self.graphics.add(&mut self.window, rb.clone());
         }
+
+        // The bodies already present have just been added.
+        self.world.drain_events(&mut Vec::new());
+    }
+
+    fn sync_graphics(&mut self) {
+        let mut events = Vec::new();
+
+        self.world.drain_events(&mut events);
+
+        for event in events.iter() {
+            match *event {
+                WorldEvent::BodyAdded(ref b) => {
+                    if self.graphics.body_to_scene_node(b).is_none() {
+                        self.graphics.add(&mut self.window, b.clone())
+                    }
+                },
+                WorldEvent::BodyRemoved(ref b) => {
+                    if self.is_selected(b) {
+                        self.select(None)
+                    }
+
+                    self.stress.forget(b);
+                    self.graphics.remove(&mut self.window, b)
+                },
+                // The nodes of the bodies that did not move are only updated here.
+                WorldEvent::BodyActivated(ref b) | WorldEvent::BodyDeactivated(ref b) => {
+                    self.graphics.update_body(b)
+                },
+                WorldEvent::BodyScaled(ref b, s) => self.graphics.rescale_body(b, s),
+                WorldEvent::BodyShapeUpdated(ref b) => self.graphics.update_shape(&mut self.window, b),
+                _ => { }
+            }
+        }
+
+        match self.event_handler {
+            Some(ref mut handler) => {
+                let mut highlights = Vec::new();
+
+                for event in events.iter() {
+                    handler.handle_event(event, &mut highlights);
+                }
+
+                for &(ref b, on) in highlights.iter() {
+                    highlight(&mut self.graphics, b, on)
+                }
+            },
+            None => { }
+        }
+    }
+
+    pub fn load_scene(&mut self, path: &Path) {
+        match io::load_scene(path) {
+            Ok((world, bodies)) => {
+                for b in bodies.iter() {
+                    match b.color {
+                        Some(color) => self.graphics.set_color(&b.body, color),
+                        None        => { }
+                    }
+                }
+
+                self.set_world(world);
+            },
+            Err(e) => panic!("Unable to load the scene `{}`: {}", path.display(), e)
+        }
+    }
+
+    /// Registers a scene built by `builder`, to be selected with the `--scene` flag or the page up
+    /// and page down keys.
+    ///
+    /// Unless another scene is selected, the testbed starts with the first registered one in place
+    /// of its current world. The camera, the handlers and the display options are kept when
+    /// switching scenes.
+    pub fn register_scene(&mut self, name: &str, builder: Box<Fn() -> World + 'static>) {
+        assert!(!self.scenes.iter().any(|&(ref n, _)| n.as_slice() == name),
+                "A scene with this name is already registered.");
+
+        self.scenes.push((name.to_string(), builder))
+    }
+
+    /// The names of the registered scenes, in the order they were registered.
+    pub fn scene_names(&self) -> Vec<&str> {
+        self.scenes.iter().map(|&(ref name, _)| name.as_slice()).collect()
+    }
+
+    /// The name of the registered scene currently simulated, if any.
+    pub fn current_scene(&self) -> Option<&str> {
+        self.scene.map(|i| self.scenes[i].ref0().as_slice())
+    }
+
+    /// Replaces the world by a new one built by the registered scene `name`.
+    ///
+    /// The bodies of the current world are removed first, so that the handlers keeping them see
+    /// them go. This returns `false` if no scene has this name.
+    pub fn switch_scene(&mut self, name: &str) -> bool {
+        match self.scenes.iter().position(|&(ref n, _)| n.as_slice() == name) {
+            Some(i) => {
+                self.switch_to_scene(i);
+                true
+            },
+            None => false
+        }
+    }
+
+    fn switch_to_scene(&mut self, i: uint) {
+        self.world.clear();
+        self.sync_graphics();
+
+        let world = (**self.scenes[i].ref1())();
+
+        self.set_world(world);
+        self.scene = Some(i);
     }
 
     pub fn look_at(&mut self, eye: Pnt3<f32>, at: Pnt3<f32>) {
         self.graphics.look_at(eye, at);
     }
 
+    /// Sets whether the shapes are rendered dilated by their margins, i.e., with the same extents
+    /// as seen by the collision detection. Enabled by default.
+    pub fn set_render_margins(&mut self, render_margins: bool) {
+        if self.graphics.render_margins() == render_margins {
+            return;
+        }
+
+        self.graphics.set_render_margins(render_margins);
+
+        for rb in self.world.bodies() {
+            self.graphics.rebuild(&mut self.window, rb.clone());
+        }
+    }
+
+    /// Sets whether the testbed panics when a body has a shape it cannot render.
+    ///
+    /// Otherwise, such a body is simulated but not displayed, and a warning describing it is
+    /// printed and recorded, see `rendering_warnings`. Defaults to `false`.
+    pub fn set_strict_rendering(&mut self, strict: bool) {
+        self.graphics.set_strict_rendering(strict)
+    }
+
+    /// The descriptions of the shapes the testbed could not render.
+    pub fn rendering_warnings(&self) -> &[String] {
+        self.graphics.warnings()
+    }
+
+    /// The body currently selected, if any.
+    pub fn selected_body(&self) -> Option<Rc<RefCell<RigidBody>>> {
+        self.selected.clone()
+    }
+
+    /// Sets the object notified each time the selection changes.
+    pub fn set_selection_handler<H: SelectionHandler + 'static>(&mut self, handler: H) {
+        self.selection_handler = Some(box handler as Box<SelectionHandler + 'static>)
+    }
+
+    /// Sets the object called before each step of the world.
+    pub fn set_step_handler<H: StepHandler + 'static>(&mut self, handler: H) {
+        self.step_handler = Some(box handler as Box<StepHandler + 'static>)
+    }
+
+    /// Sets the object notified of the events of the world after each step.
+    pub fn set_event_handler<H: EventHandler + 'static>(&mut self, handler: H) {
+        self.event_handler = Some(box handler as Box<EventHandler + 'static>)
+    }
+
+    /// Sets the object notified of the keys released which the testbed does not use.
+    pub fn set_key_handler<H: KeyHandler + 'static>(&mut self, handler: H) {
+        self.key_handler = Some(box handler as Box<KeyHandler + 'static>)
+    }
+
+    /// The tool spawning bodies to measure the performances, e.g., to set the shape and the rate
+    /// of the bodies spawned with the `n` key.
+    pub fn stress_tool(&mut self) -> &mut StressTool {
+        &mut self.stress
+    }
+
+    /// Selects and highlights `body`, or clears the selection if it is `None`.
+    pub fn select(&mut self, body: Option<Rc<RefCell<RigidBody>>>) {
+        match self.selected {
+            Some(ref b) => highlight(&mut self.graphics, b, false),
+            None        => { }
+        }
+
+        self.selected = body;
+
+        match self.selected {
+            Some(ref b) => highlight(&mut self.graphics, b, true),
+            None        => { }
+        }
+
+        match self.selection_handler {
+            Some(ref mut h) => h.handle_selection(self.selected.as_ref()),
+            None            => { }
+        }
+    }
+
+    fn is_selected(&self, body: &Rc<RefCell<RigidBody>>) -> bool {
+        match self.selected {
+            Some(ref b) => b.deref() as *const RefCell<RigidBody> == body.deref() as *const RefCell<RigidBody>,
+            None        => false
+        }
+    }
+
+    // Finds the closest body hit by `ray`, and the time of impact.
+    fn pick(&mut self, ray: &Ray<Pnt3<f32>, Vec3<f32>>) -> Option<(Rc<RefCell<RigidBody>>, f32)> {
+        let mut mintoi = Bounded::max_value();
+        let mut minb   = None;
+
+        self.world.interferences_with_ray(ray, |b, inter| {
+            if inter.toi < mintoi {
+                mintoi = inter.toi;
+                minb   = Some(b.clone());
+            }
+        });
+
+        minb.map(|b| (b, mintoi))
+    }
+
     pub fn set_color(&mut self, rb: &Rc<RefCell<RigidBody>>, color: Pnt3<f32>) {
         self.graphics.set_color(rb, color);
     }
 
+    /// Sets the seed of the random colors of the dynamic bodies.
+    ///
+    /// The bodies already displayed are colored again, except those colored with `set_color`.
+    pub fn set_color_seed(&mut self, seed: u64) {
+        self.graphics.set_color_seed(seed);
+        self.regenerate_colors();
+    }
+
+    /// Sets the colors given in turn to the dynamic bodies, in the order they were added, instead
+    /// of random ones.
+    ///
+    /// The static bodies stay grey. The bodies already displayed are colored again, except those
+    /// colored with `set_color`. An empty palette restores the random colors.
+    pub fn set_palette(&mut self, palette: Vec<Pnt3<f32>>) {
+        self.graphics.set_palette(palette);
+        self.regenerate_colors();
+    }
+
+    fn regenerate_colors(&mut self) {
+        let bodies: Vec<Rc<RefCell<RigidBody>>> = self.world.bodies().map(|b| b.clone()).collect();
+
+        for b in bodies.iter() {
+            self.graphics.forget_generated_color(b);
+            self.rebuild_body(b);
+        }
+    }
+
+    /// Sets the color of every body of `group`, including those already displayed.
+    pub fn set_group_color(&mut self, group: &GroupHandle, color: Pnt3<f32>) {
+        for b in self.world.group_bodies(group).iter() {
+            self.graphics.set_color(b, color);
+
+            if self.graphics.body_to_scene_node(b).is_some() {
+                self.graphics.rebuild(&mut self.window, b.clone());
+            }
+        }
+
+        // The rebuilt nodes are not highlighted anymore.
+        match self.selected {
+            Some(ref b) => highlight(&mut self.graphics, b, true),
+            None        => { }
+        }
+    }
+
+    /// Renders `body` with the meshes of the OBJ file at `path`, scaled by `scale`, instead of its
+    /// shape.
+    ///
+    /// The meshes are expressed in the local frame of the body. If the file cannot be loaded, a
+    /// warning is printed and the shape is rendered as usual.
+    pub fn set_body_mesh(&mut self, body: &Rc<RefCell<RigidBody>>, path: &str, scale: f32) {
+        self.graphics.set_mesh(body, Path::new(path), scale);
+        self.rebuild_body(body);
+    }
+
+    /// Textures the rendering of `body` with the image at `path`.
+    ///
+    /// The texture is tinted by the color of the body, so use a white color to display it as is.
+    /// If the file does not exist, a warning is printed and the body is left untextured.
+    pub fn set_body_texture(&mut self, body: &Rc<RefCell<RigidBody>>, path: &str) {
+        self.graphics.set_texture(body, Path::new(path));
+        self.rebuild_body(body);
+    }
+
+    // Rebuilds the nodes of `body`, if it is already displayed, to apply a new rendering setting.
+    fn rebuild_body(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        if self.graphics.body_to_scene_node(body).is_none() {
+            return;
+        }
+
+        self.graphics.rebuild(&mut self.window, body.clone());
+
+        if self.is_selected(body) {
+            highlight(&mut self.graphics, body, true)
+        }
+    }
+
+    pub fn update_mesh_vertices(&mut self, body: &Rc<RefCell<RigidBody>>, vertices: &[Pnt3<f32>]) {
+        self.world.update_mesh_vertices(body, vertices);
+    }
+
+    pub fn update_bezier_control_points(&mut self, body: &Rc<RefCell<RigidBody>>, control_points: &[Pnt3<f32>]) {
+        self.world.update_bezier_control_points(body, control_points);
+    }
+
     pub fn load_obj(path: &str) -> Vec<(Vec<Pnt3<f32>>, Vec<uint>)> {
         let path    = Path::new(path);
         let empty   = Path::new("_some_non_existant_folder"); // dont bother loading mtl files correctly
@@ -114,30 +485,159 @@ impl Testbed {
         res
     }
 
+    // Spawns bodies at `rate` per second during `duration` simulated seconds without rendering,
+    // and prints the timing of each step as CSV.
+    fn run_stress(&mut self, rate: f32, duration: f32) {
+        let mut events = Vec::new();
+        let end        = self.world.time() + duration;
+
+        self.stress.set_rate(rate);
+        self.stress.start(&self.world);
+
+        println!("{}", StressTool::csv_header());
+
+        // The time would never reach `end` if `--pause` was given.
+        self.world.set_paused(false);
+
+        while self.world.time() < end {
+            match self.step_handler {
+                Some(ref mut handler) => handler.handle_step(&mut self.world),
+                None                  => { }
+            }
+
+            self.stress.spawn(&mut self.world);
+
+            let before = time::precise_time_s();
+            self.world.step_fixed();
+            let dt     = time::precise_time_s() - before;
+
+            println!("{}", StressTool::csv_line(&self.stress.record(&self.world, dt)));
+
+            // Nothing is displayed: the events are only drained to not accumulate.
+            self.world.drain_events(&mut events);
+            events.clear();
+        }
+    }
+
     pub fn run(&mut self) {
-        let args        = os::args();
-        let mut running = RunMode::Running;
+        let args          = os::args();
+        let mut step_once = false;
+        let mut timestep  = None;
+        let mut stress    = None;
+        let mut selected_scene = false;
 
         if args.len() > 1 {
-            for arg in args.iter() {
-                if arg.as_slice() == "--help" || arg.as_slice() == "-h" {
+            let mut i = 1;
+
+            while i < args.len() {
+                let arg = args[i].as_slice();
+
+                if arg == "--help" || arg == "-h" {
                     usage(args[0].as_slice());
                     os::set_exit_status(1);
                     return;
                 }
-                else if arg.as_slice() == "--pause" {
-                    running = RunMode::Stop;
+                else if arg == "--pause" {
+                    self.world.set_paused(true);
                 }
+                else if arg == "--scene" && i + 1 < args.len() {
+                    i = i + 1;
+
+                    if !self.switch_scene(args[i].as_slice()) {
+                        let path = Path::new(args[i].as_slice());
+
+                        if path.exists() || self.scenes.is_empty() {
+                            self.load_scene(&path);
+                        }
+                        else {
+                            println!("Unknown scene: {}. The registered scenes are:", args[i]);
+
+                            for name in self.scene_names().iter() {
+                                println!("    {}", name);
+                            }
+
+                            os::set_exit_status(1);
+                            return;
+                        }
+                    }
+
+                    selected_scene = true;
+                }
+                else if arg == "--no-margins" {
+                    self.set_render_margins(false);
+                }
+                else if arg == "--color-seed" && i + 1 < args.len() {
+                    i = i + 1;
+                    match from_str::<u64>(args[i].as_slice()) {
+                        Some(seed) => self.set_color_seed(seed),
+                        None       => {
+                            println!("Invalid color seed: {}", args[i]);
+                            os::set_exit_status(1);
+                            return;
+                        }
+                    }
+                }
+                else if arg == "--stress" && i + 1 < args.len() {
+                    i = i + 1;
+                    let params: Vec<Option<f32>> = args[i].as_slice().split(',').map(|p| from_str::<f32>(p)).collect();
+
+                    match params.as_slice() {
+                        [ Some(rate), Some(duration) ] if rate > 0.0 && duration > 0.0 => stress = Some((rate, duration)),
+                        _ => {
+                            println!("Invalid stress parameters: {}", args[i]);
+                            os::set_exit_status(1);
+                            return;
+                        }
+                    }
+                }
+                else if arg == "--timestep" && i + 1 < args.len() {
+                    i = i + 1;
+                    match from_str::<f32>(args[i].as_slice()) {
+                        Some(dt) if dt > 0.0 => timestep = Some(dt),
+                        _ => {
+                            println!("Invalid timestep: {}", args[i]);
+                            os::set_exit_status(1);
+                            return;
+                        }
+                    }
+                }
+
+                i = i + 1;
             }
         }
 
-        let font_mem       = include_bin!("Inconsolata.otf");
-        let font           = Font::from_memory(font_mem, 60);
-        let mut draw_colls = false;
+        if !selected_scene && self.scene.is_none() && !self.scenes.is_empty() {
+            self.switch_to_scene(0);
+        }
+
+        // Applied last since `--scene` replaces the world.
+        match timestep {
+            Some(dt) => self.world.set_timestep(dt),
+            None     => { }
+        }
+
+        match stress {
+            Some((rate, duration)) => {
+                self.run_stress(rate, duration);
+                return;
+            },
+            None => { }
+        }
+
+        let font_mem        = include_bin!("Inconsolata.otf");
+        let font            = Font::from_memory(font_mem, 60);
+        let small_font      = Font::from_memory(font_mem, 30);
+        let mut draw_colls  = false;
+        let mut draw_joints = false;
+        let mut draw_aabbs  = false;
+        let mut flagged     = Vec::new();
+        let mut lines       = DebugLines::new();
+        let mut collisions  = Vec::new();
 
         let mut cursor_pos = Pnt2::new(0.0f32, 0.0);
+        let mut click_pos  = None;
         let mut grabbed_object: Option<Rc<RefCell<RigidBody>>> = None;
-        let mut grabbed_object_joint: Option<Rc<RefCell<Fixed>>> = None;
+        let mut grabbed_object_joint: Option<Rc<RefCell<Grab>>> = None;
         let mut grabbed_object_plane: (Pnt3<f32>, Vec3<f32>) = (na::orig(), na::zero());
 
 
@@ -160,32 +660,19 @@ impl Testbed {
 
                         let body = self.world.add_body(rb);
                         self.world.add_ccd_to(&body, 1.0);
-                        self.graphics.add(&mut self.window, body);
                     },
                     glfw::MouseButtonEvent(glfw::Button1, glfw::Press, modifier) => {
-                        if modifier.contains(glfw::Shift) {
-                            // XXX: huge and uggly code duplication
+                        if modifier.is_empty() {
+                            // Only a click without drag selects, to leave the camera usable.
+                            click_pos = Some(cursor_pos);
+                        }
+                        else if modifier.contains(glfw::Shift) {
                             let size = self.window.size();
                             let (pos, dir) = self.graphics.camera().unproject(&cursor_pos, &size);
-                            let ray = Ray::new(pos, dir);
-
-                            // cast the ray
-                            let mut mintoi = Bounded::max_value();
-                            let mut minb   = None;
-
-                            self.world.interferences_with_ray(&ray, |b, inter| {
-                                if inter.toi < mintoi {
-                                    mintoi = inter.toi;
-                                    minb   = Some(b.clone());
-                                }
-                            });
 
-                            if minb.is_some() {
-                                let b = minb.as_ref().unwrap();
-                                if b.borrow().can_move() {
-                                    self.world.remove_body(b);
-                                    self.graphics.remove(&mut self.window, b);
-                                }
+                            match self.pick(&Ray::new(pos, dir)) {
+                                Some((ref b, _)) if b.borrow().can_move() => self.world.remove_body(b),
+                                _ => { }
                             }
 
                             event.inhibited = true;
@@ -200,46 +687,41 @@ impl Testbed {
                                 None => { }
                             }
 
-                            // XXX: huge and uggly code duplication
                             let size = self.window.size();
                             let (pos, dir) = self.graphics.camera().unproject(&cursor_pos, &size);
                             let ray = Ray::new(pos, dir);
 
-                            // cast the ray
                             let mut mintoi = Bounded::max_value();
-                            let mut minb   = None;
 
-                            self.world.interferences_with_ray(&ray, |b, inter| {
-                                if inter.toi < mintoi {
-                                    mintoi = inter.toi;
-                                    minb   = Some(b.clone());
-                                }
-                            });
+                            match self.pick(&ray) {
+                                Some((b, toi)) => {
+                                    mintoi = toi;
 
-                            if minb.is_some() {
-                                let b = minb.as_ref().unwrap();
-                                if b.borrow().can_move() {
-                                    grabbed_object = Some(b.clone())
-                                }
+                                    if b.borrow().can_move() {
+                                        grabbed_object = Some(b)
+                                    }
+                                },
+                                None => { }
                             }
 
                             match grabbed_object {
                                 Some(ref b) => {
                                     for sn in self.graphics.body_to_scene_node(b).unwrap().iter_mut() {
                                         match grabbed_object_joint {
-                                            Some(ref j) => self.world.remove_fixed(j),
+                                            Some(ref j) => self.world.remove_grab(j),
                                             None        => { }
                                         }
 
-                                        let _1: Iso3<f32> = na::one();
-                                        let attach2 = na::append_translation(&_1, (ray.orig + ray.dir * mintoi).as_vec());
-                                        let attach1 = na::inv(&na::transformation(b.borrow().position())).unwrap() * attach2;
-                                        let anchor1 = Anchor::new(Some(minb.as_ref().unwrap().clone()), attach1);
-                                        let anchor2 = Anchor::new(None, attach2);
-                                        let joint   = Fixed::new(anchor1, anchor2);
-                                        grabbed_object_plane = (attach2.translate(&na::orig()), -ray.dir);
-                                        grabbed_object_joint = Some(self.world.add_fixed(joint));
-                                        // add a joint
+                                        let target    = ray.orig + ray.dir * mintoi;
+                                        let local     = b.borrow().position().inv_transform(&target);
+                                        let mut joint = Grab::new(b.clone(), local, target);
+
+                                        // Bounded so that a body stuck behind others does not push
+                                        // them through the ground.
+                                        joint.set_max_force(Some(GRAB_MAX_ACCELERATION / b.borrow().inv_mass()));
+
+                                        grabbed_object_plane = (target, -ray.dir);
+                                        grabbed_object_joint = Some(self.world.add_grab(joint));
                                         sn.select()
                                     }
                                 },
@@ -249,7 +731,7 @@ impl Testbed {
                             event.inhibited = true;
                         }
                     },
-                    glfw::MouseButtonEvent(_, glfw::Release, _) => {
+                    glfw::MouseButtonEvent(button, glfw::Release, _) => {
                         match grabbed_object {
                             Some(ref b) => {
                                 for sn in self.graphics.body_to_scene_node(b).unwrap().iter_mut() {
@@ -259,8 +741,25 @@ impl Testbed {
                             None => { }
                         }
 
+                        if button == glfw::Button1 && click_pos == Some(cursor_pos) {
+                            let size = self.window.size();
+                            let (pos, dir) = self.graphics.camera().unproject(&cursor_pos, &size);
+                            let picked = self.pick(&Ray::new(pos, dir)).map(|(b, _)| b);
+
+                            self.select(picked);
+                        }
+                        else {
+                            // Releasing a grabbed body may have removed the highlight.
+                            match self.selected {
+                                Some(ref b) => highlight(&mut self.graphics, b, true),
+                                None        => { }
+                            }
+                        }
+
+                        click_pos = None;
+
                         match grabbed_object_joint {
-                            Some(ref j) => self.world.remove_fixed(j),
+                            Some(ref j) => self.world.remove_grab(j),
                             None    => { }
                         }
 
@@ -279,10 +778,7 @@ impl Testbed {
                                 let (ref ppos, ref pdir) = grabbed_object_plane;
 
                                 match ray::plane_toi_with_ray(ppos, pdir, &Ray::new(pos, dir)) {
-                                    Some(inter) => {
-                                        let _1: Iso3<f32> = na::one();
-                                        j.borrow_mut().set_local2(na::append_translation(&_1, (pos + dir * inter).as_vec()))
-                                    },
+                                    Some(inter) => j.borrow_mut().set_target(pos + dir * inter),
                                     None => { }
                                 }
 
@@ -298,23 +794,34 @@ impl Testbed {
                     },
                     glfw::KeyEvent(Key::Tab, _, Action::Release, _) => self.graphics.switch_cameras(),
                     glfw::KeyEvent(Key::T, _,   Action::Release, _) => {
-                        if running == RunMode::Stop {
-                            running = RunMode::Running;
-                        }
-                        else {
-                            running = RunMode::Stop;
-                        }
+                        let paused = self.world.is_paused();
+                        self.world.set_paused(!paused);
                     },
-                    glfw::KeyEvent(Key::S, _, Action::Release, _) => running = RunMode::Step,
-                    glfw::KeyEvent(Key::B, _, Action::Release, _) => {
-                        // XXX: there is a bug on kiss3d with the removal of objects.
-                        // draw_aabbs = !draw_aabbs;
-                        // if draw_aabbs {
-                        //     graphics.enable_aabb_draw(&mut self.window);
-                        // }
-                        // else {
-                        //     graphics.disable_aabb_draw(&mut self.window);
-                        // }
+                    glfw::KeyEvent(Key::S, _, Action::Release, _) => step_once = true,
+                    glfw::KeyEvent(Key::B, _, Action::Release, _) => draw_aabbs = !draw_aabbs,
+                    glfw::KeyEvent(Key::J, _, Action::Release, _) => draw_joints = !draw_joints,
+                    glfw::KeyEvent(Key::N, _, Action::Release, _) => self.stress.start(&self.world),
+                    glfw::KeyEvent(Key::M, _, Action::Release, _) => self.stress.stop(),
+                    glfw::KeyEvent(Key::Delete, _, Action::Release, _) => self.stress.clear(&mut self.world),
+                    glfw::KeyEvent(key @ Key::PageUp, _, Action::Release, _) |
+                    glfw::KeyEvent(key @ Key::PageDown, _, Action::Release, _) if !self.scenes.is_empty() => {
+                        let n    = self.scenes.len();
+                        let next = match self.scene {
+                            Some(i) if key == Key::PageUp => (i + n - 1) % n,
+                            Some(i)                       => (i + 1) % n,
+                            None                          => 0
+                        };
+
+                        // The grab joint is removed with the bodies.
+                        grabbed_object       = None;
+                        grabbed_object_joint = None;
+
+                        self.switch_to_scene(next);
+
+                        match timestep {
+                            Some(dt) => self.world.set_timestep(dt),
+                            None     => { }
+                        }
                     },
                     glfw::KeyEvent(Key::Space, _, Action::Release, _) => {
                         draw_colls = !draw_colls;
@@ -344,8 +851,7 @@ impl Testbed {
 
                         rb.set_lin_vel(front * 40.0f32);
 
-                        let body = self.world.add_body(rb);
-                        self.graphics.add(&mut self.window, body.clone());
+                        self.world.add_body(rb);
                     },
                     glfw::KeyEvent(Key::Num2, _, Action::Press, _) => {
                         let geom   = Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5));
@@ -364,78 +870,306 @@ impl Testbed {
 
                         rb.set_lin_vel(front * 40.0f32);
 
-                        let body = self.world.add_body(rb);
-                        self.graphics.add(&mut self.window, body.clone());
+                        self.world.add_body(rb);
+                    }
+                    glfw::KeyEvent(key, _, Action::Release, _) => {
+                        match self.key_handler {
+                            Some(ref mut handler) => handler.handle_key(key, &mut self.world),
+                            None                  => { }
+                        }
                     }
                     _ => { }
                 }
             }
 
+            self.sync_graphics();
+
             let dt;
 
-            if running != RunMode::Stop {
+            // A single step is simulated by resuming the world for it.
+            if step_once {
+                self.world.set_paused(false);
+            }
+
+            if !self.world.is_paused() {
+                match self.step_handler {
+                    Some(ref mut handler) => handler.handle_step(&mut self.world),
+                    None                  => { }
+                }
+
+                self.stress.spawn(&mut self.world);
+
                 let before = time::precise_time_s();
-                self.world.step(0.016);
+                self.world.step_fixed();
                 dt = time::precise_time_s() - before;
 
-                self.graphics.draw();
+                let _ = self.stress.record(&self.world, dt);
+
+                self.graphics.draw_bodies(self.world.moved_bodies());
+
+                // The bodies tinted at the previous frame because of their step flags get their
+                // colors back, unless the flags were set again.
+                self.graphics.draw_bodies(flagged.as_slice());
+                flagged = self.world.moved_bodies().iter()
+                              .filter(|b| !b.borrow().last_step_flags().is_empty())
+                              .map(|b| b.clone())
+                              .collect();
             }
             else {
+                // Only keeps the bodies moved by hand pickable.
+                self.world.step_fixed();
                 dt = 0.0;
             }
 
-            if running == RunMode::Step {
-                running = RunMode::Stop;
+            if step_once {
+                self.world.set_paused(true);
+                step_once = false;
             }
 
             if draw_colls {
-                self.graphics.draw_positions(&mut self.window);
-                draw_collisions(&mut self.window, &mut self.world);
+                self.graphics.draw_positions(&mut lines);
+                draw_collisions(&mut lines, &mut self.world, &mut collisions);
+            }
+
+            if draw_joints {
+                self::draw_joints(&mut lines, &self.world);
+            }
+
+            if draw_aabbs {
+                self::draw_aabbs(&mut lines, &self.world);
             }
 
+            lines.flush(&mut self.window);
+
             let color = Pnt3::new(1.0, 1.0, 1.0);
 
-            if running != RunMode::Stop {
+            if !self.world.is_paused() {
                 self.window.draw_text(dt.to_string().as_slice(), &na::orig(), &font, &color);
             }
             else {
                 self.window.draw_text("Paused", &na::orig(), &font, &color);
             }
 
+            let sim_time = match self.current_scene() {
+                Some(name) => format!("{}: t = {:.2}s, step {}", name, self.world.time(), self.world.step_count()),
+                None       => format!("t = {:.2}s, step {}", self.world.time(), self.world.step_count())
+            };
+            self.window.draw_text(sim_time.as_slice(), &Pnt2::new(0.0, 30.0), &small_font, &color);
+
+            let mut desc = match self.selected {
+                Some(ref b) => describe_body(b, &mut self.world, &mut collisions),
+                None        => Vec::new()
+            };
+
+            if self.stress.is_spawning() || self.stress.num_spawned() != 0 {
+                desc.push_all(self.stress.describe().as_slice());
+            }
+
+            for (i, line) in desc.iter().enumerate() {
+                let pos = Pnt2::new(0.0, 60.0 + 30.0 * i as f32);
+                self.window.draw_text(line.as_slice(), &pos, &small_font, &color);
+            }
+
             self.window.render_with_camera(self.graphics.camera());
         }
     }
 }
 
-#[deriving(PartialEq)]
-enum RunMode {
-    Running,
-    Stop,
-    Step
+fn highlight(graphics: &mut GraphicsManager, body: &Rc<RefCell<RigidBody>>, on: bool) {
+    match graphics.body_to_scene_node(body) {
+        Some(nodes) => {
+            for n in nodes.iter_mut() {
+                if on {
+                    n.select()
+                }
+                else {
+                    n.unselect()
+                }
+
+                n.update()
+            }
+        },
+        None => { }
+    }
 }
 
-fn draw_collisions(window: &mut Window, physics: &mut World) {
-    let mut collisions = Vec::new();
+fn describe_body(body: &Rc<RefCell<RigidBody>>, world: &mut World, collisions: &mut Vec<Constraint>) -> Vec<String> {
+    collisions.clear();
+    world.interferences(collisions);
 
-    physics.interferences(&mut collisions);
+    let key          = body.deref() as *const RefCell<RigidBody>;
+    let mut contacts = 0u;
+
+    for c in collisions.iter() {
+        match *c {
+            Constraint::RBRB(ref b1, ref b2, _) => {
+                if b1.deref() as *const RefCell<RigidBody> == key ||
+                   b2.deref() as *const RefCell<RigidBody> == key {
+                    contacts = contacts + 1;
+                }
+            },
+            _ => { }
+        }
+    }
+
+    let rb   = body.borrow();
+    let mass = if rb.inv_mass() != 0.0 { (1.0 / rb.inv_mass()).to_string() } else { "infinite".to_string() };
+
+    vec!(
+        format!("position: {}", na::translation(rb.position())),
+        format!("linear velocity: {}", rb.lin_vel()),
+        format!("angular velocity: {}", rb.ang_vel()),
+        format!("mass: {}, margin: {}", mass, rb.margin()),
+        format!("state: {}, contacts: {}", if rb.is_active() { "active" } else { "sleeping" }, contacts)
+    )
+}
+
+fn draw_collisions(lines: &mut DebugLines, physics: &mut World, collisions: &mut Vec<Constraint>) {
+    physics.interferences(collisions);
 
     for c in collisions.iter() {
         match *c {
             Constraint::RBRB(_, _, ref c) => {
-                window.draw_line(&c.world1, &c.world2, &Pnt3::new(1.0, 0.0, 0.0));
+                lines.push(&c.world1, &c.world2, &Pnt3::new(1.0, 0.0, 0.0));
 
                 let center = na::center(&c.world1, &c.world2);
                 let end    = center + c.normal * 0.4f32;
-                window.draw_line(&center, &end, &Pnt3::new(0.0, 1.0, 1.0))
+                lines.push(&center, &end, &Pnt3::new(0.0, 1.0, 1.0))
             },
             Constraint::BallInSocket(ref bis) => {
                 let bbis = bis.borrow();
-                window.draw_line(&bbis.anchor1_pos(), &bbis.anchor2_pos(), &Pnt3::new(0.0, 1.0, 0.0));
+                lines.push(&bbis.anchor1_pos(), &bbis.anchor2_pos(), &Pnt3::new(0.0, 1.0, 0.0));
             },
             Constraint::Fixed(ref f) => {
                 // FIXME: draw the rotation too
-                window.draw_line(&f.borrow().anchor1_pos().translate(&na::orig()), &f.borrow().anchor2_pos().translate(&na::orig()), &Pnt3::new(0.0, 1.0, 0.0));
+                lines.push(&f.borrow().anchor1_pos().translate(&na::orig()), &f.borrow().anchor2_pos().translate(&na::orig()), &Pnt3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::Distance(ref d) => {
+                let bd = d.borrow();
+                lines.push(&bd.anchor1_pos(), &bd.anchor2_pos(), &Pnt3::new(0.0, 1.0, 0.0));
+            },
+            Constraint::Grab(ref g) => {
+                let bg = g.borrow();
+                lines.push(&bg.anchor1_pos(), &bg.anchor2_pos(), &Pnt3::new(0.0, 1.0, 0.0));
+            }
+        }
+    }
+
+    collisions.clear();
+}
+
+fn draw_aabbs(lines: &mut DebugLines, physics: &World) {
+    let color = Pnt3::new(1.0, 1.0, 1.0);
+
+    for body in physics.bodies() {
+        let aabb = body.bounding_volume();
+        let mins = aabb.mins();
+        let maxs = aabb.maxs();
+
+        // Each edge links a corner to the one obtained by moving one of its minimal coordinates to
+        // the maximum.
+        for i in range(0u, 8) {
+            let corner = Pnt3::new(if i & 1 == 0 { mins.x } else { maxs.x },
+                                   if i & 2 == 0 { mins.y } else { maxs.y },
+                                   if i & 4 == 0 { mins.z } else { maxs.z });
+
+            for j in range(0u, 3) {
+                if i & (1 << j) == 0 {
+                    let mut other = corner;
+                    other[j] = maxs[j];
+
+                    lines.push(&corner, &other, &color);
+                }
             }
         }
     }
 }
+
+// Distance between the two anchors of a joint above which it is displayed in red.
+static JOINT_ERROR_THRESHOLD: f32 = 0.1;
+static ANCHOR_SIZE:           f32 = 0.1;
+
+fn draw_joints(lines: &mut DebugLines, physics: &World) {
+    for joint in physics.joints() {
+        match *joint {
+            Constraint::BallInSocket(ref bis) => {
+                let bbis = bis.borrow();
+                let p1   = bbis.anchor1_pos();
+                let p2   = bbis.anchor2_pos();
+
+                draw_anchor(lines, &p1);
+                draw_anchor(lines, &p2);
+                lines.push(&p1, &p2, &joint_color(&p1, &p2));
+            },
+            Constraint::Fixed(ref f) => {
+                let bf = f.borrow();
+                let m1 = bf.anchor1_pos();
+                let m2 = bf.anchor2_pos();
+                let p1 = m1.translate(&na::orig());
+                let p2 = m2.translate(&na::orig());
+
+                draw_anchor(lines, &p1);
+                draw_anchor(lines, &p2);
+                draw_frame(lines, &m1);
+                draw_frame(lines, &m2);
+                lines.push(&p1, &p2, &joint_color(&p1, &p2));
+            },
+            Constraint::Distance(ref d) => {
+                let bd = d.borrow();
+                let p1 = bd.anchor1_pos();
+                let p2 = bd.anchor2_pos();
+
+                // The anchors of a distance joint are expected to be apart: only the deviation
+                // from its length is an error.
+                let error = match bd.mode() {
+                    DistanceMode::Rigid       => (bd.distance() - bd.length()).abs(),
+                    DistanceMode::MaxDistance => (bd.distance() - bd.length()).max(0.0)
+                };
+                let color = if error > JOINT_ERROR_THRESHOLD { Pnt3::new(1.0, 0.0, 0.0) } else { Pnt3::new(0.0, 1.0, 0.0) };
+
+                draw_anchor(lines, &p1);
+                draw_anchor(lines, &p2);
+                lines.push(&p1, &p2, &color);
+            },
+            Constraint::Grab(ref g) => {
+                let bg = g.borrow();
+                let p1 = bg.anchor1_pos();
+                let p2 = bg.anchor2_pos();
+
+                // The grabbed point lags behind the target while the force is saturated.
+                draw_anchor(lines, &p1);
+                draw_anchor(lines, &p2);
+                lines.push(&p1, &p2, &Pnt3::new(1.0, 1.0, 0.0));
+            },
+            Constraint::RBRB(_, _, _) => { }
+        }
+    }
+}
+
+fn joint_color(p1: &Pnt3<f32>, p2: &Pnt3<f32>) -> Pnt3<f32> {
+    if na::dist(p1, p2) > JOINT_ERROR_THRESHOLD {
+        Pnt3::new(1.0, 0.0, 0.0)
+    }
+    else {
+        Pnt3::new(0.0, 1.0, 0.0)
+    }
+}
+
+fn draw_anchor(lines: &mut DebugLines, p: &Pnt3<f32>) {
+    let color = Pnt3::new(1.0, 1.0, 0.0);
+
+    for i in range(0u, 3) {
+        let mut delta: Vec3<f32> = na::zero();
+        delta[i] = ANCHOR_SIZE;
+
+        lines.push(&(*p - delta), &(*p + delta), &color);
+    }
+}
+
+fn draw_frame(lines: &mut DebugLines, m: &Iso3<f32>) {
+    let orig = m.translate(&na::orig());
+
+    lines.push(&orig, &(orig + na::rotate(m, &Vec3::x()) * 0.5f32), &Pnt3::new(1.0, 0.0, 0.0));
+    lines.push(&orig, &(orig + na::rotate(m, &Vec3::y()) * 0.5f32), &Pnt3::new(0.0, 1.0, 0.0));
+    lines.push(&orig, &(orig + na::rotate(m, &Vec3::z()) * 0.5f32), &Pnt3::new(0.0, 0.0, 1.0));
+}
@@ -0,0 +1,74 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::os;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::Plane;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::io;
+use nphysics_testbed3d::Testbed;
+
+fn main() {
+    let args = os::args();
+
+    // Usage: obj [--static] [--show-shape] path_to_the_obj_file
+    let is_static  = args.iter().any(|a| a.as_slice() == "--static");
+    let show_shape = args.iter().any(|a| a.as_slice() == "--show-shape");
+    let path_str   = match args.iter().skip(1).find(|a| !a.as_slice().starts_with("-")) {
+        Some(p) => p.clone(),
+        None    => "media/models/chair.obj".to_string()
+    };
+    let path = Path::new(path_str.as_slice());
+
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    /*
+     * Plane
+     */
+    let geom = Plane::new(Vec3::new(0.0, 1.0, 0.0));
+
+    world.add_body(RigidBody::new_static(geom, 0.3, 0.6));
+
+    /*
+     * The body loaded from the obj file.
+     */
+    let body =
+        if is_static {
+            io::static_mesh_from_obj(&path, 0.3, 0.6)
+        }
+        else {
+            io::convex_hull_from_obj(&path, 1.0, 0.3, 0.6)
+        };
+
+    let body = match body {
+        Ok(mut rb) => {
+            if rb.can_move() {
+                rb.append_translation(&Vec3::new(0.0, 10.0, 0.0));
+            }
+
+            world.add_body(rb)
+        },
+        Err(e) => panic!("Failed to load the obj file: {}", e)
+    };
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    // The dynamic body collides with the convex hull of the model, but is rendered with the model
+    // itself unless the collision shape is requested.
+    if !show_shape {
+        testbed.set_body_mesh(&body, path_str.as_slice(), 1.0);
+    }
+
+    testbed.look_at(Pnt3::new(-30.0, 30.0, -30.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
@@ -0,0 +1,110 @@
+/*!
+ * Checks the partial steps of the world, which simulate only some of its islands.
+ *
+ * Of two falling balls, only the one of the selected island must fall, while the other one keeps
+ * its position and velocity bit for bit. A ball falling on a frozen box must stop on it without
+ * moving it. The box, asleep, must be woken up by the ball only when asked to. The program exits
+ * with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::cell::RefCell;
+use na::{Vec3, Translation, Rotation};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::{check, steps};
+
+mod harness;
+
+fn add_ball(world: &mut World, pos: Vec3<f32>) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+    rb.append_translation(&pos);
+
+    world.add_body(rb)
+}
+
+// The position and velocities of `b`, compared bit for bit.
+fn state(b: &RigidBodyHandle) -> (Vec3<f32>, Vec3<f32>, Vec3<f32>, Vec3<f32>) {
+    let rb = b.borrow();
+
+    (rb.position().translation(), rb.position().rotation(), rb.lin_vel(), rb.ang_vel())
+}
+
+fn contains(island: &[RigidBodyHandle], b: &RigidBodyHandle) -> bool {
+    island.iter().any(|i| i.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>)
+}
+
+// A box put to sleep on the ground, and a ball falling on it.
+fn box_and_ball() -> (World, RigidBodyHandle, RigidBodyHandle) {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.0, 0.5));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(1.0f32, 0.5, 1.0)), 1.0, 0.0, 0.5);
+    rb.append_translation(&Vec3::new(0.0, 0.5, 0.0));
+    let cube = world.add_body(rb);
+
+    steps(&mut world, 300);
+
+    let ball = add_ball(&mut world, Vec3::new(0.0, 3.0, 0.0));
+
+    (world, cube, ball)
+}
+
+fn main() {
+    /*
+     * Only the islands left of the origin are simulated.
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    let left  = add_ball(&mut world, Vec3::new(-10.0, 5.0, 0.0));
+    let right = add_ball(&mut world, Vec3::new(10.0, 5.0, 0.0));
+    right.borrow_mut().set_lin_vel(Vec3::new(1.0, 2.0, 0.0));
+    right.borrow_mut().set_ang_vel(Vec3::new(0.0, 0.0, 3.0));
+
+    let before = state(&right);
+
+    for _ in range(0u, 50) {
+        world.step_islands(0.016, false, |island| island[0].borrow().position().translation().x < 0.0);
+    }
+
+    check("frozen island", state(&right) == before);
+    check("simulated island", left.borrow().position().translation().y < 4.0);
+    check("time", world.step_count() == 50);
+
+    /*
+     * A ball falling on a frozen box, which stays asleep.
+     */
+    let (mut world, cube, ball) = box_and_ball();
+
+    check("box asleep", !cube.borrow().is_active());
+
+    let before = state(&cube);
+
+    for _ in range(0u, 200) {
+        world.step_islands(0.016, false, |island| !contains(island, &cube));
+
+        check("ball stopped by the frozen box", ball.borrow().position().translation().y > 1.4);
+    }
+
+    check("box hit by the ball", state(&cube) == before);
+    check("box not woken up", !cube.borrow().is_active());
+
+    /*
+     * The same, but the box is woken up by the ball.
+     */
+    let (mut world, cube, _) = box_and_ball();
+    let before = state(&cube);
+
+    for _ in range(0u, 200) {
+        world.step_islands(0.016, true, |island| !contains(island, &cube));
+    }
+
+    check("box woken up", cube.borrow().is_active());
+    check("woken up box left frozen", state(&cube) == before);
+}
@@ -0,0 +1,105 @@
+/*!
+ * Checks the pause of the world.
+ *
+ * While paused, the steps must not move a falling ball, apply the force generators, nor advance
+ * the simulated time, and the events emitted before the pause must be kept until they are
+ * drained. A ball moved by hand during the pause must be found by the point queries when the
+ * query structures are refreshed. Once resumed, the ball must fall again. The program exits with
+ * an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::Ball;
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::integration::ForceGenerator;
+use harness::{check, steps};
+
+mod harness;
+
+// Counts the bodies it is applied to.
+struct Counter {
+    count: uint
+}
+
+impl ForceGenerator for Counter {
+    fn apply(&mut self, _: f32, _: &RigidBodyHandle, _: &mut RigidBody) {
+        self.count = self.count + 1
+    }
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.set_refresh_when_paused(true);
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.5);
+    rb.append_translation(&Vec3::new(0.0, 10.0, 0.0));
+    rb.set_deactivation_threshold(None);
+    let ball = world.add_body(rb);
+
+    let other   = world.add_body(RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.5));
+    let counter = world.add_force_generator(Counter { count: 0 });
+
+    steps(&mut world, 10);
+
+    world.drain_events(&mut Vec::new());
+
+    /*
+     * Paused.
+     */
+    world.remove_body(&other);
+    world.set_paused(true);
+
+    let pos     = ball.borrow().position().translation();
+    let vel     = ball.borrow().lin_vel();
+    let time    = world.time();
+    let count   = world.step_count();
+    let applied = counter.borrow().count;
+
+    for _ in range(0u, 50) {
+        world.step(0.016);
+        world.step_fixed();
+    }
+
+    check("is paused", world.is_paused());
+    check("ball moved", ball.borrow().position().translation() == pos);
+    check("ball accelerated", ball.borrow().lin_vel() == vel);
+    check("time", world.time() == time && world.step_count() == count);
+    check("force generator applied", counter.borrow().count == applied);
+
+    let mut events = Vec::new();
+    world.drain_events(&mut events);
+
+    check("event dropped", events.iter().any(|e| {
+        match *e {
+            WorldEvent::BodyRemoved(_) => true,
+            _                          => false
+        }
+    }));
+
+    // A ball moved during the pause is found at its new position.
+    ball.borrow_mut().set_translation(Vec3::new(20.0, 0.0, 0.0));
+    world.step(0.016);
+
+    let mut found = false;
+    world.interferences_with_point(&Pnt3::new(20.0, 0.0, 0.0), |_| found = true);
+
+    check("moved ball not refreshed", found);
+    check("moved ball time", world.time() == time);
+
+    /*
+     * Resumed.
+     */
+    world.set_paused(false);
+
+    steps(&mut world, 10);
+
+    check("resumed ball", ball.borrow().position().translation().y < 0.0);
+    check("resumed time", world.step_count() == count + 10);
+    check("resumed force generator", counter.borrow().count > applied);
+}
@@ -0,0 +1,52 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use na::{Pnt3, Vec3, Iso3, Translation, Rotation};
+use ncollide::shape::Cuboid;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::integration::{PdController, BodyPdController};
+use nphysics_testbed3d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+
+    /*
+     * Create the boxes and their controllers.
+     */
+    let num = 4u;
+    let rad = 0.5;
+
+    for i in range(0u, num) {
+        let fi = i as f32;
+
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(rad, rad, rad)), 1.0, 0.3, 0.5);
+
+        rb.append_translation(&Vec3::new(fi * 3.0, 5.0 + fi, -5.0));
+        rb.append_rotation(&Vec3::new(fi, 0.5, 0.0));
+        rb.set_deactivation_threshold(None);
+
+        let body = world.add_body(rb);
+
+        // Each box converges to a pose aligned with the axes, with increasingly stiff controllers.
+        let mut target: Iso3<f32> = na::one();
+        target.append_translation(&Vec3::new(fi * 3.0, 0.0, 0.0));
+
+        let controller = PdController::new(5.0 * (fi + 1.0), 2.0 * (fi + 1.0), 100.0, 100.0);
+
+        world.add_force_generator(BodyPdController::new(controller, &body, target));
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.look_at(Pnt3::new(-5.0, 5.0, 15.0), Pnt3::new(4.0, 2.0, 0.0));
+    testbed.run();
+}
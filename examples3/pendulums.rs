@@ -0,0 +1,136 @@
+/*!
+ * Checks the behaviour of pendulums made of ball-in-socket joints against analytic values.
+ *
+ * A small ball hangs at a distance `L` from a ball-in-socket joint attached to the ground. Released
+ * from a small angle, it must oscillate with the period `2π·sqrt(L/g)` within 2% over 20
+ * periods, at 120 steps per second.
+ *
+ * A double pendulum made of two balls must then conserve its total energy within 5% over 10
+ * seconds, with the default solver settings.
+ *
+ * The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::f32;
+use std::num::{Float, FloatMath};
+use na::{Pnt3, Vec3, Iso3, Translation};
+use ncollide::shape::Ball;
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::detection::joint::{Anchor, BallInSocket};
+use harness::check;
+
+mod harness;
+
+static GRAVITY: f32 = 9.81;
+static LENGTH:  f32 = 1.0;
+static DT:      f32 = 1.0 / 120.0;
+
+// Adds a small ball hanging from `pivot` with the angle `angle` around `z`. The local origin of
+// the ball is its center.
+fn add_bob(world: &mut World, pivot: &Pnt3<f32>, angle: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.05f32), 1.0, 0.0, 0.0);
+    let center = *pivot.as_vec() + Vec3::new(LENGTH * angle.sin(), -LENGTH * angle.cos(), 0.0);
+
+    rb.set_transformation(Iso3::new(center, Vec3::new(0.0, 0.0, angle)));
+    rb.set_deactivation_threshold(None);
+
+    world.add_body(rb)
+}
+
+fn energy(bodies: &[RigidBodyHandle]) -> f32 {
+    let mut energy = 0.0;
+
+    for body in bodies.iter() {
+        let rb      = body.borrow();
+        let mass    = 1.0 / rb.inv_mass();
+        let inertia = na::inv(rb.inv_inertia()).unwrap();
+        let av      = rb.ang_vel();
+
+        energy = energy + 0.5 * mass * na::sqnorm(&rb.lin_vel()) + 0.5 * na::dot(&av, &(inertia * av)) +
+                 mass * GRAVITY * rb.position().translation().y;
+    }
+
+    energy
+}
+
+fn check_period() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -GRAVITY, 0.0));
+
+    let bob   = add_bob(&mut world, &na::orig(), 0.1);
+    let joint = world.add_ball_in_socket(BallInSocket::new(
+        Anchor::new(None, na::orig()),
+        Anchor::new(Some(bob.clone()), Pnt3::new(0.0, LENGTH, 0.0))));
+
+    let expected      = 2.0 * f32::consts::PI * (LENGTH / GRAVITY).sqrt();
+    let mut crossings = Vec::new();
+    let mut prev      = joint.borrow().relative_rotation().z;
+    let mut t         = 0.0f32;
+
+    // Times at which the pendulum passes through its rest position, moving in the same direction.
+    while crossings.len() < 21 && t < expected * 30.0 {
+        world.step(DT);
+        t = t + DT;
+
+        let curr = joint.borrow().relative_rotation().z;
+
+        if prev > 0.0 && curr <= 0.0 {
+            crossings.push(t - DT + DT * prev / (prev - curr));
+        }
+
+        prev = curr;
+    }
+
+    if !check(format!("pendulum stopped after {} periods", crossings.len()).as_slice(), crossings.len() >= 21) {
+        return;
+    }
+
+    let period = (crossings[20] - crossings[0]) / 20.0;
+
+    println!("Pendulum period: {}, expected: {}.", period, expected);
+
+    check("pendulum period", (period - expected).abs() <= expected * 0.02);
+}
+
+fn check_double_pendulum_energy() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -GRAVITY, 0.0));
+
+    let angle = f32::consts::PI / 6.0;
+    let bob1  = add_bob(&mut world, &na::orig(), angle);
+    let bob2  = add_bob(&mut world, &(na::orig::<Pnt3<f32>>() + bob1.borrow().position().translation()), -angle);
+
+    world.add_ball_in_socket(BallInSocket::new(
+        Anchor::new(None, na::orig()),
+        Anchor::new(Some(bob1.clone()), Pnt3::new(0.0, LENGTH, 0.0))));
+    world.add_ball_in_socket(BallInSocket::new(
+        Anchor::new(Some(bob1.clone()), na::orig()),
+        Anchor::new(Some(bob2.clone()), Pnt3::new(0.0, LENGTH, 0.0))));
+
+    let bodies  = [ bob1, bob2 ];
+    let initial = energy(bodies.as_slice());
+
+    for i in range(0u, (10.0 / DT) as uint) {
+        world.step(DT);
+
+        let curr = energy(bodies.as_slice());
+
+        // The potential energy is relative to the pivot, so the total is compared to its magnitude.
+        if !check(format!("double pendulum energy at step {}: {} instead of {}", i, curr, initial).as_slice(),
+                  (curr - initial).abs() <= initial.abs() * 0.05) {
+            return;
+        }
+    }
+
+    println!("The double pendulum energy was conserved.");
+}
+
+fn main() {
+    check_period();
+    check_double_pendulum_energy();
+}
@@ -0,0 +1,80 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::integration::GravityField;
+use nphysics_testbed3d::Testbed;
+
+/// A gravity field pulling every body toward the origin.
+struct PlanetGravity {
+    acceleration: f32
+}
+
+impl GravityField for PlanetGravity {
+    fn gravity_at(&self, at: &Pnt3<f32>) -> Vec3<f32> {
+        if na::is_zero(&na::norm(at.as_vec())) {
+            na::zero()
+        }
+        else {
+            -na::normalize(at.as_vec()) * self.acceleration
+        }
+    }
+}
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity_field(PlanetGravity { acceleration: 9.81 });
+
+    /*
+     * The planet.
+     */
+    world.add_body(RigidBody::new_static(Ball::new(5.0f32), 0.3, 0.6));
+
+    /*
+     * Create the balls and boxes raining from every direction.
+     */
+    let num = 6;
+    let rad = 0.5;
+
+    for i in range(0u, num) {
+        for j in range(0u, num) {
+            for k in range(0u, num) {
+                let pos = Vec3::new(i as f32 - num as f32 / 2.0,
+                                    j as f32 - num as f32 / 2.0,
+                                    k as f32 - num as f32 / 2.0) * 4.0;
+
+                // Skip the bodies that would be inside the planet.
+                if na::norm(&pos) < 7.0 {
+                    continue;
+                }
+
+                let mut rb = if (i + j + k) % 2 == 0 {
+                    RigidBody::new_dynamic(Ball::new(rad), 1.0, 0.3, 0.6)
+                }
+                else {
+                    RigidBody::new_dynamic(Cuboid::new(Vec3::new(rad, rad, rad)), 1.0, 0.3, 0.6)
+                };
+
+                rb.append_translation(&pos);
+
+                world.add_body(rb);
+            }
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.look_at(Pnt3::new(-30.0, 30.0, -30.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
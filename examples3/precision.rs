@@ -0,0 +1,107 @@
+/*!
+ * Compares the accuracy of the 32-bits and the 64-bits builds of nphysics.
+ *
+ * A ball is dropped from very high above a plane located far from the origin. During the free
+ * fall, its position is compared to the exact result of the semi-implicit Euler scheme. Then, the
+ * ball bounces on the plane and the height it reaches after the bounce is reported.
+ *
+ * The program exits with an error if the 64-bits build does not stay within a tight error bound
+ * or does not bounce back close to its initial height, or if the 32-bits build does not drift
+ * noticeably more, which would mean this scene cannot tell the builds apart.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics3df64;
+
+use harness::check;
+
+mod harness;
+
+static FREE_FALL_STEPS: uint = 600;
+static BOUNCE_STEPS:    uint = 1800;
+static DROP_HEIGHT:     f64  = 1000.0;
+static MAX_F64_ERROR:   f64  = 1.0e-6;
+// Near the coordinate 10000, consecutive 32-bits floats are about 1.0e-3 apart.
+static MIN_F32_ERROR:   f64  = 1.0e-4;
+// The relative height loss tolerated for the 64-bits bounce, with a restitution of one.
+static MAX_HEIGHT_LOSS: f64  = 0.05;
+
+macro_rules! precision_test(
+    ($name: ident, $nphysics: ident, $N: ty) => (
+        mod $name {
+            use std::num::Float;
+            use na::{Vec3, Translation};
+            use na;
+            use ncollide::shape::{Ball, Plane};
+            use $nphysics::world::World;
+            use $nphysics::object::RigidBody;
+
+            /// Returns the maximum free fall error and the maximum height reached after the bounce.
+            pub fn run() -> (f64, f64) {
+                let origin: Vec3<$N> = Vec3::new(10000.0, 10000.0, 10000.0);
+                let gravity: $N      = 9.81;
+                let dt: $N           = 1.0 / 60.0;
+                let drop: f64        = super::DROP_HEIGHT;
+
+                let mut world = World::new();
+                world.set_gravity(Vec3::new(0.0, -gravity, 0.0));
+
+                let mut rb = RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 1.0, 0.0);
+                rb.append_translation(&origin);
+                world.add_body(rb);
+
+                let mut rb = RigidBody::new_dynamic(Ball::new(1.0), 1.0, 1.0, 0.0);
+                rb.append_translation(&(origin + Vec3::new(0.0, na::cast(drop), 0.0)));
+                rb.set_deactivation_threshold(None);
+                let ball = world.add_body(rb);
+
+                let y0: f64 = na::cast(origin.y);
+                let g:  f64 = na::cast(gravity);
+                let h:  f64 = na::cast(dt);
+
+                let mut max_error = 0.0f64;
+
+                for i in range(1u, super::FREE_FALL_STEPS + 1) {
+                    world.step(dt);
+
+                    let n     = i as f64;
+                    let exact = y0 + drop - g * h * h * n * (n + 1.0) / 2.0;
+                    let y: f64 = na::cast(ball.borrow().position().translation().y);
+
+                    max_error = max_error.max((y - exact).abs());
+                }
+
+                let mut max_height = Float::neg_infinity();
+
+                for i in range(0u, super::BOUNCE_STEPS) {
+                    world.step(dt);
+
+                    // Skip the steps where the ball is still falling toward the plane.
+                    if i > super::BOUNCE_STEPS / 2 {
+                        let y: f64 = na::cast(ball.borrow().position().translation().y);
+                        max_height = max_height.max(y - y0);
+                    }
+                }
+
+                (max_error, max_height)
+            }
+        }
+    )
+)
+
+precision_test!(single_precision, nphysics, f32)
+precision_test!(double_precision, nphysics3df64, f64)
+
+fn main() {
+    let (error32, height32) = single_precision::run();
+    let (error64, height64) = double_precision::run();
+
+    println!("32 bits: free fall error: {}, height after the bounce: {}", error32, height32);
+    println!("64 bits: free fall error: {}, height after the bounce: {}", error64, height64);
+
+    check("64 bits free fall error", error64 <= MAX_F64_ERROR);
+    check("64 bits height after the bounce", (height64 - DROP_HEIGHT).abs() <= DROP_HEIGHT * MAX_HEIGHT_LOSS);
+    check("32 bits drift", error32 > MIN_F32_ERROR);
+}
@@ -0,0 +1,77 @@
+/*!
+ * Checks the pressure balls built by `build_pressure_ball`.
+ *
+ * A ball subdivided once must have 42 vertices and 120 joints, and its measured volume must be
+ * close to the one of the sphere it is inscribed in. Resting on the ground, it must keep most of its
+ * volume, while a ball without pressure collapses. Raising the pressure must make it rise. The
+ * program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::f32;
+use na::{Pnt3, Vec3};
+use ncollide::shape::Plane;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::utils::{build_pressure_ball, PressureBall};
+use harness::{check, steps};
+
+mod harness;
+
+fn volume(ball: &PressureBall) -> f32 {
+    ball.generator().borrow().volume()
+}
+
+fn rest_volume(ball: &PressureBall) -> f32 {
+    ball.generator().borrow().rest_volume()
+}
+
+fn dropped_ball(pressure: f32) -> (World, PressureBall) {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    let ball = build_pressure_ball(&mut world, Pnt3::new(0.0, 1.5, 0.0), 1.0, 1, 500.0, pressure);
+
+    (world, ball)
+}
+
+fn main() {
+    /*
+     * The construction.
+     */
+    let (mut world, ball) = dropped_ball(200.0);
+
+    check("vertices", ball.vertices().len() == 42);
+    check("joints", ball.joints().len() == 120);
+
+    let sphere = 4.0 / 3.0 * f32::consts::PI;
+    check("rest volume", rest_volume(&ball) < sphere && rest_volume(&ball) > 0.8 * sphere);
+
+    /*
+     * Resting on the ground.
+     */
+    steps(&mut world, 500);
+
+    check("inflated", volume(&ball) > 0.8 * rest_volume(&ball));
+    check("on the ground", ball.center().y > 0.6 && ball.center().y < 1.2);
+
+    let (mut flat_world, flat) = dropped_ball(0.0);
+
+    steps(&mut flat_world, 500);
+
+    check("deflated", volume(&flat) < volume(&ball));
+
+    /*
+     * More pressure.
+     */
+    let height = ball.center().y;
+
+    ball.generator().borrow_mut().set_pressure(2000.0);
+    steps(&mut world, 300);
+
+    check("pumped", ball.center().y > height);
+}
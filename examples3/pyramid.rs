@@ -16,6 +16,9 @@ fn main() {
     let mut world = World::new();
     world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
 
+    // Keeps the pyramid from sagging under its own weight.
+    world.set_shock_propagation(true);
+
     /*
      * Planes
      */
@@ -26,7 +29,7 @@ fn main() {
     /*
      * Create the boxes
      */
-    let num     = 30;
+    let num     = 20;
     let rad     = 0.5;
     let shift   = rad * 2.0;
     let centerx = shift * (num as f32) / 2.0;
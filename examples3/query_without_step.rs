@@ -0,0 +1,86 @@
+/*!
+ * Checks that the geometric queries follow bodies moved without stepping the world.
+ *
+ * A static box and a dynamic box are moved with `set_transformation`. After
+ * `update_query_structures`, rays must hit them at their new positions only. A box resting on the
+ * ground is then simulated while its world is updated for queries between every step: it must stay
+ * at rest. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Pnt3, Vec3, Iso3, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use ncollide::ray::Ray;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::{check, steps};
+
+mod harness;
+
+fn hits(world: &mut World, x: f32) -> uint {
+    let ray = Ray::new(Pnt3::new(x, 10.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+
+    world.ray_hits(&ray, None, None, true).len()
+}
+
+fn check_moved_bodies() {
+    let mut world = World::new();
+
+    let fixed   = world.add_body(RigidBody::new_static(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 0.0, 0.5));
+    let dynamic = world.add_body(RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5));
+
+    dynamic.borrow_mut().append_translation(&Vec3::new(20.0, 0.0, 0.0));
+    world.update_query_structures();
+
+    check("initial static body", hits(&mut world, 0.0) == 1);
+    check("initial dynamic body", hits(&mut world, 20.0) == 1);
+
+    fixed.borrow_mut().set_transformation(Iso3::new(Vec3::new(10.0, 0.0, 0.0), na::zero()));
+    dynamic.borrow_mut().set_transformation(Iso3::new(Vec3::new(30.0, 0.0, 0.0), na::zero()));
+
+    world.update_query_structures();
+
+    check("static body old position", hits(&mut world, 0.0) == 0);
+    check("static body new position", hits(&mut world, 10.0) == 1);
+    check("dynamic body old position", hits(&mut world, 20.0) == 0);
+    check("dynamic body new position", hits(&mut world, 30.0) == 1);
+
+    // Nothing was integrated.
+    check("integration", na::approx_eq(&dynamic.borrow().position().translation().x, &30.0));
+}
+
+fn check_interleaved_steps() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0f32, 1.0, 0.0)), 0.0, 0.5));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+    rb.append_translation(&Vec3::new(0.0, 0.5, 0.0));
+    rb.set_deactivation_threshold(None);
+
+    let body = world.add_body(rb);
+
+    steps(&mut world, 100);
+
+    let rest = body.borrow().position().translation().y;
+
+    for _ in range(0u, 100) {
+        world.update_query_structures();
+        world.step(0.016);
+        world.update_query_structures();
+    }
+
+    let curr = body.borrow().position().translation().y;
+
+    check("resting box", (curr - rest).abs() < 1.0e-2 && na::norm(&body.borrow().lin_vel()) < 1.0e-1);
+}
+
+fn main() {
+    check_moved_bodies();
+    check_interleaved_steps();
+}
@@ -0,0 +1,97 @@
+/*!
+ * Checks the self-collision of the ragdolls.
+ *
+ * An arm made of capsules is posed so that its lower arm overlaps its upper arm, and its hand
+ * overlaps the torso. By default, only the hand and the torso must have contacts since the other
+ * overlapping bones are linked by a joint. Enabling and disabling the collisions of those pairs at
+ * runtime must swap their contacts. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::cell::RefCell;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Ball, Capsule};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::utils::RagdollBuilder;
+use harness::check;
+
+mod harness;
+
+fn placed(mut rb: RigidBody, pos: Vec3<f32>) -> RigidBody {
+    rb.append_translation(&pos);
+
+    rb
+}
+
+// An arm posed so that the lower arm overlaps the upper arm, and the hand overlaps the torso.
+fn arm() -> (RagdollBuilder, uint, uint, uint, uint) {
+    let mut builder = RagdollBuilder::new();
+
+    let torso = builder.push_bone(
+        placed(RigidBody::new_dynamic(Capsule::new(1.0, 0.5f32), 1.0, 0.3, 0.5), na::zero()));
+    let upper = builder.push_child_bone(
+        placed(RigidBody::new_dynamic(Capsule::new(0.5, 0.2f32), 1.0, 0.3, 0.5), Vec3::new(1.0, 0.5, 0.0)),
+        torso, Pnt3::new(0.5, 1.0, 0.0), Pnt3::new(0.0, 0.5, 0.0));
+    let lower = builder.push_child_bone(
+        placed(RigidBody::new_dynamic(Capsule::new(0.5, 0.2f32), 1.0, 0.3, 0.5), Vec3::new(1.0, -0.3, 0.0)),
+        upper, Pnt3::new(0.0, -0.5, 0.0), Pnt3::new(0.0, 0.5, 0.0));
+    let hand  = builder.push_child_bone(
+        placed(RigidBody::new_dynamic(Ball::new(0.2f32), 1.0, 0.3, 0.5), Vec3::new(0.4, -0.5, 0.0)),
+        lower, Pnt3::new(0.0, -0.5, 0.0), Pnt3::new(0.0, 0.2, 0.0));
+
+    (builder, torso, upper, lower, hand)
+}
+
+// Whether the last step solved a contact between `b1` and `b2`.
+fn touching(world: &World, b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> bool {
+    let k1 = b1.deref() as *const RefCell<RigidBody>;
+    let k2 = b2.deref() as *const RefCell<RigidBody>;
+
+    world.contacts().iter().any(|c| {
+        let c1 = c.body1.deref() as *const RefCell<RigidBody>;
+        let c2 = c.body2.deref() as *const RefCell<RigidBody>;
+
+        (c1 == k1 && c2 == k2) || (c1 == k2 && c2 == k1)
+    })
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+
+    let (builder, torso, upper, lower, hand) = arm();
+
+    check("default disabled pairs", builder.disabled_pairs() == vec!((torso, upper), (upper, lower), (lower, hand)));
+
+    let ragdoll = builder.build(&mut world);
+    let bones   = ragdoll.bones().to_vec();
+
+    world.step(0.016);
+
+    check("no contact between the upper and lower arms", !touching(&world, &bones[upper], &bones[lower]));
+    check("contact between the hand and the torso", touching(&world, &bones[hand], &bones[torso]));
+    check("disabled pair", !ragdoll.self_collision(&world, upper, lower));
+    check("enabled pair", ragdoll.self_collision(&world, hand, torso));
+
+    /*
+     * Toggle the pairs at runtime, in the same pose.
+     */
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+
+    let (builder, torso, upper, lower, hand) = arm();
+    let ragdoll = builder.build(&mut world);
+    let bones   = ragdoll.bones().to_vec();
+
+    ragdoll.set_self_collision(&mut world, upper, lower, true);
+    ragdoll.set_self_collision(&mut world, hand, torso, false);
+
+    world.step(0.016);
+
+    check("contact between the enabled arms", touching(&world, &bones[upper], &bones[lower]));
+    check("no contact between the disabled hand and torso", !touching(&world, &bones[hand], &bones[torso]));
+}
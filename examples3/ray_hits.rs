@@ -0,0 +1,72 @@
+/*!
+ * Checks that a ray reports every body it crosses, in order.
+ *
+ * A ray is cast through a row of five boxes. With one hit per body, the five boxes must be
+ * reported in the order they are crossed. Otherwise, both the entry and the exit of each box must
+ * be reported. The maximum number of hits and the maximum time of impact must be honored. The
+ * program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::cell::RefCell;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::Cuboid;
+use ncollide::ray::Ray;
+use nphysics::world::{World, RayHit};
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::check;
+
+mod harness;
+
+fn is(hit: &RayHit, body: &RigidBodyHandle) -> bool {
+    hit.body.deref() as *const RefCell<RigidBody> == body.deref() as *const RefCell<RigidBody>
+}
+
+fn main() {
+    let mut world = World::new();
+    let mut boxes = Vec::new();
+
+    // Added in a shuffled order so that the body order does not match the hit order.
+    for i in [ 3u, 0, 4, 1, 2 ].iter() {
+        let mut rb = RigidBody::new_static(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 0.3, 0.6);
+
+        rb.append_translation(&Vec3::new(*i as f32 * 2.0, 0.0, 0.0));
+
+        boxes.push((*i, world.add_body(rb)));
+    }
+
+    boxes.sort_by(|a, b| a.ref0().cmp(b.ref0()));
+
+    let ray  = Ray::new(Pnt3::new(-10.0f32, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+    let hits = world.ray_hits(&ray, None, None, true);
+
+    check("one per body count", hits.len() == 5);
+
+    for (hit, &(_, ref b)) in hits.iter().zip(boxes.iter()) {
+        check("one per body order", is(hit, b));
+    }
+
+    let hits = world.ray_hits(&ray, None, None, false);
+
+    check("all surfaces count", hits.len() == 10);
+
+    for (i, hit) in hits.iter().enumerate() {
+        let (k, ref b) = boxes[i / 2];
+        // Boxes of half-width 0.5 centered at `2 * k`.
+        let expected   = 10.0 + k as f32 * 2.0 + if i % 2 == 0 { -0.5 } else { 0.5 };
+
+        check("all surfaces order", is(hit, b) && na::approx_eq_eps(&hit.toi, &expected, &1.0e-3));
+    }
+
+    let hits = world.ray_hits(&ray, None, Some(3), true);
+
+    check("max hits", hits.len() == 3 && is(&hits[2], boxes[2].ref1()));
+
+    // The third box is entered at 13.5.
+    let hits = world.ray_hits(&ray, Some(13.0), None, true);
+
+    check("max toi", hits.len() == 2 && is(&hits[1], boxes[1].ref1()));
+}
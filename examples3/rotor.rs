@@ -0,0 +1,100 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::detection::joint::{Anchor, BallInSocket};
+use nphysics::integration::Motor;
+use nphysics_testbed3d::{Testbed, StepHandler, EventHandler};
+
+// The number of steps between two boxes thrown at the rotor.
+static THROW_PERIOD: uint = 120;
+
+// Throws a box at the rotor periodically, from alternating sides.
+struct Thrower {
+    num_steps: uint
+}
+
+impl StepHandler for Thrower {
+    fn handle_step(&mut self, world: &mut World) {
+        self.num_steps = self.num_steps + 1;
+
+        if self.num_steps % THROW_PERIOD != 0 {
+            return;
+        }
+
+        let side = if (self.num_steps / THROW_PERIOD) % 2 == 0 { 1.0f32 } else { -1.0 };
+
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.4f32, 0.4, 0.4)), 1.0, 0.1, 0.8);
+        rb.append_translation(&Vec3::new(side * 1.5, 3.0, side * 10.0));
+        rb.set_lin_vel(Vec3::new(0.0, 2.0, -side * 8.0));
+
+        world.add_body(rb);
+    }
+}
+
+// Displays the rotor in red while it is stalled.
+struct StallHighlighter;
+
+impl EventHandler for StallHighlighter {
+    fn handle_event(&mut self, event: &WorldEvent, highlights: &mut Vec<(Rc<RefCell<RigidBody>>, bool)>) {
+        match *event {
+            WorldEvent::BodyStalled(ref b)   => highlights.push((b.clone(), true)),
+            WorldEvent::BodyUnstalled(ref b) => highlights.push((b.clone(), false)),
+            _ => { }
+        }
+    }
+}
+
+fn add_rotor(world: &mut World) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(3.0f32, 0.25, 0.25)), 1.0, 0.1, 0.8);
+    rb.append_translation(&Vec3::new(0.0, 0.6, 0.0));
+    // The rotor must keep spinning without anything hitting it.
+    rb.set_deactivation_threshold(None);
+    rb.set_max_angular_velocity(Some(2.0));
+    rb.set_stall_speed(Some(0.5));
+
+    let rotor = world.add_body(rb);
+
+    world.add_ball_in_socket(BallInSocket::new(Anchor::new(None, Pnt3::new(0.0, 0.6, 0.0)),
+                                               Anchor::new(Some(rotor.clone()), na::orig())));
+
+    rotor
+}
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    /*
+     * Floor
+     */
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    /*
+     * The rotor, driven by a motor around the vertical axis. Its spin drops when the boxes thrown
+     * at it wedge it.
+     */
+    let rotor = add_rotor(&mut world);
+
+    let _ = world.add_force_generator(Motor::new(&rotor, Vec3::new(0.0, 40.0, 0.0)));
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.set_step_handler(Thrower { num_steps: 0 });
+    testbed.set_event_handler(StallHighlighter);
+    testbed.look_at(Pnt3::new(-15.0, 12.0, -15.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
@@ -0,0 +1,72 @@
+/*!
+ * Checks that scaling a body scales its shape and its mass properties consistently.
+ *
+ * A ball, and a compound made of two cuboids, are scaled by 2. Their mass properties must match
+ * those of bodies created directly with the scaled shapes, with the same density. Scaling by zero
+ * must fail without modifying the body. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::sync::Arc;
+use std::any::AnyRefExt;
+use na::{Vec3, Iso3, Translation};
+use ncollide::shape::{Ball, Cuboid, Shape3};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::RigidBody;
+use nphysics::utils::CompoundBuilder;
+use harness::check;
+
+mod harness;
+
+fn same_mass_properties(a: &RigidBody, b: &RigidBody) -> bool {
+    na::approx_eq_eps(&(1.0 / a.inv_mass()), &(1.0 / b.inv_mass()), &1.0e-3) &&
+    na::approx_eq_eps(a.local_center_of_mass(), b.local_center_of_mass(), &1.0e-3) &&
+    na::approx_eq_eps(&na::inv(a.local_inv_inertia()).unwrap(), &na::inv(b.local_inv_inertia()).unwrap(), &1.0e-2)
+}
+
+fn compound(size: f32) -> RigidBody {
+    let mut builder = CompoundBuilder::new();
+
+    builder.push_shape(Iso3::new(Vec3::new(size, 0.0, 0.0), na::zero()), Cuboid::new(Vec3::new(size, size, size) * 0.5), 1.0);
+    builder.push_shape(Iso3::new(Vec3::new(size * 2.0, size, 0.0), na::zero()), Cuboid::new(Vec3::new(size, size, size) * 0.5), 1.0);
+
+    let (shape, props) = builder.build();
+
+    RigidBody::new(Arc::new(box shape as Box<Shape3<f32>>), Some(props), 0.3, 0.6)
+}
+
+fn main() {
+    let mut world = World::new();
+
+    let ball = world.add_body(RigidBody::new_dynamic(Ball::new(1.0f32), 1.0, 0.3, 0.6));
+    ball.borrow_mut().append_translation(&Vec3::new(5.0, 0.0, 0.0));
+
+    check("ball result", world.scale_body(&ball, 2.0).is_ok());
+    check("ball mass properties", same_mass_properties(ball.borrow().deref(),
+                                                       &RigidBody::new_dynamic(Ball::new(2.0f32), 1.0, 0.3, 0.6)));
+    check("ball position", na::approx_eq(&ball.borrow().position().translation().x, &5.0));
+    check("ball shape", match ball.borrow().shape_ref().downcast_ref::<Ball<f32>>() {
+        Some(b) => na::approx_eq(&b.radius(), &2.0),
+        None    => false
+    });
+
+    let body = world.add_body(compound(1.0));
+
+    check("compound result", world.scale_body(&body, 2.0).is_ok());
+    check("compound mass properties", same_mass_properties(body.borrow().deref(), &compound(2.0)));
+
+    let mass = 1.0 / body.borrow().inv_mass();
+
+    check("zero factor", world.scale_body(&body, 0.0).is_err());
+    check("unchanged body", na::approx_eq(&(1.0 / body.borrow().inv_mass()), &mass));
+
+    let mut events = Vec::new();
+    world.drain_events(&mut events);
+
+    let scaled = events.iter().filter(|e| match **e { WorldEvent::BodyScaled(_, s) => s == 2.0, _ => false }).count();
+
+    check("events", scaled == 2);
+}
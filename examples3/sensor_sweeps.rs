@@ -0,0 +1,86 @@
+/*!
+ * Checks the sensor events of a fast body.
+ *
+ * A CCD-enabled ball moves fast enough to traverse each of two thin sensors during a single step,
+ * without overlapping them at the end of any step. Each sensor must be reported entered then left
+ * exactly once, both events being emitted at the same step, and the lower sensor must be crossed
+ * at a later step than the upper one. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::cell::RefCell;
+use na::{Vec3, Translation};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics::world::{World, WorldEvent, TimedEvent};
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::{check, steps};
+
+mod harness;
+
+fn is(a: &RigidBodyHandle, b: &RigidBodyHandle) -> bool {
+    a.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>
+}
+
+fn add_sensor(world: &mut World, y: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_static(Cuboid::new(Vec3::new(2.0f32, 0.05, 2.0)), 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(0.0, y, 0.0));
+    rb.set_sensor(true);
+
+    world.add_body(rb)
+}
+
+// The steps at which `body` entered and left `sensor` in `events`, in order.
+fn crossings(events: &[TimedEvent], sensor: &RigidBodyHandle, body: &RigidBodyHandle) -> Vec<(bool, u64)> {
+    let mut res = Vec::new();
+
+    for e in events.iter() {
+        match e.event {
+            WorldEvent::SensorEntered(ref s, ref b) if is(s, sensor) && is(b, body) => res.push((true, e.step)),
+            WorldEvent::SensorLeft(ref s, ref b)    if is(s, sensor) && is(b, body) => res.push((false, e.step)),
+            _ => { }
+        }
+    }
+
+    res
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+
+    // The ball travels 4.8 per step: it is at 5.2 after the first step and at 0.4 after the
+    // second, out of both sensors.
+    let upper = add_sensor(&mut world, 5.0);
+    let lower = add_sensor(&mut world, -2.0);
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.1f32), 1.0, 0.0, 0.5);
+    rb.append_translation(&Vec3::new(0.0, 10.0, 0.0));
+    rb.set_lin_vel(Vec3::new(0.0, -300.0, 0.0));
+
+    let ball = world.add_body(rb);
+    world.add_ccd_to(&ball, 0.05);
+
+    steps(&mut world, 5);
+
+    let mut events = Vec::new();
+    world.drain_timed_events(&mut events);
+
+    check("ball not clamped", ball.borrow().position().translation().y < -10.0);
+    check("no overlap", world.sensor_overlaps().is_empty());
+
+    let upper_crossings = crossings(events.as_slice(), &upper, &ball);
+    let lower_crossings = crossings(events.as_slice(), &lower, &ball);
+
+    if !check("upper sensor crossings", upper_crossings.len() == 2 && upper_crossings[0].0 && !upper_crossings[1].0) ||
+       !check("lower sensor crossings", lower_crossings.len() == 2 && lower_crossings[0].0 && !lower_crossings[1].0) {
+        return;
+    }
+
+    check("upper sensor crossed in one step", upper_crossings[0].1 == upper_crossings[1].1);
+    check("lower sensor crossed in one step", lower_crossings[0].1 == lower_crossings[1].1);
+    check("crossing order", upper_crossings[0].1 < lower_crossings[0].1);
+}
@@ -0,0 +1,102 @@
+/*!
+ * Checks the sensor events.
+ *
+ * Boxes fall through a sensor strip and come to rest on the ground, inside a second sensor. Each
+ * box must enter and leave the strip exactly once, enter the ground sensor exactly once, and never
+ * leave it while asleep. No contact with a sensor must be solved. Removing a box must end its
+ * overlap with the ground sensor exactly once. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::cell::RefCell;
+use na::{Vec3, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::{check, steps};
+
+mod harness;
+
+fn is(a: &RigidBodyHandle, b: &RigidBodyHandle) -> bool {
+    a.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>
+}
+
+fn add_sensor(world: &mut World, y: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_static(Cuboid::new(Vec3::new(10.0f32, 0.5, 10.0)), 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(0.0, y, 0.0));
+    rb.set_sensor(true);
+
+    world.add_body(rb)
+}
+
+// The number of times `body` entered and left `sensor` in `events`.
+fn crossings(events: &[WorldEvent], sensor: &RigidBodyHandle, body: &RigidBodyHandle) -> (uint, uint) {
+    let mut entered = 0;
+    let mut left    = 0;
+
+    for e in events.iter() {
+        match *e {
+            WorldEvent::SensorEntered(ref s, ref b) if is(s, sensor) && is(b, body) => entered = entered + 1,
+            WorldEvent::SensorLeft(ref s, ref b)    if is(s, sensor) && is(b, body) => left = left + 1,
+            _ => { }
+        }
+    }
+
+    (entered, left)
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.0, 0.5));
+
+    let strip  = add_sensor(&mut world, 5.0);
+    let ground = add_sensor(&mut world, 0.5);
+    let mut boxes = Vec::new();
+
+    for i in range(0u, 3) {
+        let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.4f32, 0.4, 0.4)), 1.0, 0.0, 0.5);
+
+        rb.append_translation(&Vec3::new(i as f32 * 2.0 - 2.0, 10.0 + i as f32, 0.0));
+
+        boxes.push(world.add_body(rb));
+    }
+
+    let mut events        = Vec::new();
+    let mut sensor_solved = false;
+
+    for _ in range(0u, 600) {
+        world.step(0.016);
+        world.drain_events(&mut events);
+
+        sensor_solved = sensor_solved || world.contacts().iter().any(|c| {
+            c.body1.borrow().is_sensor() || c.body2.borrow().is_sensor()
+        });
+    }
+
+    check("no contact with a sensor", !sensor_solved);
+
+    for b in boxes.iter() {
+        check("strip crossings", crossings(events.as_slice(), &strip, b) == (1, 1));
+        check("ground sensor crossings", crossings(events.as_slice(), &ground, b) == (1, 0));
+        check("box on the ground", b.borrow().position().translation().y < 0.5);
+        check("box asleep", !b.borrow().is_active());
+    }
+
+    check("overlaps", world.sensor_overlaps().len() == boxes.len());
+
+    events.clear();
+    world.remove_body(&boxes[0]);
+
+    steps(&mut world, 10);
+
+    world.drain_events(&mut events);
+
+    check("removed box leaves", crossings(events.as_slice(), &ground, &boxes[0]) == (0, 1));
+    check("other boxes stay", crossings(events.as_slice(), &ground, &boxes[1]) == (0, 0));
+}
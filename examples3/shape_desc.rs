@@ -0,0 +1,80 @@
+/*!
+ * Checks the descriptions of the shapes of the bodies.
+ *
+ * A ball and a cuboid must be described with their dimensions. A compound shape nesting another
+ * one must describe all its parts with their local transformation. A capsule, which has no
+ * description, must be given as is. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::any::AnyRefExt;
+use na::{Vec3, Iso3, Translation};
+use ncollide::shape::{Ball, Cuboid, Capsule, Compound, CompoundData};
+use nphysics::object::{RigidBody, ShapeDesc};
+use harness::check;
+
+mod harness;
+
+fn main() {
+    let ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.5);
+
+    check("ball", match ball.shape_desc() {
+        ShapeDesc::Ball(radius) => radius == 0.5,
+        _                       => false
+    });
+
+    let cuboid = RigidBody::new_dynamic(Cuboid::new(Vec3::new(1.0f32, 2.0, 3.0)), 1.0, 0.3, 0.5);
+
+    check("cuboid", match cuboid.shape_desc() {
+        ShapeDesc::Cuboid(half_extents) => half_extents == Vec3::new(1.0, 2.0, 3.0),
+        _                               => false
+    });
+
+    /*
+     * Nested compounds.
+     */
+    let mut inner = CompoundData::new();
+    inner.push_shape(Iso3::new(Vec3::new(0.0, 1.0, 0.0), na::zero()), Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0);
+
+    let mut outer = CompoundData::new();
+    outer.push_shape(Iso3::new(Vec3::new(-2.0, 0.0, 0.0), na::zero()), Ball::new(0.5f32), 1.0);
+    outer.push_shape(Iso3::new(Vec3::new(2.0, 0.0, 0.0), na::zero()), Compound::new(inner), 1.0);
+
+    let compound = RigidBody::new_dynamic(Compound::new(outer), 1.0, 0.3, 0.5);
+
+    check("nested compound", match compound.shape_desc() {
+        ShapeDesc::Compound(ref parts) if parts.len() == 2 => {
+            let ball_ok = match parts[0] {
+                (ref delta, ShapeDesc::Ball(radius)) => delta.translation().x == -2.0 && radius == 0.5,
+                _                                    => false
+            };
+
+            let inner_ok = match parts[1] {
+                (ref delta, ShapeDesc::Compound(ref inner)) if inner.len() == 1 => {
+                    delta.translation().x == 2.0 &&
+                    match inner[0] {
+                        (ref d, ShapeDesc::Cuboid(_)) => d.translation().y == 1.0,
+                        _                             => false
+                    }
+                },
+                _ => false
+            };
+
+            ball_ok && inner_ok
+        },
+        _ => false
+    });
+
+    /*
+     * Shape without description.
+     */
+    let capsule = RigidBody::new_dynamic(Capsule::new(1.0, 0.5f32), 1.0, 0.3, 0.5);
+
+    check("other shape", match capsule.shape_desc() {
+        ShapeDesc::Other(shape) => shape.downcast_ref::<Capsule<f32>>().is_some(),
+        _                       => false
+    });
+}
@@ -0,0 +1,73 @@
+/*!
+ * Checks that the unsupported shapes are rejected when a rigid body is created.
+ *
+ * Planes inside of compounds, dynamic planes, non-positive densities, non-finite masses, empty
+ * compounds and empty meshes must be rejected with an error, or a panic for the constructors not
+ * returning a `Result`. A flat convex, which has a zero volume, must be accepted with a small
+ * positive mass instead. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::task;
+use std::sync::Arc;
+use std::num::Float;
+use na::{Pnt3, Vec3, Iso3};
+use ncollide::shape::{Plane, Ball, Cuboid, Convex, Compound, CompoundData, Mesh, Mesh3, Shape3};
+use nphysics::object::RigidBody;
+use harness::check;
+
+mod harness;
+
+fn rejected(name: &str, body: Result<RigidBody, String>) {
+    match body {
+        Ok(_)  => check(name, false),
+        Err(e) => println!("{} rejected: {}", name, e)
+    }
+}
+
+fn main() {
+    let mut data = CompoundData::new();
+    data.push_shape(na::one(), Cuboid::new(Vec3::new(1.0f32, 1.0, 1.0)), 1.0);
+    data.push_shape_with_mass_properties(Iso3::new(Vec3::new(0.0, -1.0, 0.0), na::zero()),
+                                         Plane::new(Vec3::new(0.0, 1.0, 0.0)),
+                                         (1.0, na::orig(), na::one()));
+
+    let compound = Arc::new(box Compound::new(data) as Box<Shape3<f32>>);
+
+    rejected("static compound with a plane", RigidBody::try_new(compound, None, 0.3, 0.6));
+
+    let plane = Arc::new(box Plane::new(Vec3::new(0.0f32, 1.0, 0.0)) as Box<Shape3<f32>>);
+
+    rejected("dynamic plane", RigidBody::try_new(plane, Some((1.0, na::orig(), na::one())), 0.3, 0.6));
+    rejected("zero density", RigidBody::try_new_dynamic(Ball::new(1.0f32), 0.0, 0.3, 0.6));
+    rejected("negative density", RigidBody::try_new_dynamic(Ball::new(1.0f32), -1.0, 0.3, 0.6));
+
+    let ball = Arc::new(box Ball::new(1.0f32) as Box<Shape3<f32>>);
+    let nan: f32 = Float::nan();
+
+    rejected("non-finite mass", RigidBody::try_new(ball, Some((nan, na::orig(), na::one())), 0.3, 0.6));
+
+    let empty = Arc::new(box Compound::new(CompoundData::new()) as Box<Shape3<f32>>);
+
+    rejected("empty compound", RigidBody::try_new(empty, None, 0.3, 0.6));
+
+    let mesh: Mesh3<f32> = Mesh::new(Arc::new(Vec::new()), Arc::new(Vec::new()), None, None);
+
+    rejected("empty mesh", RigidBody::try_new_static(mesh, 0.3, 0.6));
+
+    check("panic on zero density", task::try(proc() {
+        let _ = RigidBody::new_dynamic(Ball::new(1.0f32), 0.0, 0.3, 0.6);
+    }).is_err());
+
+    // A flat convex has a zero volume, hence a zero mass.
+    let flat = Convex::new(vec!(Pnt3::new(0.0f32, 0.0, 0.0), Pnt3::new(1.0, 0.0, 0.0),
+                                Pnt3::new(0.0, 0.0, 1.0), Pnt3::new(1.0, 0.0, 1.0)));
+
+    match RigidBody::try_new_dynamic(flat, 1.0, 0.3, 0.6) {
+        Ok(rb) => check("flat convex mass", rb.inv_mass().is_finite() && rb.inv_mass() > 0.0),
+        Err(e) => check(format!("flat convex accepted ({})", e).as_slice(), false)
+    }
+}
@@ -0,0 +1,102 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::sync::Arc;
+use na::{Pnt3, Vec3, Iso3, Translation};
+use ncollide::shape::{Plane, Ball, Cuboid, Shape3};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::detection::joint::{Anchor, BallInSocket};
+use nphysics::integration::{TorsionSpring, AxisSpring};
+use nphysics::utils::CompoundBuilder;
+use nphysics_testbed3d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    /*
+     * Ground
+     */
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0f32, 1.0, 0.0)), 0.3, 0.6));
+
+    /*
+     * The signpost, with its local origin at the bottom of the post.
+     */
+    let mut builder = CompoundBuilder::new();
+
+    builder.push_shape(Iso3::new(Vec3::new(0.0, 1.5, 0.0), na::zero()), Cuboid::new(Vec3::new(0.1f32, 1.5, 0.1)), 1.0);
+    builder.push_shape(Iso3::new(Vec3::new(0.0, 2.6, 0.0), na::zero()), Cuboid::new(Vec3::new(0.8f32, 0.4, 0.05)), 1.0);
+
+    let (shape, props) = builder.build();
+    let base           = Pnt3::new(0.0, 0.05, 0.0);
+    let mut rb         = RigidBody::new(Arc::new(box shape as Box<Shape3<f32>>), Some(props), 0.3, 0.6);
+
+    rb.append_translation(base.as_vec());
+
+    let post = world.add_body(rb);
+
+    // The post pivots around its base, and the torsion spring brings it back upright.
+    world.add_ball_in_socket(BallInSocket::new(Anchor::new(None, base), Anchor::new(Some(post.clone()), na::orig())));
+
+    let ground_frame = Iso3::new(*base.as_vec(), na::zero());
+
+    world.add_force_generator(TorsionSpring::new(
+        Anchor::new(None, ground_frame),
+        Anchor::new(Some(post.clone()), na::one()),
+        20.0, 0.5, 100.0));
+
+    /*
+     * A platform on a suspension strut, upon which a box is dropped.
+     */
+    let strut_base = Iso3::new(Vec3::new(4.0, 0.0, 0.0), na::zero());
+    let mut rb     = RigidBody::new_dynamic(Cuboid::new(Vec3::new(1.0f32, 0.1, 1.0)), 1.0, 0.3, 0.6);
+
+    rb.append_translation(&Vec3::new(4.0, 1.0, 0.0));
+
+    let platform = world.add_body(rb);
+
+    world.add_force_generator(AxisSpring::new(
+        Anchor::new(None, strut_base.clone()),
+        Anchor::new(Some(platform.clone()), na::one()),
+        Vec3::new(0.0, 1.0, 0.0), 1.0, 30.0, 2.0, 200.0));
+    // Keeps the platform horizontal.
+    world.add_force_generator(TorsionSpring::new(
+        Anchor::new(None, strut_base),
+        Anchor::new(Some(platform), na::one()),
+        50.0, 5.0, 100.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.3f32, 0.3, 0.3)), 1.0, 0.3, 0.6);
+    rb.append_translation(&Vec3::new(4.0, 5.0, 0.0));
+    world.add_body(rb);
+
+    /*
+     * Balls knocking the sign, one after the other.
+     */
+    let speed = 10.0;
+
+    for i in range(0u, 3) {
+        let mut rb = RigidBody::new_dynamic(Ball::new(0.3f32), 1.0, 0.3, 0.6);
+        let dist   = 10.0 + i as f32 * 15.0;
+        // Thrown upward so that the ball is back at the height of the sign when reaching it.
+        let time   = dist / speed;
+
+        rb.append_translation(&Vec3::new(0.0, 2.6, dist));
+        rb.set_lin_vel(Vec3::new(0.0, 0.5 * 9.81 * time, -speed));
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.look_at(Pnt3::new(-8.0, 4.0, 8.0), Pnt3::new(2.0, 1.5, 0.0));
+    testbed.run();
+}
@@ -0,0 +1,104 @@
+/*!
+ * Checks the simulation time, the step counter, and the time carried by the events.
+ *
+ * The time must be the sum of the timesteps and the counter the number of steps, both restarting
+ * from zero after `reset_time`. Each event must carry the step during which it was emitted, or the
+ * last step if it was emitted between two steps, even if it is drained after a reset. The program
+ * exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Translation};
+use ncollide::shape::{Ball, Plane};
+use nphysics::world::{World, WorldEvent, TimedEvent};
+use nphysics::object::RigidBody;
+use harness::check;
+
+mod harness;
+
+fn add_ball(world: &mut World, y: f32) {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(0.0, y, 0.0));
+
+    world.add_body(rb);
+}
+
+fn drain(world: &mut World) -> Vec<TimedEvent> {
+    let mut events = Vec::new();
+
+    world.drain_timed_events(&mut events);
+
+    events
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+    world.set_impact_event_threshold(0.0);
+
+    check("initial time", world.time() == 0.0 && world.step_count() == 0);
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0f32, 1.0, 0.0)), 0.0, 0.5));
+
+    for _ in range(0u, 10) {
+        world.step(0.01);
+    }
+
+    check("accumulated time", na::approx_eq(&world.time(), &0.1) && world.step_count() == 10);
+    let initial   = drain(&mut world);
+    let mut added = initial.iter().filter(|e| match e.event { WorldEvent::BodyAdded(_) => true, _ => false });
+
+    check("events before the first step", added.all(|e| e.step == 0 && e.time == 0.0));
+
+    // Emitted between two steps.
+    add_ball(&mut world, 0.6);
+
+    let added = drain(&mut world);
+    check("event between steps", added.len() == 1 && added[0].step == 10 && na::approx_eq(&added[0].time, &0.1));
+
+    // Emitted during the steps.
+    let mut num_impacts = 0u;
+
+    for _ in range(0u, 20) {
+        world.step(0.01);
+
+        for e in drain(&mut world).iter() {
+            check("event during a step", e.step == world.step_count() && e.time == world.time());
+
+            match e.event {
+                WorldEvent::Impact(_) => num_impacts = num_impacts + 1,
+                _                     => { }
+            }
+        }
+    }
+
+    check("impact events", num_impacts > 0);
+
+    // The stamps of the events drained without their time must not be reused.
+    add_ball(&mut world, 3.0);
+
+    let mut untimed = Vec::new();
+    world.drain_events(&mut untimed);
+
+    world.step(0.01);
+    check("stamps after an untimed drain", drain(&mut world).iter().all(|e| e.step == 31));
+
+    // Events emitted before a reset keep their time.
+    add_ball(&mut world, 6.0);
+    world.reset_time();
+
+    check("reset", world.time() == 0.0 && world.step_count() == 0);
+
+    let before_reset = drain(&mut world);
+    check("event before a reset", before_reset.len() == 1 && before_reset[0].step == 31);
+
+    for _ in range(0u, 5) {
+        world.step(0.02);
+    }
+
+    check("time after a reset", na::approx_eq(&world.time(), &0.1) && world.step_count() == 5);
+}
@@ -0,0 +1,107 @@
+/*!
+ * Checks the soft contacts and joints.
+ *
+ * A box with soft contacts is dropped on the ground at 60 steps per second, with stiffnesses from
+ * 1.0e2 to 1.0e8 and damping ratios close to critical. It must settle without exploding, sinking
+ * less as the stiffness increases, by about its weight divided by the total stiffness of its
+ * contacts for the softest ones. A box hanging from a soft ball-in-socket joint must sag by its
+ * weight divided by the stiffness of the joint. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::detection::joint::{Anchor, BallInSocket};
+use nphysics::resolution::SpringDamper;
+use harness::check;
+
+mod harness;
+
+static DT: f32 = 1.0 / 60.0;
+
+// Drops a box of unit mass on the ground. Returns its final height and speed, and the maximal
+// speed it reached.
+fn drop_box(spring: Option<SpringDamper>) -> (f32, f32, f32) {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.0, 0.5));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+    rb.append_translation(&Vec3::new(0.0, 0.6, 0.0));
+    rb.set_deactivation_threshold(None);
+    rb.set_contact_spring(spring);
+
+    let body      = world.add_body(rb);
+    let mut max_v = 0.0f32;
+
+    for _ in range(0u, 600) {
+        world.step(DT);
+        max_v = max_v.max(na::norm(&body.borrow().lin_vel()));
+    }
+
+    let rb = body.borrow();
+
+    (rb.position().translation().y, na::norm(&rb.lin_vel()) + na::norm(&rb.ang_vel()), max_v)
+}
+
+fn main() {
+    let (rigid_height, _, _) = drop_box(None);
+
+    let mut prev_sink = Float::max_value();
+
+    for i in range(2i32, 9) {
+        let stiffness = 10.0f32.powi(i);
+        // Close to critical damping for the four contact points of the box.
+        let spring    = SpringDamper::new(stiffness, 0.7 * stiffness.sqrt());
+        let (height, speed, max_v) = drop_box(Some(spring));
+        let sink = rigid_height - height;
+        let name = format!("stiffness {}", stiffness);
+
+        check(format!("{}: finite", name).as_slice(), height.is_finite() && speed.is_finite());
+        check(format!("{}: did not explode", name).as_slice(), max_v < 5.0);
+        check(format!("{}: settled", name).as_slice(), speed < 0.05);
+        check(format!("{}: above the ground", name).as_slice(), height > 0.3);
+        check(format!("{}: sinks less when stiffer", name).as_slice(), sink <= prev_sink + 1.0e-3);
+
+        if i == 2 {
+            let expected = 9.81 / (4.0 * stiffness);
+            check(format!("{}: sinks by its weight, {} instead of {}", name, sink, expected).as_slice(),
+                  sink > 0.5 * expected && sink < 2.5 * expected);
+        }
+
+        prev_sink = sink;
+    }
+
+    /*
+     * Soft joint.
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+    rb.append_translation(&Vec3::new(0.0, 5.0, 0.0));
+    rb.set_deactivation_threshold(None);
+    let body = world.add_body(rb);
+
+    let mut joint = BallInSocket::new(Anchor::new(None, Pnt3::new(0.0, 5.5, 0.0)),
+                                      Anchor::new(Some(body.clone()), Pnt3::new(0.0, 0.5, 0.0)));
+    joint.set_spring(Some(SpringDamper::new(100.0, 14.0)));
+    world.add_ball_in_socket(joint);
+
+    for _ in range(0u, 600) {
+        world.step(DT);
+    }
+
+    let sag      = 5.0 - body.borrow().position().translation().y;
+    let expected = 9.81 / 100.0;
+
+    check(format!("joint sag {} instead of {}", sag, expected).as_slice(),
+          (sag - expected).abs() < 0.2 * expected);
+}
@@ -0,0 +1,93 @@
+/*!
+ * Checks the residuals of the constraints solver.
+ *
+ * The benchmark pyramid is simulated with an increasing number of solver iterations: the mean
+ * residual of its contacts must decrease, and be printed for each number of iterations. A body
+ * held by two joints at different places must keep a large joint residual whatever the number of
+ * iterations, and trigger a warning on the standard error in debug mode. The residuals must be
+ * zero when disabled. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::Ball;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::detection::joint::{Anchor, BallInSocket};
+use harness::check;
+
+mod harness;
+
+#[path = "../benches/scenes.rs"]
+mod scenes;
+
+static NUM_STEPS: uint = 100;
+
+// The mean contact residual of the steps of the pyramid.
+fn pyramid_residual(iterations: uint) -> f32 {
+    let mut world = scenes::pyramid();
+    world.constraints_solver().set_num_second_order_iter(iterations);
+    world.set_solver_residuals_enabled(true);
+
+    let mut sum = 0.0;
+
+    for _ in range(0, NUM_STEPS) {
+        world.step(0.016);
+        sum = sum + world.step_statistics().contact_residual;
+    }
+
+    sum / NUM_STEPS as f32
+}
+
+fn main() {
+    /*
+     * Pyramid.
+     */
+    let iterations = [ 2u, 5, 10, 20, 50 ];
+    let residuals: Vec<f32> = iterations.iter().map(|i| pyramid_residual(*i)).collect();
+
+    for (i, r) in iterations.iter().zip(residuals.iter()) {
+        println!("{:>3} iterations: mean contact residual {}", *i, *r);
+    }
+
+    check("decreasing with the iterations",
+          residuals.windows(2).all(|w| w[1] <= w[0] * 1.1));
+    check("much smaller with many iterations", residuals[4] < residuals[0] * 0.5);
+
+    /*
+     * Conflicting joints.
+     */
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+    world.set_residual_warning(0.1, 10);
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.6);
+    rb.set_deactivation_threshold(None);
+    rb.append_translation(&Vec3::new(0.0, 5.0, 0.0));
+    let body = world.add_body(rb);
+
+    world.add_ball_in_socket(BallInSocket::new(Anchor::new(None, Pnt3::new(-1.0, 5.0, 0.0)),
+                                               Anchor::new(Some(body.clone()), na::orig())));
+    world.add_ball_in_socket(BallInSocket::new(Anchor::new(None, Pnt3::new(1.0, 5.0, 0.0)),
+                                               Anchor::new(Some(body.clone()), na::orig())));
+
+    for _ in range(0, NUM_STEPS) {
+        world.step(0.016);
+    }
+
+    check("conflicting joints", world.step_statistics().joint_residual > 0.1);
+    check("worst joint", world.constraints_solver().worst_joint().is_some());
+
+    /*
+     * Disabled residuals.
+     */
+    let mut world = scenes::pyramid();
+
+    for _ in range(0, 10u) {
+        world.step(0.016);
+        check("zero when disabled", world.step_statistics().contact_residual == 0.0);
+    }
+}
@@ -0,0 +1,59 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use na::{Pnt3, Vec3};
+use ncollide::shape::Plane;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::utils::build_pressure_ball;
+use nphysics_testbed3d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    /*
+     * Planes
+     */
+    let normals = [
+        Vec3::new(-1.0, 1.0, -1.0 ),
+        Vec3::new(1.0, 1.0, -1.0 ),
+        Vec3::new(-1.0, 1.0, 1.0 ),
+        Vec3::new(1.0, 1.0, 1.0 )
+    ];
+    for n in normals.iter() {
+        let rb = RigidBody::new_static(Plane::new(*n), 0.3, 0.6);
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Create the squishy balls, with an increasing pressure.
+     */
+    let num   = 3u;
+    let rad   = 1.5;
+    let shift = 2.5 * rad;
+
+    for i in range(0u, num) {
+        for j in range(0u, num) {
+            let x = i as f32 * shift - shift;
+            let z = j as f32 * shift - shift;
+            let pressure = 50.0 + (i * num + j) as f32 * 50.0;
+
+            let _ = build_pressure_ball(&mut world, Pnt3::new(x, 15.0, z), rad, 1, 500.0, pressure);
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.look_at(Pnt3::new(-15.0, 20.0, -15.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
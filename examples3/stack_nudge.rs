@@ -0,0 +1,92 @@
+/*!
+ * Checks that setting the velocity or the position of a body between two steps is well handled.
+ *
+ * A stack of boxes is left to settle and fall asleep. The velocity of its middle box is then
+ * nudged: the box must be woken up and the stack must respond smoothly, without any box jumping.
+ * A sleeping box teleported elsewhere must then be found there by the geometric queries after
+ * the next step. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use std::cell::RefCell;
+use na::{Pnt3, Vec3, Iso3, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use ncollide::ray::Ray;
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::{check, steps};
+
+mod harness;
+
+fn add_box(world: &mut World, pos: Vec3<f32>) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.8);
+
+    rb.append_translation(&pos);
+
+    world.add_body(rb)
+}
+
+// The number of times a vertical ray at `x` hits `body`.
+fn hits(world: &mut World, body: &RigidBodyHandle, x: f32) -> uint {
+    let ray = Ray::new(Pnt3::new(x, 10.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+
+    world.ray_hits(&ray, None, None, true).iter().filter(|h| {
+        h.body.deref() as *const RefCell<RigidBody> == body.deref() as *const RefCell<RigidBody>
+    }).count()
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0f32, 1.0, 0.0)), 0.0, 0.8));
+
+    let stack: Vec<RigidBodyHandle> = range(0u, 5).map(|i| add_box(&mut world, Vec3::new(0.0, 0.5 + i as f32, 0.0))).collect();
+    let lone = add_box(&mut world, Vec3::new(10.0, 0.5, 0.0));
+
+    steps(&mut world, 600);
+
+    check("settled stack", stack.iter().all(|b| !b.borrow().is_active()));
+
+    let rest: Vec<f32> = stack.iter().map(|b| b.borrow().position().translation().y).collect();
+
+    /*
+     * Nudge the middle box.
+     */
+    stack[2].borrow_mut().set_lin_vel(Vec3::new(0.3, 0.0, 0.0));
+    world.step(0.016);
+
+    check("woken up", stack[2].borrow().is_active());
+
+    let mut max_vertical_vel = 0.0f32;
+    let mut max_drift        = 0.0f32;
+
+    for _ in range(0u, 60) {
+        world.step(0.016);
+
+        for (b, y) in stack.iter().zip(rest.iter()) {
+            let rb = b.borrow();
+
+            max_vertical_vel = max_vertical_vel.max(rb.lin_vel().y.abs());
+            max_drift        = max_drift.max((rb.position().translation().y - *y).abs());
+        }
+    }
+
+    check("vertical velocities", max_vertical_vel < 0.2);
+    check("vertical drift", max_drift < 0.05);
+
+    /*
+     * Teleport a sleeping box.
+     */
+    check("sleeping lone box", !lone.borrow().is_active());
+
+    lone.borrow_mut().set_transformation(Iso3::new(Vec3::new(20.0, 0.5, 0.0), na::zero()));
+    world.step(0.016);
+
+    check("old position", hits(&mut world, &lone, 10.0) == 0);
+    check("new position", hits(&mut world, &lone, 20.0) == 1);
+}
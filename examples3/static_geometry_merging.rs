@@ -0,0 +1,109 @@
+/*!
+ * Compares a floor made of 5000 static cuboids with the same floor merged into a single body.
+ *
+ * The same balls are dropped on both floors. The average duration of a step is displayed for
+ * each world, and the balls must end up at the same positions in both. The program exits with an
+ * error otherwise.
+ */
+
+extern crate time;
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Iso3, Translation};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::utils::StaticGeometryBuilder;
+use harness::{check, steps};
+
+mod harness;
+
+static NUM_X: uint = 100;
+static NUM_Z: uint = 50;
+
+fn tile_position(i: uint, k: uint) -> Vec3<f32> {
+    Vec3::new(i as f32 - NUM_X as f32 / 2.0, 0.0, k as f32 - NUM_Z as f32 / 2.0)
+}
+
+fn add_balls(world: &mut World) -> Vec<RigidBodyHandle> {
+    let mut balls = Vec::new();
+
+    for i in range(0u, 10) {
+        for k in range(0u, 10) {
+            let mut rb = RigidBody::new_dynamic(Ball::new(0.3f32), 1.0, 0.3, 0.6);
+
+            rb.append_translation(&Vec3::new(i as f32 * 1.3 - 6.0, 3.0 + k as f32 * 0.2, k as f32 * 1.3 - 6.0));
+            rb.set_lin_vel(Vec3::new(1.0, 0.0, 0.5));
+
+            balls.push(world.add_body(rb));
+        }
+    }
+
+    balls
+}
+
+// Steps the world and returns the average duration of a step, in milliseconds.
+fn run(world: &mut World) -> f64 {
+    let num_steps = 300u;
+    let before    = time::precise_time_s();
+
+    steps(world, num_steps);
+
+    (time::precise_time_s() - before) * 1000.0 / num_steps as f64
+}
+
+fn main() {
+    let tile = Vec3::new(0.5f32, 0.5, 0.5);
+
+    /*
+     * One body per tile.
+     */
+    let mut separate = World::new();
+    separate.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    for i in range(0u, NUM_X) {
+        for k in range(0u, NUM_Z) {
+            let mut rb = RigidBody::new_static(Cuboid::new(tile), 0.3, 0.6);
+
+            rb.append_translation(&tile_position(i, k));
+
+            separate.add_body(rb);
+        }
+    }
+
+    /*
+     * All the tiles merged.
+     */
+    let mut merged = World::new();
+    merged.set_gravity(Vec3::new(0.0f32, -9.81, 0.0));
+
+    let mut builder = StaticGeometryBuilder::new();
+
+    for i in range(0u, NUM_X) {
+        for k in range(0u, NUM_Z) {
+            builder.push_shape(Iso3::new(tile_position(i, k), na::zero()), Cuboid::new(tile));
+        }
+    }
+
+    merged.add_body(builder.build(0.3, 0.6));
+
+    let balls1 = add_balls(&mut separate);
+    let balls2 = add_balls(&mut merged);
+
+    let t1 = run(&mut separate);
+    let t2 = run(&mut merged);
+
+    println!("Average step duration: {}ms with {} static bodies, {}ms once merged.", t1, NUM_X * NUM_Z, t2);
+
+    for (b1, b2) in balls1.iter().zip(balls2.iter()) {
+        let p1 = b1.borrow().position().translation();
+        let p2 = b2.borrow().position().translation();
+
+        if !check(format!("ball at {} on the merged floor instead of {}", p2, p1).as_slice(),
+                  na::approx_eq_eps(&p1, &p2, &1.0e-2)) {
+            return;
+        }
+    }
+}
@@ -0,0 +1,87 @@
+/*!
+ * Checks the flags of the safety mechanisms which fired during the last step of the bodies.
+ *
+ * A fast ball with continuous collision detection is fired at a thin wall: its CCD flag must be
+ * set exactly on the step it hits the wall. A spinning ball with a maximum angular velocity must
+ * have its velocity flag set, and two deeply overlapping boxes their depenetration flag. The
+ * counts of the step statistics must match the flags. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Translation};
+use ncollide::shape::{Ball, Cuboid};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::check;
+
+mod harness;
+
+fn main() {
+    /*
+     * A ball fired at a wall.
+     */
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+    world.set_max_correction_velocity(1.0);
+
+    let mut wall = RigidBody::new_static(Cuboid::new(Vec3::new(0.05f32, 5.0, 5.0)), 0.0, 0.5);
+    wall.append_translation(&Vec3::new(5.0, 0.0, 0.0));
+    world.add_body(wall);
+
+    // The ball moves by 3.2 per step, and goes through the wall during the second step.
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.1f32), 1.0, 0.0, 0.5);
+    rb.set_lin_vel(Vec3::new(200.0, 0.0, 0.0));
+    let ball = world.add_body(rb);
+
+    world.add_ccd_to(&ball, 0.1);
+
+    for i in range(0u, 10) {
+        world.step(0.016);
+
+        let clamped = ball.borrow().last_step_flags().ccd_clamped();
+
+        check("CCD flag", clamped == (i == 1));
+        check("CCD count", world.step_statistics().num_ccd_clamped == if i == 1 { 1 } else { 0 });
+    }
+
+    check("wall crossed", ball.borrow().position().translation().x < 5.0);
+    check("CCD only", !ball.borrow().last_step_flags().velocity_clamped() &&
+                      !ball.borrow().last_step_flags().depenetration_capped());
+
+    /*
+     * A ball spinning too fast.
+     */
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+    rb.append_translation(&Vec3::new(-20.0, 0.0, 0.0));
+    rb.set_ang_vel(Vec3::new(0.0, 10.0, 0.0));
+    rb.set_max_angular_velocity(Some(2.0));
+    let spinning = world.add_body(rb);
+
+    world.step(0.016);
+
+    check("velocity flag", spinning.borrow().last_step_flags().velocity_clamped());
+    check("velocity count", world.step_statistics().num_velocity_clamped == 1);
+
+    world.step(0.016);
+
+    check("velocity flag cleared", spinning.borrow().last_step_flags().is_empty());
+
+    /*
+     * Two boxes overlapping by half their height.
+     */
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+    rb.append_translation(&Vec3::new(20.0, 0.0, 0.0));
+    let box1 = world.add_body(rb.clone());
+
+    rb.append_translation(&Vec3::new(0.0, 0.5, 0.0));
+    let box2 = world.add_body(rb);
+
+    world.step(0.016);
+
+    check("depenetration flags", box1.borrow().last_step_flags().depenetration_capped() &&
+                                 box2.borrow().last_step_flags().depenetration_capped());
+    check("depenetration count", world.step_statistics().num_depenetration_capped == 2);
+}
@@ -0,0 +1,55 @@
+/*!
+ * Checks that a ball subject to a quadratic drag reaches its terminal velocity.
+ *
+ * With a mass `m`, a gravity `g` and a quadratic drag coefficient `c`, the terminal velocity is
+ * `sqrt(m * g / c)`. The program exits with an error if the simulated velocity differs from this
+ * value by more than a few percents.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Vec3, Translation};
+use ncollide::shape::Ball;
+use ncollide::volumetric::Volumetric;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::integration::{Drag, DragCoefficients};
+use harness::{check, steps};
+
+mod harness;
+
+fn main() {
+    let gravity   = 9.81f32;
+    let quadratic = 0.1f32;
+    let ball      = Ball::new(0.5f32);
+    let (mass, _, _) = ball.mass_properties(1.0);
+
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -gravity, 0.0));
+    world.add_force_generator(Drag::new(DragCoefficients::new(0.0, quadratic, 0.0, 0.0)));
+
+    let mut rb = RigidBody::new_dynamic(ball, 1.0, 0.3, 0.6);
+    rb.append_translation(&Vec3::new(0.0, 1000.0, 0.0));
+    rb.set_deactivation_threshold(None);
+
+    let body = world.add_body(rb);
+
+    /*
+     * Fall for 20 seconds.
+     */
+    steps(&mut world, 1250);
+
+    let expected = (mass * gravity / quadratic).sqrt();
+    let actual   = -body.borrow().lin_vel().y;
+    let error    = (actual - expected).abs() / expected;
+
+    println!("Terminal velocity: {} (expected: {}, relative error: {}).", actual, expected, error);
+
+    check("terminal velocity", error <= 0.03);
+}
@@ -0,0 +1,70 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::num::Float;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Plane, Cuboid};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics_testbed3d::{Testbed, StepHandler};
+
+// The floor stops tilting once it reaches this angle, in radians.
+static MAX_ANGLE:  f32 = 0.6;
+// The tilting speed, in radians per second.
+static TILT_SPEED: f32 = 0.05;
+
+struct Tilter {
+    floor: RigidBodyHandle,
+    angle: f32
+}
+
+impl StepHandler for Tilter {
+    fn handle_step(&mut self, world: &mut World) {
+        if self.angle >= MAX_ANGLE {
+            return;
+        }
+
+        self.angle = (self.angle + TILT_SPEED * world.timestep()).min(MAX_ANGLE);
+
+        self.floor.borrow_mut().set_rotation(Vec3::new(0.0, 0.0, self.angle));
+    }
+}
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    /*
+     * Floor
+     */
+    let floor = world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    /*
+     * Create the boxes
+     */
+    let rad = 0.5;
+
+    for i in range(0u, 5) {
+        for k in range(0u, 5) {
+            let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(rad, rad, rad)), 1.0, 0.3, 0.6);
+
+            rb.append_translation(&Vec3::new(i as f32 * 2.5 - 5.0, rad + 0.05, k as f32 * 2.5 - 5.0));
+
+            world.add_body(rb);
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.set_step_handler(Tilter { floor: floor, angle: 0.0 });
+    testbed.look_at(Pnt3::new(0.0, 15.0, -30.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
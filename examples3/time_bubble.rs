@@ -0,0 +1,49 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use na::{Pnt3, Vec3, Iso3, Translation};
+use ncollide::shape::{Plane, Ball, Cuboid};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, ModifierVolume, Containment, Modifier};
+use nphysics_testbed3d::Testbed;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    /*
+     * Floor
+     */
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    /*
+     * A half-speed bubble above the left half of the floor. The balls dropped into it slow down
+     * and speed up again once they leave it.
+     */
+    let _ = world.add_modifier_volume(ModifierVolume::new(
+            Cuboid::new(Vec3::new(5.0f32, 4.0, 5.0)),
+            Iso3::new(Vec3::new(-5.0, 8.0, 0.0), na::zero()),
+            Containment::CenterOfMass,
+            Modifier::TimeScale(0.5)));
+
+    for i in range(0u, 10) {
+        let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.6, 0.6);
+
+        rb.append_translation(&Vec3::new(-9.0 + i as f32 * 2.0, 20.0, 0.0));
+
+        world.add_body(rb);
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    testbed.look_at(Pnt3::new(0.0, 10.0, -30.0), Pnt3::new(0.0, 8.0, 0.0));
+    testbed.run();
+}
@@ -0,0 +1,64 @@
+/*!
+ * Checks the time of impact between two moving bodies.
+ *
+ * Two balls move toward each other along the same line. The time of impact must match the
+ * closed-form answer, account for the margins, and decrease by the timestep after each step. It
+ * must be `None` if the balls move apart, or if they do not touch soon enough. The query must not
+ * move the balls. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Vec3, Translation};
+use ncollide::shape::Ball;
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::check;
+
+mod harness;
+
+fn add_ball(world: &mut World, radius: f32, x: f32, vx: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Ball::new(radius), 1.0, 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(x, 0.0, 0.0));
+    rb.set_lin_vel(Vec3::new(vx, 0.0, 0.0));
+
+    world.add_body(rb)
+}
+
+fn main() {
+    let mut world = World::new();
+
+    let b1 = add_ball(&mut world, 0.5, -5.0, 2.0);
+    let b2 = add_ball(&mut world, 1.0, 5.0, -3.0);
+
+    // The balls touch when the distance between their centers equals the sum of their radii
+    // dilated by their margins.
+    let margins  = b1.borrow().margin() + b2.borrow().margin();
+    let expected = (10.0 - 1.5 - margins) / 5.0;
+
+    match world.time_of_impact(&b1, &b2, 10.0) {
+        Some(toi) => check("closed-form time", (toi - expected).abs() < 1.0e-3),
+        None      => check("impact found", false)
+    }
+
+    check("symmetric", world.time_of_impact(&b2, &b1, 10.0).map_or(false, |t| (t - expected).abs() < 1.0e-3));
+    check("too late", world.time_of_impact(&b1, &b2, expected * 0.5).is_none());
+    check("not moved", b1.borrow().position().translation().x == -5.0 &&
+                       b2.borrow().position().translation().x == 5.0);
+
+    world.step(0.1);
+
+    match world.time_of_impact(&b1, &b2, 10.0) {
+        Some(toi) => check("time after one step", (toi - (expected - 0.1)).abs() < 1.0e-3),
+        None      => check("impact found after one step", false)
+    }
+
+    b1.borrow_mut().set_lin_vel(Vec3::new(-2.0, 0.0, 0.0));
+    b2.borrow_mut().set_lin_vel(Vec3::new(3.0, 0.0, 0.0));
+
+    check("moving apart", world.time_of_impact(&b1, &b2, 100.0).is_none());
+}
@@ -0,0 +1,123 @@
+/*!
+ * Checks the time scale of the bodies and the slow-motion modifier volumes.
+ *
+ * A ball falling through a half-speed volume must take twice as many steps to cross it as a ball
+ * falling beside it, and leave it with the energy it would have had without it, up to 1%. A ball
+ * with a time scale of `0.5` must fall four times less far in the same time, and rest on the
+ * ground like the others. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Vec3, Iso3, Translation};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle, ModifierVolume, Containment, Modifier};
+use harness::{check, steps};
+
+mod harness;
+
+fn new_world() -> World {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    world
+}
+
+fn add_ball(world: &mut World, x: f32, y: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(x, y, 0.0));
+    rb.set_deactivation_threshold(None);
+
+    world.add_body(rb)
+}
+
+fn height(body: &RigidBodyHandle) -> f32 {
+    body.borrow().position().translation().y
+}
+
+// The kinetic plus potential energy of a unit mass, with the velocity of the body in its own time.
+fn energy(body: &RigidBodyHandle) -> f32 {
+    0.5 * na::sqnorm(&body.borrow().lin_vel()) + 9.81 * height(body)
+}
+
+fn main() {
+    /*
+     * A ball falling through a half-speed bubble spanning the heights from 5 to 15, and a ball
+     * falling outside of it.
+     */
+    let mut world = new_world();
+
+    let _ = world.add_modifier_volume(ModifierVolume::new(
+            Cuboid::new(Vec3::new(5.0f32, 5.0, 5.0)),
+            Iso3::new(Vec3::new(0.0, 10.0, 0.0), na::zero()),
+            Containment::CenterOfMass,
+            Modifier::TimeScale(0.5)));
+
+    let slowed = add_ball(&mut world, 2.0, 25.0);
+    let free   = add_ball(&mut world, -20.0, 25.0);
+
+    let initial_energy      = energy(&slowed);
+    let mut slowed_inside   = 0u;
+    let mut free_inside     = 0u;
+    let mut slowed_speed_in = 0.0f32;
+
+    for _ in range(0u, 2000) {
+        if height(&slowed) < 0.0 {
+            break;
+        }
+
+        let (ys, yf) = (height(&slowed), height(&free));
+
+        world.step(0.016);
+
+        if ys < 15.0 && ys > 5.0 {
+            slowed_inside = slowed_inside + 1;
+            // The displacement of the slowed ball, relative to its own velocity.
+            slowed_speed_in = (height(&slowed) - ys) / (slowed.borrow().lin_vel().y * 0.016);
+        }
+
+        if yf < 15.0 && yf > 5.0 {
+            free_inside = free_inside + 1;
+        }
+    }
+
+    check("fall in the bubble", (slowed_speed_in - 0.5).abs() < 1.0e-3);
+    check("time in the bubble", (slowed_inside as int - 2 * free_inside as int).abs() <= 3);
+    check("speed after the bubble", height(&slowed) < 0.0 && slowed.borrow().lin_vel().y < -1.0);
+    check("energy after the bubble", ((energy(&slowed) - initial_energy) / initial_energy).abs() < 0.01);
+    check("time scale outside of the bubble", slowed.borrow().effective_time_scale() == 1.0);
+
+    /*
+     * A ball in slow motion.
+     */
+    let mut world = new_world();
+
+    let slowed = add_ball(&mut world, 0.0, 100.0);
+    let free   = add_ball(&mut world, 10.0, 100.0);
+
+    slowed.borrow_mut().set_time_scale(0.5);
+
+    steps(&mut world, 100);
+
+    let ratio = (100.0 - height(&slowed)) / (100.0 - height(&free));
+    check("slow-motion fall", (ratio - 0.25).abs() < 0.01);
+
+    /*
+     * A ball in slow motion resting on the ground.
+     */
+    let mut world = new_world();
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.0, 0.5));
+
+    let slowed = add_ball(&mut world, 0.0, 2.0);
+    slowed.borrow_mut().set_time_scale(0.5);
+
+    steps(&mut world, 500);
+
+    check("rest on the ground", (height(&slowed) - 0.5).abs() < 0.05);
+    check("rest velocity", na::norm(&slowed.borrow().lin_vel()) < 0.05);
+}
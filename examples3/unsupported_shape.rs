@@ -0,0 +1,66 @@
+/*!
+ * Checks how the testbed handles the shapes it cannot render.
+ *
+ * The shapes the testbed renders must be described without any warning. A capsule,
+ * which it cannot render, must take the skip path and record a warning naming its type. With the
+ * strict rendering, it must panic instead. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::task;
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Vec3, Translation};
+use ncollide::shape::{Ball, Capsule};
+use nphysics::object::{RigidBody, RigidBodyHandle, ShapeDesc};
+use nphysics_testbed3d::dispatch_shape;
+use harness::check;
+
+mod harness;
+
+fn capsule() -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Capsule::new(1.0, 0.5f32), 1.0, 0.3, 0.5);
+    rb.append_translation(&Vec3::new(1.0, 2.0, 3.0));
+
+    Rc::new(RefCell::new(rb))
+}
+
+fn main() {
+    let mut warnings = Vec::new();
+
+    /*
+     * Supported shape.
+     */
+    let ball = Rc::new(RefCell::new(RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.3, 0.5)));
+    let desc = match dispatch_shape(ball.borrow().shape_ref(), &ball, false, &mut warnings) {
+        Some(ShapeDesc::Ball(radius)) => Some(radius),
+        _                             => None
+    };
+
+    check("ball description", desc == Some(0.5));
+    check("no warning", warnings.is_empty());
+
+    /*
+     * Unsupported shape, skipped.
+     */
+    let body = capsule();
+    let skipped = dispatch_shape(body.borrow().shape_ref(), &body, false, &mut warnings).is_none();
+
+    check("skipped capsule", skipped);
+    check("one warning", warnings.len() == 1);
+    check("shape type in the warning", warnings.iter().all(|w| w.as_slice().contains("Capsule3")));
+
+    /*
+     * Unsupported shape, strict rendering.
+     */
+    let res = task::try(proc() {
+        let body = capsule();
+        let _    = dispatch_shape(body.borrow().shape_ref(), &body, true, &mut Vec::new());
+    });
+
+    check("strict rendering", res.is_err());
+}
@@ -0,0 +1,73 @@
+/*!
+ * Checks the velocity Verlet integrator on a long-running orbit.
+ *
+ * A planet orbits a static sun, pulled by an attractor, during 100000 steps. The semi-major axis of
+ * its orbit, computed from its energy, must drift at least ten times less with the velocity Verlet
+ * integrator than with the semi-implicit Euler integrator. The program exits with an error
+ * otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use std::num::Float;
+use na::{Vec3, Translation};
+use ncollide::shape::Ball;
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics::integration::{Attractor, IntegratorKind};
+use harness::check;
+
+mod harness;
+
+// The strength of the attractor, i.e., the gravitational parameter of the sun.
+static GM: f32 = 1000.0;
+
+// The semi-major axis of the orbit of a body at the distance `r` of the sun, with the speed `v`.
+fn semi_major_axis(r: f32, v: f32) -> f32 {
+    let energy = v * v / 2.0 - GM / r;
+
+    -GM / (2.0 * energy)
+}
+
+// The largest relative drift of the semi-major axis of an eccentric orbit.
+fn max_drift(kind: IntegratorKind) -> f32 {
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+    world.set_integrator(kind);
+
+    world.add_body(RigidBody::new_static(Ball::new(1.0f32), 0.3, 0.5));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.2f32), 1.0, 0.3, 0.5);
+    rb.append_translation(&Vec3::new(10.0, 0.0, 0.0));
+    // Slower than the circular speed, for an eccentric orbit.
+    rb.set_lin_vel(Vec3::new(0.0, 0.0, 8.0));
+    rb.set_deactivation_threshold(None);
+    let planet = world.add_body(rb);
+
+    let _ = world.add_force_generator(Attractor::new(na::orig(), GM));
+
+    let a0    = semi_major_axis(10.0, 8.0);
+    let mut d = 0.0f32;
+
+    for _ in range(0u, 100000) {
+        world.step(0.016);
+
+        let rb = planet.borrow();
+        let a  = semi_major_axis(na::norm(&rb.position().translation()), na::norm(&rb.lin_vel()));
+
+        d = d.max((a - a0).abs() / a0);
+    }
+
+    d
+}
+
+fn main() {
+    let euler  = max_drift(IntegratorKind::SymplecticEuler);
+    let verlet = max_drift(IntegratorKind::VelocityVerlet);
+
+    println!("Largest semi-major axis drift: {} with Euler, {} with Verlet.", euler, verlet);
+
+    check("Verlet drift", verlet.is_finite() && verlet * 10.0 < euler);
+}
@@ -0,0 +1,81 @@
+/*!
+ * Checks that removing a body wakes up the bodies that were touching it.
+ *
+ * A stack of three boxes is left to fall asleep on a static box. The middle box is removed: the
+ * top box must fall onto the bottom one. The static box is then removed once everything is asleep
+ * again: the boxes resting on it must fall too. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Translation};
+use ncollide::shape::Cuboid;
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::{check, steps};
+
+mod harness;
+
+fn add_box(world: &mut World, y: f32) -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(0.0, y, 0.0));
+
+    world.add_body(rb)
+}
+
+fn height(body: &RigidBodyHandle) -> f32 {
+    body.borrow().position().translation().y
+}
+
+// Steps until every body of `bodies` is asleep, and returns whether they all fell asleep.
+fn sleep(world: &mut World, bodies: &[&RigidBodyHandle]) -> bool {
+    for _ in range(0u, 2000) {
+        world.step(0.016);
+
+        if bodies.iter().all(|b| !b.borrow().is_active()) {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    let mut rb = RigidBody::new_static(Cuboid::new(Vec3::new(5.0f32, 0.5, 5.0)), 0.0, 0.5);
+
+    rb.append_translation(&Vec3::new(0.0, -0.5, 0.0));
+
+    let ground = world.add_body(rb);
+    let bottom = add_box(&mut world, 0.55);
+    let middle = add_box(&mut world, 1.65);
+    let top    = add_box(&mut world, 2.75);
+
+    check("stack asleep", sleep(&mut world, &[&bottom, &middle, &top]));
+
+    world.remove_body(&middle);
+
+    let before = height(&top);
+
+    steps(&mut world, 60);
+
+    // The top box falls by the height of the middle box.
+    check("top box falls", before - height(&top) > 0.8);
+
+    check("boxes asleep", sleep(&mut world, &[&bottom, &top]));
+
+    world.remove_body(&ground);
+
+    let before     = height(&bottom);
+    let top_before = height(&top);
+
+    steps(&mut world, 60);
+
+    check("bottom box falls", before - height(&bottom) > 1.0);
+    check("top box follows", top_before - height(&top) > 1.0);
+}
@@ -0,0 +1,72 @@
+/*!
+ * Checks the bounding boxes of the bodies and of the world.
+ *
+ * A ball and a box are added above a plane. Right after they are added, their bounding boxes must
+ * be their shapes' loosened by their margins, and the bounding box of the world must be their
+ * union, without the plane. The bounding box of the world must still contain every body after the
+ * ball fell on the ground. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Plane, Ball, Cuboid};
+use ncollide::bounding_volume::{AABB, BoundingVolume, HasAABB};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use harness::{check, steps};
+
+mod harness;
+
+fn approx_eq(a: &AABB<Pnt3<f32>>, b: &AABB<Pnt3<f32>>) -> bool {
+    na::approx_eq(a.mins(), b.mins()) && na::approx_eq(a.maxs(), b.maxs())
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    check("empty world", world.aabb().is_none());
+
+    let plane = world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.0, 0.5));
+
+    check("only a plane", world.aabb().is_none());
+
+    let mut ball = RigidBody::new_dynamic(Ball::new(0.5f32), 1.0, 0.0, 0.5);
+    ball.append_translation(&Vec3::new(-2.0, 3.0, 0.0));
+    let ball = world.add_body(ball);
+
+    let mut krate = RigidBody::new_static(Cuboid::new(Vec3::new(1.0f32, 0.5, 2.0)), 0.0, 0.5);
+    krate.append_translation(&Vec3::new(3.0, 0.5, 1.0));
+    let krate = world.add_body(krate);
+
+    for b in [ &ball, &krate ].iter() {
+        let rb       = b.borrow();
+        let expected = rb.shape_ref().aabb(rb.position()).loosened(rb.margin());
+
+        check("body after being added", approx_eq(&rb.aabb(), &expected));
+    }
+
+    let union = ball.borrow().aabb().merged(&krate.borrow().aabb());
+
+    match world.aabb() {
+        Some(aabb) => check("world after the bodies were added", approx_eq(&aabb, &union)),
+        None       => check("world not empty", false)
+    }
+
+    check("plane unbounded", plane.borrow().aabb().maxs().x > 1.0e30);
+
+    steps(&mut world, 100);
+
+    check("ball on the ground", ball.borrow().aabb().maxs().y < 1.5);
+
+    match world.aabb() {
+        Some(aabb) => {
+            check("world contains the ball", aabb.contains(&ball.borrow().aabb()));
+            check("world contains the box", aabb.contains(&krate.borrow().aabb()));
+        },
+        None => check("world not empty", false)
+    }
+}
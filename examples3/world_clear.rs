@@ -0,0 +1,82 @@
+/*!
+ * Checks the removal of all the bodies of a world, as done by the testbed when switching scenes.
+ *
+ * Clearing a world must remove its bodies and their joints, emit one `BodyRemoved` event per body,
+ * and keep its settings. A scene built again in the cleared world must simulate like in a new
+ * world, except for the identifiers of its bodies. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics::world::{World, WorldEvent};
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use nphysics::detection::joint::{Anchor, BallInSocket};
+use harness::{check, steps};
+
+mod harness;
+
+// A ball hanging from a joint above a box resting on the ground.
+fn build(world: &mut World) -> Vec<RigidBodyHandle> {
+    let mut res = Vec::new();
+
+    res.push(world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6)));
+
+    let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+    rb.append_translation(&Vec3::new(0.0, 0.6, 0.0));
+    res.push(world.add_body(rb));
+
+    let mut rb = RigidBody::new_dynamic(Ball::new(0.3f32), 1.0, 0.3, 0.6);
+    rb.append_translation(&Vec3::new(1.0, 3.0, 0.0));
+    let ball = world.add_body(rb);
+
+    world.add_ball_in_socket(BallInSocket::new(Anchor::new(None, Pnt3::new(0.0, 3.0, 0.0)),
+                                               Anchor::new(Some(ball.clone()), Pnt3::new(-1.0, 0.0, 0.0))));
+    res.push(ball);
+
+    res
+}
+
+fn simulate(world: &mut World, bodies: &[RigidBodyHandle]) -> Vec<Vec3<f32>> {
+    steps(world, 100);
+
+    bodies.iter().map(|b| b.borrow().position().translation()).collect()
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+
+    let bodies   = build(&mut world);
+    let expected = simulate(&mut world, bodies.as_slice());
+
+    let mut events = Vec::new();
+    world.drain_events(&mut events);
+    events.clear();
+
+    world.clear();
+    world.drain_events(&mut events);
+
+    let removed = events.iter().filter(|e| match **e { WorldEvent::BodyRemoved(_) => true, _ => false }).count();
+
+    check("bodies", world.bodies().next().is_none());
+    check("joints", world.joints().next().is_none());
+    check("removed events", removed == bodies.len());
+    check("gravity", world.gravity() == Vec3::new(0.0, -9.81, 0.0));
+
+    world.clear();
+    check("clear of an empty world", world.bodies().next().is_none());
+
+    /*
+     * The scene built again.
+     */
+    let rebuilt = build(&mut world);
+
+    check("uids", rebuilt.iter().all(|r| bodies.iter().all(|b| b.borrow().uid() != r.borrow().uid())));
+    let positions = simulate(&mut world, rebuilt.as_slice());
+
+    check("rebuilt scene", positions.iter().zip(expected.iter()).all(|(p, e)| na::norm(&(*p - *e)) < 1.0e-3));
+}
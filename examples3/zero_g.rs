@@ -0,0 +1,74 @@
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+extern crate nphysics_testbed3d;
+
+use std::num::Float;
+use na::{Pnt3, Vec3, Translation};
+use ncollide::shape::{Ball, Cuboid, Plane};
+use nphysics::world::World;
+use nphysics::object::RigidBody;
+use nphysics_testbed3d::Testbed;
+
+// Half the width of the closed box.
+static HALF_WIDTH: f32 = 10.0;
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+
+    /*
+     * A closed box made of six planes facing inward.
+     */
+    for i in range(0u, 3) {
+        for sign in [ -1.0f32, 1.0 ].iter() {
+            let mut normal: Vec3<f32> = na::zero();
+            normal[i] = -*sign;
+
+            let mut rb = RigidBody::new_static(Plane::new(normal), 1.0, 0.0);
+            rb.append_translation(&(normal * -HALF_WIDTH));
+
+            world.add_body(rb);
+        }
+    }
+
+    /*
+     * Balls and boxes launched in every direction.
+     */
+    let rad = 0.5;
+
+    for i in range(0u, 4) {
+        for j in range(0u, 4) {
+            for k in range(0u, 4) {
+                let pos = Vec3::new(i as f32 * 3.0 - 4.5, j as f32 * 3.0 - 4.5, k as f32 * 3.0 - 4.5);
+                let n   = (i * 16 + j * 4 + k) as f32;
+                let vel = Vec3::new((n * 1.3).sin(), (n * 2.1).cos(), (n * 0.7).sin()) * 4.0;
+
+                let mut rb = if (i + j + k) % 2 == 0 {
+                    RigidBody::new_dynamic(Ball::new(rad), 1.0, 1.0, 0.0)
+                }
+                else {
+                    RigidBody::new_dynamic(Cuboid::new(Vec3::new(rad, rad, rad)), 1.0, 1.0, 0.0)
+                };
+
+                rb.append_translation(&pos);
+                rb.set_lin_vel(vel);
+                rb.set_ang_vel(Vec3::new(vel.y, vel.z, vel.x));
+
+                world.add_body(rb);
+            }
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+
+    // There is no ground to look down at: the camera starts in a corner, inside the box.
+    testbed.look_at(Pnt3::new(-9.0, 8.0, -9.0), Pnt3::new(0.0, 0.0, 0.0));
+    testbed.run();
+}
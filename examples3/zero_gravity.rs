@@ -0,0 +1,103 @@
+/*!
+ * Checks the conservation laws without gravity.
+ *
+ * Two spinning balls of different masses collide off-center, with friction, in a world without
+ * gravity. Their total linear and angular momentums must be conserved through the collision and
+ * over a long run afterward. A ball left alone with a tiny velocity must be stopped once the drift
+ * cleanup is enabled, and only then. The program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Translation};
+use ncollide::shape::Ball;
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::check;
+
+mod harness;
+
+fn add_ball(world: &mut World, radius: f32, density: f32, pos: Vec3<f32>, lin_vel: Vec3<f32>, ang_vel: Vec3<f32>)
+            -> RigidBodyHandle {
+    let mut rb = RigidBody::new_dynamic(Ball::new(radius), density, 0.5, 0.8);
+
+    rb.append_translation(&pos);
+    rb.set_lin_vel(lin_vel);
+    rb.set_ang_vel(ang_vel);
+    rb.set_deactivation_threshold(None);
+
+    world.add_body(rb)
+}
+
+// The linear momentum, and the angular momentum wrt. the origin, of `bodies`.
+fn momentums(bodies: &[RigidBodyHandle]) -> (Vec3<f32>, Vec3<f32>) {
+    let mut linear  = na::zero();
+    let mut angular = na::zero();
+
+    for body in bodies.iter() {
+        let rb      = body.borrow();
+        let mass    = 1.0 / rb.inv_mass();
+        let inertia = na::inv(rb.inv_inertia()).unwrap();
+        let p       = rb.lin_vel() * mass;
+
+        linear  = linear + p;
+        angular = angular + na::cross(rb.center_of_mass().as_vec(), &p) + inertia * rb.ang_vel();
+    }
+
+    (linear, angular)
+}
+
+fn close(a: &Vec3<f32>, b: &Vec3<f32>, tolerance: f32) -> bool {
+    na::norm(&(*a - *b)) <= tolerance * (1.0 + na::norm(a))
+}
+
+fn main() {
+    let mut world = World::new();
+    world.set_gravity(na::zero());
+
+    let b1 = add_ball(&mut world, 0.5, 1.0, Vec3::new(-3.0, 0.3, 0.0), Vec3::new(2.0, 0.0, 0.1), Vec3::new(0.0, 0.0, 3.0));
+    let b2 = add_ball(&mut world, 0.8, 2.0, Vec3::new(3.0, -0.3, 0.0), Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+    let bodies = [ b1.clone(), b2.clone() ];
+
+    let (linear0, angular0) = momentums(&bodies);
+    let mut collided        = false;
+
+    for i in range(0u, 10000) {
+        world.step(0.016);
+
+        collided = collided || world.contacts().len() != 0;
+
+        if i % 100 == 0 {
+            let (linear, angular) = momentums(&bodies);
+
+            check(format!("linear momentum {} instead of {}", linear, linear0).as_slice(), close(&linear, &linear0, 1.0e-4));
+            check(format!("angular momentum {} instead of {}", angular, angular0).as_slice(), close(&angular, &angular0, 1.0e-3));
+        }
+    }
+
+    check("collision", collided);
+    check("bounced", b1.borrow().lin_vel().x < 0.0);
+
+    /*
+     * Drift cleanup.
+     */
+    let slow = add_ball(&mut world, 0.5, 1.0, Vec3::new(0.0, 100.0, 0.0), Vec3::new(1.0e-6, 0.0, 0.0), Vec3::new(0.0, 1.0e-6, 0.0));
+    world.step(0.016);
+
+    check("tiny velocity kept by default", slow.borrow().lin_vel().x == 1.0e-6);
+
+    world.set_drift_threshold(Some(1.0e-5));
+    world.step(0.016);
+
+    check("tiny linear velocity zeroed", na::is_zero(&slow.borrow().lin_vel()));
+    check("tiny angular velocity zeroed", na::is_zero(&slow.borrow().ang_vel()));
+    check("large velocities kept", !na::is_zero(&b1.borrow().lin_vel()) && !na::is_zero(&b2.borrow().lin_vel()));
+
+    world.set_drift_threshold(None);
+    slow.borrow_mut().set_lin_vel(Vec3::new(1.0e-6, 0.0, 0.0));
+    world.step(0.016);
+
+    check("tiny velocity kept without cleanup", slow.borrow().lin_vel().x == 1.0e-6);
+}
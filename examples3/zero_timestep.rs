@@ -0,0 +1,74 @@
+/*!
+ * Checks that the steps with a null or tiny timestep are ignored.
+ *
+ * A pyramid of boxes, hit by balls, is simulated twice: once with normal steps only, and once with
+ * steps of zero and of tiny durations interleaved with the same normal steps. Every position must
+ * stay finite, and both runs must end in the same state, at the same time and step count. The
+ * program exits with an error otherwise.
+ */
+
+extern crate "nalgebra" as na;
+extern crate ncollide;
+extern crate nphysics;
+
+use na::{Vec3, Translation};
+use ncollide::shape::{Plane, Ball, Cuboid};
+use nphysics::world::World;
+use nphysics::object::{RigidBody, RigidBodyHandle};
+use harness::check;
+
+mod harness;
+
+static DT: f32 = 0.016;
+
+fn build() -> (World, Vec<RigidBodyHandle>) {
+    let mut world  = World::new();
+    let mut bodies = Vec::new();
+
+    world.set_gravity(Vec3::new(0.0, -9.81, 0.0));
+    world.add_body(RigidBody::new_static(Plane::new(Vec3::new(0.0, 1.0, 0.0)), 0.3, 0.6));
+
+    for i in range(0u, 6) {
+        for j in range(0u, 6 - i) {
+            let mut rb = RigidBody::new_dynamic(Cuboid::new(Vec3::new(0.5f32, 0.5, 0.5)), 1.0, 0.3, 0.6);
+            rb.append_translation(&Vec3::new(j as f32 * 1.1 + i as f32 * 0.55 - 3.0, i as f32 * 1.05 + 0.5, 0.0));
+            bodies.push(world.add_body(rb));
+        }
+    }
+
+    for i in range(0u, 3) {
+        let mut rb = RigidBody::new_dynamic(Ball::new(0.4f32), 2.0, 0.5, 0.6);
+        rb.append_translation(&Vec3::new(i as f32 - 1.0, 8.0 + i as f32, -0.2));
+        rb.set_lin_vel(Vec3::new(0.0, -5.0, 0.0));
+        bodies.push(world.add_body(rb));
+    }
+
+    (world, bodies)
+}
+
+fn main() {
+    let (mut reference, ref_bodies) = build();
+    let (mut world, bodies)         = build();
+
+    for i in range(0u, 300) {
+        reference.step(DT);
+
+        world.step(0.0);
+        world.step(DT);
+
+        if i % 3 == 0 {
+            world.step(1.0e-7);
+            world.step(0.0);
+        }
+
+        check("finite positions", bodies.iter().all(|b| b.borrow().is_finite()));
+    }
+
+    check("same time", world.time() == reference.time());
+    check("same step count", world.step_count() == reference.step_count());
+
+    for (b, r) in bodies.iter().zip(ref_bodies.iter()) {
+        check("same positions", na::approx_eq_eps(b.borrow().position(), r.borrow().position(), &1.0e-4));
+        check("same velocities", na::approx_eq_eps(&b.borrow().lin_vel(), &r.borrow().lin_vel(), &1.0e-4));
+    }
+}
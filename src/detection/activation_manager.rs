@@ -4,7 +4,7 @@ use std::cell::RefCell;
 use na;
 use ncollide::utils::data::hash_map::HashMap;
 use ncollide::utils::data::hash::UintTWHash;
-use world::RigidBodyCollisionWorld;
+use world::{RigidBodyCollisionWorld, WorldEvent};
 use detection::constraint::Constraint;
 use detection::joint::{JointManager, Joint};
 use object::{RigidBody, ActivationState};
@@ -47,6 +47,66 @@ impl ActivationManager {
         }
     }
 
+    /// Notify the `ActivationManager` that it has to activate, at the next update, every object in
+    /// contact with `b`.
+    ///
+    /// This must be called before `b` is removed from `world`, or when `b` cannot move: the
+    /// objects touching a dynamic body are already woken up with it.
+    pub fn will_activate_neighbors(&mut self, world: &mut RigidBodyCollisionWorld, b: &Rc<RefCell<RigidBody>>) {
+        let key = b.deref() as *const RefCell<RigidBody>;
+
+        world.contact_pairs(|b1, b2, cd| {
+            if touching(b1, b2, cd.num_colls()) {
+                if b1.deref() as *const RefCell<RigidBody> == key {
+                    self.will_activate(b2)
+                }
+                else if b2.deref() as *const RefCell<RigidBody> == key {
+                    self.will_activate(b1)
+                }
+            }
+        });
+    }
+
+    /// Activates an object right away, instead of at the next update.
+    ///
+    /// An event is pushed to `events` if it was inactive.
+    pub fn activate_now(&mut self, b: &Rc<RefCell<RigidBody>>, events: &mut Vec<WorldEvent>) {
+        activate_dynamic(b, events)
+    }
+
+    /// The total capacity of the buffers reused from one update to the next.
+    #[cfg(feature = "alloc_stats")]
+    pub fn buffers_capacity(&self) -> uint {
+        self.ufind.capacity() + self.can_deactivate.capacity() + self.to_activate.capacity()
+    }
+
+    /// The islands of dynamic bodies that are not connected to any body that cannot move.
+    ///
+    /// The islands are built like those deactivated by the `ActivationManager`: two dynamic bodies
+    /// are part of the same island if they touch each other or are attached by a joint. An island is
+    /// grounded if one of its bodies touches a body that cannot move, or is attached by a joint to
+    /// such a body or to the ground. The islands are given in the order of their first body in
+    /// `bodies`. This does not change the state of the bodies nor of the manager.
+    pub fn ungrounded_islands(&self,
+                              world:  &mut RigidBodyCollisionWorld,
+                              joints: &JointManager,
+                              bodies: &HashMap<uint, Rc<RefCell<RigidBody>>, UintTWHash>)
+                              -> Vec<Vec<Rc<RefCell<RigidBody>>>> {
+        build_islands(world, joints, bodies, false)
+    }
+
+    /// The islands of dynamic bodies, grounded or not.
+    ///
+    /// The islands are built like those of `ungrounded_islands`, their first body being their
+    /// representative. This does not change the state of the bodies nor of the manager.
+    pub fn islands(&self,
+                   world:  &mut RigidBodyCollisionWorld,
+                   joints: &JointManager,
+                   bodies: &HashMap<uint, Rc<RefCell<RigidBody>>, UintTWHash>)
+                   -> Vec<Vec<Rc<RefCell<RigidBody>>>> {
+        build_islands(world, joints, bodies, true)
+    }
+
     fn update_energy(&self, b: &mut RigidBody) {
         match b.deactivation_threshold() {
             Some(threshold) => {
@@ -62,10 +122,13 @@ impl ActivationManager {
     }
 
     /// Update the activation manager, activating and deactivating objects when needed.
+    ///
+    /// An event is pushed to `events` for each body which activation state changed.
     pub fn update(&mut self,
                   world:  &mut RigidBodyCollisionWorld,
                   joints: &JointManager,
-                  bodies: &HashMap<uint, Rc<RefCell<RigidBody>>, UintTWHash>) {
+                  bodies: &HashMap<uint, Rc<RefCell<RigidBody>>, UintTWHash>,
+                  events: &mut Vec<WorldEvent>) {
         /*
          *
          * Update bodies energy
@@ -75,7 +138,8 @@ impl ActivationManager {
             let mut b = b.value.borrow_mut();
 
             assert!(*b.activation_state() != ActivationState::Deleted);
-            if b.is_active() {
+            // The bodies frozen by a partial step are left as they are.
+            if b.is_active() && !b.is_frozen() {
                 self.update_energy(&mut *b);
             }
 
@@ -88,12 +152,25 @@ impl ActivationManager {
          *
          */
         for b in self.to_activate.iter() {
-            let mut rb = b.borrow_mut();
+            activate(b, events)
+        }
 
-            match rb.deactivation_threshold() {
-                Some(threshold) => rb.activate(threshold * na::cast(2.0f64)),
-                None => { }
-            }
+        // The objects touching a woken up object are woken up too, even if they are not part of
+        // its island, e.g., because the object is falling away from them. One ring is enough: the
+        // resolution wakes up the others through the islands if needed.
+        if !self.to_activate.is_empty() {
+            let to_activate = &self.to_activate;
+
+            world.contact_pairs(|b1, b2, cd| {
+                if touching(b1, b2, cd.num_colls()) {
+                    if contains(to_activate.as_slice(), b1) {
+                        activate_dynamic(b2, events)
+                    }
+                    else if contains(to_activate.as_slice(), b2) {
+                        activate_dynamic(b1, events)
+                    }
+                }
+            });
         }
 
         self.to_activate.clear();
@@ -134,26 +211,15 @@ impl ActivationManager {
         }
 
         world.contact_pairs(|b1, b2, cd| {
-            if cd.num_colls() != 0 {
+            if touching(b1, b2, cd.num_colls()) {
                 make_union(b1, b2, self.ufind.as_mut_slice())
             }
         });
 
         for e in joints.joints().elements().iter() {
-            match e.value {
-                Constraint::RBRB(ref b1, ref b2, _) => make_union(b1, b2, self.ufind.as_mut_slice()),
-                Constraint::BallInSocket(ref b)   => {
-                    match (b.borrow().anchor1().body.as_ref(), b.borrow().anchor2().body.as_ref()) {
-                        (Some(b1), Some(b2)) => make_union(b1, b2, self.ufind.as_mut_slice()),
-                        _ => { }
-                    }
-                },
-                Constraint::Fixed(ref f)   => {
-                    match (f.borrow().anchor1().body.as_ref(), f.borrow().anchor2().body.as_ref()) {
-                        (Some(b1), Some(b2)) => make_union(b1, b2, self.ufind.as_mut_slice()),
-                        _ => { }
-                    }
-                }
+            match joint_bodies(&e.value) {
+                (Some(b1), Some(b2)) => make_union(&b1, &b2, self.ufind.as_mut_slice()),
+                _                    => { }
             }
         }
 
@@ -176,16 +242,28 @@ impl ActivationManager {
 
         // Activate/deactivate islands.
         for i in range(0u, self.ufind.len()) {
-            let root = union_find::find(i, self.ufind.as_mut_slice());
-            let mut b = bodies.elements()[i].value.borrow_mut();
+            let root   = union_find::find(i, self.ufind.as_mut_slice());
+            let handle = &bodies.elements()[i].value;
+            let mut b  = handle.borrow_mut();
+
+            if b.is_frozen() {
+                continue;
+            }
 
             if self.can_deactivate[root] { // Everybody in this set can be deactivacted.
+                if b.is_active() && b.can_move() {
+                    events.push(WorldEvent::BodyDeactivated(handle.clone()))
+                }
+
                 b.deactivate();
             }
             else { // Everybody in this set must be reactivated.
                 if !b.is_active() && b.can_move() {
                     match b.deactivation_threshold() {
-                        Some(threshold) => b.activate(threshold * na::cast(2.0f64)),
+                        Some(threshold) => {
+                            events.push(WorldEvent::BodyActivated(handle.clone()));
+                            b.activate(threshold * na::cast(2.0f64))
+                        },
                         None => { }
                     }
                 }
@@ -193,3 +271,123 @@ impl ActivationManager {
         }
     }
 }
+
+// The islands of dynamic bodies, see `ActivationManager::ungrounded_islands`. The grounded ones
+// are left out unless `keep_grounded` is `true`.
+fn build_islands(world:         &mut RigidBodyCollisionWorld,
+                 joints:        &JointManager,
+                 bodies:        &HashMap<uint, Rc<RefCell<RigidBody>>, UintTWHash>,
+                 keep_grounded: bool)
+                 -> Vec<Vec<Rc<RefCell<RigidBody>>>> {
+let mut ids = HashMap::new(UintTWHash::new());
+
+    for (i, e) in bodies.elements().iter().enumerate() {
+        let _ = ids.insert(e.key, i);
+    }
+
+    let mut ufind: Vec<UnionFindSet> = range(0, bodies.len()).map(|i| UnionFindSet::new(i)).collect();
+    let mut grounded = Vec::from_elem(bodies.len(), false);
+
+    world.contact_pairs(|b1, b2, cd| {
+        if touching(b1, b2, cd.num_colls()) {
+            link(Some(b1), Some(b2), &ids, ufind.as_mut_slice(), grounded.as_mut_slice())
+        }
+    });
+
+    for e in joints.joints().elements().iter() {
+        let (b1, b2) = joint_bodies(&e.value);
+
+        link(b1.as_ref(), b2.as_ref(), &ids, ufind.as_mut_slice(), grounded.as_mut_slice())
+    }
+
+    for i in range(0u, ufind.len()) {
+        if grounded[i] {
+            grounded[union_find::find(i, ufind.as_mut_slice())] = true
+        }
+    }
+
+    let mut island_of: Vec<Option<uint>> = Vec::from_elem(bodies.len(), None);
+    let mut res                          = Vec::new();
+
+    for i in range(0u, ufind.len()) {
+        let root = union_find::find(i, ufind.as_mut_slice());
+        let b    = &bodies.elements()[i].value;
+
+        if (grounded[root] && !keep_grounded) || !b.borrow().can_move() {
+            continue;
+        }
+
+        match island_of[root] {
+            Some(island) => res[island].push(b.clone()),
+            None => {
+                island_of[root] = Some(res.len());
+                res.push(vec!(b.clone()))
+            }
+        }
+    }
+
+    res
+}
+
+// Merges the islands of two linked bodies, or grounds the island of the first if the second is
+// static or the ground, and conversely.
+fn link(b1:       Option<&Rc<RefCell<RigidBody>>>,
+        b2:       Option<&Rc<RefCell<RigidBody>>>,
+        ids:      &HashMap<uint, uint, UintTWHash>,
+        ufind:    &mut [UnionFindSet],
+        grounded: &mut [bool]) {
+    match (dynamic_id(b1, ids), dynamic_id(b2, ids)) {
+        (Some(i1), Some(i2))              => union_find::union(i1, i2, ufind),
+        (Some(i), None) | (None, Some(i)) => grounded[i] = true,
+        (None, None)                      => { }
+    }
+}
+
+// The index of `b` in the world if it can move, or `None` if it is static or the ground.
+fn dynamic_id(b: Option<&Rc<RefCell<RigidBody>>>, ids: &HashMap<uint, uint, UintTWHash>) -> Option<uint> {
+    match b {
+        Some(b) if b.borrow().can_move() => ids.find(&(b.deref() as *const RefCell<RigidBody> as uint)).map(|i| *i),
+        _                                => None
+    }
+}
+
+// The bodies attached by a joint. `None` is the ground.
+fn joint_bodies(joint: &Constraint) -> (Option<Rc<RefCell<RigidBody>>>, Option<Rc<RefCell<RigidBody>>>) {
+    match *joint {
+        Constraint::RBRB(ref b1, ref b2, _) => (Some(b1.clone()), Some(b2.clone())),
+        Constraint::BallInSocket(ref b)     => (b.borrow().anchor1().body.clone(), b.borrow().anchor2().body.clone()),
+        Constraint::Fixed(ref f)            => (f.borrow().anchor1().body.clone(), f.borrow().anchor2().body.clone()),
+        Constraint::Distance(ref d)         => (d.borrow().anchor1().body.clone(), d.borrow().anchor2().body.clone()),
+        Constraint::Grab(ref g)             => (g.borrow().anchor1().body.clone(), g.borrow().anchor2().body.clone())
+    }
+}
+
+fn activate_dynamic(b: &Rc<RefCell<RigidBody>>, events: &mut Vec<WorldEvent>) {
+    if b.borrow().can_move() {
+        activate(b, events)
+    }
+}
+
+// Whether two bodies with `num_colls` contacts touch each other, i.e., neither is a sensor.
+fn touching(b1: &Rc<RefCell<RigidBody>>, b2: &Rc<RefCell<RigidBody>>, num_colls: uint) -> bool {
+    num_colls != 0 && !b1.borrow().is_sensor() && !b2.borrow().is_sensor()
+}
+
+fn contains(bodies: &[Rc<RefCell<RigidBody>>], b: &Rc<RefCell<RigidBody>>) -> bool {
+    bodies.iter().any(|e| e.deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody>)
+}
+
+fn activate(b: &Rc<RefCell<RigidBody>>, events: &mut Vec<WorldEvent>) {
+    let mut rb = b.borrow_mut();
+
+    match rb.deactivation_threshold() {
+        Some(threshold) => {
+            if !rb.is_active() {
+                events.push(WorldEvent::BodyActivated(b.clone()))
+            }
+
+            rb.activate(threshold * na::cast(2.0f64))
+        },
+        None => { }
+    }
+}
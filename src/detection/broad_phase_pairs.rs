@@ -0,0 +1,95 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use ncollide::broad_phase::ProximitySignalHandler;
+use object::{RigidBody, RigidBodyHandle};
+
+/// The pairs of bodies currently tracked by the broad phase.
+///
+/// A pair is tracked while the bounding volumes of its bodies, enlarged by the collision world
+/// margin, overlap and the pair is not filtered out. It is kept up to date from the proximity
+/// signals of the collision world, so reading it does not query the broad phase itself.
+pub struct BroadPhasePairs {
+    pairs: HashMap<(uint, uint), (RigidBodyHandle, RigidBodyHandle)>
+}
+
+impl BroadPhasePairs {
+    /// Creates an empty set of pairs.
+    pub fn new() -> BroadPhasePairs {
+        BroadPhasePairs {
+            pairs: HashMap::new()
+        }
+    }
+
+    /// The number of pairs tracked.
+    #[inline]
+    pub fn len(&self) -> uint {
+        self.pairs.len()
+    }
+
+    /// Whether the pair made of `b1` and `b2` is tracked, in any order.
+    pub fn contains(&self, b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> bool {
+        self.pairs.contains_key(&key(b1, b2))
+    }
+
+    /// Appends all the pairs tracked to `out`.
+    pub fn pairs(&self, out: &mut Vec<(RigidBodyHandle, RigidBodyHandle)>) {
+        for &(ref b1, ref b2) in self.pairs.values() {
+            out.push((b1.clone(), b2.clone()))
+        }
+    }
+
+    /// Calls `f` with each pair tracked.
+    pub fn for_each(&self, f: |&RigidBodyHandle, &RigidBodyHandle| -> ()) {
+        for &(ref b1, ref b2) in self.pairs.values() {
+            f(b1, b2)
+        }
+    }
+
+    /// Starts or stops tracking the pair made of `b1` and `b2`.
+    pub fn set(&mut self, b1: &RigidBodyHandle, b2: &RigidBodyHandle, tracked: bool) {
+        if tracked {
+            let _ = self.pairs.insert(key(b1, b2), (b1.clone(), b2.clone()));
+        }
+        else {
+            let _ = self.pairs.remove(&key(b1, b2));
+        }
+    }
+
+    /// Stops tracking all the pairs involving `b`.
+    pub fn remove(&mut self, b: &RigidBodyHandle) {
+        let id = b.deref() as *const RefCell<RigidBody> as uint;
+        let to_remove: Vec<(uint, uint)> = self.pairs.keys().filter(|&&(k1, k2)| k1 == id || k2 == id).map(|k| *k).collect();
+
+        for k in to_remove.iter() {
+            let _ = self.pairs.remove(k);
+        }
+    }
+}
+
+/// A proximity signal handler updating some shared `BroadPhasePairs`.
+pub struct BroadPhasePairsUpdater {
+    pairs: Rc<RefCell<BroadPhasePairs>>
+}
+
+impl BroadPhasePairsUpdater {
+    /// Creates a handler updating `pairs`.
+    pub fn new(pairs: Rc<RefCell<BroadPhasePairs>>) -> BroadPhasePairsUpdater {
+        BroadPhasePairsUpdater {
+            pairs: pairs
+        }
+    }
+}
+
+impl ProximitySignalHandler<RigidBodyHandle> for BroadPhasePairsUpdater {
+    fn handle_proximity(&mut self, b1: &RigidBodyHandle, b2: &RigidBodyHandle, started: bool) {
+        self.pairs.borrow_mut().set(b1, b2, started)
+    }
+}
+
+fn key(b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> (uint, uint) {
+    let k1 = b1.deref() as *const RefCell<RigidBody> as uint;
+    let k2 = b2.deref() as *const RefCell<RigidBody> as uint;
+
+    if k1 < k2 { (k1, k2) } else { (k2, k1) }
+}
@@ -4,7 +4,7 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use ncollide::geometry::Contact;
 use object::RigidBody;
-use detection::joint::{Fixed, BallInSocket};
+use detection::joint::{Fixed, BallInSocket, Distance, Grab};
 use math::{Scalar, Point, Vect};
 
 /// A constraint between two rigid bodies.
@@ -15,6 +15,10 @@ pub enum Constraint {
     BallInSocket(Rc<RefCell<BallInSocket>>),
     /// A fixed joint.
     Fixed(Rc<RefCell<Fixed>>),
+    /// A distance joint.
+    Distance(Rc<RefCell<Distance>>),
+    /// A grab joint.
+    Grab(Rc<RefCell<Grab>>),
 }
 
 impl Clone for Constraint {
@@ -23,6 +27,8 @@ impl Clone for Constraint {
             Constraint::RBRB(ref a, ref b, ref c) => Constraint::RBRB(a.clone(), b.clone(), c.clone()),
             Constraint::BallInSocket(ref bis) => Constraint::BallInSocket(bis.clone()),
             Constraint::Fixed(ref f) => Constraint::Fixed(f.clone()),
+            Constraint::Distance(ref d) => Constraint::Distance(d.clone()),
+            Constraint::Grab(ref g) => Constraint::Grab(g.clone()),
         }
     }
 }
@@ -0,0 +1,188 @@
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use std::collections::HashMap;
+use std::mem;
+use std::cell::RefCell;
+use na::Bounded;
+use na;
+use ncollide::shape::{Shape, Cylinder, Cone};
+use ncollide::geometry::Contact;
+use object::{RigidBody, RigidBodyHandle};
+use math::{Scalar, Point, Vect, Matrix};
+
+// A persisted contact is dropped when its points drift apart by more than this distance.
+static BREAKING_DISTANCE: f64 = 0.05;
+// A persisted contact is replaced by a new one closer than this distance.
+static MERGING_DISTANCE:  f64 = 0.1;
+
+struct LocalContact {
+    local1: Point,
+    local2: Point,
+    normal: Vect // In the local space of the first body.
+}
+
+struct Manifold {
+    contacts: Vec<LocalContact>,
+    // The first contact generated for this pair during the current update.
+    template: Contact<Scalar, Point, Vect>
+}
+
+/// Accumulates the contact points of curved shapes across several updates.
+///
+/// The narrow phase generates only one or two contact points per update between a cylinder or a
+/// cone and a flat surface. Keeping the points found during the previous updates, as long as they
+/// remain valid, gives a manifold covering the whole rim and lets those shapes rest without
+/// wobbling. Pairs involving other shapes are not affected.
+pub struct ContactAccumulator {
+    manifolds:      HashMap<(uint, uint), Manifold>,
+    prev_manifolds: HashMap<(uint, uint), Manifold>,
+    pairs:          Vec<((uint, uint), RigidBodyHandle, RigidBodyHandle)>
+}
+
+impl ContactAccumulator {
+    /// Creates a new contact accumulator.
+    pub fn new() -> ContactAccumulator {
+        ContactAccumulator {
+            manifolds:      HashMap::new(),
+            prev_manifolds: HashMap::new(),
+            pairs:          Vec::new()
+        }
+    }
+
+    /// Removes all the persisted contacts.
+    pub fn clear(&mut self) {
+        self.manifolds.clear();
+        self.prev_manifolds.clear();
+    }
+
+    /// Appends to `contacts` the still valid contacts persisted from the previous update.
+    ///
+    /// `contacts` must contain all the contacts generated by the narrow phase during the current
+    /// update. The pairs missing from this set are forgotten.
+    pub fn update(&mut self, contacts: &mut Vec<(RigidBodyHandle, RigidBodyHandle, Contact<Scalar, Point, Vect>)>) {
+        mem::swap(&mut self.manifolds, &mut self.prev_manifolds);
+        self.manifolds.clear();
+
+        for &(ref b1, ref b2, ref c) in contacts.iter() {
+            if !accumulates(b1.borrow().shape_ref()) && !accumulates(b2.borrow().shape_ref()) {
+                continue;
+            }
+
+            let key = key(b1, b2);
+
+            if !self.manifolds.contains_key(&key) {
+                let _ = self.manifolds.insert(key, Manifold {
+                    contacts: Vec::new(),
+                    template: c.clone()
+                });
+                self.pairs.push((key, b1.clone(), b2.clone()));
+            }
+
+            let manifold = self.manifolds.get_mut(&key).unwrap();
+            manifold.contacts.push(to_local(b1.borrow().deref(), b2.borrow().deref(), c));
+        }
+
+        let max_contacts  = 2 * na::dim::<Vect>() - 2;
+        let breaking_dist = na::cast::<f64, Scalar>(BREAKING_DISTANCE);
+        let merging_dist  = na::cast::<f64, Scalar>(MERGING_DISTANCE);
+
+        for &(ref key, ref b1, ref b2) in self.pairs.iter() {
+            let old = match self.prev_manifolds.remove(key) {
+                Some(old) => old,
+                None      => continue
+            };
+
+            let rb1      = b1.borrow();
+            let rb2      = b2.borrow();
+            let manifold = self.manifolds.get_mut(key).unwrap();
+            let num_new  = manifold.contacts.len();
+
+            if num_new >= max_contacts {
+                continue;
+            }
+
+            let mut survivors = Vec::new();
+
+            for lc in old.contacts.into_iter() {
+                let c = to_world(rb1.deref(), rb2.deref(), &lc, &manifold.template);
+
+                let tangent = (c.world1 - c.world2) - c.normal * c.depth;
+
+                if c.depth < -breaking_dist || na::sqnorm(&tangent) > breaking_dist * breaking_dist {
+                    continue;
+                }
+
+                let replaced = manifold.contacts.slice_to(num_new).iter().any(|new| {
+                    na::sqdist(&na::transform(rb1.position(), &new.local1), &c.world1) <
+                    merging_dist * merging_dist
+                });
+
+                if !replaced {
+                    survivors.push(lc);
+                }
+            }
+
+            // Keep the persisted contacts that are the most spread out.
+            while num_new + survivors.len() > max_contacts {
+                let mut worst    = 0;
+                let mut worst_sq = Bounded::max_value();
+
+                for (i, a) in survivors.iter().enumerate() {
+                    for (j, b) in manifold.contacts.iter().chain(survivors.iter()).enumerate() {
+                        if j != num_new + i {
+                            let sq = na::sqdist(&a.local1, &b.local1);
+
+                            if sq < worst_sq {
+                                worst    = i;
+                                worst_sq = sq;
+                            }
+                        }
+                    }
+                }
+
+                let _ = survivors.remove(worst);
+            }
+
+            for lc in survivors.into_iter() {
+                contacts.push((b1.clone(), b2.clone(), to_world(rb1.deref(), rb2.deref(), &lc, &manifold.template)));
+                manifold.contacts.push(lc);
+            }
+        }
+
+        self.pairs.clear();
+        self.prev_manifolds.clear();
+    }
+}
+
+fn accumulates(shape: &Shape<Scalar, Point, Vect, Matrix>) -> bool {
+    let tid = shape.get_type_id();
+
+    tid == TypeId::of::<Cylinder<Scalar>>() || tid == TypeId::of::<Cone<Scalar>>()
+}
+
+fn to_local(rb1: &RigidBody, rb2: &RigidBody, c: &Contact<Scalar, Point, Vect>) -> LocalContact {
+    LocalContact {
+        local1: na::inv_transform(rb1.position(), &c.world1),
+        local2: na::inv_transform(rb2.position(), &c.world2),
+        normal: na::inv_rotate(rb1.position(), &c.normal)
+    }
+}
+
+fn to_world(rb1:      &RigidBody,
+            rb2:      &RigidBody,
+            lc:       &LocalContact,
+            template: &Contact<Scalar, Point, Vect>)
+            -> Contact<Scalar, Point, Vect> {
+    let mut c = template.clone();
+
+    c.world1 = na::transform(rb1.position(), &lc.local1);
+    c.world2 = na::transform(rb2.position(), &lc.local2);
+    c.normal = na::rotate(rb1.position(), &lc.normal);
+    c.depth  = na::dot(&(c.world1 - c.world2), &c.normal);
+
+    c
+}
+
+fn key(b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> (uint, uint) {
+    (b1.deref() as *const RefCell<RigidBody> as uint, b2.deref() as *const RefCell<RigidBody> as uint)
+}
@@ -0,0 +1,261 @@
+use std::mem;
+use std::cell::RefCell;
+use std::intrinsics::TypeId;
+use std::collections::HashMap;
+use na::{Translation, Rotation, Transform, Rotate};
+use na;
+use ncollide::shape::Shape;
+use ncollide::geometry::Contact;
+use detection::BroadPhasePairs;
+use object::{RigidBody, RigidBodyHandle};
+use math::{Scalar, Point, Vect, Matrix};
+
+/// A user-defined contact generator for a specific pair of shape types.
+pub trait ContactGenerator {
+    /// Appends to `out` the contacts between the shape `s1` transformed by `m1` and the shape `s2`
+    /// transformed by `m2`.
+    ///
+    /// The shapes always have the types this generator was registered for, in the same order.
+    /// Contacts must be generated as long as the shapes are closer than `prediction`. Their normal
+    /// points toward the second shape, and their depth is the penetration depth of the shapes,
+    /// without the body margins, negative if they are apart.
+    fn generate(&mut self,
+                m1:         &Matrix,
+                s1:         &Shape<Scalar, Point, Vect, Matrix>,
+                m2:         &Matrix,
+                s2:         &Shape<Scalar, Point, Vect, Matrix>,
+                prediction: Scalar,
+                out:        &mut Vec<Contact<Scalar, Point, Vect>>);
+}
+
+// The contacts of a pair computed by a generator, in the local frames of its bodies.
+struct CachedContacts {
+    // The pose of the second body relative to the first one when the contacts were computed.
+    relative_pose: Matrix,
+    // The contact points on each body and the normal, in the local frame of the first body.
+    contacts:      Vec<(Point, Point, Vect)>,
+    visited:       bool
+}
+
+/// Dispatches the pairs of bodies to the user-defined contact generators.
+///
+/// A pair of bodies whose shape types match a registered generator, in any order, is ignored by
+/// the default narrow phase.
+pub struct ContactGenerators {
+    generators: HashMap<(TypeId, TypeId), Box<ContactGenerator + 'static>>,
+    prediction: Scalar,
+    contacts:   Vec<Contact<Scalar, Point, Vect>>,
+    // The maximum translation and rotation angle of the relative pose of two bodies for their
+    // cached contacts to be reused.
+    caching:    Option<(Scalar, Scalar)>,
+    cache:      HashMap<(uint, uint), CachedContacts>,
+    num_reused: uint
+}
+
+impl ContactGenerators {
+    /// Creates a dispatcher without any generator.
+    ///
+    /// The generators will be asked for the contacts closer than `prediction`.
+    pub fn new(prediction: Scalar) -> ContactGenerators {
+        assert!(prediction >= na::zero(), "The prediction distance must not be negative.");
+
+        ContactGenerators {
+            generators: HashMap::new(),
+            prediction: prediction,
+            contacts:   Vec::new(),
+            caching:    None,
+            cache:      HashMap::new(),
+            num_reused: 0
+        }
+    }
+
+    /// The maximum translation and rotation angle, since their contacts were generated, of the
+    /// relative pose of two bodies for their contacts to be reused.
+    #[inline]
+    pub fn caching(&self) -> Option<(Scalar, Scalar)> {
+        self.caching
+    }
+
+    /// Enables or disables the caching of the contacts of each pair.
+    ///
+    /// When the relative pose of the bodies of a pair moved by less than `max_translation` and
+    /// rotated by less than `max_angle` since its contacts were generated, the previous contacts
+    /// are moved with the bodies instead of asking the generator again. Their depth is updated
+    /// along their normal. Otherwise, the generator is called and its contacts are cached.
+    ///
+    /// This never caches the pairs left to the default narrow phase, i.e., the pairs without a
+    /// registered generator.
+    pub fn set_caching(&mut self, caching: Option<(Scalar, Scalar)>) {
+        match caching {
+            Some((ref max_translation, ref max_angle)) => {
+                assert!(*max_translation >= na::zero() && *max_angle >= na::zero(),
+                        "The contact caching tolerances must not be negative.");
+            },
+            None => { }
+        }
+
+        self.caching = caching;
+        self.cache.clear();
+    }
+
+    /// The number of pairs which cached contacts were reused by the last call to `generate`.
+    #[inline]
+    pub fn num_reused(&self) -> uint {
+        self.num_reused
+    }
+
+    /// Forgets the cached contacts of the pairs involving `b`, e.g., because its shape changed.
+    pub fn forget(&mut self, b: &RigidBodyHandle) {
+        let id = b.deref() as *const RefCell<RigidBody> as uint;
+        let to_remove: Vec<(uint, uint)> = self.cache.keys().filter(|&&(k1, k2)| k1 == id || k2 == id).map(|k| *k).collect();
+
+        for k in to_remove.iter() {
+            let _ = self.cache.remove(k);
+        }
+    }
+
+    /// Whether no generator is registered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+
+    /// Registers `generator` for the pairs of shapes of types `t1` and `t2`.
+    ///
+    /// It replaces any generator registered for the same types in the same order.
+    pub fn register(&mut self, t1: TypeId, t2: TypeId, generator: Box<ContactGenerator + 'static>) {
+        let _ = self.generators.insert((t1, t2), generator);
+        self.cache.clear();
+    }
+
+    /// Unregisters the generator for the pairs of shapes of types `t1` and `t2`.
+    pub fn unregister(&mut self, t1: TypeId, t2: TypeId) {
+        let _ = self.generators.remove(&(t1, t2));
+        self.cache.clear();
+    }
+
+    /// Whether the contacts between `b1` and `b2` are computed by a registered generator.
+    pub fn overrides(&self, b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> bool {
+        if self.generators.is_empty() {
+            return false;
+        }
+
+        let t1 = b1.borrow().shape_ref().get_type_id();
+        let t2 = b2.borrow().shape_ref().get_type_id();
+
+        self.generators.contains_key(&(t1, t2)) || self.generators.contains_key(&(t2, t1))
+    }
+
+    /// Calls `f` with each contact generated for the pairs of `pairs` having a registered
+    /// generator.
+    pub fn generate(&mut self,
+                    pairs: &BroadPhasePairs,
+                    f:     |&RigidBodyHandle, &RigidBodyHandle, &Contact<Scalar, Point, Vect>| -> ()) {
+        if self.generators.is_empty() {
+            return;
+        }
+
+        let generators = &mut self.generators;
+        let contacts   = &mut self.contacts;
+        let cache      = &mut self.cache;
+        let caching    = self.caching.clone();
+        let prediction = self.prediction.clone();
+        let mut reused = 0u;
+
+        pairs.for_each(|b1, b2| {
+            let rb1 = b1.borrow();
+            let rb2 = b2.borrow();
+            let t1  = rb1.shape_ref().get_type_id();
+            let t2  = rb2.shape_ref().get_type_id();
+
+            let swapped = !generators.contains_key(&(t1, t2));
+            let key     = if swapped { (t2, t1) } else { (t1, t2) };
+
+            let generator = match generators.get_mut(&key) {
+                Some(generator) => generator,
+                None            => return
+            };
+
+            let pair_key      = (b1.deref() as *const RefCell<RigidBody> as uint, b2.deref() as *const RefCell<RigidBody> as uint);
+            let relative_pose = na::inv(rb1.position()).unwrap() * rb2.position().clone();
+
+            let cached = match (caching, cache.get_mut(&pair_key)) {
+                (Some((ref max_translation, ref max_angle)), Some(entry)) => {
+                    let delta = na::inv(&entry.relative_pose).unwrap() * relative_pose;
+
+                    if na::norm(&delta.translation()) <= *max_translation &&
+                       na::norm(&delta.rotation()) <= *max_angle {
+                        entry.visited = true;
+
+                        for &(ref local1, ref local2, ref local_normal) in entry.contacts.iter() {
+                            let world1 = rb1.position().transform(local1);
+                            let world2 = rb2.position().transform(local2);
+                            let normal = rb1.position().rotate(local_normal);
+                            let depth  = na::dot(&(world1 - world2), &normal);
+
+                            contacts.push(Contact::new(world1, world2, normal, depth));
+                        }
+
+                        true
+                    }
+                    else {
+                        false
+                    }
+                },
+                _ => false
+            };
+
+            if cached {
+                reused = reused + 1;
+            }
+            else {
+                if swapped {
+                    generator.generate(rb2.position(), rb2.shape_ref(), rb1.position(), rb1.shape_ref(),
+                                       prediction.clone(), contacts);
+
+                    for c in contacts.iter_mut() {
+                        mem::swap(&mut c.world1, &mut c.world2);
+                        c.normal = -c.normal;
+                    }
+                }
+                else {
+                    generator.generate(rb1.position(), rb1.shape_ref(), rb2.position(), rb2.shape_ref(),
+                                       prediction.clone(), contacts);
+                }
+
+                if caching.is_some() {
+                    let local = contacts.iter().map(|c| {
+                        (rb1.position().inv_transform(&c.world1),
+                         rb2.position().inv_transform(&c.world2),
+                         rb1.position().inv_rotate(&c.normal))
+                    }).collect();
+
+                    let _ = cache.insert(pair_key, CachedContacts {
+                        relative_pose: relative_pose,
+                        contacts:      local,
+                        visited:       true
+                    });
+                }
+            }
+
+            for c in contacts.iter() {
+                f(b1, b2, c)
+            }
+
+            contacts.clear();
+        });
+
+        self.num_reused = reused;
+
+        // The pairs no longer tracked by the broad phase.
+        let stale: Vec<(uint, uint)> = self.cache.iter().filter(|&(_, e)| !e.visited).map(|(k, _)| *k).collect();
+
+        for k in stale.iter() {
+            let _ = self.cache.remove(k);
+        }
+
+        for e in self.cache.values_mut() {
+            e.visited = false;
+        }
+    }
+}
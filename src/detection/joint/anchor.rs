@@ -40,3 +40,11 @@ impl<P> Anchor<P> {
         }
     }
 }
+
+/// Tests whether `anchor` is attached to `body`.
+pub fn is_attached_to<P>(anchor: &Anchor<P>, body: &Rc<RefCell<RigidBody>>) -> bool {
+    match anchor.body {
+        Some(ref b) => b.deref() as *const RefCell<RigidBody> == body.deref() as *const RefCell<RigidBody>,
+        None        => false
+    }
+}
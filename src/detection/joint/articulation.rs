@@ -0,0 +1,140 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use na;
+use ncollide::utils::data::hash_map::HashMap;
+use ncollide::utils::data::hash::UintTWHash;
+use math::{Scalar, Point, Vect, Orientation};
+use detection::joint::anchor::is_attached_to;
+use detection::joint::joint::Joint;
+use detection::joint::ball_in_socket::BallInSocket;
+use object::{RigidBody, RigidBodyHandle};
+
+/// A tree of ball-in-socket joints, e.g., a robotic arm.
+///
+/// The first anchor of each joint is attached to the parent body, or to the ground for the root of
+/// the tree, and its second anchor to the child body. The subtree of a joint is its child with
+/// every body below it. Knowing them, the world can hold up the subtree of each joint which gravity
+/// compensation is enabled, see `BallInSocket::set_gravity_compensation`.
+pub struct Articulation {
+    joints: Vec<Rc<RefCell<BallInSocket>>>
+}
+
+impl Articulation {
+    /// Creates an articulation without any joint.
+    pub fn new() -> Articulation {
+        Articulation {
+            joints: Vec::new()
+        }
+    }
+
+    /// The joints of this articulation.
+    #[inline]
+    pub fn joints(&self) -> &[Rc<RefCell<BallInSocket>>] {
+        self.joints.as_slice()
+    }
+
+    /// Adds `joint` to this articulation, linking the body of its first anchor to the one of its
+    /// second anchor.
+    pub fn add_joint(&mut self, joint: &Rc<RefCell<BallInSocket>>) {
+        {
+            let j = joint.borrow();
+            let child = j.anchor2().body.as_ref().expect("The second anchor of an articulation joint must be attached to a body.");
+
+            assert!(!self.joints.iter().any(|other| is_attached_to(other.borrow().anchor2(), child)),
+                    "A body cannot be the child of two joints of an articulation.");
+        }
+
+        self.joints.push(joint.clone())
+    }
+
+    /// Removes `joint` from this articulation.
+    ///
+    /// The bodies below it are then not part of the subtree of the joints above it anymore.
+    pub fn remove_joint(&mut self, joint: &Rc<RefCell<BallInSocket>>) {
+        let key = joint.deref() as *const RefCell<BallInSocket>;
+
+        self.joints.retain(|j| j.deref() as *const RefCell<BallInSocket> != key)
+    }
+
+    /// Removes from this articulation every joint attached to `body`.
+    pub fn remove_joints_of(&mut self, body: &RigidBodyHandle) {
+        self.joints.retain(|j| {
+            let j = j.borrow();
+
+            !is_attached_to(j.anchor1(), body) && !is_attached_to(j.anchor2(), body)
+        })
+    }
+
+    // The bodies of the subtree of `child`, including itself.
+    fn subtree(&self, child: &RigidBodyHandle) -> Vec<RigidBodyHandle> {
+        let mut res = vec!(child.clone());
+        let mut i   = 0;
+
+        while i < res.len() {
+            let parent = res[i].clone();
+
+            for joint in self.joints.iter() {
+                let j = joint.borrow();
+
+                match j.anchor2().body {
+                    Some(ref b) if is_attached_to(j.anchor1(), &parent) &&
+                                   !res.iter().any(|other| body_key(other) == body_key(b)) => res.push(b.clone()),
+                    _ => { }
+                }
+            }
+
+            i = i + 1;
+        }
+
+        res
+    }
+
+    /// Adds to `torques` the torques holding up the subtree of each joint which gravity
+    /// compensation is enabled, under the gravity acceleration `gravity`.
+    ///
+    /// The child of the joint is given the opposite of the torque of the weight of the subtree
+    /// around the joint, and the parent the torque of the weight.
+    #[doc(hidden)]
+    pub fn compensation_torques(&self,
+                                gravity: |&Point| -> Vect,
+                                torques: &mut HashMap<uint, Orientation, UintTWHash>) {
+        for joint in self.joints.iter() {
+            let j = joint.borrow();
+
+            if !j.gravity_compensation() {
+                continue;
+            }
+
+            let child = j.anchor2().body.as_ref().unwrap();
+            let pivot = j.anchor2_pos();
+            let mut weight_torque: Orientation = na::zero();
+
+            for b in self.subtree(child).iter() {
+                let rb = b.borrow();
+
+                if rb.can_move() {
+                    let weight = gravity(rb.center_of_mass()) * (na::one::<Scalar>() / rb.inv_mass());
+
+                    weight_torque = weight_torque + na::cross(&(*rb.center_of_mass() - pivot), &weight);
+                }
+            }
+
+            add_torque(torques, child, -weight_torque);
+
+            match j.anchor1().body {
+                Some(ref parent) => add_torque(torques, parent, weight_torque),
+                None             => { }
+            }
+        }
+    }
+}
+
+fn add_torque(torques: &mut HashMap<uint, Orientation, UintTWHash>, body: &RigidBodyHandle, torque: Orientation) {
+    let t = torques.find_or_insert_lazy(body_key(body), || Some(na::zero())).unwrap();
+
+    *t = *t + torque
+}
+
+fn body_key(body: &RigidBodyHandle) -> uint {
+    body.deref() as *const RefCell<RigidBody> as uint
+}
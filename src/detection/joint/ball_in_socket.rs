@@ -1,27 +1,91 @@
-use na::Transform;
-use math::Point;
-use detection::joint::anchor::Anchor;
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Transform, Rotation};
+use na;
+use math::{Point, Orientation, Matrix};
+use detection::joint::anchor::{Anchor, is_attached_to};
 use detection::joint::joint::Joint;
+use object::RigidBody;
+use resolution::SpringDamper;
 
 /// A ball-in-socket joint.
 ///
 /// This is usually used to create ragdolls.
 pub struct BallInSocket {
-    up_to_date: bool,
-    anchor1:    Anchor<Point>,
-    anchor2:    Anchor<Point>,
+    up_to_date:           bool,
+    collision_enabled:    bool,
+    anchor1:              Anchor<Point>,
+    anchor2:              Anchor<Point>,
+    spring:               Option<SpringDamper>,
+    gravity_compensation: bool
 }
 
 impl BallInSocket {
     /// Creates a ball-in-socket joint.
     pub fn new(anchor1: Anchor<Point>, anchor2: Anchor<Point>) -> BallInSocket {
         BallInSocket {
-            up_to_date: false,
-            anchor1:    anchor1,
-            anchor2:    anchor2
+            up_to_date:           false,
+            collision_enabled:    false,
+            anchor1:              anchor1,
+            anchor2:              anchor2,
+            spring:               None,
+            gravity_compensation: false
         }
     }
 
+    /// Whether the two bodies attached to this joint can collide with each other.
+    ///
+    /// This is `false` by default.
+    #[inline]
+    pub fn collision_enabled(&self) -> bool {
+        self.collision_enabled
+    }
+
+    /// Enables or disables the contacts between the two bodies attached to this joint.
+    ///
+    /// Contacts are only generated between those bodies if they are allowed by their collision
+    /// groups and by every joint connecting them.
+    #[inline]
+    pub fn set_collision_enabled(&mut self, enabled: bool) {
+        self.collision_enabled = enabled
+    }
+
+    /// The spring-damper making this joint soft, if any.
+    #[inline]
+    pub fn spring(&self) -> Option<SpringDamper> {
+        self.spring.clone()
+    }
+
+    /// Sets the spring-damper pulling the anchors of this joint toward each other.
+    ///
+    /// With `None`, the default, the joint is rigid unless the world has a default joint
+    /// spring-damper, see `World::set_default_joint_spring`.
+    pub fn set_spring(&mut self, spring: Option<SpringDamper>) {
+        if spring != self.spring {
+            self.up_to_date = false;
+            self.spring     = spring
+        }
+    }
+
+    /// Whether this joint holds up the weight of the bodies below it.
+    ///
+    /// This is `false` by default.
+    #[inline]
+    pub fn gravity_compensation(&self) -> bool {
+        self.gravity_compensation
+    }
+
+    /// Enables or disables the gravity compensation of this joint.
+    ///
+    /// When enabled and the joint is part of an `Articulation` added to the world, the torque
+    /// needed to statically support the weight of the subtree of the joint is applied at each
+    /// step, before the force generators. A motor or a servo driving the joint, e.g., a
+    /// `TorsionSpring`, then only has to move the bodies instead of fighting gravity.
+    #[inline]
+    pub fn set_gravity_compensation(&mut self, enabled: bool) {
+        self.gravity_compensation = enabled
+    }
+
     /// Tells if this joint has been modified by the user.
     pub fn up_to_date(&self) -> bool {
         self.up_to_date
@@ -51,6 +115,34 @@ impl BallInSocket {
             self.anchor2.position = local2
         }
     }
+
+    /// The rotation of the second attached body relatively to the first one.
+    ///
+    /// This is expressed as an axis-angle vector in the local space of the first body, or of the
+    /// ground if the first anchor is not attached to any body. For example, the swing angle of a
+    /// pendulum hanging from the ground is the norm of this vector if its body was created
+    /// aligned with the ground.
+    pub fn relative_rotation(&self) -> Orientation {
+        let m1 = body_position(&self.anchor1);
+        let m2 = body_position(&self.anchor2);
+
+        (na::inv(&m1).expect("A body position must be invertible.") * m2).rotation()
+    }
+
+    /// Attaches to the ground every anchor of this joint attached to `body`.
+    ///
+    /// The detached anchors keep their current position, expressed in global coordinates.
+    pub fn detach(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        if is_attached_to(&self.anchor1, body) {
+            self.anchor1 = Anchor::new(None, self.anchor1_pos());
+            self.up_to_date = false;
+        }
+
+        if is_attached_to(&self.anchor2, body) {
+            self.anchor2 = Anchor::new(None, self.anchor2_pos());
+            self.up_to_date = false;
+        }
+    }
 }
 
 
@@ -89,3 +181,10 @@ impl Joint<Point> for BallInSocket {
         }
     }
 }
+
+fn body_position(anchor: &Anchor<Point>) -> Matrix {
+    match anchor.body {
+        Some(ref b) => b.borrow().position().clone(),
+        None        => na::one()
+    }
+}
@@ -0,0 +1,237 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::Transform;
+use na;
+use math::{Scalar, Point};
+use detection::joint::anchor::{Anchor, is_attached_to};
+use detection::joint::joint::Joint;
+use object::RigidBody;
+use resolution::SpringDamper;
+
+/// How the length of a `Distance` joint is enforced.
+#[deriving(Clone, Show, PartialEq)]
+pub enum DistanceMode {
+    /// The distance between the anchors is kept equal to the joint length, like with a rod.
+    Rigid,
+    /// The distance between the anchors is only prevented from exceeding the joint length, like
+    /// with a rope.
+    MaxDistance
+}
+
+/// A joint that constrains the distance between two points.
+///
+/// Unlike a ball-in-socket joint, the relative rotation of the bodies is free and only the
+/// distance between the anchors is constrained. A short rope made of several of those joints is
+/// typically used to build bridges. A soft joint of zero length with a bounded force pulls a body
+/// toward a point, e.g., to drag it with the mouse.
+pub struct Distance {
+    up_to_date:        bool,
+    collision_enabled: bool,
+    anchor1:           Anchor<Point>,
+    anchor2:           Anchor<Point>,
+    length:            Scalar,
+    mode:              DistanceMode,
+    softness:          Scalar,
+    max_force:         Option<Scalar>,
+    spring:            Option<SpringDamper>
+}
+
+impl Distance {
+    /// Creates a rigid distance joint keeping the anchors at the distance `length`.
+    pub fn new(anchor1: Anchor<Point>, anchor2: Anchor<Point>, length: Scalar) -> Distance {
+        assert!(length >= na::zero(), "The length of a distance joint must be positive.");
+
+        Distance {
+            up_to_date:        false,
+            collision_enabled: false,
+            anchor1:           anchor1,
+            anchor2:           anchor2,
+            length:            length,
+            mode:              DistanceMode::Rigid,
+            softness:          na::zero(),
+            max_force:         None,
+            spring:            None
+        }
+    }
+
+    /// Tells if this joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Whether the two bodies attached to this joint can collide with each other.
+    ///
+    /// This is `false` by default.
+    #[inline]
+    pub fn collision_enabled(&self) -> bool {
+        self.collision_enabled
+    }
+
+    /// Enables or disables the contacts between the two bodies attached to this joint.
+    #[inline]
+    pub fn set_collision_enabled(&mut self, enabled: bool) {
+        self.collision_enabled = enabled
+    }
+
+    /// The distance enforced between the two anchors.
+    #[inline]
+    pub fn length(&self) -> Scalar {
+        self.length.clone()
+    }
+
+    /// Sets the distance enforced between the two anchors.
+    pub fn set_length(&mut self, length: Scalar) {
+        assert!(length >= na::zero(), "The length of a distance joint must be positive.");
+
+        if length != self.length {
+            self.up_to_date = false;
+            self.length     = length
+        }
+    }
+
+    /// How the length of this joint is enforced.
+    #[inline]
+    pub fn mode(&self) -> DistanceMode {
+        self.mode.clone()
+    }
+
+    /// Sets how the length of this joint is enforced.
+    pub fn set_mode(&mut self, mode: DistanceMode) {
+        if mode != self.mode {
+            self.up_to_date = false;
+            self.mode       = mode
+        }
+    }
+
+    /// The softness of this joint.
+    #[inline]
+    pub fn softness(&self) -> Scalar {
+        self.softness.clone()
+    }
+
+    /// Sets the softness of this joint, in `[0, 1[`.
+    ///
+    /// This is the fraction of the velocity and length errors left uncorrected by each step. A
+    /// rigid joint has a softness of zero, the default. Higher values make the joint springy.
+    pub fn set_softness(&mut self, softness: Scalar) {
+        assert!(softness >= na::zero() && softness < na::one(),
+                "The softness of a distance joint must be in [0, 1[.");
+
+        self.softness = softness
+    }
+
+    /// The maximum force this joint can apply, if any.
+    #[inline]
+    pub fn max_force(&self) -> Option<Scalar> {
+        self.max_force.clone()
+    }
+
+    /// Sets the maximum force this joint can apply.
+    ///
+    /// Set it to `None`, the default, for an unbounded force.
+    #[inline]
+    pub fn set_max_force(&mut self, max_force: Option<Scalar>) {
+        self.max_force = max_force
+    }
+
+    /// The spring-damper making this joint soft, if any.
+    #[inline]
+    pub fn spring(&self) -> Option<SpringDamper> {
+        self.spring.clone()
+    }
+
+    /// Sets the spring-damper acting along the line between the anchors.
+    ///
+    /// It replaces the correction of the length error, and the `softness` still scales its
+    /// objective. With `None`, the default, the world default is used, see
+    /// `World::set_default_joint_spring`.
+    pub fn set_spring(&mut self, spring: Option<SpringDamper>) {
+        if spring != self.spring {
+            self.up_to_date = false;
+            self.spring     = spring
+        }
+    }
+
+    /// The current distance between the two anchors.
+    pub fn distance(&self) -> Scalar {
+        na::dist(&self.anchor1_pos(), &self.anchor2_pos())
+    }
+
+    /// Sets the the first anchor position.
+    ///
+    /// The position is expressed in the first attached body’s local coordinates, or in global
+    /// coordinates if it is attached to the ground.
+    pub fn set_local1(&mut self, local1: Point) {
+        if local1 != self.anchor1.position {
+            self.up_to_date = false;
+            self.anchor1.position = local1
+        }
+    }
+
+    /// Sets the the second anchor position.
+    ///
+    /// The position is expressed in the second attached body’s local coordinates, or in global
+    /// coordinates if it is attached to the ground.
+    pub fn set_local2(&mut self, local2: Point) {
+        if local2 != self.anchor2.position {
+            self.up_to_date = false;
+            self.anchor2.position = local2
+        }
+    }
+
+    /// Attaches to the ground every anchor of this joint attached to `body`.
+    ///
+    /// The detached anchors keep their current position, expressed in global coordinates.
+    pub fn detach(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        if is_attached_to(&self.anchor1, body) {
+            self.anchor1 = Anchor::new(None, self.anchor1_pos());
+            self.up_to_date = false;
+        }
+
+        if is_attached_to(&self.anchor2, body) {
+            self.anchor2 = Anchor::new(None, self.anchor2_pos());
+            self.up_to_date = false;
+        }
+    }
+}
+
+impl Joint<Point> for Distance {
+    /// The first anchor affected by this joint.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<Point> {
+        &self.anchor1
+    }
+
+    /// The second anchor affected by this joint.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<Point> {
+        &self.anchor2
+    }
+
+    /// The first attach point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Point {
+        match self.anchor1.body {
+            Some(ref b) => {
+                b.borrow().position().transform(&self.anchor1.position)
+            },
+            None => self.anchor1.position.clone()
+        }
+    }
+
+    /// The second attach point in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Point {
+        match self.anchor2.body {
+            Some(ref b) => {
+                b.borrow().position().transform(&self.anchor2.position)
+            },
+            None => self.anchor2.position.clone()
+        }
+    }
+}
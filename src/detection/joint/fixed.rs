@@ -1,21 +1,64 @@
+use std::rc::Rc;
+use std::cell::RefCell;
 use math::Matrix;
-use detection::joint::anchor::Anchor;
+use detection::joint::anchor::{Anchor, is_attached_to};
 use detection::joint::joint::Joint;
+use object::RigidBody;
+use resolution::SpringDamper;
 
 /// A joint that prevents any relative movement (linear and angular) between two objects.
 pub struct Fixed {
-    up_to_date: bool,
-    anchor1:    Anchor<Matrix>,
-    anchor2:    Anchor<Matrix>,
+    up_to_date:        bool,
+    collision_enabled: bool,
+    anchor1:           Anchor<Matrix>,
+    anchor2:           Anchor<Matrix>,
+    spring:            Option<SpringDamper>
 }
 
 impl Fixed {
     /// Creates a new `Fixed` joint.
     pub fn new(anchor1: Anchor<Matrix>, anchor2: Anchor<Matrix>) -> Fixed {
         Fixed {
-            up_to_date: false,
-            anchor1:    anchor1,
-            anchor2:    anchor2
+            up_to_date:        false,
+            collision_enabled: false,
+            anchor1:           anchor1,
+            anchor2:           anchor2,
+            spring:            None
+        }
+    }
+
+    /// Whether the two bodies attached to this joint can collide with each other.
+    ///
+    /// This is `false` by default.
+    #[inline]
+    pub fn collision_enabled(&self) -> bool {
+        self.collision_enabled
+    }
+
+    /// Enables or disables the contacts between the two bodies attached to this joint.
+    ///
+    /// Contacts are only generated between those bodies if they are allowed by their collision
+    /// groups and by every joint connecting them.
+    #[inline]
+    pub fn set_collision_enabled(&mut self, enabled: bool) {
+        self.collision_enabled = enabled
+    }
+
+    /// The spring-damper making this joint soft, if any.
+    #[inline]
+    pub fn spring(&self) -> Option<SpringDamper> {
+        self.spring.clone()
+    }
+
+    /// Sets the spring-damper making this joint soft.
+    ///
+    /// Its stiffness applies to both the relative translation and the relative rotation of the
+    /// anchors. With `None`, the default, the world default is used, see
+    /// `World::set_default_joint_spring`.
+    pub fn set_spring(&mut self, spring: Option<SpringDamper>) {
+        if spring != self.spring {
+            self.up_to_date = false;
+            self.spring     = spring
         }
     }
 
@@ -48,6 +91,21 @@ impl Fixed {
             self.anchor2.position = local2
         }
     }
+
+    /// Attaches to the ground every anchor of this joint attached to `body`.
+    ///
+    /// The detached anchors keep their current position, expressed in global coordinates.
+    pub fn detach(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        if is_attached_to(&self.anchor1, body) {
+            self.anchor1 = Anchor::new(None, self.anchor1_pos());
+            self.up_to_date = false;
+        }
+
+        if is_attached_to(&self.anchor2, body) {
+            self.anchor2 = Anchor::new(None, self.anchor2_pos());
+            self.up_to_date = false;
+        }
+    }
 }
 
 impl Joint<Matrix> for Fixed {
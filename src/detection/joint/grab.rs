@@ -0,0 +1,158 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::Transform;
+use na;
+use math::{Scalar, Point};
+use detection::joint::anchor::{Anchor, is_attached_to};
+use detection::joint::joint::Joint;
+use object::RigidBody;
+use resolution::SpringDamper;
+
+/// A joint pulling a point of a body toward a target moving in global coordinates.
+///
+/// This is meant to carry a body around, e.g., following the mouse or the hand of a character: the
+/// target is updated each frame with `set_target`, and the joint is solved together with the
+/// contacts so that the body is not pushed through the others. A bounded force keeps a body stuck
+/// behind an obstacle from dragging the whole scene along. The second anchor of this joint is the
+/// target, attached to the ground.
+pub struct Grab {
+    up_to_date: bool,
+    anchor1:    Anchor<Point>,
+    anchor2:    Anchor<Point>,
+    max_force:  Option<Scalar>,
+    spring:     Option<SpringDamper>
+}
+
+impl Grab {
+    /// Creates a joint pulling the point `local_point`, in the local coordinates of `body`, toward
+    /// `target`, in global coordinates.
+    pub fn new(body: Rc<RefCell<RigidBody>>, local_point: Point, target: Point) -> Grab {
+        Grab {
+            up_to_date: false,
+            anchor1:    Anchor::new(Some(body), local_point),
+            anchor2:    Anchor::new(None, target),
+            max_force:  None,
+            spring:     None
+        }
+    }
+
+    /// Tells if this joint has been modified by the user.
+    pub fn up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    #[doc(hidden)]
+    pub fn update(&mut self) {
+        self.up_to_date = true
+    }
+
+    /// Whether the grabbed body can collide with the ground.
+    ///
+    /// This is always `true` since the target is not a body.
+    #[inline]
+    pub fn collision_enabled(&self) -> bool {
+        true
+    }
+
+    /// The point pulled toward the target, in the local coordinates of the grabbed body.
+    #[inline]
+    pub fn local_point(&self) -> Point {
+        self.anchor1.position.clone()
+    }
+
+    /// The point the grabbed body is pulled toward, in global coordinates.
+    #[inline]
+    pub fn target(&self) -> Point {
+        self.anchor2.position.clone()
+    }
+
+    /// Moves the point the grabbed body is pulled toward.
+    ///
+    /// This wakes the grabbed body up if the target changed.
+    pub fn set_target(&mut self, target: Point) {
+        if target != self.anchor2.position {
+            self.up_to_date       = false;
+            self.anchor2.position = target
+        }
+    }
+
+    /// The maximum force this joint can apply, if any.
+    #[inline]
+    pub fn max_force(&self) -> Option<Scalar> {
+        self.max_force.clone()
+    }
+
+    /// Sets the maximum force this joint can apply.
+    ///
+    /// The force is bounded on each axis so that its norm never exceeds `max_force`. Set it to
+    /// `None`, the default, for an unbounded force.
+    pub fn set_max_force(&mut self, max_force: Option<Scalar>) {
+        match max_force {
+            Some(ref f) => assert!(*f >= na::zero(), "The maximum force of a grab joint must not be negative."),
+            None        => { }
+        }
+
+        if max_force != self.max_force {
+            self.up_to_date = false;
+            self.max_force  = max_force
+        }
+    }
+
+    /// The stiffness and damping pulling the body toward the target, if any.
+    #[inline]
+    pub fn spring(&self) -> Option<SpringDamper> {
+        self.spring.clone()
+    }
+
+    /// Sets the stiffness and damping pulling the body toward the target.
+    ///
+    /// Without a spring-damper, the default, the world default joint spring is used, or the body
+    /// is moved to the target as fast as a rigid joint would, see `World::set_default_joint_spring`.
+    pub fn set_spring(&mut self, spring: Option<SpringDamper>) {
+        if spring != self.spring {
+            self.up_to_date = false;
+            self.spring     = spring
+        }
+    }
+
+    /// Attaches the grabbed point to the ground if `body` is the grabbed body.
+    ///
+    /// The detached anchor keeps its current position, expressed in global coordinates.
+    pub fn detach(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        if is_attached_to(&self.anchor1, body) {
+            self.anchor1    = Anchor::new(None, self.anchor1_pos());
+            self.up_to_date = false;
+        }
+    }
+}
+
+impl Joint<Point> for Grab {
+    /// The anchor on the grabbed body.
+    #[inline]
+    fn anchor1(&self) -> &Anchor<Point> {
+        &self.anchor1
+    }
+
+    /// The target, attached to the ground.
+    #[inline]
+    fn anchor2(&self) -> &Anchor<Point> {
+        &self.anchor2
+    }
+
+    /// The grabbed point in global coordinates.
+    #[inline]
+    fn anchor1_pos(&self) -> Point {
+        match self.anchor1.body {
+            Some(ref b) => {
+                b.borrow().position().transform(&self.anchor1.position)
+            },
+            None => self.anchor1.position.clone()
+        }
+    }
+
+    /// The target in global coordinates.
+    #[inline]
+    fn anchor2_pos(&self) -> Point {
+        self.anchor2.position.clone()
+    }
+}
@@ -7,22 +7,36 @@ use detection::activation_manager::ActivationManager;
 use detection::detector::Detector;
 use detection::joint::ball_in_socket::BallInSocket;
 use detection::joint::fixed::Fixed;
+use detection::joint::distance::Distance;
+use detection::joint::grab::Grab;
 use detection::joint::joint::Joint;
+use detection::joint::anchor::{Anchor, is_attached_to};
 use detection::constraint::Constraint;
 use object::RigidBody;
 
+/// Signal handler for joint removal events.
+pub trait JointSignalHandler {
+    /// Activate an action when a joint is removed from the world.
+    ///
+    /// This is called for joints removed explicitly as well as for joints removed because one of
+    /// their bodies has been removed.
+    fn handle_joint_removed(&mut self, joint: &Constraint);
+}
+
 /// Structure that handles creation and removal of joints.
 pub struct JointManager {
-    joints:      HashMap<uint, Constraint, UintTWHash>,
-    body2joints: HashMap<uint, Vec<Constraint>, UintTWHash>
+    joints:          HashMap<uint, Constraint, UintTWHash>,
+    body2joints:     HashMap<uint, Vec<Constraint>, UintTWHash>,
+    signal_handlers: Vec<(String, Box<JointSignalHandler + 'static>)>
 }
 
 impl JointManager {
     /// Creates a new `JointManager`.
     pub fn new() -> JointManager {
         JointManager {
-            joints:      HashMap::new(UintTWHash::new()),
-            body2joints: HashMap::new(UintTWHash::new())
+            joints:          HashMap::new(UintTWHash::new()),
+            body2joints:     HashMap::new(UintTWHash::new()),
+            signal_handlers: Vec::new()
         }
     }
 
@@ -38,6 +52,39 @@ impl JointManager {
         self.body2joints.find(&(body.deref() as *const RefCell<RigidBody> as uint)).map(|v| v.as_slice())
     }
 
+    /// Whether the contacts between `b1` and `b2` are disabled by a joint connecting them.
+    ///
+    /// This is the case if any of the joints attaching one of those bodies to the other does not
+    /// have its collisions enabled. Joints attaching a body to the ground or to a third body have
+    /// no effect. Since the joints of a body are tracked from their addition to their removal,
+    /// this filter follows the lifetime of the joints.
+    pub fn collision_disabled(&self, b1: &Rc<RefCell<RigidBody>>, b2: &Rc<RefCell<RigidBody>>) -> bool {
+        let joints = match self.joints_with_body(b1) {
+            Some(js) => js,
+            None     => return false
+        };
+
+        joints.iter().any(|joint| {
+            match *joint {
+                Constraint::BallInSocket(ref bis) => {
+                    let bbis = bis.borrow();
+                    !bbis.collision_enabled() && connects(bbis.anchor1(), bbis.anchor2(), b1, b2)
+                },
+                Constraint::Fixed(ref f) => {
+                    let bf = f.borrow();
+                    !bf.collision_enabled() && connects(bf.anchor1(), bf.anchor2(), b1, b2)
+                },
+                Constraint::Distance(ref d) => {
+                    let bd = d.borrow();
+                    !bd.collision_enabled() && connects(bd.anchor1(), bd.anchor2(), b1, b2)
+                },
+                // The target is not a body.
+                Constraint::Grab(_) => false,
+                Constraint::RBRB(_, _, _) => panic!("Internal error: a contact RBRB should not be here.")
+            }
+        })
+    }
+
     /// Add a `BallInSocket` joint to this manager.
     ///
     /// This will force the activation of the two objects attached to the joint.
@@ -72,10 +119,7 @@ impl JointManager {
     ///
     /// This will force the activation of the two objects attached to the joint.
     pub fn remove_ball_in_socket(&mut self, joint: &Rc<RefCell<BallInSocket>>, activation: &mut ActivationManager) {
-        if self.joints.remove(&(joint.deref() as *const RefCell<BallInSocket> as uint)) {
-            let _  = joint.borrow().anchor1().body.as_ref().map(|b| activation.will_activate(b));
-            let _  = joint.borrow().anchor2().body.as_ref().map(|b| activation.will_activate(b));
-        }
+        self.remove_joint(joint, activation)
     }
 
     /// Add a `Fixed` joint to this manager.
@@ -105,43 +149,123 @@ impl JointManager {
         }
     }
 
+    /// Add a `Distance` joint to this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint.
+    pub fn add_distance(&mut self, joint: Rc<RefCell<Distance>>, activation: &mut ActivationManager) {
+        if self.joints.insert(joint.deref() as *const RefCell<Distance> as uint, Constraint::Distance(joint.clone())) {
+            match joint.borrow().anchor1().body.as_ref() {
+                Some(b) => {
+                    activation.will_activate(b);
+                    let js = self.body2joints.find_or_insert_lazy(b.deref() as *const RefCell<RigidBody> as uint,
+                                                                  || Some(Vec::new()));
+                    js.unwrap().push(Constraint::Distance(joint.clone()));
+                },
+                _ => { }
+            }
+
+            match joint.borrow().anchor2().body.as_ref() {
+                Some(b) => {
+                    activation.will_activate(b);
+                    let js = self.body2joints.find_or_insert_lazy(b.deref() as *const RefCell<RigidBody> as uint,
+                                                                  || Some(Vec::new()));
+                    js.unwrap().push(Constraint::Distance(joint.clone()));
+                },
+                _ => { }
+            }
+        }
+    }
+
+    /// Add a `Grab` joint to this manager.
+    ///
+    /// This will force the activation of the grabbed object.
+    pub fn add_grab(&mut self, joint: Rc<RefCell<Grab>>, activation: &mut ActivationManager) {
+        if self.joints.insert(joint.deref() as *const RefCell<Grab> as uint, Constraint::Grab(joint.clone())) {
+            match joint.borrow().anchor1().body.as_ref() {
+                Some(b) => {
+                    activation.will_activate(b);
+                    let js = self.body2joints.find_or_insert_lazy(b.deref() as *const RefCell<RigidBody> as uint,
+                                                                  || Some(Vec::new()));
+                    js.unwrap().push(Constraint::Grab(joint.clone()));
+                },
+                _ => { }
+            }
+        }
+    }
+
     /// Removes a joint from this manager.
     ///
     /// This will force the activation of the two objects attached to the joint.
     pub fn remove_joint<T: Joint<M>, M>(&mut self,
                                         joint:      &Rc<RefCell<T>>,
                                         activation: &mut ActivationManager) {
-        if self.joints.remove(&(joint.deref() as *const RefCell<T> as uint)) {
-            self.remove_joint_for_body(joint, joint.borrow().anchor1().body.as_ref(), activation);
-            self.remove_joint_for_body(joint, joint.borrow().anchor2().body.as_ref(), activation);
+        let constraint = self.joints.find(&(joint.deref() as *const RefCell<T> as uint)).map(|c| c.clone());
+
+        match constraint {
+            Some(c) => self.remove_constraint(&c, activation),
+            None    => { }
         }
     }
 
-    fn remove_joint_for_body<T: Joint<M>, M>(&mut self,
-                                             joint:      &Rc<RefCell<T>>,
-                                             body:       Option<&Rc<RefCell<RigidBody>>>,
-                                             activation: &mut ActivationManager) {
+    /// Removes a joint, given as a `Constraint`, from this manager.
+    ///
+    /// This will force the activation of the two objects attached to the joint, and call every
+    /// registered joint signal handler.
+    pub fn remove_constraint(&mut self, joint: &Constraint, activation: &mut ActivationManager) {
+        if !self.joints.remove(&joint_key(joint)) {
+            return;
+        }
+
+        match *joint {
+            Constraint::BallInSocket(ref bis) => {
+                let bbis = bis.borrow();
+                self.remove_joint_for_body(joint, bbis.anchor1().body.as_ref(), activation);
+                self.remove_joint_for_body(joint, bbis.anchor2().body.as_ref(), activation);
+            },
+            Constraint::Fixed(ref f) => {
+                let bf = f.borrow();
+                self.remove_joint_for_body(joint, bf.anchor1().body.as_ref(), activation);
+                self.remove_joint_for_body(joint, bf.anchor2().body.as_ref(), activation);
+            },
+            Constraint::Distance(ref d) => {
+                let bd = d.borrow();
+                self.remove_joint_for_body(joint, bd.anchor1().body.as_ref(), activation);
+                self.remove_joint_for_body(joint, bd.anchor2().body.as_ref(), activation);
+            },
+            Constraint::Grab(ref g) => {
+                self.remove_joint_for_body(joint, g.borrow().anchor1().body.as_ref(), activation);
+            },
+            Constraint::RBRB(_, _, _) => panic!("A contact is not a joint.")
+        }
+
+        for handler in self.signal_handlers.iter_mut() {
+            let (_, ref mut handler) = *handler;
+            handler.handle_joint_removed(joint)
+        }
+    }
+
+    fn remove_joint_for_body(&mut self,
+                             joint:      &Constraint,
+                             body:       Option<&Rc<RefCell<RigidBody>>>,
+                             activation: &mut ActivationManager) {
         match body {
             Some(b) => {
                 activation.will_activate(b);
                 let key = b.deref() as *const RefCell<RigidBody> as uint;
+                let mut empty = false;
+
                 match self.body2joints.find_mut(&key) {
                     Some(ref mut js) => {
-                        let jkey = joint.deref() as *const RefCell<T>;
-                        js.retain(|j| {
-                            // we do not know the type of the joint, so cast it to uint for
-                            // comparison.
-                            let id = match *j {
-                                Constraint::RBRB(_, _, _) => ptr::null::<uint>() as uint,
-                                Constraint::BallInSocket(ref b) => b.deref() as *const RefCell<BallInSocket> as uint,
-                                Constraint::Fixed(ref f) => f.deref() as *const RefCell<Fixed> as uint
-                            };
-
-                            id != jkey as uint
-                        });
+                        let jkey = joint_key(joint);
+                        js.retain(|j| joint_key(j) != jkey);
+                        empty = js.is_empty();
                     }
                     None => { }
                 }
+
+                if empty {
+                    let _ = self.body2joints.remove(&key);
+                }
             }
             None => { }
         }
@@ -151,35 +275,76 @@ impl JointManager {
     ///
     /// This will force the activation of every object attached to the deleted joints.
     pub fn remove(&mut self, b: &Rc<RefCell<RigidBody>>, activation: &mut ActivationManager) {
-        for joints in self.body2joints.get_and_remove(&(b.deref() as *const RefCell<RigidBody> as uint)).iter() {
-            for joint in joints.value.iter() {
-                fn do_remove<T: Joint<M>, M>(_self:      &mut JointManager,
-                                             joint:      &Rc<RefCell<T>>,
-                                             b:          &Rc<RefCell<RigidBody>>,
-                                             activation: &mut ActivationManager) {
-                    let bj    = joint.borrow();
-                    let body1 = bj.anchor1().body.as_ref();
-                    let body2 = bj.anchor2().body.as_ref();
-
-                    for body in bj.anchor1().body.as_ref().iter() {
-                        if (*body).deref() as *const RefCell<RigidBody> == b.deref() as *const RefCell<RigidBody> {
-                            _self.remove_joint_for_body(joint, body2, activation);
-                        }
-                        else {
-                            _self.remove_joint_for_body(joint, body1, activation);
-                        }
-                    }
-                }
+        let joints = match self.joints_with_body(b) {
+            Some(js) => js.to_vec(),
+            None     => return
+        };
 
-                match *joint {
-                    Constraint::BallInSocket(ref bis) => do_remove(self, bis, b, activation),
-                    Constraint::Fixed(ref f)          => do_remove(self, f, b, activation),
-                    Constraint::RBRB(_, _, _) => panic!("Internal error: a contact RBRB should not be here.")
-                }
+        for joint in joints.iter() {
+            self.remove_constraint(joint, activation)
+        }
+    }
+
+    /// Detaches every joint from a given rigid body.
+    ///
+    /// The anchors on `b` are attached to the ground instead, at their current position. Joints
+    /// that end up attached to the ground only are removed.
+    pub fn detach(&mut self, b: &Rc<RefCell<RigidBody>>, activation: &mut ActivationManager) {
+        let joints = match self.joints_with_body(b) {
+            Some(js) => js.to_vec(),
+            None     => return
+        };
+
+        let key = b.deref() as *const RefCell<RigidBody> as uint;
+        let _   = self.body2joints.remove(&key);
+
+        for joint in joints.iter() {
+            activation.will_activate(b);
+
+            let grounded = match *joint {
+                Constraint::BallInSocket(ref bis) => {
+                    let mut bbis = bis.borrow_mut();
+                    bbis.detach(b);
+                    bbis.anchor1().body.is_none() && bbis.anchor2().body.is_none()
+                },
+                Constraint::Fixed(ref f) => {
+                    let mut bf = f.borrow_mut();
+                    bf.detach(b);
+                    bf.anchor1().body.is_none() && bf.anchor2().body.is_none()
+                },
+                Constraint::Distance(ref d) => {
+                    let mut bd = d.borrow_mut();
+                    bd.detach(b);
+                    bd.anchor1().body.is_none() && bd.anchor2().body.is_none()
+                },
+                Constraint::Grab(ref g) => {
+                    let mut bg = g.borrow_mut();
+                    bg.detach(b);
+                    bg.anchor1().body.is_none()
+                },
+                Constraint::RBRB(_, _, _) => panic!("Internal error: a contact RBRB should not be here.")
+            };
+
+            if grounded {
+                self.remove_constraint(joint, activation)
             }
         }
     }
 
+    /// Registers a handler for joint removal events.
+    ///
+    /// If another handler with the same name already exists, it is replaced.
+    pub fn register_joint_signal_handler<H>(&mut self, name: &str, handler: H)
+        where H: JointSignalHandler + 'static {
+        self.unregister_joint_signal_handler(name);
+        self.signal_handlers.push((name.to_string(), box handler as Box<JointSignalHandler + 'static>))
+    }
+
+    /// Unregisters a handler for joint removal events.
+    pub fn unregister_joint_signal_handler(&mut self, name: &str) {
+        self.signal_handlers.retain(|&(ref n, _)| n.as_slice() != name)
+    }
+
     // FIXME: do we really want to handle this here instead of in the activation manager directly?
     /// Activates the objects that interact with an activated object through a joint.
     pub fn update(&mut self, activation: &mut ActivationManager) {
@@ -215,6 +380,32 @@ impl JointManager {
                         }
                     }
                 },
+                Constraint::Distance(ref d) => {
+                    let mut bd = d.borrow_mut();
+                    if !bd.up_to_date() {
+                        // the joint has been invalidated by the user: wake up the attached bodies
+                        bd.update();
+                        match bd.anchor1().body {
+                            Some(ref b) => activation.will_activate(b),
+                            None        => { }
+                        }
+                        match bd.anchor2().body {
+                            Some(ref b) => activation.will_activate(b),
+                            None        => { }
+                        }
+                    }
+                },
+                Constraint::Grab(ref g) => {
+                    let mut bg = g.borrow_mut();
+                    if !bg.up_to_date() {
+                        // the target has been moved by the user: wake up the grabbed body
+                        bg.update();
+                        match bg.anchor1().body {
+                            Some(ref b) => activation.will_activate(b),
+                            None        => { }
+                        }
+                    }
+                },
                 Constraint::RBRB(_, _, _) => panic!("Internal error: a contact RBRB should not be here.")
  
             }
@@ -228,3 +419,19 @@ impl JointManager {
         }
     }
 }
+
+// We do not know the type of the joint, so cast it to uint for comparison.
+fn joint_key(joint: &Constraint) -> uint {
+    match *joint {
+        Constraint::RBRB(_, _, _)       => ptr::null::<uint>() as uint,
+        Constraint::BallInSocket(ref b) => b.deref() as *const RefCell<BallInSocket> as uint,
+        Constraint::Fixed(ref f)        => f.deref() as *const RefCell<Fixed> as uint,
+        Constraint::Distance(ref d)     => d.deref() as *const RefCell<Distance> as uint,
+        Constraint::Grab(ref g)         => g.deref() as *const RefCell<Grab> as uint
+    }
+}
+
+// Tests whether the anchors `a1` and `a2` attach `b1` to `b2`, in any order.
+fn connects<P>(a1: &Anchor<P>, a2: &Anchor<P>, b1: &Rc<RefCell<RigidBody>>, b2: &Rc<RefCell<RigidBody>>) -> bool {
+    (is_attached_to(a1, b1) && is_attached_to(a2, b2)) || (is_attached_to(a1, b2) && is_attached_to(a2, b1))
+}
@@ -0,0 +1,156 @@
+use std::num::Float;
+use std::collections::HashMap;
+use std::cell::RefCell;
+use na::Bounded;
+use na;
+use ncollide::geometry::Contact;
+use object::{RigidBody, RigidBodyHandle};
+use math::{Scalar, Point, Vect};
+
+/// Limits the number of contact points between each pair of bodies.
+///
+/// A box resting on a finely tessellated mesh touches many triangles, and gets nearly coplanar
+/// contacts from each of them. They make the solver slower without making the simulation any
+/// better, since a few well-chosen points give the same support. For each pair with too many
+/// contacts, the deepest one is kept, and the others are chosen one by one as far as possible
+/// from the points already kept so that they cover the largest area. The other pairs are not
+/// affected.
+pub struct ManifoldReducer {
+    max_contacts: uint,
+    pairs:        HashMap<(uint, uint), Vec<uint>>,
+    keep:         Vec<bool>,
+    selected:     Vec<uint>
+}
+
+impl ManifoldReducer {
+    /// Creates a new manifold reducer keeping at most `max_contacts` points per pair.
+    pub fn new(max_contacts: uint) -> ManifoldReducer {
+        assert!(max_contacts > 0, "At least one contact must be kept per pair.");
+
+        ManifoldReducer {
+            max_contacts: max_contacts,
+            pairs:        HashMap::new(),
+            keep:         Vec::new(),
+            selected:     Vec::new()
+        }
+    }
+
+    /// The maximum number of contact points kept per pair of bodies.
+    #[inline]
+    pub fn max_contacts(&self) -> uint {
+        self.max_contacts
+    }
+
+    /// Sets the maximum number of contact points kept per pair of bodies.
+    #[inline]
+    pub fn set_max_contacts(&mut self, max_contacts: uint) {
+        assert!(max_contacts > 0, "At least one contact must be kept per pair.");
+        self.max_contacts = max_contacts
+    }
+
+    /// Removes from `contacts` the points exceeding the maximum number of contacts of each pair.
+    ///
+    /// The order of the remaining contacts is preserved.
+    pub fn update(&mut self, contacts: &mut Vec<(RigidBodyHandle, RigidBodyHandle, Contact<Scalar, Point, Vect>)>) {
+        if contacts.len() <= self.max_contacts {
+            return;
+        }
+
+        for (i, &(ref b1, ref b2, _)) in contacts.iter().enumerate() {
+            let key = key(b1, b2);
+
+            if !self.pairs.contains_key(&key) {
+                let _ = self.pairs.insert(key, Vec::new());
+            }
+
+            self.pairs.get_mut(&key).unwrap().push(i);
+        }
+
+        self.keep.clear();
+        self.keep.grow(contacts.len(), true);
+
+        let mut reduced = false;
+
+        for (_, ids) in self.pairs.iter() {
+            if ids.len() <= self.max_contacts {
+                continue;
+            }
+
+            select(contacts.as_slice(), ids.as_slice(), self.max_contacts, &mut self.selected);
+
+            for id in ids.iter() {
+                self.keep[*id] = false;
+            }
+
+            for id in self.selected.iter() {
+                self.keep[*id] = true;
+            }
+
+            reduced = true;
+        }
+
+        if reduced {
+            let keep  = &self.keep;
+            let mut i = 0;
+
+            contacts.retain(|_| {
+                i = i + 1;
+                keep[i - 1]
+            });
+        }
+
+        self.pairs.clear();
+    }
+}
+
+// Selects `max` contacts among the contacts `ids` of a single pair.
+fn select(contacts: &[(RigidBodyHandle, RigidBodyHandle, Contact<Scalar, Point, Vect>)],
+          ids:      &[uint],
+          max:      uint,
+          selected: &mut Vec<uint>) {
+    selected.clear();
+
+    let mut deepest = ids[0];
+
+    for id in ids.iter() {
+        if contacts[*id].ref2().depth > contacts[deepest].ref2().depth {
+            deepest = *id;
+        }
+    }
+
+    selected.push(deepest);
+
+    while selected.len() < max {
+        let _max: Scalar = Bounded::max_value();
+        let mut best     = deepest;
+        let mut best_sq  = -_max;
+
+        for id in ids.iter() {
+            if selected.contains(id) {
+                continue;
+            }
+
+            let p = &contacts[*id].ref2().world1;
+
+            // The squared distance to the closest point already selected.
+            let sq = selected.iter().fold(Bounded::max_value(), |m: Scalar, s| {
+                m.min(na::sqdist(p, &contacts[*s].ref2().world1))
+            });
+
+            if sq > best_sq {
+                best    = *id;
+                best_sq = sq;
+            }
+        }
+
+        selected.push(best);
+    }
+}
+
+// The same key regardless of the order of the bodies.
+fn key(b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> (uint, uint) {
+    let k1 = b1.deref() as *const RefCell<RigidBody> as uint;
+    let k2 = b2.deref() as *const RefCell<RigidBody> as uint;
+
+    if k1 < k2 { (k1, k2) } else { (k2, k1) }
+}
@@ -2,6 +2,10 @@
 
 pub use detection::detector::Detector;
 pub use detection::activation_manager::ActivationManager;
+pub use detection::contact_accumulator::ContactAccumulator;
+pub use detection::manifold_reduction::ManifoldReducer;
+pub use detection::broad_phase_pairs::{BroadPhasePairs, BroadPhasePairsUpdater};
+pub use detection::contact_generator::{ContactGenerator, ContactGenerators};
 
 pub mod constraint;
 
@@ -14,15 +18,25 @@ pub mod joint {
     pub use detection::joint::joint::Joint;
     pub use detection::joint::ball_in_socket::BallInSocket;
     pub use detection::joint::fixed::Fixed;
-    pub use detection::joint::joint_manager::JointManager;
+    pub use detection::joint::distance::{Distance, DistanceMode};
+    pub use detection::joint::grab::Grab;
+    pub use detection::joint::joint_manager::{JointManager, JointSignalHandler};
+    pub use detection::joint::articulation::Articulation;
 
     mod joint_manager;
     mod anchor;
     mod ball_in_socket;
     mod fixed;
+    mod distance;
+    mod grab;
+    mod articulation;
     // XXX: `pub` due to rust#18241
     #[allow(missing_docs)]
     pub mod joint;
 }
 
 mod activation_manager;
+mod contact_accumulator;
+mod manifold_reduction;
+mod broad_phase_pairs;
+mod contact_generator;
@@ -0,0 +1,155 @@
+//! Point attractor with an inverse-square falloff.
+
+use std::cell::RefCell;
+use std::num::Float;
+use na;
+use math::{Scalar, Point};
+use object::{RigidBody, RigidBodyHandle};
+use integration::ForceGenerator;
+
+/// A force generator pulling rigid bodies toward a point, with an inverse-square falloff.
+///
+/// A body at a distance `r` of the attractor is subject to a force of magnitude `G * m / r²`,
+/// where `G` is the attractor strength and `m` the body mass. This means every body gets the same
+/// acceleration independently of its mass, like with gravity. A negative strength makes the
+/// attractor repulsive.
+pub struct Attractor {
+    position:   Point,
+    body:       Option<RigidBodyHandle>,
+    strength:   Scalar,
+    min_radius: Scalar,
+    radius:     Option<Scalar>,
+    affected:   Vec<uint>
+}
+
+impl Attractor {
+    /// Creates a new attractor located at `position`, in global coordinates.
+    ///
+    /// # Arguments:
+    /// * `position` - the attraction point.
+    /// * `strength` - the attractor strength. Set it to a negative value for a repulsive force.
+    pub fn new(position: Point, strength: Scalar) -> Attractor {
+        Attractor {
+            position:   position,
+            body:       None,
+            strength:   strength,
+            min_radius: na::cast(0.1f64),
+            radius:     None,
+            affected:   Vec::new()
+        }
+    }
+
+    /// The attraction point of this attractor, in global coordinates.
+    ///
+    /// If the attractor is attached to a body, this is the last known center of mass of the body.
+    #[inline]
+    pub fn position(&self) -> &Point {
+        &self.position
+    }
+
+    /// Sets the attraction point of this attractor, in global coordinates.
+    ///
+    /// This has no effect if the attractor is attached to a body.
+    #[inline]
+    pub fn set_position(&mut self, position: Point) {
+        self.position = position
+    }
+
+    /// The strength of this attractor.
+    #[inline]
+    pub fn strength(&self) -> Scalar {
+        self.strength.clone()
+    }
+
+    /// Sets the strength of this attractor. A negative value makes it repulsive.
+    #[inline]
+    pub fn set_strength(&mut self, strength: Scalar) {
+        self.strength = strength
+    }
+
+    /// The distance under which the force stops increasing.
+    #[inline]
+    pub fn min_radius(&self) -> Scalar {
+        self.min_radius.clone()
+    }
+
+    /// Sets the distance under which the force stops increasing.
+    ///
+    /// This avoids the singularity of the inverse-square law when a body reaches the attraction
+    /// point.
+    #[inline]
+    pub fn set_min_radius(&mut self, min_radius: Scalar) {
+        assert!(min_radius > na::zero(), "The minimum radius of an attractor must be positive.");
+        self.min_radius = min_radius
+    }
+
+    /// The distance beyond which bodies are not affected by this attractor.
+    #[inline]
+    pub fn radius(&self) -> Option<Scalar> {
+        self.radius.clone()
+    }
+
+    /// Sets the distance beyond which bodies are not affected by this attractor.
+    ///
+    /// Set it to `None` for an infinite range.
+    #[inline]
+    pub fn set_radius(&mut self, radius: Option<Scalar>) {
+        self.radius = radius
+    }
+
+    /// Attaches this attractor to the center of mass of a body.
+    ///
+    /// The body itself is not affected by the attractor. Set it to `None` to use the position set
+    /// by `set_position` instead.
+    #[inline]
+    pub fn set_body(&mut self, body: Option<RigidBodyHandle>) {
+        self.body = body
+    }
+
+    /// Restricts the effect of this attractor to a set of bodies.
+    ///
+    /// If this is never called, every dynamic body is affected.
+    #[inline]
+    pub fn add_affected_body(&mut self, body: &RigidBodyHandle) {
+        self.affected.push(body.deref() as *const RefCell<RigidBody> as uint)
+    }
+}
+
+impl ForceGenerator for Attractor {
+    fn apply(&mut self, _: Scalar, handle: &RigidBodyHandle, rb: &mut RigidBody) {
+        let key = handle.deref() as *const RefCell<RigidBody> as uint;
+
+        if !self.affected.is_empty() && !self.affected.contains(&key) {
+            return;
+        }
+
+        match self.body {
+            Some(ref b) => {
+                if b.deref() as *const RefCell<RigidBody> as uint == key {
+                    return;
+                }
+
+                self.position = b.borrow().center_of_mass().clone();
+            },
+            None => { }
+        }
+
+        let dir  = self.position - *rb.center_of_mass();
+        let dist = na::norm(&dir);
+
+        match self.radius {
+            Some(ref r) if dist > *r => return,
+            _ => { }
+        }
+
+        if na::is_zero(&dist) || na::is_zero(&rb.inv_mass()) {
+            return;
+        }
+
+        let _1: Scalar = na::one();
+        let r          = dist.max(self.min_radius);
+        let mass       = _1 / rb.inv_mass();
+
+        rb.append_lin_force(dir * (self.strength * mass / (r * r * dist)));
+    }
+}
@@ -32,7 +32,12 @@ impl Integrator<RigidBody> for BodyExpEulerIntegrator {
                 &rb.lin_acc(),
                 &rb.ang_acc());
 
-            rb.append_transformation(&t);
+            let mut position = rb.position().clone();
+
+            position.append_transformation(&t);
+            euler::orthonormalize(&mut position);
+
+            rb.move_to(position);
             rb.set_lin_vel(lv);
             rb.set_ang_vel(av);
         }
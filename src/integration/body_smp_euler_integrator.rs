@@ -1,19 +1,41 @@
 //! Semi-implicit Euler integrator.
 
 use na::Transformation;
+use na;
 use object::RigidBody;
 use integration::Integrator;
 use integration::euler;
 use math::Scalar;
 
 /// A semi-implicit Euler integrator.
-pub struct BodySmpEulerIntegrator;
+pub struct BodySmpEulerIntegrator {
+    max_angular_step: Option<Scalar>
+}
 
 impl BodySmpEulerIntegrator {
     /// Creates a new `BodySmpEulerIntegrator`.
     #[inline]
     pub fn new() -> BodySmpEulerIntegrator {
-        BodySmpEulerIntegrator
+        BodySmpEulerIntegrator {
+            max_angular_step: None
+        }
+    }
+
+    /// The maximum angle, in radians, a body can rotate by during one step.
+    #[inline]
+    pub fn max_angular_step(&self) -> Option<Scalar> {
+        self.max_angular_step.clone()
+    }
+
+    /// Sets the maximum angle, in radians, a body can rotate by during one step.
+    ///
+    /// The angular velocity of the bodies spinning faster is clamped. Set this to `None` to
+    /// disable the clamping.
+    #[inline]
+    pub fn set_max_angular_step(&mut self, max: Option<Scalar>) {
+        assert!(max.map_or(true, |m| m > na::zero()), "The maximum angular step must be positive.");
+
+        self.max_angular_step = max
     }
 }
 
@@ -21,7 +43,7 @@ impl Integrator<RigidBody> for BodySmpEulerIntegrator {
     #[inline]
     fn update(&mut self, dt: Scalar, rb: &mut RigidBody) {
         if rb.can_move() {
-            let (t, lv, av) = euler::semi_implicit_integrate(
+            let (mut t, lv, mut av) = euler::semi_implicit_integrate(
                 dt.clone(),
                 rb.position(),
                 rb.center_of_mass(),
@@ -30,7 +52,25 @@ impl Integrator<RigidBody> for BodySmpEulerIntegrator {
                 &rb.lin_acc(),
                 &rb.ang_acc());
 
-            rb.append_transformation(&t);
+            match self.max_angular_step {
+                Some(ref max) => {
+                    let step = na::norm(&av) * dt;
+
+                    if step > *max {
+                        av = av * (*max / step);
+                        t  = euler::displacement(dt.clone(), rb.position(), rb.center_of_mass(), &lv, &av);
+                        rb.last_step_flags_mut().set_velocity_clamped();
+                    }
+                },
+                None => { }
+            }
+
+            let mut position = rb.position().clone();
+
+            position.append_transformation(&t);
+            euler::orthonormalize(&mut position);
+
+            rb.move_to(position);
             rb.set_lin_vel(lv);
             rb.set_ang_vel(av);
         }
@@ -0,0 +1,92 @@
+//! Velocity Verlet integrator.
+
+use na::Transformation;
+use na;
+use object::RigidBody;
+use integration::Integrator;
+use integration::euler;
+use math::Scalar;
+
+/// A velocity Verlet integrator.
+///
+/// This is a second-order integrator: with forces depending on the position only, e.g., those of
+/// an `Attractor`, the energy of the bodies oscillates much less than with the semi-implicit Euler
+/// integrator, and the orbits keep their shape. The accelerations of each body are remembered from
+/// one step to the next.
+pub struct BodyVerletIntegrator {
+    max_angular_step: Option<Scalar>
+}
+
+impl BodyVerletIntegrator {
+    /// Creates a new `BodyVerletIntegrator`.
+    #[inline]
+    pub fn new() -> BodyVerletIntegrator {
+        BodyVerletIntegrator {
+            max_angular_step: None
+        }
+    }
+
+    /// The maximum angle, in radians, a body can rotate by during one step.
+    #[inline]
+    pub fn max_angular_step(&self) -> Option<Scalar> {
+        self.max_angular_step.clone()
+    }
+
+    /// Sets the maximum angle, in radians, a body can rotate by during one step.
+    ///
+    /// The angular velocity of the bodies spinning faster is clamped. Set this to `None` to
+    /// disable the clamping.
+    #[inline]
+    pub fn set_max_angular_step(&mut self, max: Option<Scalar>) {
+        assert!(max.map_or(true, |m| m > na::zero()), "The maximum angular step must be positive.");
+
+        self.max_angular_step = max
+    }
+}
+
+impl Integrator<RigidBody> for BodyVerletIntegrator {
+    #[inline]
+    fn update(&mut self, dt: Scalar, rb: &mut RigidBody) {
+        if rb.can_move() {
+            let lf = rb.lin_acc();
+            let af = rb.ang_acc();
+
+            // The first step of a body starts from its current velocities.
+            let (prev_lf, prev_af) = rb.integrated_acc().unwrap_or((lf.clone(), af.clone()));
+
+            let (mut t, lv, mut av) = euler::velocity_verlet_integrate(
+                dt.clone(),
+                rb.position(),
+                rb.center_of_mass(),
+                &rb.lin_vel(),
+                &rb.ang_vel(),
+                &lf,
+                &af,
+                &prev_lf,
+                &prev_af);
+
+            match self.max_angular_step {
+                Some(ref max) => {
+                    let step = na::norm(&av) * dt;
+
+                    if step > *max {
+                        av = av * (*max / step);
+                        t  = euler::displacement(dt.clone(), rb.position(), rb.center_of_mass(), &lv, &av);
+                        rb.last_step_flags_mut().set_velocity_clamped();
+                    }
+                },
+                None => { }
+            }
+
+            let mut position = rb.position().clone();
+
+            position.append_transformation(&t);
+            euler::orthonormalize(&mut position);
+
+            rb.move_to(position);
+            rb.set_lin_vel(lv);
+            rb.set_ang_vel(av);
+            rb.set_integrated_acc(Some((lf, af)));
+        }
+    }
+}
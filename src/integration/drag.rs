@@ -0,0 +1,120 @@
+//! Aerodynamic drag.
+
+use std::cell::RefCell;
+use na;
+use math::{Scalar, Vect};
+use object::{RigidBody, RigidBodyHandle};
+use integration::ForceGenerator;
+
+/// The coefficients of a drag force.
+#[deriving(Clone, Show)]
+pub struct DragCoefficients {
+    /// The coefficient of the drag force proportional to the velocity.
+    pub linear:            Scalar,
+    /// The coefficient of the drag force proportional to the square of the velocity.
+    pub quadratic:         Scalar,
+    /// The coefficient of the drag torque proportional to the angular velocity.
+    pub angular_linear:    Scalar,
+    /// The coefficient of the drag torque proportional to the square of the angular velocity.
+    pub angular_quadratic: Scalar
+}
+
+impl DragCoefficients {
+    /// Creates new drag coefficients.
+    pub fn new(linear:            Scalar,
+               quadratic:         Scalar,
+               angular_linear:    Scalar,
+               angular_quadratic: Scalar)
+               -> DragCoefficients {
+        DragCoefficients {
+            linear:            linear,
+            quadratic:         quadratic,
+            angular_linear:    angular_linear,
+            angular_quadratic: angular_quadratic
+        }
+    }
+}
+
+/// A force generator applying air resistance to rigid bodies.
+///
+/// A body moving at the velocity `v` relative to the wind is subject to the force
+/// `-(c1 * v + c2 * |v| * v)`. Similarly, a body rotating at the angular velocity `w` is subject to
+/// the torque `-(a1 * w + a2 * |w| * w)`.
+pub struct Drag {
+    coefficients: DragCoefficients,
+    wind:         Vect,
+    overrides:    Vec<(uint, DragCoefficients)>
+}
+
+impl Drag {
+    /// Creates a new drag force generator applying the same coefficients to every body.
+    pub fn new(coefficients: DragCoefficients) -> Drag {
+        Drag {
+            coefficients: coefficients,
+            wind:         na::zero(),
+            overrides:    Vec::new()
+        }
+    }
+
+    /// The coefficients applied to bodies without specific coefficients.
+    #[inline]
+    pub fn coefficients(&self) -> &DragCoefficients {
+        &self.coefficients
+    }
+
+    /// Sets the coefficients applied to bodies without specific coefficients.
+    #[inline]
+    pub fn set_coefficients(&mut self, coefficients: DragCoefficients) {
+        self.coefficients = coefficients
+    }
+
+    /// The velocity of the air.
+    #[inline]
+    pub fn wind(&self) -> &Vect {
+        &self.wind
+    }
+
+    /// Sets the velocity of the air.
+    #[inline]
+    pub fn set_wind(&mut self, wind: Vect) {
+        self.wind = wind
+    }
+
+    /// Sets coefficients specific to a given body.
+    pub fn set_body_coefficients(&mut self, body: &RigidBodyHandle, coefficients: DragCoefficients) {
+        let key = body.deref() as *const RefCell<RigidBody> as uint;
+
+        self.remove_body_coefficients(body);
+        self.overrides.push((key, coefficients))
+    }
+
+    /// Removes the coefficients specific to a given body.
+    pub fn remove_body_coefficients(&mut self, body: &RigidBodyHandle) {
+        let key = body.deref() as *const RefCell<RigidBody> as uint;
+
+        self.overrides.retain(|&(k, _)| k != key)
+    }
+}
+
+impl ForceGenerator for Drag {
+    fn apply(&mut self, _: Scalar, handle: &RigidBodyHandle, rb: &mut RigidBody) {
+        let key    = handle.deref() as *const RefCell<RigidBody> as uint;
+        let mut cs = &self.coefficients;
+
+        for &(k, ref c) in self.overrides.iter() {
+            if k == key {
+                cs = c;
+                break;
+            }
+        }
+
+        let lin_vel = rb.lin_vel() - self.wind;
+        let ang_vel = rb.ang_vel();
+
+        let force  = lin_vel * (cs.linear + cs.quadratic * na::norm(&lin_vel));
+        let torque = ang_vel * (cs.angular_linear + cs.angular_quadratic * na::norm(&ang_vel));
+
+        rb.append_lin_force(-force);
+        rb.append_ang_force(-torque);
+    }
+}
@@ -1,6 +1,14 @@
 //! Euler integration functions.
 
-use na::{Translation, RotationWithTranslation};
+#[cfg(not(feature = "deterministic"))]
+use na::{Rotation, RotationWithTranslation};
+#[cfg(all(feature = "deterministic", feature = "3d"))]
+use na::{Vec3, Mat3, Rot3, Iso3};
+#[cfg(all(feature = "deterministic", feature = "2d"))]
+use na::{Vec1, Vec2, Mat2, Rot2, Iso2};
+#[cfg(feature = "deterministic")]
+use utils::deterministic;
+use na::Translation;
 use na;
 use math::{Scalar, Point, Vect, Orientation, Matrix};
 
@@ -43,13 +51,199 @@ pub fn semi_implicit_integrate_wo_rotation(dt: Scalar, p: &Point, lv: &Vect, lf:
     )
 }
 
+/// Velocity Verlet integrator.
+///
+/// The accelerations `lf` and `af` are those at the current position, and `prev_lf` and `prev_af`
+/// those used by the previous step. The velocities `lv` and `av` given and returned are the
+/// velocities at the end of a step estimated with its accelerations only: each step first corrects
+/// them with the average of the previous and current accelerations, which are only known now.
+pub fn velocity_verlet_integrate(dt:      Scalar,
+                                 p:       &Matrix,
+                                 c:       &Point,
+                                 lv:      &Vect,
+                                 av:      &Orientation,
+                                 lf:      &Vect,
+                                 af:      &Orientation,
+                                 prev_lf: &Vect,
+                                 prev_af: &Orientation)
+                                 -> (Matrix, Vect, Orientation) {
+    let half_dt = dt * na::cast(0.5f64);
+    let clv     = *lv + (*lf - *prev_lf) * half_dt;
+    let cav     = *av + (*af - *prev_af) * half_dt;
+
+    (
+        displacement(dt.clone(), p, c, &(clv + *lf * half_dt), &(cav + *af * half_dt)),
+        clv + *lf * dt,
+        cav + *af * dt
+    )
+}
+
 /// Computes the transformation matrix required to move an object with a `lin_vel` linear velocity,
 /// a `ang_vil` angular velocity, and a center of mass `center_of_mass`, during the time step `dt`.
 pub fn displacement(dt: Scalar, _: &Matrix, center_of_mass: &Point, lin_vel: &Vect, ang_vel: &Orientation) -> Matrix {
     let mut res: Matrix = na::one();
-    res.append_rotation_wrt_point(&(*ang_vel * dt), center_of_mass.as_vec());
+    append_rotation_wrt_point(&mut res, &(*ang_vel * dt), center_of_mass.as_vec());
 
     res.append_translation(&(*lin_vel * dt));
 
     res
 }
+
+/// Appends to `m` the rotation `rot`, expressed in axis-angle form, wrt. the point `center`.
+///
+/// With the `deterministic` feature, the rotation matrix is computed with the approximations of
+/// `utils::deterministic` instead of the trigonometric functions of the platform.
+#[cfg(not(feature = "deterministic"))]
+pub fn append_rotation_wrt_point(m: &mut Matrix, rot: &Orientation, center: &Vect) {
+    m.append_rotation_wrt_point(rot, center)
+}
+
+/// Appends to `m` the rotation `rot`, expressed in axis-angle form, wrt. the point `center`.
+///
+/// With the `deterministic` feature, the rotation matrix is computed with the approximations of
+/// `utils::deterministic` instead of the trigonometric functions of the platform.
+#[cfg(feature = "deterministic")]
+pub fn append_rotation_wrt_point(m: &mut Matrix, rot: &Orientation, center: &Vect) {
+    m.append_translation(&-*center);
+    *m = rotation(rot) * *m;
+    m.append_translation(center);
+}
+
+/// The axis-angle representation of the rotational part of `m`.
+///
+/// This is the inverse of `append_rotation_wrt_point` applied to the identity, with an angle in
+/// `[0, pi]`.
+#[cfg(not(feature = "deterministic"))]
+pub fn rotation_vector(m: &Matrix) -> Orientation {
+    m.rotation()
+}
+
+/// The axis-angle representation of the rotational part of `m`.
+///
+/// This is the inverse of `append_rotation_wrt_point` applied to the identity, with an angle in
+/// `[0, pi]`.
+#[cfg(all(feature = "deterministic", feature = "3d"))]
+pub fn rotation_vector(m: &Matrix) -> Orientation {
+    let (x, y, z) = columns(m);
+
+    // For R = exp([w]), the skew-symmetric part of R is sin(|w|) [w / |w|].
+    let v   = Vec3::new(y.z - z.y, z.x - x.z, x.y - y.x);
+    let sin = na::norm(&v) / na::cast(2.0f64);
+    let cos = (x.x + y.y + z.z - na::one()) / na::cast(2.0f64);
+
+    if sin == na::zero() {
+        if cos >= na::zero() {
+            return na::zero();
+        }
+
+        // Half turn: R = 2 k k^T - I for the unit axis k, so each column of R + I is parallel to
+        // k. The one with the largest diagonal element is the most accurate.
+        let pi: Scalar = na::cast(::std::f64::consts::PI);
+        let axis =
+            if x.x >= y.y && x.x >= z.z { Vec3::new(x.x + na::one(), x.y, x.z) }
+            else if y.y >= z.z          { Vec3::new(y.x, y.y + na::one(), y.z) }
+            else                        { Vec3::new(z.x, z.y, z.z + na::one()) };
+
+        return na::normalize(&axis) * pi;
+    }
+
+    v * (deterministic::atan2(sin, cos) / (sin * na::cast(2.0f64)))
+}
+
+/// The axis-angle representation of the rotational part of `m`.
+///
+/// This is the inverse of `append_rotation_wrt_point` applied to the identity, with an angle in
+/// `[-pi, pi]`.
+#[cfg(all(feature = "deterministic", feature = "2d"))]
+pub fn rotation_vector(m: &Matrix) -> Orientation {
+    let x = m.rotation * Vec2::new(na::one(), na::zero());
+
+    Vec1::new(deterministic::atan2(x.y, x.x))
+}
+
+/// Removes the numerical drift of the rotational part of `m`.
+///
+/// A rotation matrix obtained by composing many small rotations slowly loses its orthonormality,
+/// which visibly shears and scales quickly spinning bodies. Converting it to its axis-angle
+/// representation and back gives an exact rotation again.
+#[cfg(not(feature = "deterministic"))]
+pub fn orthonormalize(m: &mut Matrix) {
+    let rot = m.rotation();
+
+    m.set_rotation(rot)
+}
+
+/// Removes the numerical drift of the rotational part of `m`.
+///
+/// A rotation matrix obtained by composing many small rotations slowly loses its orthonormality,
+/// which visibly shears and scales quickly spinning bodies. The Gram-Schmidt process gives an
+/// exact rotation again, using only square roots which, unlike the trigonometric functions, are
+/// exactly rounded on every platform.
+#[cfg(all(feature = "deterministic", feature = "3d"))]
+pub fn orthonormalize(m: &mut Matrix) {
+    let (x, y, _) = columns(m);
+
+    let x = na::normalize(&x);
+    let y = na::normalize(&(y - x * na::dot(&x, &y)));
+    let z = na::cross(&x, &y);
+
+    m.rotation = rotation_matrix(x, y, z);
+}
+
+/// Removes the numerical drift of the rotational part of `m`.
+///
+/// A rotation matrix obtained by composing many small rotations slowly loses its orthonormality,
+/// which visibly shears and scales quickly spinning bodies. Normalizing its first column gives an
+/// exact rotation again, using only a square root which, unlike the trigonometric functions, is
+/// exactly rounded on every platform.
+#[cfg(all(feature = "deterministic", feature = "2d"))]
+pub fn orthonormalize(m: &mut Matrix) {
+    let x = na::normalize(&(m.rotation * Vec2::new(na::one(), na::zero())));
+
+    m.rotation = unsafe { Rot2::new_with_mat(Mat2::new(x.x, -x.y, x.y, x.x)) };
+}
+
+// The rotation of angle `|rot|` around the axis `rot`, by Rodrigues' formula.
+#[cfg(all(feature = "deterministic", feature = "3d"))]
+fn rotation(rot: &Orientation) -> Matrix {
+    let angle = na::norm(rot);
+
+    if angle == na::zero() {
+        return na::one();
+    }
+
+    let k      = *rot / angle;
+    let (s, c) = deterministic::sin_cos(angle);
+    let t      = na::one::<Scalar>() - c;
+
+    let x = Vec3::new(t * k.x * k.x + c,       t * k.x * k.y + s * k.z, t * k.x * k.z - s * k.y);
+    let y = Vec3::new(t * k.x * k.y - s * k.z, t * k.y * k.y + c,       t * k.y * k.z + s * k.x);
+    let z = Vec3::new(t * k.x * k.z + s * k.y, t * k.y * k.z - s * k.x, t * k.z * k.z + c);
+
+    Iso3::new_with_rotmat(na::zero(), rotation_matrix(x, y, z))
+}
+
+#[cfg(all(feature = "deterministic", feature = "2d"))]
+fn rotation(rot: &Orientation) -> Matrix {
+    let (s, c) = deterministic::sin_cos(rot.x);
+
+    Iso2::new_with_rotmat(na::zero(), unsafe { Rot2::new_with_mat(Mat2::new(c, -s, s, c)) })
+}
+
+// The images of the basis vectors by the rotational part of `m`.
+#[cfg(all(feature = "deterministic", feature = "3d"))]
+fn columns(m: &Matrix) -> (Vect, Vect, Vect) {
+    let (o, z) = (na::one::<Scalar>(), na::zero::<Scalar>());
+
+    (m.rotation * Vec3::new(o, z, z), m.rotation * Vec3::new(z, o, z), m.rotation * Vec3::new(z, z, o))
+}
+
+// The rotation matrix with the columns `x`, `y` and `z`, which must be orthonormal.
+#[cfg(all(feature = "deterministic", feature = "3d"))]
+fn rotation_matrix(x: Vect, y: Vect, z: Vect) -> Rot3<Scalar> {
+    unsafe {
+        Rot3::new_with_mat(Mat3::new(x.x, y.x, z.x,
+                                     x.y, y.y, z.y,
+                                     x.z, y.z, z.z))
+    }
+}
@@ -0,0 +1,25 @@
+//! Trait implemented by user-defined force generators.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use math::Scalar;
+use object::{RigidBody, RigidBodyHandle};
+
+/// Trait implemented by force generators registered on the physics world.
+///
+/// Force generators are applied to every active dynamic body at each step, after the gravity and
+/// before the position and orientation integration.
+pub trait ForceGenerator {
+    /// Applies forces to the rigid body `rb` during a time step of `dt`.
+    ///
+    /// `handle` is the handle of `rb`. It is already mutably borrowed and should only be used to
+    /// identify the body.
+    fn apply(&mut self, dt: Scalar, handle: &RigidBodyHandle, rb: &mut RigidBody);
+}
+
+impl<G: ForceGenerator> ForceGenerator for Rc<RefCell<G>> {
+    #[inline]
+    fn apply(&mut self, dt: Scalar, handle: &RigidBodyHandle, rb: &mut RigidBody) {
+        self.borrow_mut().apply(dt, handle, rb)
+    }
+}
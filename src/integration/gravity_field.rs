@@ -0,0 +1,12 @@
+//! Gravity varying with the position.
+
+use math::{Point, Vect};
+
+/// Trait implemented by gravity fields, i.e., gravity accelerations depending on the position.
+///
+/// Once set on the world with `World::set_gravity_field`, a gravity field replaces the constant
+/// gravity. It is evaluated at the center of mass of every active dynamic body at each step.
+pub trait GravityField {
+    /// The gravity acceleration at the point `at`, in global coordinates.
+    fn gravity_at(&self, at: &Point) -> Vect;
+}
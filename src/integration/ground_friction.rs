@@ -0,0 +1,78 @@
+//! Friction with a virtual ground, for the worlds seen from above.
+
+use std::num::Float;
+use na;
+use math::Scalar;
+use object::{RigidBody, RigidBodyHandle};
+use integration::ForceGenerator;
+
+/// A force generator slowing the bodies down as if they were sliding on the ground.
+///
+/// In a world seen from above, e.g., an air hockey table, the ground is not simulated. This applies
+/// to each body the force of the dry friction it would have with it: a force opposed to its
+/// velocity, of norm `coefficient * mass * gravity`. Unlike a damping, this stops the bodies after
+/// a finite time and distance. The force is reduced during the last step of the motion so that the
+/// bodies stop instead of moving backward. Their rotation is not affected, see
+/// `RigidBody::set_angular_damping`.
+pub struct GroundFriction {
+    coefficient: Scalar,
+    gravity:     Scalar
+}
+
+impl GroundFriction {
+    /// Creates the friction with a ground pushing the bodies with the virtual gravity `gravity`.
+    pub fn new(coefficient: Scalar, gravity: Scalar) -> GroundFriction {
+        assert!(coefficient >= na::zero(), "The ground friction coefficient must not be negative.");
+        assert!(gravity >= na::zero(), "The virtual gravity must not be negative.");
+
+        GroundFriction {
+            coefficient: coefficient,
+            gravity:     gravity
+        }
+    }
+
+    /// The friction coefficient between the bodies and the ground.
+    #[inline]
+    pub fn coefficient(&self) -> Scalar {
+        self.coefficient.clone()
+    }
+
+    /// Sets the friction coefficient between the bodies and the ground.
+    #[inline]
+    pub fn set_coefficient(&mut self, coefficient: Scalar) {
+        assert!(coefficient >= na::zero(), "The ground friction coefficient must not be negative.");
+
+        self.coefficient = coefficient
+    }
+
+    /// The virtual gravity pushing the bodies against the ground.
+    #[inline]
+    pub fn gravity(&self) -> Scalar {
+        self.gravity.clone()
+    }
+
+    /// Sets the virtual gravity pushing the bodies against the ground.
+    #[inline]
+    pub fn set_gravity(&mut self, gravity: Scalar) {
+        assert!(gravity >= na::zero(), "The virtual gravity must not be negative.");
+
+        self.gravity = gravity
+    }
+}
+
+impl ForceGenerator for GroundFriction {
+    fn apply(&mut self, dt: Scalar, _: &RigidBodyHandle, rb: &mut RigidBody) {
+        let inv_mass = rb.inv_mass();
+        let lin_vel  = rb.lin_vel();
+        let speed    = na::norm(&lin_vel);
+
+        if na::is_zero(&inv_mass) || na::is_zero(&speed) || na::is_zero(&dt) {
+            return;
+        }
+
+        // The deceleration, which does not depend on the mass.
+        let decel = (self.coefficient * self.gravity).min(speed / dt);
+
+        rb.append_lin_force(lin_vel * (-decel / (speed * inv_mass)));
+    }
+}
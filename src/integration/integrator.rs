@@ -2,6 +2,18 @@
 
 use math::Scalar;
 
+/// The integration schemes the world can use to update the bodies.
+#[deriving(Show, PartialEq, Eq, Clone)]
+pub enum IntegratorKind {
+    /// The semi-implicit, or symplectic, Euler integrator: the velocities are updated first, and
+    /// the positions are then moved with the new velocities. This is a first-order integrator.
+    SymplecticEuler,
+    /// The velocity Verlet integrator: the positions are moved with the average of the velocities
+    /// at the start and at the end of the step. This is a second-order integrator, with less energy
+    /// drift in the long-running scenes, e.g., orbits.
+    VelocityVerlet
+}
+
 /// Trait implemented by every integrator.
 ///
 /// An integrator is a structure capable of updating a dynamic body position and orientation after a given time-step.
@@ -1,17 +1,39 @@
 //! Position and orientation update of rigid bodies.
 
-pub use integration::integrator::Integrator;
+pub use integration::integrator::{Integrator, IntegratorKind};
 pub use integration::body_exp_euler_integrator::BodyExpEulerIntegrator;
 pub use integration::body_smp_euler_integrator::BodySmpEulerIntegrator;
+pub use integration::body_verlet_integrator::BodyVerletIntegrator;
 pub use integration::body_force_generator::BodyForceGenerator;
 pub use integration::body_damping::BodyDamping;
+pub use integration::force_generator::ForceGenerator;
+pub use integration::pd_controller::{PdController, BodyPdController};
+pub use integration::attractor::Attractor;
+pub use integration::spring::{TorsionSpring, AxisSpring};
+pub use integration::gravity_field::GravityField;
+pub use integration::drag::{Drag, DragCoefficients};
+pub use integration::ground_friction::GroundFriction;
+pub use integration::motor::Motor;
+#[cfg(feature = "3d")]
+pub use integration::pressure::Pressure;
 pub use integration::translational_ccd_motion_clamping::TranslationalCCDMotionClamping;
 
 // XXX: `pub` due to rust#18241
 pub mod integrator;
+pub mod force_generator;
+pub mod gravity_field;
 mod body_exp_euler_integrator;
 mod body_smp_euler_integrator;
+mod body_verlet_integrator;
 mod body_force_generator;
 mod body_damping;
+mod pd_controller;
+mod attractor;
+mod spring;
+mod drag;
+mod ground_friction;
+mod motor;
+#[cfg(feature = "3d")]
+mod pressure;
 mod translational_ccd_motion_clamping;
 pub mod euler;
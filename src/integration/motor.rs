@@ -0,0 +1,62 @@
+//! Constant torque driving a body.
+
+use std::cell::RefCell;
+use na;
+use math::{Scalar, Orientation};
+use object::{RigidBody, RigidBodyHandle};
+use integration::ForceGenerator;
+
+/// A force generator applying a constant torque to a single body, e.g., the rotor of a fan.
+///
+/// The spin of the body can be capped with `RigidBody::set_max_angular_velocity`. When something
+/// slows the rotor down while the motor drives it, the world reports it with a `BodyStalled`
+/// event, see `RigidBody::set_stall_speed`.
+pub struct Motor {
+    body:    uint,
+    torque:  Orientation,
+    enabled: bool
+}
+
+impl Motor {
+    /// Creates a motor applying `torque`, in global coordinates, to `body`.
+    pub fn new(body: &RigidBodyHandle, torque: Orientation) -> Motor {
+        Motor {
+            body:    body.deref() as *const RefCell<RigidBody> as uint,
+            torque:  torque,
+            enabled: true
+        }
+    }
+
+    /// The torque applied by this motor, in global coordinates.
+    #[inline]
+    pub fn torque(&self) -> &Orientation {
+        &self.torque
+    }
+
+    /// Sets the torque applied by this motor, in global coordinates.
+    #[inline]
+    pub fn set_torque(&mut self, torque: Orientation) {
+        self.torque = torque
+    }
+
+    /// Whether this motor applies its torque.
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Turns this motor on or off.
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled
+    }
+}
+
+impl ForceGenerator for Motor {
+    fn apply(&mut self, _: Scalar, handle: &RigidBodyHandle, rb: &mut RigidBody) {
+        if self.enabled && !na::is_zero(&self.torque) &&
+           handle.deref() as *const RefCell<RigidBody> as uint == self.body {
+            rb.append_ang_force(self.torque.clone())
+        }
+    }
+}
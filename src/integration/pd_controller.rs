@@ -0,0 +1,140 @@
+//! Proportional-derivative controllers making a rigid body track a target pose.
+
+use std::cell::RefCell;
+use na::Transform;
+use na;
+use math::{Scalar, Vect, Orientation, Matrix};
+use object::{RigidBody, RigidBodyHandle};
+use integration::ForceGenerator;
+use integration::euler;
+
+/// A proportional-derivative controller.
+///
+/// It computes the force and torque to apply to a rigid body so that it reaches a target pose
+/// with springy, damped, motions.
+pub struct PdController {
+    kp:         Scalar,
+    kd:         Scalar,
+    max_force:  Scalar,
+    max_torque: Scalar
+}
+
+impl PdController {
+    /// Creates a new `PdController`.
+    ///
+    /// # Arguments:
+    /// * `kp`         - the proportional gain, i.e., the stiffness of the spring pulling the body.
+    /// * `kd`         - the derivative gain, i.e., the damping of the body velocity.
+    /// * `max_force`  - the maximum norm of the force computed by this controller.
+    /// * `max_torque` - the maximum norm of the torque computed by this controller.
+    pub fn new(kp: Scalar, kd: Scalar, max_force: Scalar, max_torque: Scalar) -> PdController {
+        PdController {
+            kp:         kp,
+            kd:         kd,
+            max_force:  max_force,
+            max_torque: max_torque
+        }
+    }
+
+    /// The proportional gain of this controller.
+    #[inline]
+    pub fn kp(&self) -> Scalar {
+        self.kp.clone()
+    }
+
+    /// Sets the proportional gain of this controller.
+    #[inline]
+    pub fn set_kp(&mut self, kp: Scalar) {
+        self.kp = kp
+    }
+
+    /// The derivative gain of this controller.
+    #[inline]
+    pub fn kd(&self) -> Scalar {
+        self.kd.clone()
+    }
+
+    /// Sets the derivative gain of this controller.
+    #[inline]
+    pub fn set_kd(&mut self, kd: Scalar) {
+        self.kd = kd
+    }
+
+    /// Computes the force and the torque to apply to `body` for it to reach the pose `target`.
+    ///
+    /// `target` is the desired value of `body.position()`. The force is meant to be applied at
+    /// the center of mass.
+    pub fn control(&self, body: &RigidBody, target: &Matrix) -> (Vect, Orientation) {
+        let target_com = target.transform(body.local_center_of_mass());
+        let lin_err    = target_com - *body.center_of_mass();
+        let force      = lin_err * self.kp - body.lin_vel() * self.kd;
+
+        // The rotation bringing the current orientation to the target orientation.
+        let delta   = *target * na::inv(body.position()).expect("A body position must be invertible.");
+        let ang_err = euler::rotation_vector(&delta);
+        let torque  = ang_err * self.kp - body.ang_vel() * self.kd;
+
+        (clamp_norm(force, self.max_force.clone()), clamp_norm(torque, self.max_torque.clone()))
+    }
+}
+
+fn clamp_norm<V: na::Norm<Scalar> + Mul<Scalar, V>>(v: V, max: Scalar) -> V {
+    let norm = na::norm(&v);
+
+    if norm > max {
+        v * (max / norm)
+    }
+    else {
+        v
+    }
+}
+
+/// A force generator making a single rigid body track a target pose with a `PdController`.
+pub struct BodyPdController {
+    controller: PdController,
+    body:       uint,
+    target:     Matrix
+}
+
+impl BodyPdController {
+    /// Creates a new force generator making `body` track the pose `target`.
+    ///
+    /// Sleeping bodies are not controlled, so the deactivation of `body` should be disabled with
+    /// `set_deactivation_threshold(None)` if its target is to be modified.
+    pub fn new(controller: PdController, body: &RigidBodyHandle, target: Matrix) -> BodyPdController {
+        BodyPdController {
+            controller: controller,
+            body:       body.deref() as *const RefCell<RigidBody> as uint,
+            target:     target
+        }
+    }
+
+    /// The controller used by this force generator.
+    #[inline]
+    pub fn controller(&mut self) -> &mut PdController {
+        &mut self.controller
+    }
+
+    /// The pose tracked by the controlled body.
+    #[inline]
+    pub fn target(&self) -> &Matrix {
+        &self.target
+    }
+
+    /// Sets the pose tracked by the controlled body.
+    #[inline]
+    pub fn set_target(&mut self, target: Matrix) {
+        self.target = target
+    }
+}
+
+impl ForceGenerator for BodyPdController {
+    fn apply(&mut self, _: Scalar, handle: &RigidBodyHandle, rb: &mut RigidBody) {
+        if handle.deref() as *const RefCell<RigidBody> as uint == self.body {
+            let (force, torque) = self.controller.control(rb, &self.target);
+
+            rb.append_lin_force(force);
+            rb.append_ang_force(torque);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Internal pressure of a closed surface made of bodies.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use na;
+use math::{Scalar, Point, Vect};
+use object::{RigidBody, RigidBodyHandle};
+use integration::ForceGenerator;
+
+/// A force generator inflating a closed surface whose vertices are bodies, e.g., a pressure ball.
+///
+/// The surface is a triangle mesh with a body at each vertex. When its volume `V` drops below its
+/// rest volume `V0`, each vertex is pushed outward by the pressure difference
+/// `pressure * (V0 - V) / V0` applied to its share of the surface. The vertices are pulled inward
+/// when the surface is inflated beyond its rest volume.
+///
+/// The volume is measured once per step, from the positions of the vertices at the last step.
+pub struct Pressure {
+    // The index of the vertex of each body.
+    indices:     HashMap<uint, uint>,
+    triangles:   Vec<(uint, uint, uint)>,
+    positions:   Vec<Point>,
+    // The vertices which got their force since the volume was last measured.
+    visited:     Vec<bool>,
+    // The derivative of the volume with respect to the position of each vertex, i.e., a third of
+    // the sum of the area-weighted normals of the triangles around it.
+    gradients:   Vec<Vect>,
+    volume:      Scalar,
+    rest_volume: Scalar,
+    pressure:    Scalar
+}
+
+impl Pressure {
+    /// Creates the pressure of the closed surface with the vertices `vertices` and the triangles
+    /// `triangles`.
+    ///
+    /// The triangles give the indices of their vertices counterclockwise, seen from the outside of
+    /// the surface. The rest volume is the current volume of the surface.
+    pub fn new(vertices: &[RigidBodyHandle], triangles: Vec<(uint, uint, uint)>, pressure: Scalar) -> Pressure {
+        assert!(triangles.iter().all(|&(a, b, c)| a < vertices.len() && b < vertices.len() && c < vertices.len()),
+                "Vertex index out of bounds.");
+
+        let mut res = Pressure {
+            indices:     vertices.iter().enumerate()
+                                 .map(|(i, b)| (b.deref() as *const RefCell<RigidBody> as uint, i))
+                                 .collect(),
+            triangles:   triangles,
+            positions:   vertices.iter().map(|b| b.borrow().center_of_mass().clone()).collect(),
+            visited:     Vec::from_elem(vertices.len(), false),
+            gradients:   Vec::from_elem(vertices.len(), na::zero()),
+            volume:      na::zero(),
+            rest_volume: na::zero(),
+            pressure:    pressure
+        };
+
+        res.measure();
+
+        assert!(res.volume > na::zero(), "The triangles of a pressure surface must be oriented outward.");
+
+        res.rest_volume = res.volume;
+
+        res
+    }
+
+    /// The volume of the surface, measured at the last step.
+    #[inline]
+    pub fn volume(&self) -> Scalar {
+        self.volume.clone()
+    }
+
+    /// The volume the surface is inflated to.
+    #[inline]
+    pub fn rest_volume(&self) -> Scalar {
+        self.rest_volume.clone()
+    }
+
+    /// Sets the volume the surface is inflated to, e.g., to deflate it.
+    #[inline]
+    pub fn set_rest_volume(&mut self, rest_volume: Scalar) {
+        assert!(rest_volume > na::zero(), "The rest volume of a pressure surface must be positive.");
+
+        self.rest_volume = rest_volume
+    }
+
+    /// The pressure difference applied per unit of relative volume loss.
+    #[inline]
+    pub fn pressure(&self) -> Scalar {
+        self.pressure.clone()
+    }
+
+    /// Sets the pressure difference applied per unit of relative volume loss.
+    #[inline]
+    pub fn set_pressure(&mut self, pressure: Scalar) {
+        self.pressure = pressure
+    }
+
+    // Measures the volume of the surface and its gradients from the last known positions of the
+    // vertices. This is the sum of the signed volumes of the tetrahedra joining each triangle to
+    // the centroid of the vertices, which is better conditioned than the origin far from it.
+    fn measure(&mut self) {
+        let mut centroid: Vect = na::zero();
+
+        for p in self.positions.iter() {
+            centroid = centroid + *p.as_vec();
+        }
+
+        let n: Scalar = na::cast(self.positions.len() as f64);
+        let centroid  = na::orig::<Point>() + centroid / n;
+        let sixth: Scalar = na::cast(1.0f64 / 6.0);
+
+        self.volume = na::zero();
+
+        for g in self.gradients.iter_mut() {
+            *g = na::zero();
+        }
+
+        for &(ia, ib, ic) in self.triangles.iter() {
+            let a = self.positions[ia] - centroid;
+            let b = self.positions[ib] - centroid;
+            let c = self.positions[ic] - centroid;
+
+            self.volume = self.volume + na::dot(&a, &na::cross(&b, &c)) * sixth;
+
+            self.gradients[ia] = self.gradients[ia] + na::cross(&b, &c) * sixth;
+            self.gradients[ib] = self.gradients[ib] + na::cross(&c, &a) * sixth;
+            self.gradients[ic] = self.gradients[ic] + na::cross(&a, &b) * sixth;
+        }
+
+        for v in self.visited.iter_mut() {
+            *v = false;
+        }
+    }
+}
+
+impl ForceGenerator for Pressure {
+    fn apply(&mut self, _: Scalar, handle: &RigidBodyHandle, rb: &mut RigidBody) {
+        let i = match self.indices.find(&(handle.deref() as *const RefCell<RigidBody> as uint)) {
+            Some(i) => *i,
+            None    => return
+        };
+
+        // A vertex is visited twice once a step has gone by.
+        if self.visited[i] {
+            self.measure();
+        }
+
+        self.visited[i]   = true;
+        self.positions[i] = rb.center_of_mass().clone();
+
+        let difference = self.pressure * (self.rest_volume - self.volume) / self.rest_volume;
+
+        // The gradient of the volume is the area-weighted normal of the share of the surface of
+        // the vertex.
+        rb.append_lin_force(self.gradients[i] * difference)
+    }
+}
@@ -0,0 +1,347 @@
+//! Springs acting only on the relative orientation, or only along one axis, of two bodies.
+
+use std::cell::RefCell;
+use na::{Transform, Rotate};
+use na;
+use math::{Scalar, Point, Vect, Orientation, Matrix};
+use object::{RigidBody, RigidBodyHandle};
+use detection::joint::Anchor;
+use integration::ForceGenerator;
+use integration::euler;
+
+/// A force generator pulling the relative orientation of two bodies toward a rest rotation.
+///
+/// Each anchor is a frame attached to a body, or to the ground. The spring is at rest when both
+/// frames have the same orientation, so the rest rotation is given by the frames themselves. Only
+/// torques are applied: the spring does not keep the bodies together. This is typically combined
+/// with a ball-in-socket joint to make objects that wobble and come back upright.
+pub struct TorsionSpring {
+    anchor1:    Anchor<Matrix>,
+    anchor2:    Anchor<Matrix>,
+    stiffness:  Scalar,
+    damping:    Scalar,
+    max_torque: Scalar,
+    pending:    Option<(uint, Vect, Orientation)>
+}
+
+impl TorsionSpring {
+    /// Creates a new torsion spring between two frames.
+    ///
+    /// # Arguments:
+    /// * `anchor1`    - the first frame, in local coordinates of its body.
+    /// * `anchor2`    - the second frame, in local coordinates of its body.
+    /// * `stiffness`  - the torque applied per radian between the two frames.
+    /// * `damping`    - the torque applied per radian per second of relative angular velocity.
+    /// * `max_torque` - the maximum norm of the torque applied to each body.
+    pub fn new(anchor1:    Anchor<Matrix>,
+               anchor2:    Anchor<Matrix>,
+               stiffness:  Scalar,
+               damping:    Scalar,
+               max_torque: Scalar)
+               -> TorsionSpring {
+        TorsionSpring {
+            anchor1:    anchor1,
+            anchor2:    anchor2,
+            stiffness:  stiffness,
+            damping:    damping,
+            max_torque: max_torque,
+            pending:    None
+        }
+    }
+
+    /// The stiffness of this spring.
+    #[inline]
+    pub fn stiffness(&self) -> Scalar {
+        self.stiffness.clone()
+    }
+
+    /// Sets the stiffness of this spring.
+    #[inline]
+    pub fn set_stiffness(&mut self, stiffness: Scalar) {
+        self.stiffness = stiffness
+    }
+
+    /// The damping of this spring.
+    #[inline]
+    pub fn damping(&self) -> Scalar {
+        self.damping.clone()
+    }
+
+    /// Sets the damping of this spring.
+    #[inline]
+    pub fn set_damping(&mut self, damping: Scalar) {
+        self.damping = damping
+    }
+
+    /// The maximum norm of the torque applied by this spring.
+    #[inline]
+    pub fn max_torque(&self) -> Scalar {
+        self.max_torque.clone()
+    }
+
+    /// Sets the maximum norm of the torque applied by this spring.
+    #[inline]
+    pub fn set_max_torque(&mut self, max_torque: Scalar) {
+        self.max_torque = max_torque
+    }
+}
+
+impl ForceGenerator for TorsionSpring {
+    fn apply(&mut self, _: Scalar, handle: &RigidBodyHandle, rb: &mut RigidBody) {
+        let stiffness  = self.stiffness.clone();
+        let damping    = self.damping.clone();
+        let max_torque = self.max_torque.clone();
+
+        apply_between(&self.anchor1, &self.anchor2, &mut self.pending, handle, rb, |s1, s2| {
+            // The rotation bringing the second frame to the first one.
+            let delta  = s1.frame * na::inv(&s2.frame).expect("A body position must be invertible.");
+            let err    = euler::rotation_vector(&delta);
+            let torque = clamp_norm(err * stiffness - (s2.ang_vel - s1.ang_vel) * damping, max_torque);
+
+            (na::zero(), -torque, na::zero(), torque)
+        })
+    }
+}
+
+/// A force generator acting like a spring along a single axis.
+///
+/// Each anchor is a frame attached to a body, or to the ground. The origins of the frames are the
+/// attach points of the spring. Only the distance between the attach points along the spring axis
+/// is controlled, the axis being fixed relative to the first frame. This makes a suspension strut
+/// when combined with constraints, or contacts, keeping the bodies aligned.
+pub struct AxisSpring {
+    anchor1:     Anchor<Matrix>,
+    anchor2:     Anchor<Matrix>,
+    axis:        Vect,
+    rest_length: Scalar,
+    stiffness:   Scalar,
+    damping:     Scalar,
+    max_force:   Scalar,
+    pending:     Option<(uint, Vect, Orientation)>
+}
+
+impl AxisSpring {
+    /// Creates a new spring along one axis between two frames.
+    ///
+    /// # Arguments:
+    /// * `anchor1`     - the first frame, in local coordinates of its body.
+    /// * `anchor2`     - the second frame, in local coordinates of its body.
+    /// * `axis`        - the axis of the spring, in local coordinates of the first frame. It does
+    ///                   not have to be normalized.
+    /// * `rest_length` - the signed distance along the axis from the first attach point to the
+    ///                   second one at which the spring is at rest.
+    /// * `stiffness`   - the force applied per unit of extension of the spring.
+    /// * `damping`     - the force applied per unit of relative velocity along the axis.
+    /// * `max_force`   - the maximum norm of the force applied to each body.
+    pub fn new(anchor1:     Anchor<Matrix>,
+               anchor2:     Anchor<Matrix>,
+               axis:        Vect,
+               rest_length: Scalar,
+               stiffness:   Scalar,
+               damping:     Scalar,
+               max_force:   Scalar)
+               -> AxisSpring {
+        assert!(!na::is_zero(&na::sqnorm(&axis)), "The axis of a spring must not be zero.");
+
+        AxisSpring {
+            anchor1:     anchor1,
+            anchor2:     anchor2,
+            axis:        na::normalize(&axis),
+            rest_length: rest_length,
+            stiffness:   stiffness,
+            damping:     damping,
+            max_force:   max_force,
+            pending:     None
+        }
+    }
+
+    /// The distance along the axis at which this spring is at rest.
+    #[inline]
+    pub fn rest_length(&self) -> Scalar {
+        self.rest_length.clone()
+    }
+
+    /// Sets the distance along the axis at which this spring is at rest.
+    #[inline]
+    pub fn set_rest_length(&mut self, rest_length: Scalar) {
+        self.rest_length = rest_length
+    }
+
+    /// The stiffness of this spring.
+    #[inline]
+    pub fn stiffness(&self) -> Scalar {
+        self.stiffness.clone()
+    }
+
+    /// Sets the stiffness of this spring.
+    #[inline]
+    pub fn set_stiffness(&mut self, stiffness: Scalar) {
+        self.stiffness = stiffness
+    }
+
+    /// The damping of this spring.
+    #[inline]
+    pub fn damping(&self) -> Scalar {
+        self.damping.clone()
+    }
+
+    /// Sets the damping of this spring.
+    #[inline]
+    pub fn set_damping(&mut self, damping: Scalar) {
+        self.damping = damping
+    }
+
+    /// The maximum norm of the force applied by this spring.
+    #[inline]
+    pub fn max_force(&self) -> Scalar {
+        self.max_force.clone()
+    }
+
+    /// Sets the maximum norm of the force applied by this spring.
+    #[inline]
+    pub fn set_max_force(&mut self, max_force: Scalar) {
+        self.max_force = max_force
+    }
+}
+
+impl ForceGenerator for AxisSpring {
+    fn apply(&mut self, _: Scalar, handle: &RigidBodyHandle, rb: &mut RigidBody) {
+        let local_axis  = self.axis.clone();
+        let rest_length = self.rest_length.clone();
+        let stiffness   = self.stiffness.clone();
+        let damping     = self.damping.clone();
+        let max_force   = self.max_force.clone();
+
+        apply_between(&self.anchor1, &self.anchor2, &mut self.pending, handle, rb, |s1, s2| {
+            let axis = s1.frame.rotate(&local_axis);
+            let p1   = s1.frame.transform(&na::orig());
+            let p2   = s2.frame.transform(&na::orig());
+            let r1   = p1 - s1.center_of_mass;
+            let r2   = p2 - s2.center_of_mass;
+            let ext  = na::dot(&(p2 - p1), &axis) - rest_length;
+
+            // The relative velocity of the attach points, along the axis.
+            let vel1 = na::dot(&s1.lin_vel, &axis) + na::dot(&s1.ang_vel, &na::cross(&r1, &axis));
+            let vel2 = na::dot(&s2.lin_vel, &axis) + na::dot(&s2.ang_vel, &na::cross(&r2, &axis));
+
+            let mut f = -(ext * stiffness + (vel2 - vel1) * damping);
+
+            if f > max_force {
+                f = max_force.clone();
+            }
+            else if f < -max_force {
+                f = -max_force;
+            }
+
+            let force = axis * f;
+
+            (-force, na::cross(&r1, &-force), force, na::cross(&r2, &force))
+        })
+    }
+}
+
+// The state of a body needed to compute the force applied by a spring attached to it.
+struct BodyState {
+    frame:          Matrix,
+    center_of_mass: Point,
+    lin_vel:        Vect,
+    ang_vel:        Orientation
+}
+
+fn body_state(anchor: &Anchor<Matrix>, key: uint, rb: &RigidBody) -> BodyState {
+    match anchor.body {
+        Some(ref b) if body_key(b) == key => state_of(rb, &anchor.position),
+        Some(ref b) => {
+            let other = b.borrow();
+
+            state_of(&*other, &anchor.position)
+        },
+        None => BodyState {
+            frame:          anchor.position.clone(),
+            center_of_mass: anchor.position.transform(&na::orig()),
+            lin_vel:        na::zero(),
+            ang_vel:        na::zero()
+        }
+    }
+}
+
+fn state_of(rb: &RigidBody, local: &Matrix) -> BodyState {
+    BodyState {
+        frame:          *rb.position() * *local,
+        center_of_mass: rb.center_of_mass().clone(),
+        lin_vel:        rb.lin_vel(),
+        ang_vel:        rb.ang_vel()
+    }
+}
+
+fn body_key(body: &RigidBodyHandle) -> uint {
+    body.deref() as *const RefCell<RigidBody> as uint
+}
+
+fn anchor_key(anchor: &Anchor<Matrix>) -> Option<uint> {
+    anchor.body.as_ref().map(|b| body_key(b))
+}
+
+// Applies to `rb` its share of the forces computed by `forces`, which returns the force and
+// torque applied to the first body, then those applied to the second one.
+//
+// Each body is integrated as soon as its forces are applied. Thus, the forces are computed only
+// once per step, when the first of the two bodies is visited, and the share of the other body is
+// kept in `pending` until it is visited.
+fn apply_between(anchor1: &Anchor<Matrix>,
+                 anchor2: &Anchor<Matrix>,
+                 pending: &mut Option<(uint, Vect, Orientation)>,
+                 handle:  &RigidBodyHandle,
+                 rb:      &mut RigidBody,
+                 forces:  |&BodyState, &BodyState| -> (Vect, Orientation, Vect, Orientation)) {
+    let key  = body_key(handle);
+    let key1 = anchor_key(anchor1);
+    let key2 = anchor_key(anchor2);
+
+    if (key1 != Some(key) && key2 != Some(key)) || key1 == key2 {
+        return;
+    }
+
+    match pending.take() {
+        Some((k, force, torque)) if k == key => {
+            rb.append_lin_force(force);
+            rb.append_ang_force(torque);
+
+            return;
+        },
+        _ => { }
+    }
+
+    let (f1, t1, f2, t2) = {
+        let s1 = body_state(anchor1, key, rb);
+        let s2 = body_state(anchor2, key, rb);
+
+        forces(&s1, &s2)
+    };
+
+    let (own_force, own_torque, other, other_force, other_torque) = if key1 == Some(key) {
+        (f1, t1, anchor2, f2, t2)
+    }
+    else {
+        (f2, t2, anchor1, f1, t1)
+    };
+
+    rb.append_lin_force(own_force);
+    rb.append_ang_force(own_torque);
+
+    match other.body {
+        Some(ref b) if b.borrow().can_move() => *pending = Some((body_key(b), other_force, other_torque)),
+        _ => { }
+    }
+}
+
+fn clamp_norm(v: Orientation, max: Scalar) -> Orientation {
+    let norm = na::norm(&v);
+
+    if norm > max {
+        v * (max / norm)
+    }
+    else {
+        v
+    }
+}
@@ -32,7 +32,9 @@ impl CCDBody {
 
 /// Handles Continuous Collision Detection.
 pub struct TranslationalCCDMotionClamping {
-    objects: HashMap<uint, CCDBody, UintTWHash>
+    objects:         HashMap<uint, CCDBody, UintTWHash>,
+    // The sensors swept by a CCD-enabled body during the last update, given first.
+    crossed_sensors: Vec<(RigidBodyHandle, RigidBodyHandle)>
 }
 
 impl TranslationalCCDMotionClamping {
@@ -40,7 +42,8 @@ impl TranslationalCCDMotionClamping {
     /// fast-moving rigid bodies.
     pub fn new() -> TranslationalCCDMotionClamping {
         TranslationalCCDMotionClamping {
-            objects: HashMap::new(UintTWHash::new())
+            objects:         HashMap::new(UintTWHash::new()),
+            crossed_sensors: Vec::new()
         }
     }
 
@@ -54,10 +57,31 @@ impl TranslationalCCDMotionClamping {
         self.objects.remove(&body.uid());
     }
 
+    /// Forgets the motion of the given rigid body since the last update.
+    ///
+    /// Use this when the body is teleported so that it is not swept along the way.
+    pub fn reset_motion_of(&mut self, body: &RigidBodyHandle) {
+        match self.objects.find_mut(&body.uid()) {
+            Some(o) => o.last_pos = body.borrow().position().translation(),
+            None    => { }
+        }
+    }
+
+    /// The pairs of a sensor, given first, and of a CCD-enabled body which swept it during the
+    /// last update, before being clamped at its time of impact if any.
+    ///
+    /// This includes the sensors the body only traversed: those do not overlap it at the end of
+    /// the step.
+    pub fn crossed_sensors(&self) -> &[(RigidBodyHandle, RigidBodyHandle)] {
+        self.crossed_sensors.as_slice()
+    }
+
     /// Update the time of impacts and apply motion clamping when necessary.
     pub fn update(&mut self, cw: &mut RigidBodyCollisionWorld) {
         let mut update_collision_world = false;
 
+        self.crossed_sensors.clear();
+
         // XXX: we should no do this in a sequential order because CCD betwen two fast, CCD-enabled
         // objects, will not work properly (it will be biased toward the first object).
         for o in self.objects.elements_mut().iter_mut() {
@@ -65,7 +89,8 @@ impl TranslationalCCDMotionClamping {
 
             let movement = brb1.position().translation() - o.value.last_pos;
 
-            if na::sqnorm(&movement) > o.value.sqthreshold {
+            // Sensors are traversed and do not traverse anything.
+            if na::sqnorm(&movement) > o.value.sqthreshold && !brb1.is_sensor() {
                 // Use CCD for this object.
                 let last_transform = na::append_translation(brb1.position(), &-movement);
                 let begin_aabb = brb1.shape_ref().aabb(&last_transform);
@@ -77,13 +102,30 @@ impl TranslationalCCDMotionClamping {
                  */
                 let mut min_toi = na::one::<Scalar>();
                 let mut toi_found = false;
+                let mut sensors   = Vec::new();
                 let dir = movement.clone();
 
                 let _eps: Scalar = Float::epsilon();
 
                 // FIXME: performing a convex-cast here would be much more efficient.
                 cw.interferences_with_aabb(&swept_aabb, |rb2| {
-                    if rb2.uid() != o.value.body.uid() {
+                    if rb2.uid() != o.value.body.uid() && rb2.borrow().is_sensor() {
+                        let brb2 = rb2.borrow();
+
+                        let toi = geometry::time_of_impact_internal::shape_against_shape(
+                            &last_transform,
+                            &dir,
+                            brb1.shape_ref(),
+                            brb2.position(),
+                            &na::zero(),
+                            brb2.shape_ref());
+
+                        match toi {
+                            Some(t) => sensors.push((rb2.clone(), t)),
+                            None    => { }
+                        }
+                    }
+                    else if rb2.uid() != o.value.body.uid() {
                         let brb2 = rb2.borrow();
 
                         let toi = geometry::time_of_impact_internal::shape_against_shape(
@@ -114,8 +156,22 @@ impl TranslationalCCDMotionClamping {
                  */
                 drop(brb1);
 
+                // The sensors reached after the impact are not crossed.
+                for (sensor, t) in sensors.into_iter() {
+                    if t <= min_toi {
+                        self.crossed_sensors.push((sensor, o.value.body.clone()));
+                    }
+                }
+
                 if toi_found {
-                    o.value.body.borrow_mut().append_translation(&(-dir * (na::one::<Scalar>() - min_toi)));
+                    let mut rb = o.value.body.borrow_mut();
+
+                    rb.append_translation(&(-dir * (na::one::<Scalar>() - min_toi)));
+
+                    if min_toi < na::one() {
+                        rb.last_step_flags_mut().set_ccd_clamped();
+                    }
+
                     o.value.accept_zero = false;
                 }
                 else {
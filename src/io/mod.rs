@@ -0,0 +1,9 @@
+//! Loading of external data into the physics world.
+
+pub use io::scene::{load_scene, BodyDescriptor};
+#[cfg(feature = "3d")]
+pub use io::obj::{load_obj, parse_obj, static_mesh_from_obj, convex_hull_from_obj};
+
+mod scene;
+#[cfg(feature = "3d")]
+mod obj;
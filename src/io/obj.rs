@@ -0,0 +1,133 @@
+//! Minimal Wavefront OBJ loader.
+
+use std::io::File;
+use std::sync::Arc;
+use std::collections::HashMap;
+use na;
+use ncollide::shape::{Mesh, Convex};
+use ncollide::utils::AsBytes;
+use object::RigidBody;
+use math::{Scalar, Point};
+
+/// Parses the content of a Wavefront OBJ file.
+///
+/// Only the vertex positions (`v`) and faces (`f`) are read, every other statement is ignored.
+/// Vertices with identical positions are welded together. Polygonal faces are triangulated as
+/// fans, and degenerate triangles are discarded.
+///
+/// Returns the vertex buffer and the index buffer (each consecutive triplet of indices is a
+/// triangle).
+pub fn parse_obj(content: &str) -> Result<(Vec<Point>, Vec<uint>), String> {
+    let mut raw_vertices = Vec::new();
+    let mut vertices     = Vec::new();
+    let mut welded       = Vec::new(); // maps raw vertex indices to welded vertex indices.
+    let mut positions    = HashMap::new();
+    let mut indices      = Vec::new();
+
+    for (l, line) in content.lines().enumerate() {
+        let mut words = line.words();
+
+        match words.next() {
+            Some("v") => {
+                let mut pt: Point = na::orig();
+
+                for i in range(0u, na::dim::<Point>()) {
+                    match words.next().and_then(|w| from_str::<Scalar>(w)) {
+                        Some(x) => pt[i] = x,
+                        None    => return Err(format!("line {}: invalid vertex.", l + 1))
+                    }
+                }
+
+                let key = pt.as_bytes().to_vec();
+                let id  = match positions.get(&key) {
+                    Some(id) => *id,
+                    None     => {
+                        vertices.push(pt.clone());
+                        vertices.len() - 1
+                    }
+                };
+
+                let _ = positions.insert(key, id);
+                raw_vertices.push(pt);
+                welded.push(id);
+            },
+            Some("f") => {
+                let mut face = Vec::new();
+
+                for w in words {
+                    // Only the position index of `v/vt/vn` is used.
+                    let id = match w.split('/').next().and_then(|i| from_str::<int>(i)) {
+                        Some(i) if i > 0 => i - 1,
+                        Some(i) if i < 0 => raw_vertices.len() as int + i, // relative index.
+                        _                => return Err(format!("line {}: invalid face index `{}`.", l + 1, w))
+                    };
+
+                    if id < 0 || id as uint >= raw_vertices.len() {
+                        return Err(format!("line {}: face index `{}` out of bounds.", l + 1, w))
+                    }
+
+                    face.push(welded[id as uint]);
+                }
+
+                for i in range(1u, if face.len() > 1 { face.len() - 1 } else { 0 }) {
+                    push_triangle(vertices.as_slice(), face[0], face[i], face[i + 1], &mut indices);
+                }
+            },
+            _ => { }
+        }
+    }
+
+    Ok((vertices, indices))
+}
+
+fn push_triangle(vertices: &[Point], a: uint, b: uint, c: uint, out: &mut Vec<uint>) {
+    if a == b || b == c || a == c {
+        return;
+    }
+
+    let area = na::norm(&na::cross(&(vertices[b] - vertices[a]), &(vertices[c] - vertices[a])));
+
+    if !na::is_zero(&area) {
+        out.push(a);
+        out.push(b);
+        out.push(c);
+    }
+}
+
+/// Loads the vertices and triangles of a Wavefront OBJ file.
+///
+/// See `parse_obj` for details.
+pub fn load_obj(path: &Path) -> Result<(Vec<Point>, Vec<uint>), String> {
+    match File::open(path).read_to_string() {
+        Ok(content) => parse_obj(content.as_slice()),
+        Err(e)      => Err(format!("unable to read `{}`: {}", path.display(), e))
+    }
+}
+
+/// Creates a static rigid body with the triangle mesh described by a Wavefront OBJ file.
+pub fn static_mesh_from_obj(path: &Path, restitution: Scalar, friction: Scalar) -> Result<RigidBody, String> {
+    let (vertices, indices) = try!(load_obj(path));
+
+    if indices.is_empty() {
+        return Err(format!("`{}` does not contain any valid triangle.", path.display()))
+    }
+
+    let mesh = Mesh::new(Arc::new(vertices), Arc::new(indices), None, None);
+
+    Ok(RigidBody::new_static(mesh, restitution, friction))
+}
+
+/// Creates a dynamic rigid body with the convex hull of the vertices of a Wavefront OBJ file.
+pub fn convex_hull_from_obj(path:        &Path,
+                            density:     Scalar,
+                            restitution: Scalar,
+                            friction:    Scalar)
+                            -> Result<RigidBody, String> {
+    let (vertices, _) = try!(load_obj(path));
+
+    if vertices.len() < na::dim::<Point>() + 1 {
+        return Err(format!("`{}` does not contain enough vertices for a convex hull.", path.display()))
+    }
+
+    RigidBody::try_new_dynamic(Convex::new(vertices), density, restitution, friction)
+}
@@ -0,0 +1,429 @@
+//! Loading of scenes described with JSON files.
+
+use std::io::File;
+use std::io::stdio;
+use std::num::Float;
+use std::sync::Arc;
+use std::collections::HashSet;
+use serialize::json;
+use serialize::json::Json;
+use na::{Pnt3, Translation, Rotation};
+use na;
+use ncollide::shape::{Shape, Ball, Cuboid, Cylinder, Cone, Plane};
+use ncollide::volumetric::Volumetric;
+use object::{RigidBody, RigidBodyHandle};
+use world::World;
+use utils::CompoundBuilder;
+use math::{Scalar, Point, Vect, Orientation, Matrix, AngularInertia};
+
+/// A body created by `load_scene` together with its rendering informations.
+pub struct BodyDescriptor {
+    /// The body added to the world.
+    pub body:  RigidBodyHandle,
+    /// The color the body should be rendered with, if specified by the scene.
+    pub color: Option<Pnt3<f32>>
+}
+
+/// Loads a scene described by a JSON file.
+///
+/// The file must contain an object with an optional `gravity` vector and a `bodies` array. Each
+/// body is an object with the following fields, all optional except `shape`:
+///
+/// * `shape`       - an object with a `type` field with one of the values `"ball"` (with a
+///                   `radius`), `"cuboid"` (with `half_extents`), `"plane"` (with a `normal`),
+///                   `"cylinder"` or `"cone"` (with a `half_height` and a `radius`), or
+///                   `"compound"` (with a `children` array of bodies, positioned wrt. the parent).
+/// * `dynamic`     - whether the body can move. Defaults to `false`.
+/// * `density`     - the density of a dynamic body. Defaults to `1.0`.
+/// * `position`    - the translation of the body.
+/// * `orientation` - the rotation of the body as an axis-angle vector.
+/// * `restitution` - the restitution coefficient. Defaults to `0.3`.
+/// * `friction`    - the friction coefficient. Defaults to `0.5`.
+/// * `color`       - the RGB color of the body, with components in [0, 1].
+/// * `groups`      - an array with the names of the groups the body is part of. The groups are
+///                   created when first named, see `World::find_group`.
+/// * `uid`         - the unique identifier of the body, see `RigidBody::uid`. Two bodies cannot
+///                   have the same. The world gives one to the bodies without it.
+///
+/// Vectors are arrays of numbers. Unknown fields are reported on the standard error output and
+/// then ignored.
+pub fn load_scene(path: &Path) -> Result<(World, Vec<BodyDescriptor>), String> {
+    let content = match File::open(path).read_to_string() {
+        Ok(content) => content,
+        Err(e)      => return Err(format!("unable to read `{}`: {}", path.display(), e))
+    };
+
+    let scene = match json::from_str(content.as_slice()) {
+        Ok(scene) => scene,
+        Err(e)    => return Err(format!("`{}` is not a valid JSON file: {}", path.display(), e))
+    };
+
+    let mut world  = World::new();
+    let mut bodies = Vec::new();
+
+    try!(check_fields(&scene, &["gravity", "bodies"], "scene"));
+
+    match scene.find("gravity") {
+        Some(g) => world.set_gravity(try!(vector(g, "gravity"))),
+        None    => { }
+    }
+
+    match scene.find("bodies").map(|bs| bs.as_array()) {
+        Some(Some(bs)) => {
+            let mut free_uid = try!(first_free_uid(bs.as_slice()));
+
+            for b in bs.iter() {
+                let mut rb = try!(body(b));
+                let color  = match b.find("color") {
+                    Some(c) => Some(try!(color(c))),
+                    None    => None
+                };
+                let names  = try!(group_names(b));
+
+                // The bodies without an uid are given the ones after the largest of the scene.
+                match b.find("uid").and_then(|uid| uid.as_u64()) {
+                    Some(uid) => rb.set_uid(uid),
+                    None      => {
+                        rb.set_uid(free_uid);
+                        free_uid = free_uid + 1;
+                    }
+                }
+
+                let handle = world.add_body(rb);
+
+                for name in names.iter() {
+                    let group = match world.find_group(name.as_slice()) {
+                        Some(group) => group,
+                        None        => world.create_group(name.as_slice())
+                    };
+
+                    world.add_to_group(&group, &handle);
+                }
+
+                bodies.push(BodyDescriptor {
+                    body:  handle,
+                    color: color
+                });
+            }
+        },
+        Some(_) => return Err("`bodies` must be an array.".to_string()),
+        None    => { }
+    }
+
+    Ok((world, bodies))
+}
+
+/// Checks the `uid` fields of the bodies, and gives the smallest uid larger than all of them.
+fn first_free_uid(bodies: &[Json]) -> Result<u64, String> {
+    let mut uids = HashSet::new();
+
+    for b in bodies.iter() {
+        match b.find("uid") {
+            Some(uid) => {
+                let uid = match uid.as_u64() {
+                    Some(uid) if uid != 0 => uid,
+                    _ => return Err("`uid` must be a positive integer.".to_string())
+                };
+
+                if !uids.insert(uid) {
+                    return Err(format!("two bodies have the uid {}.", uid));
+                }
+            },
+            None => { }
+        }
+    }
+
+    Ok(uids.iter().fold(0, |max, &uid| if uid > max { uid } else { max }) + 1)
+}
+
+fn warn(msg: &str) {
+    let _ = stdio::stderr().write_line(format!("Warning: {}", msg).as_slice());
+}
+
+fn check_fields(obj: &Json, known: &[&str], what: &str) -> Result<(), String> {
+    match obj.as_object() {
+        Some(fields) => {
+            for (name, _) in fields.iter() {
+                if !known.iter().any(|k| *k == name.as_slice()) {
+                    warn(format!("unknown field `{}` ignored on {}.", name, what).as_slice());
+                }
+            }
+
+            Ok(())
+        },
+        None => Err(format!("the {} must be a JSON object.", what))
+    }
+}
+
+fn scalar(json: &Json, what: &str) -> Result<Scalar, String> {
+    match json.as_f64() {
+        Some(x) => Ok(na::cast(x)),
+        None    => Err(format!("`{}` must be a number.", what))
+    }
+}
+
+fn scalar_or(obj: &Json, field: &str, default: f64) -> Result<Scalar, String> {
+    match obj.find(field) {
+        Some(x) => scalar(x, field),
+        None    => Ok(na::cast(default))
+    }
+}
+
+fn components(json: &Json, dim: uint, what: &str) -> Result<Vec<Scalar>, String> {
+    match json.as_array() {
+        Some(xs) if xs.len() == dim => {
+            let mut res = Vec::with_capacity(dim);
+
+            for x in xs.iter() {
+                res.push(try!(scalar(x, what)));
+            }
+
+            Ok(res)
+        },
+        _ => Err(format!("`{}` must be an array of {} numbers.", what, dim))
+    }
+}
+
+fn vector(json: &Json, what: &str) -> Result<Vect, String> {
+    let xs = try!(components(json, na::dim::<Vect>(), what));
+    let mut res: Vect = na::zero();
+
+    for (i, x) in xs.into_iter().enumerate() {
+        res[i] = x;
+    }
+
+    Ok(res)
+}
+
+fn orientation(json: &Json, what: &str) -> Result<Orientation, String> {
+    let xs = try!(components(json, na::dim::<Orientation>(), what));
+    let mut res: Orientation = na::zero();
+
+    for (i, x) in xs.into_iter().enumerate() {
+        res[i] = x;
+    }
+
+    Ok(res)
+}
+
+fn color(json: &Json) -> Result<Pnt3<f32>, String> {
+    match json.as_array() {
+        Some(cs) if cs.len() == 3 => {
+            let mut res = Pnt3::new(0.0, 0.0, 0.0);
+
+            for (i, c) in cs.iter().enumerate() {
+                match c.as_f64() {
+                    Some(c) => res[i] = c as f32,
+                    None    => return Err("`color` must be an array of 3 numbers.".to_string())
+                }
+            }
+
+            Ok(res)
+        },
+        _ => Err("`color` must be an array of 3 numbers.".to_string())
+    }
+}
+
+/// The transformation described by the `position` and `orientation` fields of an object.
+fn transform(obj: &Json) -> Result<Matrix, String> {
+    let mut res: Matrix = na::one();
+
+    match obj.find("orientation") {
+        Some(o) => res.append_rotation(&try!(orientation(o, "orientation"))),
+        None    => { }
+    }
+
+    match obj.find("position") {
+        Some(p) => res.append_translation(&try!(vector(p, "position"))),
+        None    => { }
+    }
+
+    Ok(res)
+}
+
+static BODY_FIELDS: [&'static str, ..10] = [
+    "shape", "dynamic", "density", "position", "orientation", "restitution", "friction", "color",
+    "groups", "uid"
+];
+
+fn group_names(obj: &Json) -> Result<Vec<String>, String> {
+    match obj.find("groups") {
+        Some(gs) => match gs.as_array() {
+            Some(gs) => {
+                let mut res = Vec::with_capacity(gs.len());
+
+                for g in gs.iter() {
+                    match g.as_string() {
+                        Some(g) => res.push(g.to_string()),
+                        None    => return Err("`groups` must be an array of strings.".to_string())
+                    }
+                }
+
+                Ok(res)
+            },
+            None => Err("`groups` must be an array of strings.".to_string())
+        },
+        None => Ok(Vec::new())
+    }
+}
+
+fn body(obj: &Json) -> Result<RigidBody, String> {
+    try!(check_fields(obj, BODY_FIELDS.as_slice(), "body"));
+
+    let dynamic = match obj.find("dynamic") {
+        Some(d) => match d.as_boolean() {
+            Some(d) => d,
+            None    => return Err("`dynamic` must be a boolean.".to_string())
+        },
+        None => false
+    };
+
+    let density     = try!(scalar_or(obj, "density", 1.0));
+    let restitution = try!(scalar_or(obj, "restitution", 0.3));
+    let friction    = try!(scalar_or(obj, "friction", 0.5));
+    let shape       = match obj.find("shape") {
+        Some(s) => s,
+        None    => return Err("a body must have a `shape`.".to_string())
+    };
+
+    let mut rb = match try!(shape_type(shape)) {
+        "plane" => {
+            if dynamic {
+                return Err("a plane cannot be dynamic.".to_string())
+            }
+
+            try!(check_fields(shape, &["type", "normal"], "plane"));
+
+            let normal = match shape.find("normal") {
+                Some(n) => try!(vector(n, "normal")),
+                None    => return Err("a plane must have a `normal`.".to_string())
+            };
+
+            RigidBody::new_static(Plane::new(na::normalize(&normal)), restitution, friction)
+        },
+        "compound" => {
+            let mut builder = CompoundBuilder::new();
+
+            try!(push_shape(shape, na::one(), density.clone(), &mut builder));
+
+            // The children may have their own densities.
+            let (compound, props) = builder.build();
+            let compound          = Arc::new(box compound as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>);
+
+            try!(RigidBody::try_new(compound, if dynamic { Some(props) } else { None }, restitution, friction))
+        },
+        _ => {
+            // Simple shapes are parsed like compound children, but the compound indirection is
+            // removed afterward.
+            let mut builder = CompoundBuilder::new();
+
+            try!(push_shape(shape, na::one(), density.clone(), &mut builder));
+
+            let (compound, props) = builder.build();
+            let props             = if dynamic { Some(props) } else { None };
+            let (_, ref s)        = compound.shapes()[0];
+
+            try!(RigidBody::try_new(s.clone(), props, restitution, friction))
+        }
+    };
+
+    rb.set_transformation(try!(transform(obj)));
+
+    Ok(rb)
+}
+
+fn shape_type(shape: &Json) -> Result<&str, String> {
+    match shape.find("type").and_then(|t| t.as_string()) {
+        Some(t) => Ok(t),
+        None    => Err("a shape must have a `type` string.".to_string())
+    }
+}
+
+fn push_shape(shape:   &Json,
+              delta:   Matrix,
+              density: Scalar,
+              out:     &mut CompoundBuilder)
+              -> Result<(), String> {
+    match try!(shape_type(shape)) {
+        "ball" => {
+            try!(check_fields(shape, &["type", "radius"], "ball"));
+            let radius = try!(required_scalar(shape, "radius"));
+            push(out, delta, Ball::new(radius), density);
+        },
+        "cuboid" => {
+            try!(check_fields(shape, &["type", "half_extents"], "cuboid"));
+            let half_extents = match shape.find("half_extents") {
+                Some(h) => try!(vector(h, "half_extents")),
+                None    => return Err("a cuboid must have `half_extents`.".to_string())
+            };
+            push(out, delta, Cuboid::new(half_extents), density);
+        },
+        "cylinder" => {
+            try!(check_fields(shape, &["type", "half_height", "radius"], "cylinder"));
+            let half_height = try!(required_scalar(shape, "half_height"));
+            let radius      = try!(required_scalar(shape, "radius"));
+            push(out, delta, Cylinder::new(half_height, radius), density);
+        },
+        "cone" => {
+            try!(check_fields(shape, &["type", "half_height", "radius"], "cone"));
+            let half_height = try!(required_scalar(shape, "half_height"));
+            let radius      = try!(required_scalar(shape, "radius"));
+            push(out, delta, Cone::new(half_height, radius), density);
+        },
+        "compound" => {
+            try!(check_fields(shape, &["type", "children"], "compound"));
+
+            match shape.find("children").and_then(|c| c.as_array()) {
+                Some(children) if !children.is_empty() => {
+                    for child in children.iter() {
+                        try!(check_fields(child, &["shape", "density", "position", "orientation"], "compound child"));
+
+                        let child_shape = match child.find("shape") {
+                            Some(s) => s,
+                            None    => return Err("a compound child must have a `shape`.".to_string())
+                        };
+
+                        if shape_type(child_shape) == Ok("plane") {
+                            return Err("a plane cannot be part of a compound.".to_string())
+                        }
+
+                        let child_density = match child.find("density") {
+                            Some(d) => try!(scalar(d, "density")),
+                            None    => density.clone()
+                        };
+
+                        try!(push_shape(child_shape, delta * try!(transform(child)), child_density, out));
+                    }
+                },
+                _ => return Err("a compound must have a non-empty `children` array.".to_string())
+            }
+        },
+        t => return Err(format!("unknown shape type `{}`.", t))
+    }
+
+    Ok(())
+}
+
+fn required_scalar(obj: &Json, field: &str) -> Result<Scalar, String> {
+    match obj.find(field) {
+        Some(x) => {
+            let x = try!(scalar(x, field));
+
+            if x > na::zero() && x.is_finite() {
+                Ok(x)
+            }
+            else {
+                Err(format!("`{}` must be positive.", field))
+            }
+        },
+        None => Err(format!("missing field `{}`.", field))
+    }
+}
+
+fn push<S>(out:     &mut CompoundBuilder,
+           delta:   Matrix,
+           shape:   S,
+           density: Scalar)
+    where S: Send + Sync + Shape<Scalar, Point, Vect, Matrix> + Volumetric<Scalar, Point, AngularInertia> {
+    out.push_shape(delta, shape, density)
+}
@@ -38,6 +38,23 @@ Simply add the following to your `Cargo.toml` file:
 git = "https://github.com/sebcrozet/nphysics"
 ```
 
+Depending on the accuracy or the dimension that you need, `nphysics3df32` may
+be replaced by:
+
+* `nphysics2df32` − for 2d physics and 32 bits precision.
+* `nphysics3df32` − for 3d physics and 32 bits precision.
+* `nphysics2df64` − for 2d physics and 64 bits precision.
+* `nphysics3df64` − for 3d physics and 64 bits precision.
+
+Those crates are distinct, so several of them may be used by the same program at the
+same time, e.g. to run a 3d world together with a 2d overlay simulation. Their types
+are then accessible with different paths like `nphysics3df32::world::World` and
+`nphysics2df32::world::World`. The `mixed_dimensions` example shows how.
+
+The 64 bits versions are recommended for large worlds or long simulations where the
+accumulated rounding errors of 32 bits floating point numbers become visible. The
+`precision` example compares both.
+
 ## Features
 - static and dynamic rigid bodies
 - common convex primitives: cone, box, ball, cylinder
@@ -48,6 +65,7 @@ git = "https://github.com/sebcrozet/nphysics"
 - swept sphere based continuous collision detection
 - ball-in-socket joint
 - fixed joint
+- distance joint
 
 ## What is missing?
 **nphysics** is a very young library and needs to learn a lot of things to
@@ -92,6 +110,8 @@ extern crate serialize;
 extern crate collections;
 extern crate "nalgebra" as na;
 extern crate ncollide;
+#[cfg(feature = "step_stats")]
+extern crate time;
 #[cfg(test)]
 extern crate test;
 
@@ -107,6 +127,8 @@ pub mod object;
 
 pub mod utils;
 
+pub mod io;
+
 
 /// Compilation flags dependent aliases for mathematical types.
 #[cfg(feature = "3d")]
@@ -1,5 +1,15 @@
-//! Rigid bodies.
+//! Rigid bodies and modifier volumes.
 
 pub use object::rigid_body::{RigidBody, RigidBodyHandle, ActivationState, RigidBodyState};
+pub use object::sub_shape::{SubShapeId, sub_shape_hit_by_ray, sub_shape};
+pub use object::shape_validation::validate_shape;
+pub use object::shape_desc::{ShapeDesc, shape_desc};
+pub use object::modifier_volume::{ModifierVolume, ModifierVolumeHandle, Containment, Modifier};
+pub use object::step_flags::StepFlags;
 
 mod rigid_body;
+mod sub_shape;
+mod shape_validation;
+mod shape_desc;
+mod modifier_volume;
+mod step_flags;
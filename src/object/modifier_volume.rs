@@ -0,0 +1,204 @@
+use std::rc::Rc;
+use std::mem;
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::collections::HashMap;
+use na;
+use ncollide::bounding_volume::AABB;
+use ncollide::shape::Shape;
+use ncollide::ray::{Ray, RayCast};
+use integration::ForceGenerator;
+use object::{RigidBody, RigidBodyHandle};
+use math::{Scalar, Point, Vect, Matrix};
+
+/// A modifier volume shared with the physics world.
+pub type ModifierVolumeHandle = Rc<RefCell<ModifierVolume>>;
+
+/// How a body is determined to be inside a modifier volume.
+#[deriving(Clone, Show, PartialEq)]
+pub enum Containment {
+    /// The center of mass of the body is inside the shape of the volume.
+    ///
+    /// The shape must be convex, or a compound of convex shapes.
+    CenterOfMass,
+    /// The bounding box of the body overlaps the bounding box of the volume.
+    AABBOverlap
+}
+
+/// The effect of a modifier volume on the dynamic bodies inside of it.
+pub enum Modifier {
+    /// Multiplies the gravity by the given factor, e.g., zero for a zero-gravity room.
+    ///
+    /// The factors of several overlapping volumes are multiplied together.
+    GravityScale(Scalar),
+    /// Damps the linear and angular velocities with the given coefficients, e.g., for water.
+    ///
+    /// The velocities are divided by `1 + coefficient * dt` at each step.
+    Damping(Scalar, Scalar),
+    /// Applies a constant force at the center of mass, e.g., for a wind tunnel.
+    Force(Vect),
+    /// Multiplies the time scale of the bodies by the given positive factor, e.g., `0.5` for a
+    /// slow-motion bubble. See `RigidBody::set_time_scale`.
+    ///
+    /// The factors of several overlapping volumes are multiplied together.
+    TimeScale(Scalar),
+    /// Applies a user-defined force generator.
+    Custom(Box<ForceGenerator + 'static>)
+}
+
+/// A region of space modifying the motion of the dynamic bodies inside of it.
+///
+/// The bodies inside each volume are found at the beginning of each step of the world, which
+/// then emits the `VolumeEntered` and `VolumeLeft` events. The modifier is applied to the active
+/// bodies inside the volume during the integration, after the gravity. Changing the modifier, the
+/// position, or the containment mode of a volume wakes up the bodies inside of it at the next
+/// step, and so does entering or leaving it.
+pub struct ModifierVolume {
+    shape:       Arc<Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>>,
+    position:    Matrix,
+    containment: Containment,
+    modifier:    Modifier,
+    changed:     bool,
+    inside:      HashMap<uint, RigidBodyHandle>
+}
+
+impl ModifierVolume {
+    /// Creates a new modifier volume with the given shape and position.
+    pub fn new<S>(shape: S, position: Matrix, containment: Containment, modifier: Modifier) -> ModifierVolume
+        where S: Send + Sync + Shape<Scalar, Point, Vect, Matrix> {
+        check_modifier(&modifier);
+
+        ModifierVolume {
+            shape:       Arc::new(box shape as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>),
+            position:    position,
+            containment: containment,
+            modifier:    modifier,
+            changed:     true,
+            inside:      HashMap::new()
+        }
+    }
+
+    /// The shape of this volume.
+    #[inline]
+    pub fn shape_ref(&self) -> &Shape<Scalar, Point, Vect, Matrix> {
+        &***self.shape
+    }
+
+    /// The position of this volume.
+    #[inline]
+    pub fn position(&self) -> &Matrix {
+        &self.position
+    }
+
+    /// Moves this volume.
+    pub fn set_position(&mut self, position: Matrix) {
+        self.position = position;
+        self.changed  = true
+    }
+
+    /// How the bodies are determined to be inside this volume.
+    #[inline]
+    pub fn containment(&self) -> Containment {
+        self.containment.clone()
+    }
+
+    /// Sets how the bodies are determined to be inside this volume.
+    pub fn set_containment(&mut self, containment: Containment) {
+        self.containment = containment;
+        self.changed     = true
+    }
+
+    /// The effect of this volume on the bodies inside of it.
+    #[inline]
+    pub fn modifier(&self) -> &Modifier {
+        &self.modifier
+    }
+
+    /// Sets the effect of this volume on the bodies inside of it.
+    pub fn set_modifier(&mut self, modifier: Modifier) {
+        check_modifier(&modifier);
+
+        self.modifier = modifier;
+        self.changed  = true
+    }
+
+    /// Whether `body` was inside this volume at the beginning of the last step.
+    pub fn contains(&self, body: &RigidBodyHandle) -> bool {
+        self.inside.contains_key(&(body.deref() as *const RefCell<RigidBody> as uint))
+    }
+
+    /// The bodies inside this volume at the beginning of the last step.
+    pub fn bodies(&self) -> Vec<RigidBodyHandle> {
+        self.inside.values().map(|b| b.clone()).collect()
+    }
+
+    #[doc(hidden)]
+    pub fn aabb(&self) -> AABB<Point> {
+        self.shape.aabb(&self.position)
+    }
+
+    #[doc(hidden)]
+    pub fn accepts(&self, rb: &RigidBody) -> bool {
+        match self.containment {
+            // The broad phase already checked the bounding boxes overlap.
+            Containment::AABBOverlap  => true,
+            Containment::CenterOfMass => {
+                // A solid ray starting inside of a convex shape hits it right away.
+                let mut dir: Vect = na::zero();
+                dir[0] = na::one();
+
+                let ray = Ray::new(rb.center_of_mass().clone(), dir);
+
+                match self.shape.toi_with_transform_and_ray(&self.position, &ray, true) {
+                    Some(toi) => toi == na::zero(),
+                    None      => false
+                }
+            }
+        }
+    }
+
+    // Replaces the bodies inside this volume, and returns those which entered and left it.
+    #[doc(hidden)]
+    pub fn update_inside(&mut self, bodies: Vec<RigidBodyHandle>) -> (Vec<RigidBodyHandle>, Vec<RigidBodyHandle>) {
+        let mut inside  = HashMap::new();
+        let mut entered = Vec::new();
+
+        for b in bodies.into_iter() {
+            let key = b.deref() as *const RefCell<RigidBody> as uint;
+
+            if self.inside.remove(&key).is_none() {
+                entered.push(b.clone());
+            }
+
+            let _ = inside.insert(key, b);
+        }
+
+        let left = mem::replace(&mut self.inside, inside).into_iter().map(|(_, b)| b).collect();
+
+        (entered, left)
+    }
+
+    #[doc(hidden)]
+    pub fn remove_body(&mut self, body: &RigidBodyHandle) -> bool {
+        self.inside.remove(&(body.deref() as *const RefCell<RigidBody> as uint)).is_some()
+    }
+
+    #[doc(hidden)]
+    pub fn modifier_mut(&mut self) -> &mut Modifier {
+        &mut self.modifier
+    }
+
+    #[doc(hidden)]
+    pub fn take_changed(&mut self) -> bool {
+        let res = self.changed;
+        self.changed = false;
+        res
+    }
+}
+
+fn check_modifier(modifier: &Modifier) {
+    match *modifier {
+        Modifier::TimeScale(ref s) => assert!(*s > na::zero(), "The time scale of a modifier volume must be positive."),
+        _ => { }
+    }
+}
@@ -1,15 +1,41 @@
 use std::rc::Rc;
+use std::num::Float;
 use std::sync::Arc;
 use std::cell::RefCell;
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use std::io::stdio;
 use na::{Transformation, Translation, Rotation, Bounded};
 use na;
 use na::Transform;
+#[cfg(feature = "2d")]
+use na::Vec2;
 use ncollide::bounding_volume::{HasBoundingVolume, BoundingVolume, AABB, HasAABB};
-use ncollide::shape::Shape;
+use ncollide::shape::{Shape, Compound};
 use ncollide::volumetric::{InertiaTensor, Volumetric};
 use ncollide::world::CollisionGroups;
+use ncollide::ray::Ray;
+use object::sub_shape;
+use object::shape_validation;
+use object::shape_desc;
+use object::{SubShapeId, ShapeDesc, StepFlags};
+use resolution::SpringDamper;
 use math::{Scalar, Point, Vect, Orientation, Matrix, AngularInertia};
 
+// The smallest mass of a dynamic body. Lighter bodies are made heavier.
+static MIN_MASS: f64 = 1.0e-6;
+
+// The velocity, due to the angular velocity `ang_vel`, of the point at `r` from the center of mass.
+#[cfg(feature = "3d")]
+fn rotational_velocity(ang_vel: &Orientation, r: &Vect) -> Vect {
+    na::cross(ang_vel, r)
+}
+
+#[cfg(feature = "2d")]
+fn rotational_velocity(ang_vel: &Orientation, r: &Vect) -> Vect {
+    Vec2::new(-ang_vel.x * r.y, ang_vel.x * r.x)
+}
+
 /// A shared, mutable, rigid body.
 pub type RigidBodyHandle = Rc<RefCell<RigidBody>>;
 
@@ -52,6 +78,13 @@ pub struct RigidBody {
     state:                RigidBodyState,
     shape:                Arc<Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>>,
     local_to_world:       Matrix,
+    prev_local_to_world:  Matrix,
+    // The position last reported as moved to the world.
+    reported_position:    Matrix,
+    // The position and velocities at the end of the last step.
+    simulated_state:      (Matrix, Vect, Orientation),
+    // Whether the friction or the restitution changed since the end of the last step.
+    material_changed:     bool,
     lin_vel:              Vect,
     ang_vel:              Orientation,
     inv_mass:             Scalar,
@@ -69,33 +102,74 @@ pub struct RigidBody {
     lin_acc_scale:        Vect,        // FIXME: find a better way of doing that.
     ang_acc_scale:        Orientation, // FIXME: find a better way of doing that.
     margin:               Scalar,
-    collision_groups:     CollisionGroups
+    collision_groups:     CollisionGroups,
+    one_way:              Option<Vect>,
+    sensor:               bool,
+    predicted_motion:     Vect,
+    part_margins:         Vec<Option<Scalar>>,
+    contact_spring:       Option<SpringDamper>,
+    // The padding of the bounding volume given by the world, and the one overriding it.
+    default_padding:      Scalar,
+    padding:              Option<Scalar>,
+    lin_damping:          Scalar,
+    ang_damping:          Scalar,
+    max_ang_vel:          Option<Scalar>,
+    stall_speed:          Option<Scalar>,
+    frozen:               bool,
+    uid:                  u64,
+    time_scale:           Scalar,
+    // The product of the time scales of the modifier volumes containing this body.
+    volume_time_scale:    Scalar,
+    // The accelerations used by the last step of a second-order integrator.
+    integrated_acc:       Option<(Vect, Orientation)>,
+    last_step_flags:      StepFlags
 }
 
 impl Clone for RigidBody {
     fn clone(&self) -> RigidBody {
         RigidBody {
-            state:             self.state.clone(),
-            shape:             self.shape.clone(),
-            local_to_world:    self.local_to_world.clone(),
-            lin_vel:           self.lin_vel.clone(),
-            ang_vel:           self.ang_vel.clone(),
-            inv_mass:          self.inv_mass.clone(),
-            ls_inv_inertia:    self.ls_inv_inertia.clone(),
-            inv_inertia:       self.inv_inertia.clone(),
-            ls_center_of_mass: self.ls_center_of_mass.clone(),
-            center_of_mass:    self.center_of_mass.clone(),
-            lin_acc:           self.lin_acc.clone(),
-            ang_acc:           self.ang_acc.clone(),
-            restitution:       self.restitution.clone(),
-            friction:          self.friction.clone(),
-            index:             self.index.clone(),
-            activation_state:  self.activation_state.clone(),
-            sleep_threshold:   self.sleep_threshold.clone(),
-            lin_acc_scale:     self.lin_acc_scale.clone(),
-            ang_acc_scale:     self.ang_acc_scale.clone(),
-            margin:            self.margin.clone(),
-            collision_groups:  self.collision_groups.clone()
+            state:               self.state.clone(),
+            shape:               self.shape.clone(),
+            local_to_world:      self.local_to_world.clone(),
+            prev_local_to_world: self.prev_local_to_world.clone(),
+            reported_position:   self.reported_position.clone(),
+            simulated_state:     self.simulated_state.clone(),
+            material_changed:    self.material_changed,
+            lin_vel:             self.lin_vel.clone(),
+            ang_vel:             self.ang_vel.clone(),
+            inv_mass:            self.inv_mass.clone(),
+            ls_inv_inertia:      self.ls_inv_inertia.clone(),
+            inv_inertia:         self.inv_inertia.clone(),
+            ls_center_of_mass:   self.ls_center_of_mass.clone(),
+            center_of_mass:      self.center_of_mass.clone(),
+            lin_acc:             self.lin_acc.clone(),
+            ang_acc:             self.ang_acc.clone(),
+            restitution:         self.restitution.clone(),
+            friction:            self.friction.clone(),
+            index:               self.index.clone(),
+            activation_state:    self.activation_state.clone(),
+            sleep_threshold:     self.sleep_threshold.clone(),
+            lin_acc_scale:       self.lin_acc_scale.clone(),
+            ang_acc_scale:       self.ang_acc_scale.clone(),
+            margin:              self.margin.clone(),
+            collision_groups:    self.collision_groups.clone(),
+            one_way:             self.one_way.clone(),
+            sensor:              self.sensor,
+            predicted_motion:    self.predicted_motion.clone(),
+            part_margins:        self.part_margins.clone(),
+            default_padding:     self.default_padding.clone(),
+            padding:             self.padding.clone(),
+            lin_damping:         self.lin_damping.clone(),
+            ang_damping:         self.ang_damping.clone(),
+            max_ang_vel:         self.max_ang_vel.clone(),
+            stall_speed:         self.stall_speed.clone(),
+            frozen:              self.frozen,
+            uid:                 self.uid,
+            time_scale:          self.time_scale.clone(),
+            volume_time_scale:   self.volume_time_scale.clone(),
+            integrated_acc:      self.integrated_acc.clone(),
+            last_step_flags:     self.last_step_flags.clone(),
+            contact_spring:      self.contact_spring.clone()
         }
     }
 }
@@ -116,6 +190,40 @@ impl RigidBody {
         self.activation_state = ActivationState::Deleted;
     }
 
+    /// The unique identifier of this body within the world it is part of.
+    ///
+    /// Unlike the handles, this identifies the body in save files or network messages. It is given
+    /// by the world when the body is added, and zero before. The world never gives the same
+    /// identifier twice, even after the body is removed, and a body keeps its identifier when it
+    /// is cloned or added back.
+    #[inline]
+    pub fn uid(&self) -> u64 {
+        self.uid
+    }
+
+    /// Sets the unique identifier of this body, e.g., to restore a body saved with it.
+    ///
+    /// This must be done before the body is added to a world. The world gives another identifier
+    /// to the body if this one is zero or already used by one of its bodies.
+    #[inline]
+    pub fn set_uid(&mut self, uid: u64) {
+        self.uid = uid
+    }
+
+    /// Whether this body is left out of the current partial step of the world.
+    ///
+    /// A frozen dynamic body cannot move until it is thawed, see `World::step_islands`.
+    #[inline]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen
+    }
+
     /// Updates the inertia tensor of this rigid body.
     #[inline]
     fn update_inertia_tensor(&mut self) {
@@ -130,29 +238,311 @@ impl RigidBody {
     }
 
     /// Gets a reference to this body's transform.
+    ///
+    /// This is the transformation from the body's local frame (the frame its shape is expressed
+    /// in) to the global frame. Its origin is not necessarily the center of mass: use
+    /// `center_of_mass` or `local_to_com_transform` for that.
     #[inline]
     pub fn position(&self) -> &Matrix {
         &self.local_to_world
     }
 
+    /// The position of this body at the beginning of the last step.
+    ///
+    /// This is the same as `position` if the body did not move during the last step, or if it was
+    /// teleported with `set_transformation`, `set_translation` or `set_rotation` since then.
+    #[inline]
+    pub fn previous_position(&self) -> &Matrix {
+        &self.prev_local_to_world
+    }
+
+    /// Interpolates between the previous and the current position of this body.
+    ///
+    /// This lets a renderer running faster than the simulation display smooth motions: `alpha`
+    /// is the fraction of the timestep elapsed since the last step, `0` giving the previous
+    /// position and `1` the current one. The translation is interpolated linearly, and the
+    /// rotation along the shortest arc.
+    pub fn interpolated_position(&self, alpha: Scalar) -> Matrix {
+        let prev = &self.prev_local_to_world;
+        let curr = &self.local_to_world;
+
+        // The rotation bringing the previous orientation to the current one.
+        let rel     = na::inv(prev).unwrap() * *curr;
+        let mut res = *prev * na::append_rotation(&na::one::<Matrix>(), &(rel.rotation() * alpha));
+
+        res.set_translation(prev.translation() + (curr.translation() - prev.translation()) * alpha);
+
+        res
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn save_previous_position(&mut self) {
+        self.prev_local_to_world = self.local_to_world.clone()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn save_simulated_state(&mut self) {
+        self.simulated_state  = (self.local_to_world.clone(), self.lin_vel.clone(), self.ang_vel.clone());
+        self.material_changed = false;
+    }
+
+    /// Whether the position and the velocities of this rigid body are finite.
+    ///
+    /// They become infinite or NaN when the simulation diverges, e.g., because of an invalid mass.
+    pub fn is_finite(&self) -> bool {
+        na::sqnorm(&self.local_to_world.translation()).is_finite() &&
+        na::sqnorm(&self.local_to_world.rotation()).is_finite() &&
+        na::sqnorm(&self.lin_vel).is_finite() &&
+        na::sqnorm(&self.ang_vel).is_finite()
+    }
+
+    // Returns `None` if the position, the velocities, and the material of this body did not change
+    // since the end of the last step. Otherwise, returns the magnitude of the change of velocity,
+    // which is zero if only the material changed, or the maximum value of `Scalar` if the body has
+    // been moved.
+    #[doc(hidden)]
+    pub fn external_change(&self) -> Option<Scalar> {
+        let (ref pos, ref lin_vel, ref ang_vel) = self.simulated_state;
+
+        if *pos != self.local_to_world {
+            Some(Bounded::max_value())
+        }
+        else if *lin_vel != self.lin_vel || *ang_vel != self.ang_vel {
+            Some(na::norm(&(self.lin_vel - *lin_vel)) + na::norm(&(self.ang_vel - *ang_vel)))
+        }
+        else if self.material_changed {
+            Some(na::zero())
+        }
+        else {
+            None
+        }
+    }
+
+    // Returns `true` if this body moved by more than `eps` since the last time this returned
+    // `true`, and remembers the current position if so.
+    #[doc(hidden)]
+    pub fn update_reported_position(&mut self, eps: &Scalar) -> bool {
+        if na::approx_eq_eps(&self.reported_position, &self.local_to_world, eps) {
+            false
+        }
+        else {
+            self.reported_position = self.local_to_world.clone();
+
+            true
+        }
+    }
+
     /// Gets a reference to this body's shape.
     #[inline]
     pub fn shape_ref(&self) -> &Shape<Scalar, Point, Vect, Matrix> + Send + Sync {
         &**self.shape
     }
 
+    /// Describes this body's shape, see `ShapeDesc`.
+    #[inline]
+    pub fn shape_desc(&self) -> ShapeDesc {
+        shape_desc::shape_desc(self.shape_ref())
+    }
+
     /// Gets a copy of this body's shared shape.
     #[inline]
     pub fn shape(&self) -> Arc<Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>> {
         self.shape.clone()
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_shape(&mut self, shape: Arc<Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>>) {
+        self.shape = shape
+    }
+
+    /// Scales the mass properties of this body as if its shape was uniformly scaled by `s`.
+    ///
+    /// The density is kept, so the mass is multiplied by `s³` and the angular inertia by `s⁵`
+    /// (`s²` and `s⁴` in 2D). The center of mass is scaled wrt. the origin of the body.
+    #[doc(hidden)]
+    pub fn scale_mass_properties(&mut self, s: Scalar) {
+        let _1: Scalar = na::one();
+        let mut volume = _1;
+
+        for _ in range(0, na::dim::<Vect>()) {
+            volume = volume * s;
+        }
+
+        self.inv_mass          = self.inv_mass / volume;
+        self.ls_inv_inertia    = self.ls_inv_inertia * (_1 / (volume * s * s));
+        self.ls_center_of_mass = na::orig::<Point>() + *self.ls_center_of_mass.as_vec() * s;
+
+        self.update_center_of_mass();
+        self.update_inertia_tensor();
+    }
+
     /// The margin surrounding this object's shape.
+    ///
+    /// The margin is applied exactly once to each leaf shape: the parts of a compound shape have
+    /// the margin of the body unless it is overridden with `set_part_margins`.
     #[inline]
     pub fn margin(&self) -> Scalar {
         self.margin.clone()
     }
 
+    /// Sets the margin surrounding this object's shape.
+    ///
+    /// The margin is part of the shape: the contacts are computed with it, and a body rests on the
+    /// ground at the height of its margin. It is also included in the bounding volume of the body,
+    /// in addition to its broad phase padding. Defaults to `0.04`.
+    pub fn set_margin(&mut self, margin: Scalar) {
+        assert!(margin >= na::zero(), "The margin of a body must not be negative.");
+
+        self.margin           = margin;
+        self.material_changed = true
+    }
+
+    /// The padding added to the bounding volume of this body for the broad phase.
+    ///
+    /// This is the padding set with `set_broad_phase_padding` if any, and the world padding
+    /// otherwise, see `World::set_broad_phase_padding`.
+    #[inline]
+    pub fn broad_phase_padding(&self) -> Scalar {
+        self.padding.clone().unwrap_or(self.default_padding.clone())
+    }
+
+    /// Overrides the padding added to the bounding volume of this body for the broad phase.
+    ///
+    /// A larger padding makes the pairs of this body exist before its shape gets close to the
+    /// others, without changing its contacts. Set it to `None` to use the world padding.
+    pub fn set_broad_phase_padding(&mut self, padding: Option<Scalar>) {
+        match padding {
+            Some(ref p) => assert!(*p >= na::zero(), "The broad phase padding must not be negative."),
+            None        => { }
+        }
+
+        self.padding          = padding;
+        self.material_changed = true
+    }
+
+    #[doc(hidden)]
+    pub fn set_default_broad_phase_padding(&mut self, padding: Scalar) {
+        if padding != self.default_padding {
+            self.default_padding  = padding;
+            self.material_changed = self.material_changed || self.padding.is_none()
+        }
+    }
+
+    /// The margin of the `i`-th part of this body's compound shape.
+    ///
+    /// This is the overridden margin of this part if any, and the body margin otherwise.
+    #[inline]
+    pub fn part_margin(&self, i: uint) -> Scalar {
+        match self.part_margins.as_slice().get(i) {
+            Some(&Some(m)) => m,
+            _              => self.margin()
+        }
+    }
+
+    /// Overrides the margins of the parts of this body's compound shape.
+    ///
+    /// The `i`-th element of `margins` is the margin of the `i`-th part of the compound, or `None`
+    /// to keep the body margin. The parts of nested compounds are not distinguished.
+    pub fn set_part_margins(&mut self, margins: Vec<Option<Scalar>>) {
+        assert!(margins.iter().all(|m| m.map_or(true, |m| m >= na::zero())),
+                "The margin of a compound part must not be negative.");
+        let is_compound = self.shape_ref().get_type_id() == TypeId::of::<Compound<Scalar, Point, Vect, Matrix, AngularInertia>>();
+
+        assert!(margins.is_empty() || is_compound, "Only the parts of a compound shape can have their own margins.");
+
+        self.part_margins = margins
+    }
+
+    /// The margin around the leaf shape of this body that generated a contact at `point`.
+    ///
+    /// If the parts of this body's compound shape have their own margin, the part is the first one
+    /// with a bounding box containing `point`. Otherwise, this is the body margin.
+    pub fn margin_at(&self, point: &Point) -> Scalar {
+        if self.part_margins.is_empty() {
+            return self.margin();
+        }
+
+        let compound = self.shape_ref().downcast_ref::<Compound<Scalar, Point, Vect, Matrix, AngularInertia>>().unwrap();
+        let pt       = AABB::new(point.clone(), point.clone());
+
+        for (i, &(ref delta, ref part)) in compound.shapes().iter().enumerate() {
+            let margin = self.part_margin(i);
+
+            if part.aabb(&(self.local_to_world * *delta)).loosened(margin).contains(&pt) {
+                return margin;
+            }
+        }
+
+        self.margin()
+    }
+
+    /// The part of this body's shape hit by `ray` at the time of impact `toi`.
+    ///
+    /// `toi` is typically given by a ray cast on the whole body, e.g. with
+    /// `World::interferences_with_ray`.
+    pub fn sub_shape_hit_by_ray(&self, ray: &Ray<Point, Vect>, toi: Scalar) -> SubShapeId {
+        sub_shape::sub_shape_hit_by_ray(self.shape_ref(), &self.local_to_world, ray, toi)
+    }
+
+    /// The part of this body's shape that generated a contact at `point`.
+    ///
+    /// `normal` is the outward normal of the body shape at `point`: the contact normal if this is
+    /// the first body of the contact, its opposite otherwise.
+    pub fn sub_shape_at(&self, point: &Point, normal: &Vect) -> SubShapeId {
+        // Cast a ray toward the surface from slightly outside of the margin.
+        let dist = self.max_margin() + na::cast(0.01f64);
+        let ray  = Ray::new(*point + *normal * dist, -*normal);
+
+        self.sub_shape_hit_by_ray(&ray, dist)
+    }
+
+    /// The part of this body's shape designated by `id`, with its transform relative to the body.
+    pub fn sub_shape(&self, id: &[uint]) -> Option<(Matrix, &Shape<Scalar, Point, Vect, Matrix>)> {
+        sub_shape::sub_shape(self.shape_ref(), id)
+    }
+
+    // The largest margin around any of the leaf shapes of this body.
+    fn max_margin(&self) -> Scalar {
+        let mut res = self.margin();
+
+        for m in self.part_margins.iter() {
+            match *m {
+                Some(m) if m > res => res = m,
+                _ => { }
+            }
+        }
+
+        res
+    }
+
+    /// The displacement this body is expected to perform during the next step.
+    ///
+    /// The bounding volume of this body is extended along this displacement so that the pairs it
+    /// will be part of are found before its shape touches the other bodies. This is set by the
+    /// world at each step, see `World::set_aabb_motion_prediction`.
+    #[inline]
+    pub fn predicted_motion(&self) -> &Vect {
+        &self.predicted_motion
+    }
+
+    /// Sets the displacement this body is expected to perform during the next step.
+    #[inline]
+    pub fn set_predicted_motion(&mut self, motion: Vect) {
+        self.predicted_motion = motion
+    }
+
+    /// The bounding box of this body in world-space, margins included.
+    ///
+    /// This is the bounding volume given to the broad phase, i.e., it is enlarged by the broad
+    /// phase padding and extended along the predicted motion. It is unbounded along the directions the shape is, e.g., for a plane.
+    #[inline]
+    pub fn aabb(&self) -> AABB<Point> {
+        self.bounding_volume()
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn index(&self) -> int {
@@ -165,30 +555,220 @@ impl RigidBody {
         self.index = id
     }
 
-    /// Gets a reference to this body's center of mass.
+    /// Gets a reference to this body's center of mass, in global coordinates.
     #[inline]
     pub fn center_of_mass(&self) -> &Point {
         &self.center_of_mass
     }
 
+    /// The velocity of the point `point` of this body, in global coordinates.
+    #[inline]
+    pub fn velocity_at(&self, point: &Point) -> Vect {
+        self.lin_vel + rotational_velocity(&self.ang_vel, &(*point - self.center_of_mass))
+    }
+
+    /// Gets a reference to this body's center of mass, in the body's local coordinates.
+    #[inline]
+    pub fn local_center_of_mass(&self) -> &Point {
+        &self.ls_center_of_mass
+    }
+
+    /// The transformation from the center of mass frame to the body's local frame.
+    ///
+    /// The center of mass frame is centered at the center of mass, with axes aligned with the body's
+    /// local frame, so that `*rb.position() * rb.local_to_com_transform()` is the center of mass frame
+    /// in global coordinates.
+    #[inline]
+    pub fn local_to_com_transform(&self) -> Matrix {
+        let mut res: Matrix = na::one();
+
+        res.set_translation(self.ls_center_of_mass.as_vec().clone());
+
+        res
+    }
+
     /// Gets this body's restitution coefficent.
     ///
-    /// The actual restitution coefficient of a contact is computed averaging the two bodies
-    /// friction coefficient.
+    /// The actual restitution coefficient of a contact is computed multiplying the two bodies
+    /// restitution coefficients.
     #[inline]
     pub fn restitution(&self) -> Scalar {
         self.restitution.clone()
     }
 
+    /// Sets this body's restitution coefficient, clamped to `[0, 1]`.
+    ///
+    /// The coefficients of the contacts are computed at each step, so this applies from the next
+    /// step on. Like a change of velocity, this wakes up the body, and the bodies touching it if it
+    /// is static.
+    #[inline]
+    pub fn set_restitution(&mut self, restitution: Scalar) {
+        debug_assert!(restitution >= na::zero() && restitution <= na::one(),
+                      "The restitution coefficient must be in [0, 1].");
+
+        self.restitution      = restitution.max(na::zero()).min(na::one());
+        self.material_changed = true
+    }
+
+    /// The rate at which the linear velocity of this body decreases, per second.
+    #[inline]
+    pub fn linear_damping(&self) -> Scalar {
+        self.lin_damping.clone()
+    }
+
+    /// Sets the rate at which the linear velocity of this body decreases, per second.
+    ///
+    /// At each step, the linear velocity is divided by `1 + damping * dt`, after the forces are
+    /// applied. Defaults to zero.
+    #[inline]
+    pub fn set_linear_damping(&mut self, damping: Scalar) {
+        assert!(damping >= na::zero(), "The linear damping must not be negative.");
+
+        self.lin_damping = damping
+    }
+
+    /// The rate at which the angular velocity of this body decreases, per second.
+    #[inline]
+    pub fn angular_damping(&self) -> Scalar {
+        self.ang_damping.clone()
+    }
+
+    /// Sets the rate at which the angular velocity of this body decreases, per second.
+    ///
+    /// This is applied like the linear damping. Defaults to zero.
+    #[inline]
+    pub fn set_angular_damping(&mut self, damping: Scalar) {
+        assert!(damping >= na::zero(), "The angular damping must not be negative.");
+
+        self.ang_damping = damping
+    }
+
+    /// The factor applied to the timestep of the world to simulate this body.
+    #[inline]
+    pub fn time_scale(&self) -> Scalar {
+        self.time_scale.clone()
+    }
+
+    /// Sets the factor applied to the timestep of the world to simulate this body.
+    ///
+    /// A body with a time scale of `0.5` moves in slow motion: it is integrated with half the
+    /// timestep, so that it falls, rotates and is damped at half speed. Its velocities are those
+    /// it would have in its own time, so they are kept as is when the time scale changes: the body
+    /// neither gains nor loses energy when it enters or leaves a slow-motion region. The contacts
+    /// between two bodies use the smaller of their time scales for the restitution and the
+    /// penetration correction, while the joints use the timestep of the world. This is combined
+    /// with the `TimeScale` modifier volumes containing the body. Defaults to one.
+    #[inline]
+    pub fn set_time_scale(&mut self, scale: Scalar) {
+        assert!(scale > na::zero(), "The time scale must be positive.");
+
+        self.time_scale = scale
+    }
+
+    /// The factor actually applied to the timestep of the world, including the modifier volumes.
+    #[inline]
+    pub fn effective_time_scale(&self) -> Scalar {
+        self.time_scale * self.volume_time_scale
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_volume_time_scale(&mut self, scale: Scalar) {
+        self.volume_time_scale = scale
+    }
+
+    /// The safety mechanisms of the world which altered the motion of this body during the last
+    /// step, e.g., the continuous collision detection or the velocity clamping.
+    #[inline]
+    pub fn last_step_flags(&self) -> StepFlags {
+        self.last_step_flags.clone()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn last_step_flags_mut(&mut self) -> &mut StepFlags {
+        &mut self.last_step_flags
+    }
+
+    /// The maximum norm of the angular velocity of this body, if any.
+    #[inline]
+    pub fn max_angular_velocity(&self) -> Option<Scalar> {
+        self.max_ang_vel.clone()
+    }
+
+    /// Sets the maximum norm of the angular velocity of this body.
+    ///
+    /// The angular velocity is scaled down to this norm at the end of each step, whatever
+    /// accelerated it. This caps the spin of the rotors driven by a `Motor`. Defaults to `None`.
+    pub fn set_max_angular_velocity(&mut self, max: Option<Scalar>) {
+        match max {
+            Some(ref m) => assert!(*m > na::zero(), "The maximum angular velocity must be positive."),
+            None        => { }
+        }
+
+        self.max_ang_vel = max
+    }
+
+    /// The angular speed under which this body is stalled while a torque is applied to it, if any.
+    #[inline]
+    pub fn stall_speed(&self) -> Option<Scalar> {
+        self.stall_speed.clone()
+    }
+
+    /// Sets the angular speed under which this body is stalled while a torque is applied to it.
+    ///
+    /// While a force generator, e.g., a `Motor`, applies a torque to this body, the world emits a
+    /// `BodyStalled` event when its angular speed drops under `speed` after having reached it, and
+    /// a `BodyUnstalled` event once it spins faster again or the torque stops. Defaults to `None`.
+    pub fn set_stall_speed(&mut self, speed: Option<Scalar>) {
+        match speed {
+            Some(ref s) => assert!(*s >= na::zero(), "The stall speed must not be negative."),
+            None        => { }
+        }
+
+        self.stall_speed = speed
+    }
+
     /// Gets this body's friction coefficient.
     ///
-    /// The actual friction coefficient of a contact is computed averaging the two bodies friction
-    /// coefficient.
+    /// The actual friction coefficient of a contact is computed multiplying the two bodies friction
+    /// coefficients.
     #[inline]
     pub fn friction(&self) -> Scalar {
         self.friction.clone()
     }
 
+    /// Sets this body's friction coefficient, clamped to be positive.
+    ///
+    /// See `set_restitution` for when this applies.
+    #[inline]
+    pub fn set_friction(&mut self, friction: Scalar) {
+        debug_assert!(friction >= na::zero(), "The friction coefficient must not be negative.");
+
+        self.friction         = friction.max(na::zero());
+        self.material_changed = true
+    }
+
+    /// Gets the spring-damper making the contacts of this body soft, if any.
+    #[inline]
+    pub fn contact_spring(&self) -> Option<SpringDamper> {
+        self.contact_spring.clone()
+    }
+
+    /// Sets the spring-damper making the contacts of this body soft.
+    ///
+    /// A soft body sinks into the bodies it touches and is pushed back by the spring-damper,
+    /// instead of being stopped by a rigid contact. It applies to each contact point, so a body
+    /// resting on several points is proportionally stiffer. When two soft bodies touch, their
+    /// spring-dampers act in series. With `None`, the default, the contacts are rigid unless the
+    /// other body is soft or the world has a default contact spring-damper, see
+    /// `World::set_default_contact_spring`. See `set_restitution` for when this applies.
+    #[inline]
+    pub fn set_contact_spring(&mut self, spring: Option<SpringDamper>) {
+        self.contact_spring   = spring;
+        self.material_changed = true
+    }
+
     /// Indicates whether or not this rigid body is active.
     ///
     /// An inactive rigid body is a body that did not move for some time. It is not longer
@@ -237,42 +817,97 @@ impl RigidBody {
     }
 
     /// Creates a new rigid body that can move.
+    ///
+    /// Panics if `density` is not strictly positive, or if the shape is not supported by
+    /// `try_new`.
     pub fn new_dynamic<G>(shape: G, density: Scalar, restitution: Scalar, friction: Scalar) -> RigidBody
         where G: Send + Sync + Shape<Scalar, Point, Vect, Matrix> + Volumetric<Scalar, Point, AngularInertia> {
-        let props = shape.mass_properties(density);
-
-        RigidBody::new(
-            Arc::new(box shape as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>),
-            Some(props),
-            restitution,
-            friction)
+        unwrap_body(RigidBody::try_new_dynamic(shape, density, restitution, friction))
     }
 
     /// Creates a new rigid body that cannot move.
+    ///
+    /// Panics if the shape is not supported by `try_new`.
     pub fn new_static<G>(shape: G, restitution: Scalar, friction: Scalar) -> RigidBody
         where G: Send + Sync + Shape<Scalar, Point, Vect, Matrix> {
-        RigidBody::new(
-            Arc::new(box shape as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>),
-            None,
-            restitution,
-            friction)
+        unwrap_body(RigidBody::try_new_static(shape, restitution, friction))
     }
 
     /// Creates a new rigid body with a given shape.
     ///
     /// Use this if the shape is shared by multiple rigid bodies.
     /// Set `mass_properties` to `None` if the rigid body is to be static.
+    ///
+    /// Panics if the shape or the mass properties are not supported by `try_new`.
     pub fn new(shape:           Arc<Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>>,
                mass_properties: Option<(Scalar, Point, AngularInertia)>,
                restitution:     Scalar,
                friction:        Scalar)
                -> RigidBody {
+        unwrap_body(RigidBody::try_new(shape, mass_properties, restitution, friction))
+    }
+
+    /// Creates a new rigid body that can move, or an error if `density` is not strictly positive
+    /// or the shape is not supported by `try_new`.
+    pub fn try_new_dynamic<G>(shape: G, density: Scalar, restitution: Scalar, friction: Scalar) -> Result<RigidBody, String>
+        where G: Send + Sync + Shape<Scalar, Point, Vect, Matrix> + Volumetric<Scalar, Point, AngularInertia> {
+        if !(density > na::zero()) {
+            return Err("the density of a dynamic body must be strictly positive.".to_string());
+        }
+
+        let props = shape.mass_properties(density);
+
+        RigidBody::try_new(
+            Arc::new(box shape as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>),
+            Some(props),
+            restitution,
+            friction)
+    }
+
+    /// Creates a new rigid body that cannot move, or an error if the shape is not supported by
+    /// `try_new`.
+    pub fn try_new_static<G>(shape: G, restitution: Scalar, friction: Scalar) -> Result<RigidBody, String>
+        where G: Send + Sync + Shape<Scalar, Point, Vect, Matrix> {
+        RigidBody::try_new(
+            Arc::new(box shape as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>),
+            None,
+            restitution,
+            friction)
+    }
+
+    /// Creates a new rigid body with a given shape, or an error if it is not supported.
+    ///
+    /// The shape is rejected if it is not supported by `validate_shape`, and the mass properties
+    /// if their mass is negative or not finite. A dynamic body with a smaller, e.g. zero, volume
+    /// is made heavier, and a warning is printed on the standard error.
+    pub fn try_new(shape:           Arc<Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>>,
+                   mass_properties: Option<(Scalar, Point, AngularInertia)>,
+                   restitution:     Scalar,
+                   friction:        Scalar)
+                   -> Result<RigidBody, String> {
+        try!(shape_validation::validate_shape(&***shape, mass_properties.is_some()));
+
         let (inv_mass, center_of_mass, inv_inertia, active, state) =
             match mass_properties {
                 None => (na::zero(), na::orig(), na::zero(), ActivationState::Inactive, RigidBodyState::Static),
                 Some((mass, com, inertia)) => {
-                    if na::is_zero(&mass) {
-                        panic!("A dynamic body must not have a zero volume.")
+                    if !mass.is_finite() || mass < na::zero() {
+                        return Err(format!("the mass of a dynamic body must be positive and finite, found {}.", mass));
+                    }
+
+                    let min_mass: Scalar = na::cast(MIN_MASS);
+                    let mut mass         = mass;
+                    let mut inertia      = inertia;
+
+                    if mass < min_mass {
+                        warn(format!("the mass {} of a dynamic body is too small, {} is used instead.", mass, min_mass).as_slice());
+
+                        // The inertia is scaled as if the density was increased.
+                        if mass > na::zero() {
+                            inertia = inertia * (min_mass / mass);
+                        }
+
+                        mass = min_mass;
                     }
 
                     let ii: AngularInertia;
@@ -290,33 +925,54 @@ impl RigidBody {
 
         let mut res =
             RigidBody {
-                state:             state,
-                shape:             shape,
-                local_to_world:    na::one(),
-                lin_vel:           na::zero(),
-                ang_vel:           na::zero(),
-                inv_mass:          inv_mass,
-                ls_inv_inertia:    inv_inertia.clone(),
-                inv_inertia:       inv_inertia,
-                ls_center_of_mass: center_of_mass,
-                center_of_mass:    na::orig(),
-                lin_acc:           na::zero(),
-                ang_acc:           na::zero(),
-                friction:          friction,
-                restitution:       restitution,
-                index:             0,
-                activation_state:  active,
-                sleep_threshold:   Some(na::cast(0.1f64)),
-                lin_acc_scale:     na::one(),
-                ang_acc_scale:     na::one(),
-                margin:            na::cast(0.04f32), // FIXME: do not hard-code this.
-                collision_groups:  CollisionGroups::new()
+                state:               state,
+                shape:               shape,
+                local_to_world:      na::one(),
+                prev_local_to_world: na::one(),
+                reported_position:   na::one(),
+                simulated_state:     (na::one(), na::zero(), na::zero()),
+                material_changed:    false,
+                lin_vel:             na::zero(),
+                ang_vel:             na::zero(),
+                inv_mass:            inv_mass,
+                ls_inv_inertia:      inv_inertia.clone(),
+                inv_inertia:         inv_inertia,
+                ls_center_of_mass:   center_of_mass,
+                center_of_mass:      na::orig(),
+                lin_acc:             na::zero(),
+                ang_acc:             na::zero(),
+                friction:            friction,
+                restitution:         restitution,
+                index:               0,
+                activation_state:    active,
+                sleep_threshold:     Some(na::cast(0.1f64)),
+                lin_acc_scale:       na::one(),
+                ang_acc_scale:       na::one(),
+                margin:              na::cast(0.04f64), // FIXME: do not hard-code this.
+                collision_groups:    CollisionGroups::new(),
+                one_way:             None,
+                sensor:              false,
+                predicted_motion:    na::zero(),
+                part_margins:        Vec::new(),
+                contact_spring:      None,
+                default_padding:     na::zero(),
+                padding:             None,
+                lin_damping:         na::zero(),
+                ang_damping:         na::zero(),
+                max_ang_vel:         None,
+                stall_speed:         None,
+                frozen:              false,
+                uid:                 0,
+                time_scale:          na::one(),
+                volume_time_scale:   na::one(),
+                integrated_acc:      None,
+                last_step_flags:     StepFlags::empty()
             };
 
         res.update_center_of_mass();
         res.update_inertia_tensor();
 
-        res
+        Ok(res)
     }
 
     /// The collision groups this rigid body is part of.
@@ -325,11 +981,57 @@ impl RigidBody {
         &self.collision_groups
     }
 
+    /// Makes this rigid body collide only with bodies located on the side of a given normal.
+    ///
+    /// `normal` is expressed in this body's local coordinates. Contacts with a normal opposing it
+    /// are ignored, e.g. to create platforms that can be traversed from below but support bodies
+    /// from above.
+    #[inline]
+    pub fn set_one_way(&mut self, normal: Vect) {
+        self.one_way = Some(na::normalize(&normal))
+    }
+
+    /// Makes this rigid body collide on all its sides again.
+    #[inline]
+    pub fn unset_one_way(&mut self) {
+        self.one_way = None
+    }
+
+    /// The normal, in global coordinates, of the side this rigid body collides with.
+    ///
+    /// Returns `None` if this body is not a one-way body.
+    #[inline]
+    pub fn one_way_normal(&self) -> Option<Vect> {
+        self.one_way.as_ref().map(|n| na::rotate(&self.local_to_world, n))
+    }
+
+    /// Makes this rigid body a sensor, or a solid body again.
+    ///
+    /// A sensor does not collide with anything: other bodies pass through it, but the world emits
+    /// a `SensorEntered` event when they start overlapping it, and a `SensorLeft` event when they
+    /// stop. A dynamic sensor still moves under the effect of the forces applied to it.
+    ///
+    /// The overlaps are tested at the end of each step: a sensor thinner than the distance a body
+    /// travels during one step may miss it, unless continuous collision detection is enabled for
+    /// that body.
+    #[inline]
+    pub fn set_sensor(&mut self, sensor: bool) {
+        self.sensor = sensor
+    }
+
+    /// Whether this rigid body is a sensor.
+    #[inline]
+    pub fn is_sensor(&self) -> bool {
+        self.sensor
+    }
+
     /// Indicates whether this rigid body is static or dynamic.
+    ///
+    /// A dynamic body frozen by a partial step of the world cannot move during that step.
     #[inline]
     pub fn can_move(&self) -> bool {
         match self.state {
-            RigidBodyState::Dynamic => true,
+            RigidBodyState::Dynamic => !self.frozen,
             _ => false
         }
     }
@@ -365,6 +1067,10 @@ impl RigidBody {
     }
 
     /// Sets the linear velocity of this rigid body.
+    ///
+    /// Like any change of velocity or position made between two steps, this is detected by the
+    /// world at the next step: the body is woken up and, depending on
+    /// `World::set_warm_start_reset_threshold`, its contacts are solved without warm-starting.
     #[inline]
     pub fn set_lin_vel(&mut self, lv: Vect) {
         self.lin_vel = lv
@@ -384,6 +1090,34 @@ impl RigidBody {
         self.lin_acc = lf * self.lin_acc_scale
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn integrated_acc(&self) -> Option<(Vect, Orientation)> {
+        self.integrated_acc.clone()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_integrated_acc(&mut self, acc: Option<(Vect, Orientation)>) {
+        self.integrated_acc = acc
+    }
+
+    /// Adds a force, applied at the center of mass, to the linear acceleration of this rigid body.
+    ///
+    /// Note that the accelerations are reset by the physics engine at each step.
+    #[inline]
+    pub fn append_lin_force(&mut self, force: Vect) {
+        self.lin_acc = self.lin_acc + force * self.inv_mass * self.lin_acc_scale
+    }
+
+    /// Adds a torque to the angular acceleration of this rigid body.
+    ///
+    /// Note that the accelerations are reset by the physics engine at each step.
+    #[inline]
+    pub fn append_ang_force(&mut self, torque: Orientation) {
+        self.ang_acc = self.ang_acc + self.inv_inertia.apply(&torque) * self.ang_acc_scale
+    }
+
     /// Gets the angular velocity of this rigid body.
     #[inline]
     pub fn ang_vel(&self) -> Orientation {
@@ -391,6 +1125,8 @@ impl RigidBody {
     }
 
     /// Sets the angular velocity of this rigid body.
+    ///
+    /// See `set_lin_vel` for the interaction with the sleeping bodies and the warm-starting.
     #[inline]
     pub fn set_ang_vel(&mut self, av: Orientation) {
         self.ang_vel = av
@@ -411,6 +1147,8 @@ impl RigidBody {
     }
 
     /// Gets the inverse mass of this rigid body.
+    ///
+    /// This is zero for static bodies.
     #[inline]
     pub fn inv_mass(&self) -> Scalar {
         self.inv_mass.clone()
@@ -422,12 +1160,25 @@ impl RigidBody {
         self.inv_mass = m
     }
 
-    /// Gets the inverse inertia tensor of this rigid body.
+    /// Gets the inverse inertia tensor of this rigid body, in global coordinates.
+    ///
+    /// This tensor is expressed wrt. the center of mass and is kept up to date with the body's
+    /// current orientation: the change of angular velocity caused by an angular impulse `i` is
+    /// `rb.inv_inertia() * i`.
     #[inline]
     pub fn inv_inertia(&self) -> &AngularInertia {
         &self.inv_inertia
     }
 
+    /// Gets the inverse inertia tensor of this rigid body, in its local coordinates.
+    ///
+    /// This tensor is expressed wrt. the center of mass, with axes aligned with the body's local
+    /// frame.
+    #[inline]
+    pub fn local_inv_inertia(&self) -> &AngularInertia {
+        &self.ls_inv_inertia
+    }
+
     /// Sets the inverse inertia tensor of this rigid body.
     ///
     /// Not that this is reset at every update by the physics engine.
@@ -455,8 +1206,18 @@ impl RigidBody {
     }
 
     /// Sets the transformation of this rigid body.
+    ///
+    /// This teleports the body: its previous position is reset as well so that it is not
+    /// interpolated along the way. Use `move_to` otherwise.
     #[inline]
     pub fn set_transformation(&mut self, m: Matrix) {
+        self.move_to(m);
+        self.save_previous_position();
+    }
+
+    /// Moves this rigid body to the transformation `m`, keeping its previous position.
+    #[inline]
+    pub fn move_to(&mut self, m: Matrix) {
         self.local_to_world = m;
 
         self.update_center_of_mass();
@@ -479,9 +1240,12 @@ impl RigidBody {
 
 
     /// Stes the translation of this rigid body.
+    ///
+    /// Like `set_transformation`, this resets the previous position.
     #[inline]
     pub fn set_translation(&mut self, t: Vect) {
         self.local_to_world.set_translation(t);
+        self.save_previous_position();
 
         self.update_center_of_mass();
     }
@@ -505,9 +1269,12 @@ impl RigidBody {
     }
 
     /// Sets the rotation of this rigid body.
+    ///
+    /// Like `set_transformation`, this resets the previous position.
     #[inline]
     pub fn set_rotation(&mut self, r: Orientation) {
         self.local_to_world.set_rotation(r);
+        self.save_previous_position();
 
         self.update_center_of_mass();
         self.update_inertia_tensor();
@@ -516,13 +1283,32 @@ impl RigidBody {
 
 impl HasBoundingVolume<AABB<Point>> for RigidBody {
     fn bounding_volume(&self) -> AABB<Point> {
-        self.shape.aabb(&self.local_to_world).loosened(self.margin())
+        let aabb = self.shape.aabb(&self.local_to_world).loosened(self.max_margin() + self.broad_phase_padding());
+
+        if na::is_zero(&self.predicted_motion) {
+            aabb
+        }
+        else {
+            let moved = AABB::new(*aabb.mins() + self.predicted_motion, *aabb.maxs() + self.predicted_motion);
+
+            aabb.merged(&moved)
+        }
     }
 }
 
 impl HasBoundingVolume<AABB<Point>> for Rc<RefCell<RigidBody>> {
     fn bounding_volume(&self) -> AABB<Point> {
-        let bself = self.borrow();
-        bself.shape().aabb(&bself.local_to_world).loosened(bself.margin())
+        self.borrow().bounding_volume()
     }
 }
+
+fn unwrap_body(body: Result<RigidBody, String>) -> RigidBody {
+    match body {
+        Ok(body) => body,
+        Err(e)   => panic!("Invalid rigid body: {}", e)
+    }
+}
+
+fn warn(msg: &str) {
+    let _ = stdio::stderr().write_line(format!("Warning: {}", msg).as_slice());
+}
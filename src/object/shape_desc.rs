@@ -0,0 +1,83 @@
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use ncollide::shape::{Shape, Ball, Cuboid, Plane, Cylinder, Cone, Convex, Mesh, BezierSurface, Compound};
+use math::{Scalar, Point, Vect, Matrix, AngularInertia};
+
+type BodyCompound = Compound<Scalar, Point, Vect, Matrix, AngularInertia>;
+type BodyMesh     = Mesh<Scalar, Point, Vect, Matrix>;
+
+/// A description of the shape of a rigid body, borrowing its data.
+///
+/// This avoids downcasting the shape to each of the types of shapes of ncollide, e.g., to display
+/// its dimensions or to serialize it.
+pub enum ShapeDesc<'a> {
+    /// A ball, with its radius.
+    Ball(Scalar),
+    /// A cuboid, with its half extents.
+    Cuboid(Vect),
+    /// A plane, with its normal.
+    Plane(Vect),
+    /// A cylinder, with its half height and radius.
+    Cylinder(Scalar, Scalar),
+    /// A cone, with its half height and radius.
+    Cone(Scalar, Scalar),
+    /// A convex hull, with the points it is computed from.
+    Convex(&'a [Point]),
+    /// A mesh, with its vertices and the indices of the vertices of its elements.
+    Mesh(&'a [Point], &'a [uint]),
+    /// A Bezier surface, with its control points and their numbers along the `u` and `v`
+    /// directions.
+    BezierSurface(&'a [Point], uint, uint),
+    /// A compound shape, with the local transformation and the description of each of its parts.
+    Compound(Vec<(Matrix, ShapeDesc<'a>)>),
+    /// Any other shape, which must be downcast by hand.
+    Other(&'a Shape<Scalar, Point, Vect, Matrix> + 'a)
+}
+
+/// Describes `shape`, including the parts of the compound shapes.
+///
+/// Only the compound shapes allocate, to list their parts.
+pub fn shape_desc<'a>(shape: &'a Shape<Scalar, Point, Vect, Matrix>) -> ShapeDesc<'a> {
+    let id = shape.get_type_id();
+
+    if id == TypeId::of::<Ball<Scalar>>() {
+        ShapeDesc::Ball(shape.downcast_ref::<Ball<Scalar>>().unwrap().radius())
+    }
+    else if id == TypeId::of::<Cuboid<Vect>>() {
+        ShapeDesc::Cuboid(shape.downcast_ref::<Cuboid<Vect>>().unwrap().half_extents().clone())
+    }
+    else if id == TypeId::of::<Plane<Vect>>() {
+        ShapeDesc::Plane(shape.downcast_ref::<Plane<Vect>>().unwrap().normal().clone())
+    }
+    else if id == TypeId::of::<Cylinder<Scalar>>() {
+        let c = shape.downcast_ref::<Cylinder<Scalar>>().unwrap();
+
+        ShapeDesc::Cylinder(c.half_height(), c.radius())
+    }
+    else if id == TypeId::of::<Cone<Scalar>>() {
+        let c = shape.downcast_ref::<Cone<Scalar>>().unwrap();
+
+        ShapeDesc::Cone(c.half_height(), c.radius())
+    }
+    else if id == TypeId::of::<Convex<Point>>() {
+        ShapeDesc::Convex(shape.downcast_ref::<Convex<Point>>().unwrap().points())
+    }
+    else if id == TypeId::of::<BodyMesh>() {
+        let m = shape.downcast_ref::<BodyMesh>().unwrap();
+
+        ShapeDesc::Mesh(m.vertices().as_slice(), m.indices().as_slice())
+    }
+    else if id == TypeId::of::<BezierSurface<Point>>() {
+        let s = shape.downcast_ref::<BezierSurface<Point>>().unwrap();
+
+        ShapeDesc::BezierSurface(s.control_points(), s.nupoints(), s.nvpoints())
+    }
+    else if id == TypeId::of::<BodyCompound>() {
+        let c = shape.downcast_ref::<BodyCompound>().unwrap();
+
+        ShapeDesc::Compound(c.shapes().iter().map(|&(ref delta, ref part)| (delta.clone(), shape_desc(&***part))).collect())
+    }
+    else {
+        ShapeDesc::Other(shape)
+    }
+}
@@ -0,0 +1,47 @@
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use ncollide::shape::{Shape, Plane, Compound, Mesh};
+use math::{Scalar, Point, Vect, Matrix, AngularInertia};
+
+type BodyCompound = Compound<Scalar, Point, Vect, Matrix, AngularInertia>;
+type BodyMesh     = Mesh<Scalar, Point, Vect, Matrix>;
+
+/// Checks that `shape` can be given to a rigid body, dynamic or not.
+///
+/// This rejects planes inside of compound shapes, compound shapes without any part, meshes
+/// without any element, and dynamic bodies with a plane shape. The error describes the first
+/// unsupported configuration found.
+pub fn validate_shape(shape: &Shape<Scalar, Point, Vect, Matrix>, dynamic: bool) -> Result<(), String> {
+    if shape.get_type_id() == TypeId::of::<Plane<Vect>>() && dynamic {
+        return Err("a dynamic body cannot have a plane shape.".to_string());
+    }
+
+    validate_part(shape)
+}
+
+fn validate_part(shape: &Shape<Scalar, Point, Vect, Matrix>) -> Result<(), String> {
+    let type_id = shape.get_type_id();
+
+    if type_id == TypeId::of::<BodyCompound>() {
+        let compound = shape.downcast_ref::<BodyCompound>().unwrap();
+
+        if compound.shapes().is_empty() {
+            return Err("a compound shape must have at least one part.".to_string());
+        }
+
+        for (i, &(_, ref part)) in compound.shapes().iter().enumerate() {
+            if part.get_type_id() == TypeId::of::<Plane<Vect>>() {
+                return Err(format!("the part {} of a compound shape is a plane, which is not supported there.", i));
+            }
+
+            try!(validate_part(&***part));
+        }
+    }
+    else if type_id == TypeId::of::<BodyMesh>() {
+        if shape.downcast_ref::<BodyMesh>().unwrap().len() == 0 {
+            return Err("a mesh must have at least one element.".to_string());
+        }
+    }
+
+    Ok(())
+}
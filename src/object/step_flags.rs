@@ -0,0 +1,75 @@
+//! The safety mechanisms which altered the motion of a body during a step.
+
+static CCD_CLAMPED:          u8 = 1 << 0;
+static VELOCITY_CLAMPED:     u8 = 1 << 1;
+static DEPENETRATION_CAPPED: u8 = 1 << 2;
+
+/// The safety mechanisms of the world which altered the motion of a body during its last step.
+///
+/// They are cleared at the beginning of each step, and set as the mechanisms fire. This tells,
+/// e.g., why a fast body stopped in mid-air. See `RigidBody::last_step_flags`.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct StepFlags {
+    bits: u8
+}
+
+impl StepFlags {
+    /// Flags of a step during which no mechanism fired.
+    #[inline]
+    pub fn empty() -> StepFlags {
+        StepFlags {
+            bits: 0
+        }
+    }
+
+    /// The raw bits of these flags.
+    #[inline]
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Whether no mechanism fired.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Whether the continuous collision detection moved the body back to its time of impact, see
+    /// `World::add_ccd_to`.
+    #[inline]
+    pub fn ccd_clamped(&self) -> bool {
+        self.bits & CCD_CLAMPED != 0
+    }
+
+    /// Whether the angular velocity of the body was clamped, by its maximum angular velocity or by
+    /// the maximum angular step of the world.
+    #[inline]
+    pub fn velocity_clamped(&self) -> bool {
+        self.bits & VELOCITY_CLAMPED != 0
+    }
+
+    /// Whether the penetration correction of a contact of the body was capped by the maximum
+    /// correction velocity of the solver.
+    #[inline]
+    pub fn depenetration_capped(&self) -> bool {
+        self.bits & DEPENETRATION_CAPPED != 0
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_ccd_clamped(&mut self) {
+        self.bits = self.bits | CCD_CLAMPED
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_velocity_clamped(&mut self) {
+        self.bits = self.bits | VELOCITY_CLAMPED
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_depenetration_capped(&mut self) {
+        self.bits = self.bits | DEPENETRATION_CAPPED
+    }
+}
@@ -0,0 +1,125 @@
+use std::num::Float;
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use na;
+use ncollide::shape::{Shape, Compound, Mesh};
+use ncollide::ray::{Ray, RayCast};
+use math::{Scalar, Point, Vect, Matrix, AngularInertia};
+
+/// The path to a part of the shape of a body.
+///
+/// Each element is the index of a part of a compound shape, the first one being a part of the
+/// shape of the body itself. If the designated part is a mesh, the last element is the index of
+/// one of its triangles (segments in 2D). The path is empty for any other shape.
+pub type SubShapeId = Vec<uint>;
+
+type BodyCompound = Compound<Scalar, Point, Vect, Matrix, AngularInertia>;
+type BodyMesh     = Mesh<Scalar, Point, Vect, Matrix>;
+
+/// The part of `shape`, transformed by `m`, hit by `ray` at the time of impact `toi`.
+///
+/// If several parts are hit, the one hit at the time of impact closest to `toi` is selected.
+pub fn sub_shape_hit_by_ray(shape: &Shape<Scalar, Point, Vect, Matrix>,
+                            m:     &Matrix,
+                            ray:   &Ray<Point, Vect>,
+                            toi:   Scalar)
+                            -> SubShapeId {
+    let mut res = Vec::new();
+
+    push_sub_shape_hit_by_ray(shape, m, ray, toi, &mut res);
+
+    res
+}
+
+/// The part of `shape` designated by `id`, together with its transform relative to `shape`.
+///
+/// If `id` ends with a triangle index, the mesh containing it is returned. This returns `None`
+/// if `id` does not designate a part of `shape`.
+pub fn sub_shape<'a>(shape: &'a Shape<Scalar, Point, Vect, Matrix>,
+                     id:    &[uint])
+                     -> Option<(Matrix, &'a Shape<Scalar, Point, Vect, Matrix>)> {
+    let mut delta: Matrix = na::one();
+    let mut curr          = shape;
+
+    for (k, i) in id.iter().enumerate() {
+        let type_id = curr.get_type_id();
+
+        if type_id == TypeId::of::<BodyCompound>() {
+            let compound = curr.downcast_ref::<BodyCompound>().unwrap();
+
+            match compound.shapes().get(*i) {
+                Some(&(ref m, ref part)) => {
+                    delta = delta * *m;
+                    curr  = &***part;
+                },
+                None => return None
+            }
+        }
+        else if type_id == TypeId::of::<BodyMesh>() {
+            let mesh = curr.downcast_ref::<BodyMesh>().unwrap();
+
+            if k != id.len() - 1 || *i >= mesh.len() {
+                return None;
+            }
+        }
+        else {
+            return None;
+        }
+    }
+
+    Some((delta, curr))
+}
+
+fn push_sub_shape_hit_by_ray(shape: &Shape<Scalar, Point, Vect, Matrix>,
+                             m:     &Matrix,
+                             ray:   &Ray<Point, Vect>,
+                             toi:   Scalar,
+                             out:   &mut SubShapeId) {
+    let type_id = shape.get_type_id();
+
+    if type_id == TypeId::of::<BodyCompound>() {
+        let compound        = shape.downcast_ref::<BodyCompound>().unwrap();
+        let mut best        = None;
+        let mut err: Scalar = Float::max_value();
+
+        for (i, &(ref delta, ref part)) in compound.shapes().iter().enumerate() {
+            let pm = *m * *delta;
+
+            match part.toi_with_transform_and_ray(&pm, ray, true) {
+                Some(t) if (t - toi).abs() < err => {
+                    err  = (t - toi).abs();
+                    best = Some((i, pm));
+                },
+                _ => { }
+            }
+        }
+
+        match best {
+            Some((i, pm)) => {
+                out.push(i);
+                push_sub_shape_hit_by_ray(&***compound.shapes()[i].ref1(), &pm, ray, toi, out)
+            },
+            None => { }
+        }
+    }
+    else if type_id == TypeId::of::<BodyMesh>() {
+        let mesh            = shape.downcast_ref::<BodyMesh>().unwrap();
+        let mut best        = None;
+        let mut err: Scalar = Float::max_value();
+
+        for i in range(0, mesh.len()) {
+            match mesh.element_at(i).toi_with_transform_and_ray(m, ray, true) {
+                Some(t) if (t - toi).abs() < err => {
+                    err  = (t - toi).abs();
+                    best = Some(i);
+                },
+                _ => { }
+            }
+        }
+
+        match best {
+            Some(i) => out.push(i),
+            None    => { }
+        }
+    }
+}
@@ -1,21 +1,28 @@
+use std::num::Float;
 use std::rc::Rc;
+use std::mem;
 use std::cell::RefCell;
-// use rand::RngUtil;
-use na::{Translation, Transformation, RotationWithTranslation};
+use rand::{SeedableRng, XorShiftRng};
+use na::{Translation, Transformation, Bounded};
 use na;
-use math::{Scalar, Vect, Orientation, Matrix};
+use math::{Scalar, Point, Vect, Orientation, Matrix};
 use detection::constraint::Constraint;
 use detection::joint::Joint;
+use integration::euler;
 use object::RigidBody;
 use resolution::constraint::velocity_constraint::VelocityConstraint;
 use resolution::constraint::contact_equation;
 use resolution::constraint::contact_equation::{CorrectionMode, CorrectionParameters};
 use resolution::constraint::ball_in_socket_equation;
 use resolution::constraint::fixed_equation;
+use resolution::constraint::distance_equation;
+use resolution::constraint::grab_equation;
 use resolution::solver::Solver;
 use resolution::constraint::projected_gauss_seidel_solver as pgs;
 use resolution::constraint::projected_gauss_seidel_solver::Velocities;
 use resolution::constraint::impulse_cache::ImpulseCache;
+use resolution::constraint::impact::Impact;
+use resolution::constraint::spring_damper::SpringDamper;
 
 
 /// Constraint solver using the projected gauss seidel algorithm and warm-starting.
@@ -26,7 +33,29 @@ pub struct AccumulatedImpulseSolver {
     num_second_order_iter:   uint,
     restitution_constraints: Vec<VelocityConstraint>,
     friction_constraints:    Vec<VelocityConstraint>,
-    mj_lambda:               Vec<Velocities>
+    mj_lambda:               Vec<Velocities>,
+    approach_velocities:     Vec<Scalar>,
+    impact_threshold:        Scalar,
+    impacts:                 Vec<Impact>,
+    // The bodies of the contacts which penetration correction was capped by `max_corr_vel`.
+    capped:                  Vec<Rc<RefCell<RigidBody>>>,
+    normal_impulses:         Vec<Scalar>,
+    friction_impulses:       Vec<Vect>,
+    last_dt:                 Scalar,
+    shock_propagation:       bool,
+    // The seed of the constraint shuffling, and the generator drawing the orderings.
+    shuffling:               Option<(u64, XorShiftRng)>,
+    anchor_max_drift:        Option<Scalar>,
+    anchors:                 Vec<Option<(Point, Point)>>,
+    heights:                 Vec<Scalar>,
+    bodies:                  Vec<Rc<RefCell<RigidBody>>>,
+    joints:                  Vec<uint>,
+    residuals_enabled:       bool,
+    contact_residual:        Scalar,
+    joint_residual:          Scalar,
+    // The indices of the constraints with the largest residuals.
+    worst_contact:           Option<uint>,
+    worst_joint:             Option<uint>
 }
 
 impl AccumulatedImpulseSolver {
@@ -44,12 +73,34 @@ impl AccumulatedImpulseSolver {
             restitution_constraints: Vec::new(),
             friction_constraints:    Vec::new(),
             mj_lambda:               Vec::new(),
+            approach_velocities:     Vec::new(),
+            impact_threshold:        Bounded::max_value(),
+            impacts:                 Vec::new(),
+            capped:                  Vec::new(),
+            normal_impulses:         Vec::new(),
+            friction_impulses:       Vec::new(),
+            last_dt:                 na::zero(),
+            shock_propagation:       false,
+            shuffling:               None,
+            anchor_max_drift:        None,
+            anchors:                 Vec::new(),
+            heights:                 Vec::new(),
+            bodies:                  Vec::new(),
+            joints:                  Vec::new(),
+            residuals_enabled:       false,
+            contact_residual:        na::zero(),
+            joint_residual:          na::zero(),
+            worst_contact:           None,
+            worst_joint:             None,
             cache:                   ImpulseCache::new(step, na::dim::<Vect>()),
 
             correction: CorrectionParameters {
-                corr_mode:  correction_mode,
-                joint_corr: joint_corr_factor,
-                rest_eps:   rest_eps
+                corr_mode:      correction_mode,
+                joint_corr:     joint_corr_factor,
+                rest_eps:       rest_eps,
+                max_corr_vel:   Bounded::max_value(),
+                contact_spring: None,
+                joint_spring:   None
             }
         }
     }
@@ -78,6 +129,212 @@ impl AccumulatedImpulseSolver {
         self.num_second_order_iter = num
     }
 
+    /// Gets the maximum velocity the penetration correction may inject into a contact.
+    #[inline]
+    pub fn max_correction_velocity(&self) -> Scalar {
+        self.correction.max_corr_vel.clone()
+    }
+
+    /// Sets the maximum velocity the penetration correction may inject into a contact.
+    ///
+    /// This bounds the separation speed of bodies that start deeply overlapping. Note that the
+    /// position-based correction is not affected by this limit.
+    #[inline]
+    pub fn set_max_correction_velocity(&mut self, max_vel: Scalar) {
+        assert!(max_vel >= na::zero(), "The maximum correction velocity must be positive.");
+        self.correction.max_corr_vel = max_vel
+    }
+
+    /// Gets the minimum normal impulse a contact must receive to be reported as an impact.
+    #[inline]
+    pub fn impact_threshold(&self) -> Scalar {
+        self.impact_threshold.clone()
+    }
+
+    /// Sets the minimum normal impulse a contact must receive to be reported as an impact.
+    ///
+    /// Only contacts with bodies approaching each other are reported so resting contacts never
+    /// generate impacts. Impacts are disabled by default, i.e. the threshold is set to the
+    /// maximum value of `Scalar`.
+    #[inline]
+    pub fn set_impact_threshold(&mut self, threshold: Scalar) {
+        self.impact_threshold = threshold
+    }
+
+    /// Whether the shock propagation is enabled.
+    #[inline]
+    pub fn shock_propagation(&self) -> bool {
+        self.shock_propagation
+    }
+
+    /// Enables or disables the shock propagation.
+    ///
+    /// When enabled, the contacts are solved once more from the bottom to the top of the stacks,
+    /// considering the lowest body of each contact as immovable. The height of a body is its
+    /// distance to the static bodies in the contact graph. This prevents heavy bodies from
+    /// sinking into lighter bodies they rest on and keeps tall stacks from sagging, at the cost of
+    /// a less accurate kinetic behaviour. The impulses of this pass are included in the reported
+    /// and cached contact impulses.
+    #[inline]
+    pub fn set_shock_propagation(&mut self, enabled: bool) {
+        self.shock_propagation = enabled
+    }
+
+    /// The seed of the constraint shuffling, if it is enabled.
+    #[inline]
+    pub fn constraint_shuffling(&self) -> Option<u64> {
+        self.shuffling.as_ref().map(|&(seed, _)| seed)
+    }
+
+    /// Enables or disables the shuffling of the constraints between two iterations.
+    ///
+    /// The constraints are solved one after the other, so a fixed order favors some of them: a
+    /// symmetric scene slowly becomes asymmetric. When enabled, the constraints are visited in a
+    /// random order, drawn from a generator seeded by `seed`, so that two simulations with the
+    /// same seed stay identical. Enabling it again restarts the sequence of orderings.
+    #[inline]
+    pub fn set_constraint_shuffling(&mut self, enabled: bool, seed: u64) {
+        self.shuffling = if enabled { Some((seed, shuffling_rng(seed))) } else { None }
+    }
+
+    /// Whether the residuals of the constraints are computed after each resolution.
+    #[inline]
+    pub fn residuals_enabled(&self) -> bool {
+        self.residuals_enabled
+    }
+
+    /// Enables or disables the computation of the residuals of the constraints.
+    ///
+    /// The residual of a constraint is the velocity it still lacks once solved: it decreases as the
+    /// number of iterations increases, unless some constraints conflict. This costs one more pass
+    /// over the constraints, and is disabled by default.
+    #[inline]
+    pub fn set_residuals_enabled(&mut self, enabled: bool) {
+        self.residuals_enabled = enabled
+    }
+
+    /// The largest approach velocity left on a contact by the last resolution.
+    ///
+    /// This is zero if the residuals are not enabled.
+    #[inline]
+    pub fn contact_residual(&self) -> Scalar {
+        self.contact_residual.clone()
+    }
+
+    /// The largest velocity error left on a joint by the last resolution.
+    ///
+    /// This is zero if the residuals are not enabled.
+    #[inline]
+    pub fn joint_residual(&self) -> Scalar {
+        self.joint_residual.clone()
+    }
+
+    /// The index, among the constraints of the last resolution, of the contact with the largest
+    /// residual.
+    #[inline]
+    pub fn worst_contact(&self) -> Option<uint> {
+        self.worst_contact
+    }
+
+    /// The index, among the constraints of the last resolution, of the joint with the largest
+    /// residual.
+    #[inline]
+    pub fn worst_joint(&self) -> Option<uint> {
+        self.worst_joint
+    }
+
+    /// The maximum tangential drift of a resting contact from its friction anchors.
+    #[inline]
+    pub fn friction_anchor_max_drift(&self) -> Option<Scalar> {
+        self.anchor_max_drift.clone()
+    }
+
+    /// Sets the maximum tangential drift of a resting contact from its friction anchors.
+    ///
+    /// The friction anchors are the points where two bodies started touching. Friction pulls the
+    /// bodies back toward them, within the limits of the friction cone, so that a body resting on
+    /// a slope does not slowly creep downhill. When the anchors move farther apart than
+    /// `max_drift`, e.g., because the bodies slide or roll, they are moved to the current contact
+    /// point. The anchors are disabled with `None`, the default.
+    #[inline]
+    pub fn set_friction_anchor_max_drift(&mut self, max_drift: Option<Scalar>) {
+        self.anchor_max_drift = max_drift
+    }
+
+    /// The spring-damper of the contacts between bodies without their own, if any.
+    #[inline]
+    pub fn default_contact_spring(&self) -> Option<SpringDamper> {
+        self.correction.contact_spring.clone()
+    }
+
+    /// Sets the spring-damper of the contacts between bodies without their own.
+    #[inline]
+    pub fn set_default_contact_spring(&mut self, spring: Option<SpringDamper>) {
+        self.correction.contact_spring = spring
+    }
+
+    /// The spring-damper of the joints without their own, if any.
+    #[inline]
+    pub fn default_joint_spring(&self) -> Option<SpringDamper> {
+        self.correction.joint_spring.clone()
+    }
+
+    /// Sets the spring-damper of the joints without their own.
+    #[inline]
+    pub fn set_default_joint_spring(&mut self, spring: Option<SpringDamper>) {
+        self.correction.joint_spring = spring
+    }
+
+    /// Discards the impulses cached for warm-starting the contacts of `body`.
+    ///
+    /// Their resolution starts from zero at the next call to `solve`, as for new contacts.
+    pub fn reset_warm_start_of(&mut self, body: &Rc<RefCell<RigidBody>>) {
+        self.cache.remove_object(body.deref() as *const RefCell<RigidBody> as uint)
+    }
+
+    /// The impacts detected during the last call to `solve`.
+    #[inline]
+    pub fn impacts(&self) -> &[Impact] {
+        self.impacts.as_slice()
+    }
+
+    /// The dynamic bodies of the contacts which penetration correction was capped by the maximum
+    /// correction velocity during the last call to `solve`.
+    ///
+    /// A body touching several deeply penetrating bodies appears several times.
+    #[inline]
+    pub fn capped_bodies(&self) -> &[Rc<RefCell<RigidBody>>] {
+        self.capped.as_slice()
+    }
+
+    /// The normal impulse applied to each constraint given to the last call to `solve`.
+    ///
+    /// This is indexed like the constraints, and is zero for the joints.
+    #[inline]
+    pub fn normal_impulses(&self) -> &[Scalar] {
+        self.normal_impulses.as_slice()
+    }
+
+    /// The friction impulse applied to each constraint given to the last call to `solve`.
+    ///
+    /// This is the sum of the impulses applied along each friction direction, expressed in world
+    /// space, as applied to the second body. It is indexed like the constraints, and is zero for
+    /// the joints and the contacts that were not solved.
+    #[inline]
+    pub fn friction_impulses(&self) -> &[Vect] {
+        self.friction_impulses.as_slice()
+    }
+
+    /// The total capacity of the buffers reused from one resolution to the next.
+    #[cfg(feature = "alloc_stats")]
+    pub fn buffers_capacity(&self) -> uint {
+        self.restitution_constraints.capacity() + self.friction_constraints.capacity() +
+        self.mj_lambda.capacity() + self.approach_velocities.capacity() + self.impacts.capacity() +
+        self.normal_impulses.capacity() + self.friction_impulses.capacity() + self.anchors.capacity() +
+        self.heights.capacity() + self.bodies.capacity() + self.joints.capacity() +
+        self.cache.buffers_capacity()
+    }
+
     fn resize_buffers(&mut self, num_restitution_equations: uint, num_friction_equations: uint) {
         resize_buffer(&mut self.restitution_constraints,
                       num_restitution_equations,
@@ -98,36 +355,71 @@ impl AccumulatedImpulseSolver {
         let mut num_joint_equations = 0;
 
         for i in joints.iter() {
-            match constraints[*i] {
-                Constraint::BallInSocket(_) => {
-                    num_joint_equations = num_joint_equations + na::dim::<Vect>()
-                },
-                Constraint::Fixed(_) => {
-                    num_joint_equations = num_joint_equations + na::dim::<Vect>() + na::dim::<Orientation>()
-                },
-                Constraint::RBRB(_, _, _) => { }
-            }
+            num_joint_equations = num_joint_equations + num_equations(&constraints[*i])
         }
 
         self.resize_buffers(num_restitution_equations + num_joint_equations, num_friction_equations);
 
         let mut friction_offset = 0;
+        let report_impacts      = self.impact_threshold != Bounded::max_value();
 
-        for (i, (_, &(ci, imp))) in self.cache.hash().iter().enumerate() {
+        self.approach_velocities.clear();
+        self.anchors.clear();
+
+        for (i, &(ci, imp)) in self.cache.entries().iter().enumerate() {
             match constraints[ci] {
                 Constraint::RBRB(ref rb1, ref rb2, ref c) => {
-                    contact_equation::fill_second_order_equation(
-                        dt.clone(),
+                    let brb1 = rb1.borrow();
+                    let brb2 = rb2.borrow();
+
+                    let drift = match self.anchor_max_drift {
+                        Some(ref max_drift) => {
+                            let (anchors, drift) = contact_equation::update_friction_anchors(
+                                c, brb1.deref(), brb2.deref(), self.cache.anchors_at(imp), max_drift.clone());
+
+                            self.anchors.push(Some(anchors));
+
+                            drift
+                        },
+                        None => {
+                            self.anchors.push(None);
+
+                            na::zero()
+                        }
+                    };
+
+                    let capped = contact_equation::fill_second_order_equation(
+                        contact_dt(dt.clone(), brb1.deref(), brb2.deref()),
                         c,
-                        rb1.borrow().deref(), rb2.borrow().deref(),
+                        brb1.deref(), brb2.deref(),
                         &mut self.restitution_constraints[i],
                         i,
                         self.friction_constraints.as_mut_slice(),
                         friction_offset,
                         self.cache.impulsions_at(imp),
+                        &drift,
                         &self.correction);
+
+                    if capped {
+                        if brb1.can_move() {
+                            self.capped.push(rb1.clone())
+                        }
+
+                        if brb2.can_move() {
+                            self.capped.push(rb2.clone())
+                        }
+                    }
+
+                    if report_impacts {
+                        let rc      = &self.restitution_constraints[i];
+                        let opt_rb1 = if brb1.can_move() { Some(brb1.deref()) } else { None };
+                        let opt_rb2 = if brb2.can_move() { Some(brb2.deref()) } else { None };
+
+                        self.approach_velocities.push(-contact_equation::relative_velocity(
+                            &opt_rb1, &opt_rb2, &rc.normal, &rc.rot_axis1, &rc.rot_axis2, &dt));
+                    }
                 },
-                _ => { }
+                _ => self.anchors.push(None)
             }
 
             friction_offset = friction_offset + na::dim::<Vect>() - 1;
@@ -157,6 +449,26 @@ impl AccumulatedImpulseSolver {
 
                     joint_offset = joint_offset + na::dim::<Vect>() + na::dim::<Orientation>();
                 },
+                Constraint::Distance(ref d) => {
+                    distance_equation::fill_second_order_equation(
+                        dt.clone(),
+                        d.borrow().deref(),
+                        &mut self.restitution_constraints[joint_offset],
+                        &self.correction
+                    );
+
+                    joint_offset = joint_offset + 1;
+                },
+                Constraint::Grab(ref g) => {
+                    grab_equation::fill_second_order_equation(
+                        dt.clone(),
+                        g.borrow().deref(),
+                        self.restitution_constraints.slice_mut(joint_offset, nconstraints),
+                        &self.correction
+                    );
+
+                    joint_offset = joint_offset + na::dim::<Vect>();
+                },
                 Constraint::RBRB(_, _, _) => { }
             }
         }
@@ -164,13 +476,56 @@ impl AccumulatedImpulseSolver {
         resize_buffer(&mut self.mj_lambda, bodies.len(), Velocities::new());
 
         // FIXME: parametrize by the resolution algorithm?
-        pgs::projected_gauss_seidel_solve(
-            self.restitution_constraints.as_mut_slice(),
-            self.friction_constraints.as_mut_slice(),
-            self.mj_lambda.as_mut_slice(),
-            bodies.len(),
-            self.num_second_order_iter,
-            false);
+        match self.shuffling {
+            Some((_, ref mut rng)) => pgs::projected_gauss_seidel_solve_shuffled(
+                self.restitution_constraints.as_mut_slice(),
+                self.friction_constraints.as_mut_slice(),
+                self.mj_lambda.as_mut_slice(),
+                bodies.len(),
+                self.num_second_order_iter,
+                false,
+                rng),
+            None => pgs::projected_gauss_seidel_solve(
+                self.restitution_constraints.as_mut_slice(),
+                self.friction_constraints.as_mut_slice(),
+                self.mj_lambda.as_mut_slice(),
+                bodies.len(),
+                self.num_second_order_iter,
+                false)
+        }
+
+        if self.shock_propagation {
+            contact_graph_heights(self.restitution_constraints.slice_to(num_restitution_equations),
+                                  bodies.len(),
+                                  &mut self.heights);
+
+            // This also updates the impulses of the contacts, reported and cached below.
+            pgs::shock_propagation_solve(self.restitution_constraints.slice_to_mut(num_restitution_equations),
+                                         self.heights.as_slice(),
+                                         self.mj_lambda.as_mut_slice(),
+                                         1);
+        }
+
+        if self.residuals_enabled {
+            self.update_residuals(joints, num_restitution_equations, constraints);
+        }
+
+        for (i, &(ci, _)) in self.cache.entries().iter().enumerate() {
+            self.normal_impulses[ci] = self.restitution_constraints[i].impulse;
+
+            let mut friction: Vect = na::zero();
+
+            for j in range(0u, na::dim::<Vect>() - 1) {
+                let fc   = &self.friction_constraints[i * (na::dim::<Vect>() - 1) + j];
+                friction = friction + fc.normal * fc.impulse;
+            }
+
+            self.friction_impulses[ci] = friction;
+        }
+
+        if report_impacts {
+            self.collect_impacts(constraints);
+        }
 
         // FIXME: this is _so_ ugly!
         self.resize_buffers(num_restitution_equations, num_friction_equations);
@@ -194,10 +549,12 @@ impl AccumulatedImpulseSolver {
                 let fc = self.friction_constraints[i * (na::dim::<Vect>() - 1) + j];
                 imps[1 + j] = fc.impulse * na::cast(0.85f64);
             }
+
+            self.cache.push_anchors(self.anchors[i].clone());
         }
 
         let offset = self.cache.reserved_impulse_offset();
-        for (i, (_, kv)) in self.cache.hash_mut().iter_mut().enumerate() {
+        for (i, kv) in self.cache.entries_mut().iter_mut().enumerate() {
             *kv = (kv.val0(), offset + i * na::dim::<Vect>());
         }
 
@@ -216,11 +573,11 @@ impl AccumulatedImpulseSolver {
         if needs_correction {
             self.resize_buffers(num_restitution_equations, num_friction_equations);
 
-            for (i, (_, &(ci, _))) in self.cache.hash().iter().enumerate() {
+            for (i, &(ci, _)) in self.cache.entries().iter().enumerate() {
                 match constraints[ci] {
-                    Constraint::RBRB(_, _, ref c) => {
+                    Constraint::RBRB(ref rb1, ref rb2, ref c) => {
                         contact_equation::reinit_to_first_order_equation(
-                            dt.clone(),
+                            contact_dt(dt.clone(), rb1.borrow().deref(), rb2.borrow().deref()),
                             c,
                             &mut self.restitution_constraints[i],
                             &self.correction);
@@ -230,37 +587,142 @@ impl AccumulatedImpulseSolver {
             }
 
             // FIXME: parametrize by the resolution algorithm?
-            pgs::projected_gauss_seidel_solve(
-                self.restitution_constraints.as_mut_slice(),
-                [].as_mut_slice(),
-                self.mj_lambda.as_mut_slice(),
-                bodies.len(),
-                self.num_first_order_iter,
-                true);
+            match self.shuffling {
+                Some((_, ref mut rng)) => pgs::projected_gauss_seidel_solve_shuffled(
+                    self.restitution_constraints.as_mut_slice(),
+                    [].as_mut_slice(),
+                    self.mj_lambda.as_mut_slice(),
+                    bodies.len(),
+                    self.num_first_order_iter,
+                    true,
+                    rng),
+                None => pgs::projected_gauss_seidel_solve(
+                    self.restitution_constraints.as_mut_slice(),
+                    [].as_mut_slice(),
+                    self.mj_lambda.as_mut_slice(),
+                    bodies.len(),
+                    self.num_first_order_iter,
+                    true)
+            }
 
             for b in bodies.iter() {
                 let mut rb = b.borrow_mut();
                 let i      = rb.index();
 
-                let translation = self.mj_lambda[i as uint].lv * dt;
-                let rotation    = self.mj_lambda[i as uint].av * dt;
+                // The correction moves the body in its own time, like its velocity.
+                let body_dt     = dt * rb.effective_time_scale();
+                let translation = self.mj_lambda[i as uint].lv * body_dt;
+                let rotation    = self.mj_lambda[i as uint].av * body_dt;
 
                 let center = &rb.center_of_mass().clone();
 
                 let mut delta: Matrix = na::one();
-                delta.append_rotation_wrt_point(&rotation, center.as_vec());
+                euler::append_rotation_wrt_point(&mut delta, &rotation, center.as_vec());
                 delta.append_translation(&translation);
 
                 rb.append_transformation(&delta);
             }
         }
     }
+
+    fn update_residuals(&mut self, joints: &[uint], num_contact_equations: uint, constraints: &[Constraint]) {
+        for (i, &(ci, _)) in self.cache.entries().iter().enumerate() {
+            let residual = pgs::velocity_residual(&self.restitution_constraints[i], self.mj_lambda.as_slice());
+
+            if residual > self.contact_residual {
+                self.contact_residual = residual;
+                self.worst_contact    = Some(ci);
+            }
+        }
+
+        // The joint equations follow the contact ones, in the order of `joints`.
+        let mut offset = num_contact_equations;
+
+        for j in joints.iter() {
+            let n = num_equations(&constraints[*j]);
+
+            for c in self.restitution_constraints.slice(offset, offset + n).iter() {
+                let residual = pgs::velocity_residual(c, self.mj_lambda.as_slice()).abs();
+
+                if residual > self.joint_residual {
+                    self.joint_residual = residual;
+                    self.worst_joint    = Some(*j);
+                }
+            }
+
+            offset = offset + n;
+        }
+    }
+
+    fn collect_impacts(&mut self, constraints: &[Constraint]) {
+        for (i, &(ci, _)) in self.cache.entries().iter().enumerate() {
+            let approach_velocity = self.approach_velocities[i];
+            let impulse           = self.restitution_constraints[i].impulse;
+
+            if approach_velocity > self.correction.rest_eps && impulse >= self.impact_threshold {
+                match constraints[ci] {
+                    Constraint::RBRB(ref rb1, ref rb2, ref c) => {
+                        let brb1       = rb1.borrow();
+                        let brb2       = rb2.borrow();
+                        let point      = na::center(&c.world1, &c.world2);
+                        let sub_shape1 = brb1.sub_shape_at(&c.world1, &c.normal);
+                        let sub_shape2 = brb2.sub_shape_at(&c.world2, &-c.normal);
+                        let part1      = part_position(brb1.deref(), sub_shape1.as_slice());
+                        let part2      = part_position(brb2.deref(), sub_shape2.as_slice());
+
+                        self.impacts.push(Impact {
+                            body1:             rb1.clone(),
+                            body2:             rb2.clone(),
+                            point:             point.clone(),
+                            normal:            c.normal.clone(),
+                            approach_velocity: approach_velocity,
+                            impulse:           impulse,
+                            sub_shape1:        sub_shape1,
+                            sub_shape2:        sub_shape2,
+                            local_point1:      na::inv_transform(brb1.position(), &point),
+                            local_normal1:     na::inv_rotate(brb1.position(), &c.normal),
+                            local_point2:      na::inv_transform(brb2.position(), &point),
+                            local_normal2:     na::inv_rotate(brb2.position(), &c.normal),
+                            part_point1:       na::inv_transform(&part1, &point),
+                            part_normal1:      na::inv_rotate(&part1, &c.normal),
+                            part_point2:       na::inv_transform(&part2, &point),
+                            part_normal2:      na::inv_rotate(&part2, &c.normal),
+                            relative_velocity: brb2.velocity_at(&point) - brb1.velocity_at(&point)
+                        })
+                    },
+                    _ => { }
+                }
+            }
+        }
+    }
 }
 
 impl Solver<Constraint> for AccumulatedImpulseSolver {
     fn solve(&mut self, dt: Scalar, constraints: &[Constraint]) {
+        self.impacts.clear();
+        self.capped.clear();
+        self.normal_impulses.clear();
+        self.normal_impulses.grow(constraints.len(), na::zero());
+        self.friction_impulses.clear();
+        self.friction_impulses.grow(constraints.len(), na::zero());
+
+        // The cached impulses were computed for the previous timestep.
+        if dt != self.last_dt && !na::is_zero(&self.last_dt) && !na::is_zero(&dt) {
+            self.cache.scale_impulsions(dt / self.last_dt);
+        }
+
+        self.last_dt = dt;
+
+        self.contact_residual = na::zero();
+        self.joint_residual   = na::zero();
+        self.worst_contact    = None;
+        self.worst_joint      = None;
+
         // FIXME: bodies index assignment is very ugly
-        let mut bodies = Vec::new();
+        // The buffers are moved out of `self` temporarily and given back, with their memory, after
+        // the resolution.
+        let mut bodies = mem::replace(&mut self.bodies, Vec::new());
+        let mut joints = mem::replace(&mut self.joints, Vec::new());
 
         if constraints.len() != 0 {
             /*
@@ -279,6 +741,12 @@ impl Solver<Constraint> for AccumulatedImpulseSolver {
                     },
                     Constraint::Fixed(_) => {
                         // XXX: cache for fixed?
+                    },
+                    Constraint::Distance(_) => {
+                        // XXX: cache for distance?
+                    },
+                    Constraint::Grab(_) => {
+                        // XXX: cache for grab?
                     }
                 }
             }
@@ -327,6 +795,30 @@ impl Solver<Constraint> for AccumulatedImpulseSolver {
                             None    => { }
                         }
                     }
+                    Constraint::Distance(ref d) => {
+                        let bd = d.borrow();
+                        match bd.anchor1().body {
+                            Some(ref b) => {
+                                b.borrow_mut().set_index(-2)
+                            },
+                            None    => { }
+                        };
+
+                        match bd.anchor2().body {
+                            Some(ref b) => {
+                                b.borrow_mut().set_index(-2)
+                            },
+                            None    => { }
+                        }
+                    }
+                    Constraint::Grab(ref g) => {
+                        match g.borrow().anchor1().body {
+                            Some(ref b) => {
+                                b.borrow_mut().set_index(-2)
+                            },
+                            None    => { }
+                        }
+                    }
                 }
             }
 
@@ -346,8 +838,6 @@ impl Solver<Constraint> for AccumulatedImpulseSolver {
                 }
             }
 
-            // FIXME: avoid allocation
-            let mut joints = Vec::new();
             for (i, c) in constraints.iter().enumerate() {
                 match *c {
                     Constraint::RBRB(ref a, ref b, _) => {
@@ -379,6 +869,26 @@ impl Solver<Constraint> for AccumulatedImpulseSolver {
                             Some(ref b) => set_body_index(b, &mut bodies, &mut id),
                             None        => { }
                         }
+                    },
+                    Constraint::Distance(ref d) => {
+                        joints.push(i);
+                        let bd = d.borrow();
+                        match bd.anchor1().body {
+                            Some(ref b) => set_body_index(b, &mut bodies, &mut id),
+                            None        => { }
+                        }
+
+                        match bd.anchor2().body {
+                            Some(ref b) => set_body_index(b, &mut bodies, &mut id),
+                            None        => { }
+                        }
+                    },
+                    Constraint::Grab(ref g) => {
+                        joints.push(i);
+                        match g.borrow().anchor1().body {
+                            Some(ref b) => set_body_index(b, &mut bodies, &mut id),
+                            None        => { }
+                        }
                     }
                 }
             }
@@ -386,6 +896,54 @@ impl Solver<Constraint> for AccumulatedImpulseSolver {
             self.do_solve(dt.clone(), constraints, joints.as_slice(), bodies.as_slice());
             self.cache.swap();
         }
+
+        bodies.clear();
+        joints.clear();
+        self.bodies = bodies;
+        self.joints = joints;
+    }
+}
+
+// The number of velocity constraints needed to solve a joint.
+fn num_equations(joint: &Constraint) -> uint {
+    match *joint {
+        Constraint::BallInSocket(_)   => na::dim::<Vect>(),
+        Constraint::Fixed(_)          => na::dim::<Vect>() + na::dim::<Orientation>(),
+        Constraint::Distance(_)       => 1,
+        Constraint::Grab(_)           => na::dim::<Vect>(),
+        Constraint::RBRB(_, _, _)     => 0
+    }
+}
+
+// Computes the number of contacts separating each body from the static bodies. Bodies without any
+// path to a static body get the maximum value of `Scalar`.
+fn contact_graph_heights(contacts: &[VelocityConstraint], num_bodies: uint, heights: &mut Vec<Scalar>) {
+    let _1:  Scalar = na::one();
+    let inf: Scalar = Bounded::max_value();
+
+    heights.clear();
+    heights.grow(num_bodies, inf);
+
+    // Each pass propagates the heights through at least one more contact. The stacks are usually
+    // low enough for this to be cheaper than building the adjacency lists of the contact graph.
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        for c in contacts.iter() {
+            let h1 = if c.id1 < 0 { na::zero() } else { heights[c.id1 as uint] };
+            let h2 = if c.id2 < 0 { na::zero() } else { heights[c.id2 as uint] };
+
+            if c.id2 >= 0 && h1 + _1 < h2 {
+                heights[c.id2 as uint] = h1 + _1;
+                changed = true;
+            }
+            else if c.id1 >= 0 && h2 + _1 < h1 {
+                heights[c.id1 as uint] = h2 + _1;
+                changed = true;
+            }
+        }
     }
 }
 
@@ -398,3 +956,21 @@ fn resize_buffer<A: Clone>(buff: &mut Vec<A>, size: uint, val: A) {
         buff.truncate(size)
     }
 }
+
+// The timestep used by the contact between `rb1` and `rb2`: the slowest of them sets the pace.
+fn contact_dt(dt: Scalar, rb1: &RigidBody, rb2: &RigidBody) -> Scalar {
+    dt * rb1.effective_time_scale().min(rb2.effective_time_scale())
+}
+
+// The position of the part `id` of the shape of `rb`, in global coordinates.
+fn part_position(rb: &RigidBody, id: &[uint]) -> Matrix {
+    match rb.sub_shape(id) {
+        Some((delta, _)) => *rb.position() * delta,
+        None             => rb.position().clone()
+    }
+}
+
+fn shuffling_rng(seed: u64) -> XorShiftRng {
+    // The seed of a xorshift generator must not be zero.
+    SeedableRng::from_seed([ seed as u32, (seed >> 32) as u32, 0x9e37_79b9, 0x7f4a_7c15 ])
+}
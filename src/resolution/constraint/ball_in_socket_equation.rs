@@ -6,6 +6,7 @@ use object::RigidBody;
 use detection::joint::{Anchor, BallInSocket, Joint};
 use resolution::constraint::velocity_constraint::VelocityConstraint;
 use resolution::constraint::contact_equation::CorrectionParameters;
+use resolution::constraint::spring_damper::SpringDamper;
 use resolution::constraint::contact_equation;
 
 pub fn fill_second_order_equation(dt:          Scalar,
@@ -18,6 +19,7 @@ pub fn fill_second_order_equation(dt:          Scalar,
         &joint.anchor2_pos(),
         joint.anchor1(),
         joint.anchor2(),
+        &joint.spring().or(correction.joint_spring.clone()),
         constraints,
         correction);
 }
@@ -29,9 +31,11 @@ pub fn cancel_relative_linear_motion<P>(
                                      global2:     &Point,
                                      anchor1:     &Anchor<P>,
                                      anchor2:     &Anchor<P>,
+                                     spring:      &Option<SpringDamper>,
                                      constraints: &mut [VelocityConstraint],
                                      correction:  &CorrectionParameters) {
-    let error      = (*global2 - *global1) * correction.joint_corr;
+    let (cfm, erp) = contact_equation::joint_cfm_erp(dt.clone(), spring, correction);
+    let error      = (*global2 - *global1) * erp;
     let rot_axis1  = na::cross_matrix(&(*global1 - anchor1.center_of_mass()));
     let rot_axis2  = na::cross_matrix(&(*global2 - anchor2.center_of_mass()));
 
@@ -75,6 +79,8 @@ pub fn cancel_relative_linear_motion<P>(
             constraint
         );
 
+        contact_equation::soften(constraint, cfm.clone());
+
         let _max: Scalar = Bounded::max_value();
         constraint.lobound   = -_max;
         constraint.hibound   = _max;
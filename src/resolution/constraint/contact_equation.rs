@@ -1,9 +1,10 @@
 use std::num::FloatMath;
-use na::Bounded;
+use na::{Bounded, Transform};
 use na;
 use ncollide::geometry::Contact;
 use ncollide::volumetric::InertiaTensor;
 use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::spring_damper::SpringDamper;
 use object::RigidBody;
 use math::{Scalar, Point, Vect, Orientation};
 
@@ -64,7 +65,10 @@ impl CorrectionMode {
 pub struct CorrectionParameters {
     pub corr_mode:       CorrectionMode,
     pub joint_corr:      Scalar,
-    pub rest_eps:        Scalar
+    pub rest_eps:        Scalar,
+    pub max_corr_vel:    Scalar,
+    pub contact_spring:  Option<SpringDamper>,
+    pub joint_spring:    Option<SpringDamper>
 }
 
 pub fn reinit_to_first_order_equation(dt:         Scalar,
@@ -74,7 +78,8 @@ pub fn reinit_to_first_order_equation(dt:         Scalar,
     /*
      * Fill b
      */
-    if coll.depth >= correction.corr_mode.min_depth_for_pos_corr() {
+    // The soft contacts are not repositioned: their penetration is what makes them push back.
+    if coll.depth >= correction.corr_mode.min_depth_for_pos_corr() && na::is_zero(&constraint.cfm) {
         constraint.objective = correction.corr_mode.pos_corr_factor() * coll.depth.max(na::zero()) / dt;
     }
     else {
@@ -96,23 +101,27 @@ pub fn fill_second_order_equation(dt:           Scalar,
                                   fconstraints: &mut [VelocityConstraint],
                                   idf:          uint,
                                   cache:        &[Scalar],
-                                  correction:   &CorrectionParameters) {
+                                  drift:        &Vect,
+                                  correction:   &CorrectionParameters)
+                                  -> bool {
     let restitution = rb1.restitution() * rb2.restitution();
+    let spring      = contact_spring(rb1, rb2, correction);
 
     let center = na::center(&coll.world1, &coll.world2);
 
-    fill_velocity_constraint(dt.clone(),
-                             coll.normal.clone(),
-                             center.clone(),
-                             restitution,
-                             coll.depth.clone(),
-                             cache[0].clone(), // coll.impulses[0].clone(),
-                             na::zero(),
-                             Bounded::max_value(),
-                             rb1,
-                             rb2,
-                             rconstraint,
-                             correction);
+    let capped = fill_velocity_constraint(dt.clone(),
+                                          coll.normal.clone(),
+                                          center.clone(),
+                                          restitution,
+                                          coll.depth.clone(),
+                                          cache[0].clone(), // coll.impulses[0].clone(),
+                                          na::zero(),
+                                          Bounded::max_value(),
+                                          &spring,
+                                          rb1,
+                                          rb2,
+                                          rconstraint,
+                                          correction);
 
 
     let friction  = rb1.friction() * rb2.friction();
@@ -125,25 +134,94 @@ pub fn fill_second_order_equation(dt:           Scalar,
     na::orthonormal_subspace_basis(&coll.normal, |friction_axis| {
         let constraint = &mut fconstraints[idf + i];
 
-        fill_velocity_constraint(dt.clone(),
-                                 friction_axis,
-                                 center.clone(),
-                                 na::zero(),
-                                 na::zero(),
-                                 cache[i + 1].clone(), // coll.impulses[i].clone(),
-                                 na::zero(), // dont setup the limit now
-                                 na::zero(), // dont setup the limit now
-                                 rb1,
-                                 rb2,
-                                 constraint,
-                                 correction);
+        let _ = fill_velocity_constraint(dt.clone(),
+                                         friction_axis,
+                                         center.clone(),
+                                         na::zero(),
+                                         na::zero(),
+                                         cache[i + 1].clone(), // coll.impulses[i].clone(),
+                                         na::zero(), // dont setup the limit now
+                                         na::zero(), // dont setup the limit now
+                                         &None,
+                                         rb1,
+                                         rb2,
+                                         constraint,
+                                         correction);
+
+        // Pull the bodies back toward the place they started touching at, as far as the friction
+        // cone allows it.
+        constraint.objective = constraint.objective -
+                               na::dot(drift, &friction_axis) * correction.corr_mode.vel_corr_factor() / dt;
 
         constraint.friction_coeff    = friction.clone();
         constraint.friction_limit_id = idr;
         i = i + 1;
 
         true
-    })
+    });
+
+    capped
+}
+
+// The spring-damper of the contacts between two bodies: those of the bodies in series, or the
+// default one if neither is soft.
+fn contact_spring(rb1: &RigidBody, rb2: &RigidBody, correction: &CorrectionParameters) -> Option<SpringDamper> {
+    match (rb1.contact_spring(), rb2.contact_spring()) {
+        (Some(s1), Some(s2))              => Some(s1.in_series(&s2)),
+        (Some(s), None) | (None, Some(s)) => Some(s),
+        (None, None)                      => correction.contact_spring.clone()
+    }
+}
+
+/// The constraint force mixing and the error reduction parameter of a joint with the given
+/// spring-damper, or of a rigid joint if there is none.
+pub fn joint_cfm_erp(dt: Scalar, spring: &Option<SpringDamper>, correction: &CorrectionParameters) -> (Scalar, Scalar) {
+    match *spring {
+        Some(ref spring) => spring.cfm_erp(dt),
+        None             => (na::zero(), correction.joint_corr.clone())
+    }
+}
+
+/// Makes a constraint soft with the given constraint force mixing.
+///
+/// This must be called after its geometry is filled.
+pub fn soften(constraint: &mut VelocityConstraint, cfm: Scalar) {
+    if !na::is_zero(&cfm) {
+        let _1: Scalar = na::one();
+
+        constraint.cfm                = cfm;
+        constraint.inv_projected_mass = _1 / (_1 / constraint.inv_projected_mass + cfm);
+    }
+}
+
+/// Updates the friction anchors of a contact, and computes the tangential drift of the bodies
+/// since they were set.
+///
+/// The anchors are the points of each body, in local coordinates, that were at the contact center
+/// when the contact appeared. They are reset to the current contact center when there were none,
+/// or once the bodies moved by more than `max_drift` from them.
+pub fn update_friction_anchors(coll:      &Contact<Scalar, Point, Vect>,
+                               rb1:       &RigidBody,
+                               rb2:       &RigidBody,
+                               anchors:   Option<(Point, Point)>,
+                               max_drift: Scalar)
+                               -> ((Point, Point), Vect) {
+    match anchors {
+        Some((a1, a2)) => {
+            let drift = rb2.position().transform(&a2) - rb1.position().transform(&a1);
+
+            // The normal part of the drift is checked too: the anchors of a rolling body quickly
+            // separate along the normal, and must not brake it.
+            if na::sqnorm(&drift) <= max_drift * max_drift {
+                return ((a1, a2), drift - coll.normal * na::dot(&drift, &coll.normal))
+            }
+        },
+        None => { }
+    }
+
+    let center = na::center(&coll.world1, &coll.world2);
+
+    ((rb1.position().inv_transform(&center), rb2.position().inv_transform(&center)), na::zero())
 }
 
 pub fn fill_constraint_geometry(normal:     Vect,
@@ -154,6 +232,7 @@ pub fn fill_constraint_geometry(normal:     Vect,
                                 constraint: &mut VelocityConstraint) {
     constraint.normal             = normal;
     constraint.inv_projected_mass = na::zero();
+    constraint.cfm                = na::zero();
 
     match *rb1 {
         Some(ref rb) => {
@@ -197,10 +276,12 @@ fn fill_velocity_constraint(dt:              Scalar,
                             initial_impulse: Scalar,
                             lobound:         Scalar,
                             hibound:         Scalar,
+                            spring:          &Option<SpringDamper>,
                             rb1:             &RigidBody,
                             rb2:             &RigidBody,
                             constraint:      &mut VelocityConstraint,
-                            correction:      &CorrectionParameters) {
+                            correction:      &CorrectionParameters)
+                            -> bool {
     let rot_axis1 = na::cross(&(center - *rb1.center_of_mass()), &-normal);
     let rot_axis2 = na::cross(&(center - *rb2.center_of_mass()), &normal);
 
@@ -217,7 +298,7 @@ fn fill_velocity_constraint(dt:              Scalar,
     /*
      * correction amount
      */
-    constraint.objective = relative_velocity(
+    let rel_vel = relative_velocity(
         &opt_rb1,
         &opt_rb2,
         &constraint.normal,
@@ -225,17 +306,43 @@ fn fill_velocity_constraint(dt:              Scalar,
         &constraint.rot_axis2,
         &dt);
 
-    if constraint.objective < -correction.rest_eps {
-        constraint.objective = constraint.objective + restitution * constraint.objective
+    // The separation velocity due to the restitution depends on the approach velocity only. It
+    // never exceeds it so that a bounce cannot create energy.
+    let bounce = if rel_vel < -correction.rest_eps {
+        -rel_vel * restitution.min(na::one())
     }
+    else {
+        na::zero()
+    };
 
-    constraint.objective = -constraint.objective;
+    constraint.objective = -rel_vel;
+
+    // Whether the penetration correction was limited by `max_corr_vel`.
+    let mut capped = false;
 
     if depth < na::zero() {
-        constraint.objective = constraint.objective + depth / dt
+        constraint.objective = constraint.objective + bounce + depth / dt
+    }
+    else if spring.is_some() {
+        // The penetration is pushed back by the spring instead of the penalty correction.
+        let (cfm, erp) = spring.as_ref().unwrap().cfm_erp(dt.clone());
+        let corr       = erp * depth / dt;
+
+        soften(constraint, cfm);
+        capped = corr > correction.max_corr_vel && corr > bounce;
+        constraint.objective = constraint.objective + bounce.max(corr.min(correction.max_corr_vel))
     }
     else if depth < correction.corr_mode.max_depth_for_vel_corr() {
-        constraint.objective = constraint.objective + depth * correction.corr_mode.vel_corr_factor() / dt
+        // Never inject more than `max_corr_vel` to separate the bodies: deep initial overlaps are
+        // then resolved over several steps instead of catapulting the bodies apart.
+        let corr = depth * correction.corr_mode.vel_corr_factor() / dt;
+
+        // The penetration correction is not added on top of the bounce.
+        capped = corr > correction.max_corr_vel && corr > bounce;
+        constraint.objective = constraint.objective + bounce.max(corr.min(correction.max_corr_vel))
+    }
+    else {
+        constraint.objective = constraint.objective + bounce
     }
 
     // for warm-starting
@@ -246,6 +353,8 @@ fn fill_velocity_constraint(dt:              Scalar,
      */
     constraint.lobound = lobound;
     constraint.hibound = hibound;
+
+    capped
 }
 
 pub fn relative_velocity(rb1:       &Option<&RigidBody>,
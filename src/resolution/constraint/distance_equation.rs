@@ -0,0 +1,82 @@
+use std::num::Float;
+use na::Bounded;
+use na;
+use math::{Scalar, Vect};
+use detection::joint::{Distance, DistanceMode, Joint};
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use resolution::constraint::ball_in_socket_equation::write_anchor_id;
+use resolution::constraint::contact_equation;
+
+pub fn fill_second_order_equation(dt:         Scalar,
+                                  joint:      &Distance,
+                                  constraint: &mut VelocityConstraint,
+                                  correction: &CorrectionParameters) {
+    let global1 = joint.anchor1_pos();
+    let global2 = joint.anchor2_pos();
+    let anchor1 = joint.anchor1();
+    let anchor2 = joint.anchor2();
+
+    let delta = global2 - global1;
+    let dist  = na::norm(&delta);
+
+    // The direction is arbitrary when both anchors coincide.
+    let normal = if dist > Float::epsilon() {
+        delta / dist
+    }
+    else {
+        let mut axis: Vect = na::zero();
+        axis[0] = na::one();
+        axis
+    };
+
+    let rot_axis1 = na::cross(&(global1 - anchor1.center_of_mass()), &-normal);
+    let rot_axis2 = na::cross(&(global2 - anchor2.center_of_mass()), &normal);
+
+    let opt_rb1 = write_anchor_id(anchor1, &mut constraint.id1);
+    let opt_rb2 = write_anchor_id(anchor2, &mut constraint.id2);
+
+    let dvel = contact_equation::relative_velocity(
+        &opt_rb1.as_ref().map(|r| &**r),
+        &opt_rb2.as_ref().map(|r| &**r),
+        &normal,
+        &rot_axis1,
+        &rot_axis2,
+        &dt);
+
+    contact_equation::fill_constraint_geometry(
+        normal,
+        rot_axis1,
+        rot_axis2,
+        &opt_rb1.as_ref().map(|r| &**r),
+        &opt_rb2.as_ref().map(|r| &**r),
+        constraint
+    );
+
+    let spring     = joint.spring().or(correction.joint_spring.clone());
+    let (cfm, erp) = contact_equation::joint_cfm_erp(dt.clone(), &spring, correction);
+
+    contact_equation::soften(constraint, cfm);
+
+    let error = dist - joint.length();
+    let _max: Scalar = Bounded::max_value();
+    let max_impulse  = match joint.max_force() {
+        Some(f) => f * dt,
+        None    => _max
+    };
+
+    let objective = match joint.mode() {
+        // A slack rope lets the anchors get closer to each other by up to the whole remaining
+        // length during this step.
+        DistanceMode::MaxDistance if error < na::zero() => -dvel - error / dt,
+        _                                               => -dvel - error * erp / dt
+    };
+
+    constraint.lobound = -max_impulse;
+    constraint.hibound = match joint.mode() {
+        DistanceMode::Rigid       => max_impulse,
+        DistanceMode::MaxDistance => na::zero()
+    };
+    constraint.objective = objective * (na::one::<Scalar>() - joint.softness());
+    constraint.impulse   = na::zero(); // FIXME: cache
+}
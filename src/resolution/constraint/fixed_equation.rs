@@ -1,9 +1,11 @@
-use na::{Translate, Rotation, Bounded};
+use na::{Translate, Bounded};
 use na;
 use detection::joint::{Fixed, Anchor, Joint};
+use integration::euler;
 use resolution::constraint::ball_in_socket_equation;
 use resolution::constraint::velocity_constraint::VelocityConstraint;
 use resolution::constraint::contact_equation::CorrectionParameters;
+use resolution::constraint::spring_damper::SpringDamper;
 use resolution::constraint::contact_equation;
 use math::{Scalar, Vect, Orientation, Matrix};
 
@@ -11,8 +13,9 @@ pub fn fill_second_order_equation(dt:          Scalar,
                                   joint:       &Fixed,
                                   constraints: &mut [VelocityConstraint],
                                   correction:  &CorrectionParameters) {
-    let ref1 = joint.anchor1_pos();
-    let ref2 = joint.anchor2_pos();
+    let ref1   = joint.anchor1_pos();
+    let ref2   = joint.anchor2_pos();
+    let spring = joint.spring().or(correction.joint_spring.clone());
 
     ball_in_socket_equation::cancel_relative_linear_motion(
         dt.clone(),
@@ -20,6 +23,7 @@ pub fn fill_second_order_equation(dt:          Scalar,
         &ref2.translate(&na::orig()),
         joint.anchor1(),
         joint.anchor2(),
+        &spring,
         constraints,
         correction);
 
@@ -29,6 +33,7 @@ pub fn fill_second_order_equation(dt:          Scalar,
         &ref2,
         joint.anchor1(),
         joint.anchor2(),
+        &spring,
         constraints.slice_from_mut(na::dim::<Vect>()),
         correction);
 }
@@ -38,10 +43,12 @@ pub fn cancel_relative_angular_motion<P>(dt:          Scalar,
                                          ref2:        &Matrix,
                                          anchor1:     &Anchor<P>,
                                          anchor2:     &Anchor<P>,
+                                         spring:      &Option<SpringDamper>,
                                          constraints: &mut [VelocityConstraint],
                                          correction:  &CorrectionParameters) {
-    let delta     = na::inv(ref2).expect("ref2 must be inversible.") * *ref1;
-    let delta_rot = delta.rotation();
+    let delta      = na::inv(ref2).expect("ref2 must be inversible.") * *ref1;
+    let delta_rot  = euler::rotation_vector(&delta);
+    let (cfm, erp) = contact_equation::joint_cfm_erp(dt.clone(), spring, correction);
 
     let mut i = 0;
     na::canonical_basis(|rot_axis: Orientation| {
@@ -59,6 +66,8 @@ pub fn cancel_relative_angular_motion<P>(dt:          Scalar,
             constraint
         );
 
+        contact_equation::soften(constraint, cfm.clone());
+
         let ang_vel1 = match opt_rb1 { Some(rb) => rb.ang_vel(), None => na::zero() };
         let ang_vel2 = match opt_rb2 { Some(rb) => rb.ang_vel(), None => na::zero() };
 
@@ -66,7 +75,7 @@ pub fn cancel_relative_angular_motion<P>(dt:          Scalar,
         constraint.lobound   = -_max;
         constraint.hibound   = _max;
         // FIXME: dont compute the difference at each iteration
-        let error = na::dot(&delta_rot, &rot_axis) * correction.joint_corr / dt;
+        let error = na::dot(&delta_rot, &rot_axis) * erp / dt;
         constraint.objective = na::dot(&(ang_vel2 - ang_vel1), &rot_axis) - error;
         constraint.impulse   = na::zero(); // FIXME: cache
 
@@ -0,0 +1,37 @@
+use std::num::Float;
+use na;
+use math::{Scalar, Vect};
+use detection::joint::{Grab, Joint};
+use resolution::constraint::velocity_constraint::VelocityConstraint;
+use resolution::constraint::contact_equation::CorrectionParameters;
+use resolution::constraint::ball_in_socket_equation;
+
+pub fn fill_second_order_equation(dt:          Scalar,
+                                  joint:       &Grab,
+                                  constraints: &mut [VelocityConstraint],
+                                  correction:  &CorrectionParameters) {
+    // The target is a ball-in-socket anchor attached to the ground.
+    ball_in_socket_equation::cancel_relative_linear_motion(
+        dt.clone(),
+        &joint.anchor1_pos(),
+        &joint.anchor2_pos(),
+        joint.anchor1(),
+        joint.anchor2(),
+        &joint.spring().or(correction.joint_spring.clone()),
+        constraints,
+        correction);
+
+    match joint.max_force() {
+        Some(f) => {
+            // Bounding each axis by this keeps the norm of the total impulse under `f * dt`.
+            let dim: Scalar = na::cast(na::dim::<Vect>() as f64);
+            let max_impulse = f * dt / dim.sqrt();
+
+            for constraint in constraints.iter_mut().take(na::dim::<Vect>()) {
+                constraint.lobound = -max_impulse;
+                constraint.hibound = max_impulse;
+            }
+        },
+        None => { }
+    }
+}
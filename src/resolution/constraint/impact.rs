@@ -0,0 +1,75 @@
+use object::{RigidBodyHandle, SubShapeId};
+use math::{Scalar, Point, Vect};
+
+/// A significant impact between two rigid bodies, detected by the constraint solver.
+pub struct Impact {
+    /// The first body involved in the impact.
+    pub body1:             RigidBodyHandle,
+    /// The second body involved in the impact.
+    pub body2:             RigidBodyHandle,
+    /// The contact point, in global coordinates.
+    pub point:             Point,
+    /// The contact normal, oriented toward the second body.
+    pub normal:            Vect,
+    /// The speed at which the two bodies were approaching each other along the normal.
+    pub approach_velocity: Scalar,
+    /// The normal impulse applied by the solver to separate the bodies.
+    pub impulse:           Scalar,
+    /// The part of the shape of the first body involved in the impact.
+    pub sub_shape1:        SubShapeId,
+    /// The part of the shape of the second body involved in the impact.
+    pub sub_shape2:        SubShapeId,
+    /// The contact point, in the local coordinates of the first body.
+    ///
+    /// This and the other local coordinates are computed with the positions of the bodies at the
+    /// time of the impact, so they do not depend on when the event is processed.
+    pub local_point1:      Point,
+    /// The contact normal, oriented toward the second body, in the local coordinates of the first
+    /// body.
+    pub local_normal1:     Vect,
+    /// The contact point, in the local coordinates of the second body.
+    pub local_point2:      Point,
+    /// The contact normal, oriented toward the second body, in the local coordinates of the
+    /// second body.
+    pub local_normal2:     Vect,
+    /// The contact point, in the local coordinates of the part `sub_shape1` of the first body.
+    ///
+    /// This is `local_point1` unless the shape of the body is a compound, e.g., to know which side
+    /// of a part of a vehicle was hit.
+    pub part_point1:       Point,
+    /// The contact normal, oriented toward the second body, in the local coordinates of the part
+    /// `sub_shape1` of the first body.
+    pub part_normal1:      Vect,
+    /// The contact point, in the local coordinates of the part `sub_shape2` of the second body.
+    pub part_point2:       Point,
+    /// The contact normal, oriented toward the second body, in the local coordinates of the part
+    /// `sub_shape2` of the second body.
+    pub part_normal2:      Vect,
+    /// The velocity of the contact point of the second body relative to the one of the first body,
+    /// before the impact, in global coordinates.
+    pub relative_velocity: Vect
+}
+
+impl Clone for Impact {
+    fn clone(&self) -> Impact {
+        Impact {
+            body1:             self.body1.clone(),
+            body2:             self.body2.clone(),
+            point:             self.point.clone(),
+            normal:            self.normal.clone(),
+            approach_velocity: self.approach_velocity.clone(),
+            impulse:           self.impulse.clone(),
+            sub_shape1:        self.sub_shape1.clone(),
+            sub_shape2:        self.sub_shape2.clone(),
+            local_point1:      self.local_point1.clone(),
+            local_normal1:     self.local_normal1.clone(),
+            local_point2:      self.local_point2.clone(),
+            local_normal2:     self.local_normal2.clone(),
+            part_point1:       self.part_point1.clone(),
+            part_normal1:      self.part_normal1.clone(),
+            part_point2:       self.part_point2.clone(),
+            part_normal2:      self.part_normal2.clone(),
+            relative_velocity: self.relative_velocity.clone()
+        }
+    }
+}
@@ -11,7 +11,7 @@ use na::IterableMut;
 use math::{Scalar, Point};
 use ncollide::utils::AsBytes;
 
-#[deriving(PartialEq)]
+#[deriving(PartialEq, Clone)]
 /// The identifier of a contact stored in the impulse cache.
 pub struct ContactIdentifier {
     obj1:    uint,
@@ -44,11 +44,17 @@ impl ContactIdentifier {
     }
 }
 
+// The contacts are enumerated in the order they were inserted, not in the order of the hash map,
+// so that the solver processes them in an order which does not depend on its capacity.
 pub struct ImpulseCache {
-    hash_prev:           HashMap<ContactIdentifier, (uint, uint), SipHasher>,
+    hash_prev:           HashMap<ContactIdentifier, uint, SipHasher>,
+    entries_prev:        Vec<(uint, uint)>,
     cache_prev:          Vec<Scalar>,
-    hash_next:           HashMap<ContactIdentifier, (uint, uint), SipHasher>,
+    hash_next:           HashMap<ContactIdentifier, uint, SipHasher>,
+    entries_next:        Vec<(uint, uint)>,
     cache_next:          Vec<Scalar>,
+    anchors_prev:        Vec<Option<(Point, Point)>>,
+    anchors_next:        Vec<Option<(Point, Point)>>,
     step:                Scalar,
     impulse_per_contact: uint
 }
@@ -60,8 +66,12 @@ impl ImpulseCache {
         ImpulseCache {
             hash_prev:           HashMap::with_capacity_and_hasher(32, SipHasher::new_with_keys(rng.gen(), rng.gen())),
             hash_next:           HashMap::with_capacity_and_hasher(32, SipHasher::new_with_keys(rng.gen(), rng.gen())),
+            entries_prev:        Vec::new(),
+            entries_next:        Vec::new(),
             cache_prev:          Vec::from_elem(impulse_per_contact, na::zero()),
             cache_next:          Vec::from_elem(impulse_per_contact, na::zero()),
+            anchors_prev:        Vec::from_elem(1, None),
+            anchors_next:        Vec::from_elem(1, None),
             step:                step,
             impulse_per_contact: impulse_per_contact
         }
@@ -70,20 +80,30 @@ impl ImpulseCache {
     pub fn insert(&mut self, cid: uint, obj1: uint, obj2: uint, center: Point) {
         let id = ContactIdentifier::new(obj1, obj2, center, &self.step);
         let imp =
-            match self.hash_prev.get(&id).cloned() {
-                Some((_, i)) => i,
-                None         => 0
+            match self.hash_prev.get(&id) {
+                Some(e) => self.entries_prev[*e].val1(),
+                None    => 0
             };
 
-        let _ = self.hash_next.insert(id, (cid, imp));
+        // A contact falling in the same cell as a previous one replaces it.
+        let existing = self.hash_next.get(&id).cloned();
+
+        match existing {
+            Some(e) => self.entries_next[e] = (cid, imp),
+            None    => {
+                let _ = self.hash_next.insert(id, self.entries_next.len());
+                self.entries_next.push((cid, imp));
+            }
+        }
     }
 
-    pub fn hash(&self) -> &HashMap<ContactIdentifier, (uint, uint), SipHasher> {
-        &self.hash_next
+    // The index of each contact in the constraints, and the offset of its cached impulses.
+    pub fn entries(&self) -> &[(uint, uint)] {
+        self.entries_next.as_slice()
     }
 
-    pub fn hash_mut(&mut self) -> &mut HashMap<ContactIdentifier, (uint, uint), SipHasher> {
-        &mut self.hash_next
+    pub fn entries_mut(&mut self) -> &mut [(uint, uint)] {
+        self.entries_next.as_mut_slice()
     }
 
     pub fn push_impulsions(&mut self) -> &mut [Scalar] {
@@ -98,6 +118,15 @@ impl ImpulseCache {
         self.cache_next.slice_mut(begin, end)
     }
 
+    // Stores the friction anchors of the contact whose impulses were pushed last.
+    pub fn push_anchors(&mut self, anchors: Option<(Point, Point)>) {
+        self.anchors_next.push(anchors)
+    }
+
+    pub fn anchors_at(&self, at: uint) -> Option<(Point, Point)> {
+        self.anchors_prev[at / self.impulse_per_contact].clone()
+    }
+
     pub fn reserved_impulse_offset(&self) -> uint {
         self.impulse_per_contact
     }
@@ -107,7 +136,7 @@ impl ImpulseCache {
     }
 
     pub fn len(&self) -> uint {
-        self.hash_next.len()
+        self.entries_next.len()
     }
 
     pub fn clear(&mut self) {
@@ -115,15 +144,49 @@ impl ImpulseCache {
         self.hash_prev.clear();
         self.cache_next.clear();
         self.hash_next.clear();
+        self.entries_prev.clear();
+        self.entries_next.clear();
 
         self.cache_prev.grow(self.impulse_per_contact, na::zero());
         self.cache_next.grow(self.impulse_per_contact, na::zero());
+
+        self.anchors_prev.clear();
+        self.anchors_next.clear();
+        self.anchors_prev.push(None);
+        self.anchors_next.push(None);
+    }
+
+    // Forgets the impulses cached for the contacts involving the object `obj`.
+    pub fn remove_object(&mut self, obj: uint) {
+        let ids: Vec<ContactIdentifier> =
+            self.hash_prev.keys().filter(|id| id.obj1 == obj || id.obj2 == obj).map(|id| id.clone()).collect();
+
+        for id in ids.iter() {
+            let _ = self.hash_prev.remove(id);
+        }
+    }
+
+    pub fn scale_impulsions(&mut self, factor: Scalar) {
+        for imp in self.cache_prev.iter_mut() {
+            *imp = *imp * factor
+        }
+    }
+
+    #[cfg(feature = "alloc_stats")]
+    pub fn buffers_capacity(&self) -> uint {
+        self.hash_prev.capacity() + self.hash_next.capacity() + self.entries_prev.capacity() +
+        self.entries_next.capacity() + self.cache_prev.capacity() +
+        self.cache_next.capacity() + self.anchors_prev.capacity() + self.anchors_next.capacity()
     }
 
     pub fn swap(&mut self) {
         mem::swap(&mut self.hash_prev, &mut self.hash_next);
+        mem::swap(&mut self.entries_prev, &mut self.entries_next);
         mem::swap(&mut self.cache_prev,&mut self.cache_next);
+        mem::swap(&mut self.anchors_prev, &mut self.anchors_next);
         self.hash_next.clear();
+        self.entries_next.clear();
         self.cache_next.truncate(self.impulse_per_contact);
+        self.anchors_next.truncate(1);
     }
 }
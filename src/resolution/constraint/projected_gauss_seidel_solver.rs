@@ -1,5 +1,8 @@
+use std::num::FloatMath;
+use std::cmp::Ordering;
+use rand::{Rng, XorShiftRng};
 use na;
-use math::{Vect, Orientation};
+use math::{Scalar, Vect, Orientation};
 use resolution::constraint::velocity_constraint::VelocityConstraint;
 
 /// Structure holding the result of the projected gauss seidel solver.
@@ -45,6 +48,33 @@ pub fn projected_gauss_seidel_solve(restitution:    &mut [VelocityConstraint],
                                     num_bodies:     uint,
                                     num_iterations: uint,
                                     is_lambda_zero: bool) {
+    solve(restitution, friction, result, num_bodies, num_iterations, is_lambda_zero, None)
+}
+
+/// Solve a set of velocity constraints using the projected gauss seidel solver, visiting them in
+/// a different random order at each iteration.
+///
+/// The arguments are those of `projected_gauss_seidel_solve`, and `rng` draws the orderings. The
+/// result of the sequential resolution depends on the order of the constraints: with a fixed
+/// order, the impulses are biased toward the last constraints, e.g., a symmetric pile of bodies
+/// drifts toward one side. Shuffling the constraints spreads this bias evenly.
+pub fn projected_gauss_seidel_solve_shuffled(restitution:    &mut [VelocityConstraint],
+                                             friction:       &mut [VelocityConstraint],
+                                             result:         &mut [Velocities],
+                                             num_bodies:     uint,
+                                             num_iterations: uint,
+                                             is_lambda_zero: bool,
+                                             rng:            &mut XorShiftRng) {
+    solve(restitution, friction, result, num_bodies, num_iterations, is_lambda_zero, Some(rng))
+}
+
+fn solve(restitution:    &mut [VelocityConstraint],
+         friction:       &mut [VelocityConstraint],
+         result:         &mut [Velocities],
+         num_bodies:     uint,
+         num_iterations: uint,
+         is_lambda_zero: bool,
+         mut rng:        Option<&mut XorShiftRng>) {
     // initialize the solution with zeros...
     // mj_lambda is result
     assert!(result.len() == num_bodies);
@@ -67,23 +97,126 @@ pub fn projected_gauss_seidel_solve(restitution:    &mut [VelocityConstraint],
     /*
      * solve the system
      */
+    let mut restitution_order: Vec<uint> = range(0, restitution.len()).collect();
+    let mut friction_order: Vec<uint>    = range(0, friction.len()).collect();
+
     for _ in range(0, num_iterations) {
-        for c in restitution.iter_mut() {
+        match rng {
+            Some(ref mut rng) => {
+                rng.shuffle(restitution_order.as_mut_slice());
+                rng.shuffle(friction_order.as_mut_slice());
+            },
+            None => { }
+        }
+
+        for &i in restitution_order.iter() {
+            solve_velocity_constraint(&mut restitution[i], result);
+        }
+
+        for &i in friction_order.iter() {
+            let c = &mut friction[i];
+
+            // The friction is solved even without any normal impulse: its bounds are then zero,
+            // which cancels the warm-started friction impulse instead of leaving it applied.
+            let impulse = restitution[c.friction_limit_id].impulse.max(na::zero());
+            let bound   = c.friction_coeff * impulse;
+
+            c.lobound = -bound;
+            c.hibound = bound;
+
             solve_velocity_constraint(c, result);
         }
+    }
+}
+
+/// Propagates the contact impulses from the bottom to the top of stacks.
+///
+/// The constraints are solved once more, layer by layer, starting from the lowest ones. For each
+/// contact, the lowest body is given an infinite mass so that the heaviest bodies cannot push the
+/// lightest bodies out of the way.
+///
+/// # Arguments:
+/// * `restitution` - the contact constraints, already solved by `projected_gauss_seidel_solve`.
+/// The impulses applied by this pass are accumulated into their `impulse`, which thus stays the
+/// total impulse applied through each contact.
+/// * `heights` - the height of each body in the stacks, indexed by its solver index.
+/// * `result` - the velocity changes computed by `projected_gauss_seidel_solve`, updated in-place.
+/// * `num_iterations` - the number of iterations to perform on each layer.
+pub fn shock_propagation_solve(restitution:    &mut [VelocityConstraint],
+                               heights:        &[Scalar],
+                               result:         &mut [Velocities],
+                               num_iterations: uint) {
+    let eps: Scalar = na::cast(1.0e-3f64);
+    let mut layers  = Vec::new();
 
-        for c in friction.iter_mut() {
-            let impulse = restitution[c.friction_limit_id].impulse.clone();
+    for (i, c) in restitution.iter().enumerate() {
+        if c.id1 < 0 || c.id2 < 0 {
+            // The contact already involves a body with an infinite mass.
+            layers.push((if c.id1 < 0 { heights[c.id2 as uint] } else { heights[c.id1 as uint] }, i, c.clone()));
+            continue;
+        }
 
-            if impulse > na::zero() {
-                let bound = c.friction_coeff * impulse;
-                c.lobound = -bound;
-                c.hibound = bound;
+        let h1 = heights[c.id1 as uint];
+        let h2 = heights[c.id2 as uint];
+        let mut c = c.clone();
 
-                solve_velocity_constraint(c, result);
-            }
+        if h1 < h2 - eps {
+            c.weighted_normal1   = na::zero();
+            c.weighted_rot_axis1 = na::zero();
+        }
+        else if h2 < h1 - eps {
+            c.weighted_normal2   = na::zero();
+            c.weighted_rot_axis2 = na::zero();
+        }
+        else {
+            // Bodies side by side: this is not a stack.
+            continue;
+        }
+
+        let inv_projected_mass =
+            na::dot(&c.normal, &c.weighted_normal1) + na::dot(&c.rot_axis1, &c.weighted_rot_axis1) +
+            na::dot(&c.normal, &c.weighted_normal2) + na::dot(&c.rot_axis2, &c.weighted_rot_axis2) +
+            c.cfm;
+
+        let _1: Scalar = na::one();
+        c.inv_projected_mass = _1 / inv_projected_mass;
+
+        layers.push((h1.min(h2), i, c));
+    }
+
+    layers.sort_by(|a, b| a.ref0().partial_cmp(b.ref0()).unwrap_or(Ordering::Equal));
+
+    for _ in range(0, num_iterations) {
+        for layer in layers.iter_mut() {
+            let (_, _, ref mut c) = *layer;
+            solve_velocity_constraint(c, result);
         }
     }
+
+    // The copies started from the impulses of the constraints, so they hold the totals.
+    for &(_, i, ref c) in layers.iter() {
+        restitution[i].impulse = c.impulse;
+    }
+}
+
+/// The velocity a constraint still lacks to be satisfied, given the result of a resolution.
+///
+/// This is positive if the constraint needs a larger impulse, e.g., when two bodies in contact are
+/// still approaching each other, and negative if it needs a smaller one.
+pub fn velocity_residual(c: &VelocityConstraint, mj_lambda: &[Velocities]) -> Scalar {
+    let mut res = c.objective - c.cfm * c.impulse;
+
+    if c.id1 >= 0 {
+        res = res + na::dot(&c.normal, &mj_lambda[c.id1 as uint].lv)
+                  - na::dot(&c.rot_axis1, &mj_lambda[c.id1 as uint].av);
+    }
+
+    if c.id2 >= 0 {
+        res = res - na::dot(&c.normal, &mj_lambda[c.id2 as uint].lv)
+                  - na::dot(&c.rot_axis2, &mj_lambda[c.id2 as uint].av);
+    }
+
+    res
 }
 
 #[inline(always)]
@@ -107,7 +240,8 @@ fn solve_velocity_constraint(c: &mut VelocityConstraint, mj_lambda: &mut [Veloci
     let id1 = c.id1;
     let id2 = c.id2;
 
-    let mut d_lambda_i = c.objective.clone();
+    // A soft constraint tolerates an error proportional to the impulse it already applied.
+    let mut d_lambda_i = c.objective - c.cfm * c.impulse;
 
     if id1 >= 0 {
         d_lambda_i = d_lambda_i + na::dot(&c.normal, &mj_lambda[id1 as uint].lv)
@@ -0,0 +1,54 @@
+use na;
+use math::Scalar;
+
+/// A spring and a damper replacing the rigid response of a constraint.
+///
+/// A soft constraint applies a force proportional to its error and to the velocity of its error
+/// instead of cancelling them. It is solved implicitly, as a constraint with a constraint force
+/// mixing and an error reduction parameter computed from the timestep, so that it remains stable
+/// for arbitrarily high stiffnesses: the response then tends to the one of a rigid constraint.
+#[deriving(Clone, Show, PartialEq)]
+pub struct SpringDamper {
+    /// The force applied per unit of error, e.g. in N/m, or in N.m/rad for the angular parts of
+    /// the joints.
+    pub stiffness: Scalar,
+    /// The force applied per unit of velocity of the error, e.g. in N.s/m.
+    pub damping:   Scalar
+}
+
+impl SpringDamper {
+    /// Creates a new spring-damper.
+    pub fn new(stiffness: Scalar, damping: Scalar) -> SpringDamper {
+        assert!(stiffness >= na::zero() && damping >= na::zero(),
+                "The stiffness and the damping must not be negative.");
+        assert!(stiffness + damping > na::zero(), "The stiffness and the damping must not be both zero.");
+
+        SpringDamper {
+            stiffness: stiffness,
+            damping:   damping
+        }
+    }
+
+    /// The spring-damper equivalent to `self` and `other` in series.
+    pub fn in_series(&self, other: &SpringDamper) -> SpringDamper {
+        fn series(a: Scalar, b: Scalar) -> Scalar {
+            if na::is_zero(&(a + b)) { na::zero() } else { a * b / (a + b) }
+        }
+
+        SpringDamper {
+            stiffness: series(self.stiffness, other.stiffness),
+            damping:   series(self.damping, other.damping)
+        }
+    }
+
+    /// The constraint force mixing and the error reduction parameter for the timestep `dt`.
+    ///
+    /// The constraint force mixing is the velocity error tolerated per unit of impulse applied,
+    /// and the error reduction parameter is the fraction of the error corrected by a step.
+    pub fn cfm_erp(&self, dt: Scalar) -> (Scalar, Scalar) {
+        let _1: Scalar = na::one();
+        let denom      = self.damping + dt * self.stiffness;
+
+        (_1 / (dt * denom), dt * self.stiffness / denom)
+    }
+}
@@ -40,7 +40,10 @@ pub struct VelocityConstraint {
     /// The id of the friction constraint.
     pub friction_limit_id:  uint,
     /// The friction coefficient on this contact.
-    pub friction_coeff:     Scalar
+    pub friction_coeff:     Scalar,
+    /// The constraint force mixing, i.e., the velocity error tolerated per unit of impulse
+    /// applied. This is zero for rigid constraints.
+    pub cfm:                Scalar
 }
 
 impl VelocityConstraint {
@@ -67,7 +70,8 @@ impl VelocityConstraint {
             id1:                -1,
             id2:                -1,
             friction_limit_id:  0,
-            friction_coeff:     na::zero()
+            friction_coeff:     na::zero(),
+            cfm:                na::zero()
         }
     }
 }
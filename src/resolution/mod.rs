@@ -3,9 +3,12 @@
 pub use resolution::solver::Solver;
 pub use resolution::constraint::accumulated_impulse_solver::AccumulatedImpulseSolver;
 pub use resolution::constraint::contact_equation::CorrectionMode;
-pub use resolution::constraint::projected_gauss_seidel_solver::{Velocities, projected_gauss_seidel_solve};
+pub use resolution::constraint::impact::Impact;
+pub use resolution::constraint::projected_gauss_seidel_solver::{Velocities, projected_gauss_seidel_solve,
+                                                                projected_gauss_seidel_solve_shuffled};
 pub use resolution::constraint::impulse_cache::{ImpulseCache, ContactIdentifier};
 pub use resolution::constraint::velocity_constraint::VelocityConstraint;
+pub use resolution::constraint::spring_damper::SpringDamper;
 
 
 // XXX: `pub` due to rust#18241
@@ -20,4 +23,8 @@ mod constraint {
     pub mod contact_equation;
     pub mod ball_in_socket_equation;
     pub mod fixed_equation;
+    pub mod distance_equation;
+    pub mod grab_equation;
+    pub mod impact;
+    pub mod spring_damper;
 }
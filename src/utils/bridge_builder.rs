@@ -0,0 +1,172 @@
+//! Construction of rope bridges, made of planks hinged to each other.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use na::{Pnt3, Vec3, Mat3, Rot3, Iso3};
+use na;
+use ncollide::bounding_volume::HasAABB;
+use math::{Scalar, Point, Vect};
+use object::{RigidBody, RigidBodyHandle};
+use detection::joint::{Anchor, BallInSocket};
+use world::World;
+
+// The fraction of the distance between its hinges by which each plank is shortened. The joints then
+// pull the planks together and keep the bridge taut while they are stretched by its weight.
+static PRE_TENSION: f64 = 0.005;
+
+/// A hinge of a bridge, made of the ball-in-socket joints on both sides of the planks.
+pub type Hinge = (Rc<RefCell<BallInSocket>>, Rc<RefCell<BallInSocket>>);
+
+/// Builds a rope bridge hanging between the fixed points `anchor_a` and `anchor_b`, and adds it to
+/// `world`.
+///
+/// The bridge is made of `num_planks` copies of `plank`, whose local `x` axis is laid along the
+/// bridge and whose local `z` axis is the axis of the hinges. Each hinge is a pair of ball-in-socket
+/// joints at the sides of the planks, given by the extents of the shape of `plank` along `z`. It
+/// links two consecutive planks, or the first and last planks to the anchors.
+///
+/// The middle of the bridge hangs below the anchors by `sag` times their distance, along the
+/// gravity of the world, or along `-y` without gravity. The planks are placed on a parabola close to
+/// the curve they hang along, so the bridge does not drop during its first steps.
+pub fn build_bridge(world:      &mut World,
+                    anchor_a:   Point,
+                    anchor_b:   Point,
+                    num_planks: uint,
+                    plank:      &RigidBody,
+                    sag:        Scalar)
+                    -> Bridge {
+    assert!(num_planks != 0, "A bridge must have at least one plank.");
+    assert!(sag >= na::zero(), "The sag of a bridge must be positive or zero.");
+
+    let gravity = world.gravity();
+    let up: Vect = if na::is_zero(&gravity) { Vec3::y() } else { -na::normalize(&gravity) };
+    let span     = anchor_b - anchor_a;
+    let side     = na::cross(&span, &up);
+
+    assert!(!na::is_zero(&side), "The anchors of a bridge must not be aligned with the gravity.");
+
+    let axis       = na::normalize(&side);
+    let half_width = plank.shape_ref().aabb(&na::one()).maxs().z;
+    let depth      = sag * na::norm(&span);
+    let shrink     = na::one::<Scalar>() - na::cast(PRE_TENSION);
+    let half: Scalar = na::cast(0.5f64);
+    let four: Scalar = na::cast(4.0f64);
+    let n: Scalar    = na::cast(num_planks as f64);
+
+    // The point of the parabola at the middle of the hinge `i`.
+    let hinge_point = |i: uint| -> Point {
+        let t: Scalar = na::cast(i as f64);
+        let t = t / n;
+
+        anchor_a + span * t - up * (four * depth * t * (na::one::<Scalar>() - t))
+    };
+
+    let mut planks: Vec<RigidBodyHandle> = Vec::with_capacity(num_planks);
+    let mut hinges = Vec::with_capacity(num_planks + 1);
+    // The anchor of the previous plank on its hinge with the next one, in its local coordinates.
+    let mut last_end: Option<Point> = None;
+
+    for i in range(0, num_planks) {
+        let (a, b) = (hinge_point(i), hinge_point(i + 1));
+        let x      = na::normalize(&(b - a));
+        let y      = na::cross(&axis, &x);
+        let rot    = unsafe {
+            Rot3::new_with_mat(Mat3::new(x.x, y.x, axis.x,
+                                         x.y, y.y, axis.y,
+                                         x.z, y.z, axis.z))
+        };
+
+        let mut rb = plank.clone();
+        rb.set_transformation(Iso3::new_with_rotmat(na::center(&a, &b).as_vec().clone(), rot));
+
+        let handle   = world.add_body(rb);
+        let half_len = na::norm(&(b - a)) * half * shrink;
+        let start    = Pnt3::new(-half_len, na::zero(), na::zero());
+        let end      = Pnt3::new(half_len, na::zero(), na::zero());
+
+        let hinge = match last_end {
+            Some(ref prev_end) => {
+                let prev = &planks[i - 1];
+
+                add_hinge(world, half_width, |d| Anchor::new(Some(prev.clone()), *prev_end + d),
+                          |d| Anchor::new(Some(handle.clone()), start + d))
+            },
+            None => {
+                add_hinge(world, half_width, |d| Anchor::new(None, a + axis * d.z),
+                          |d| Anchor::new(Some(handle.clone()), start + d))
+            }
+        };
+
+        hinges.push(hinge);
+        planks.push(handle);
+        last_end = Some(end);
+    }
+
+    let last = planks[num_planks - 1].clone();
+    let end  = last_end.unwrap();
+    let b    = hinge_point(num_planks);
+
+    hinges.push(add_hinge(world, half_width, |d| Anchor::new(Some(last.clone()), end + d),
+                          |d| Anchor::new(None, b + axis * d.z)));
+
+    Bridge {
+        planks: planks,
+        hinges: hinges
+    }
+}
+
+// Adds the two joints of a hinge, whose anchors are given by `anchor1` and `anchor2` from the
+// offset of each side of the planks in their local coordinates.
+fn add_hinge(world:      &mut World,
+             half_width: Scalar,
+             anchor1:    |Vect| -> Anchor<Point>,
+             anchor2:    |Vect| -> Anchor<Point>)
+             -> Hinge {
+    let left  = Vec3::new(na::zero(), na::zero(), half_width);
+    let right = -left;
+
+    (world.add_ball_in_socket(BallInSocket::new(anchor1(left), anchor2(left))),
+     world.add_ball_in_socket(BallInSocket::new(anchor1(right), anchor2(right))))
+}
+
+/// The planks and the hinges of a bridge added to the world by `build_bridge`.
+pub struct Bridge {
+    planks: Vec<RigidBodyHandle>,
+    hinges: Vec<Hinge>
+}
+
+impl Bridge {
+    /// The planks of this bridge, from the first anchor to the second.
+    #[inline]
+    pub fn planks(&self) -> &[RigidBodyHandle] {
+        self.planks.as_slice()
+    }
+
+    /// The hinges of this bridge, from the first anchor to the second.
+    ///
+    /// The hinge `i` links the plank `i - 1` to the plank `i`. The first and last hinges link the
+    /// planks to the anchors.
+    #[inline]
+    pub fn hinges(&self) -> &[Hinge] {
+        self.hinges.as_slice()
+    }
+
+    /// The index of the hinge at the middle of this bridge, or of the one just before its middle
+    /// plank if the number of planks is odd.
+    #[inline]
+    pub fn middle_hinge(&self) -> uint {
+        self.planks.len() / 2
+    }
+
+    /// Removes the two joints of the hinge `i` from `world`, splitting this bridge in two.
+    ///
+    /// Severing a hinge twice does nothing.
+    pub fn sever(&self, world: &mut World, i: uint) {
+        assert!(i < self.hinges.len(), "Hinge index out of bounds.");
+
+        let (ref left, ref right) = self.hinges[i];
+
+        world.remove_ball_in_socket(left);
+        world.remove_ball_in_socket(right);
+    }
+}
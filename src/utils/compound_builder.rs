@@ -0,0 +1,132 @@
+//! Construction of compound shapes made of several materials.
+
+use na;
+#[cfg(feature = "2d")]
+use na::Mat1;
+#[cfg(feature = "3d")]
+use na::Mat3;
+use ncollide::shape::{Shape, Compound, CompoundData};
+use ncollide::volumetric::{InertiaTensor, Volumetric};
+use math::{Scalar, Point, Vect, Matrix, AngularInertia};
+
+/// A `CompoundData` that keeps track of the mass properties of each of its parts.
+///
+/// The mass properties of a **ncollide** `Compound` are computed from a single density shared by
+/// all its parts. This builder instead combines the mass properties of each part, computed from
+/// its own density or mass. Use it for compounds made of several materials, e.g., a hammer with a
+/// light handle and a heavy head.
+pub struct CompoundBuilder {
+    data:    CompoundData<Scalar, Point, Vect, Matrix, AngularInertia>,
+    // The mass, center of mass and angular inertia of each part, in the compound local space.
+    parts:   Vec<(Scalar, Point, AngularInertia)>,
+    margins: Vec<Option<Scalar>>
+}
+
+impl CompoundBuilder {
+    /// Creates a builder for a compound without any part.
+    pub fn new() -> CompoundBuilder {
+        CompoundBuilder {
+            data:    CompoundData::new(),
+            parts:   Vec::new(),
+            margins: Vec::new()
+        }
+    }
+
+    /// Adds a part with the given density, located at `delta` in the compound local space.
+    pub fn push_shape<S>(&mut self, delta: Matrix, shape: S, density: Scalar)
+        where S: Send + Sync + Shape<Scalar, Point, Vect, Matrix> + Volumetric<Scalar, Point, AngularInertia> {
+        assert!(density > na::zero(), "The density of a compound part must be positive.");
+
+        let props = shape.mass_properties(density);
+
+        self.push(delta, shape, density, props)
+    }
+
+    /// Adds a part with the given mass, uniformly distributed on its volume.
+    pub fn push_shape_with_mass<S>(&mut self, delta: Matrix, shape: S, mass: Scalar)
+        where S: Send + Sync + Shape<Scalar, Point, Vect, Matrix> + Volumetric<Scalar, Point, AngularInertia> {
+        assert!(mass > na::zero(), "The mass of a compound part must be positive.");
+
+        let (unit_mass, _, _) = shape.mass_properties(na::one());
+
+        assert!(unit_mass > na::zero(), "A compound part with an explicit mass must have a volume.");
+
+        let density = mass / unit_mass;
+        let props   = shape.mass_properties(density);
+
+        self.push(delta, shape, density, props)
+    }
+
+    fn push<S>(&mut self, delta: Matrix, shape: S, density: Scalar, props: (Scalar, Point, AngularInertia))
+        where S: Send + Sync + Shape<Scalar, Point, Vect, Matrix> + Volumetric<Scalar, Point, AngularInertia> {
+        let (mass, com, inertia) = props;
+
+        self.parts.push((mass, na::transform(&delta, &com), inertia.to_world_space(&delta)));
+        self.margins.push(None);
+        self.data.push_shape(delta, shape, density)
+    }
+
+    /// Overrides the margin of the `i`-th part, or resets it to the body margin if `margin` is
+    /// `None`.
+    pub fn set_margin(&mut self, i: uint, margin: Option<Scalar>) {
+        assert!(i < self.margins.len(), "Part index out of bounds.");
+
+        self.margins[i] = margin
+    }
+
+    /// The margin overrides of each part.
+    ///
+    /// Those are not part of the compound shape: give them to `RigidBody::set_part_margins` once
+    /// the body is created.
+    pub fn margins(&self) -> &[Option<Scalar>] {
+        self.margins.as_slice()
+    }
+
+    /// The mass, center of mass and angular inertia (wrt. the center of mass) of the compound.
+    ///
+    /// Those are expressed in the compound local space and can be given to `RigidBody::new`.
+    pub fn mass_properties(&self) -> (Scalar, Point, AngularInertia) {
+        assert!(!self.parts.is_empty(), "Cannot compute the mass properties of an empty compound.");
+
+        let mut mass: Scalar = na::zero();
+        let mut com:  Vect   = na::zero();
+
+        for &(m, ref c, _) in self.parts.iter() {
+            mass = mass + m;
+            com  = com + *c.as_vec() * m;
+        }
+
+        let center: Point = na::orig::<Point>() + com / mass;
+        let mut inertia: AngularInertia = na::zero();
+
+        for &(m, ref c, ref i) in self.parts.iter() {
+            inertia = inertia + translate_inertia(i, m, &(*c - center));
+        }
+
+        (mass, center, inertia)
+    }
+
+    /// Builds the compound shape, returned together with its mass properties.
+    pub fn build(self) -> (Compound<Scalar, Point, Vect, Matrix, AngularInertia>, (Scalar, Point, AngularInertia)) {
+        let props = self.mass_properties();
+
+        (Compound::new(self.data), props)
+    }
+}
+
+// Parallel axis theorem: the inertia of a part of mass `m` around an axis displaced by `-d` from
+// its center of mass.
+#[cfg(feature = "3d")]
+fn translate_inertia(i: &AngularInertia, m: Scalar, d: &Vect) -> AngularInertia {
+    let (x, y, z) = (d.x, d.y, d.z);
+
+    *i + Mat3::new(
+        m * (y * y + z * z), -m * x * y,          -m * x * z,
+        -m * x * y,          m * (x * x + z * z), -m * y * z,
+        -m * x * z,          -m * y * z,          m * (x * x + y * y))
+}
+
+#[cfg(feature = "2d")]
+fn translate_inertia(i: &AngularInertia, m: Scalar, d: &Vect) -> AngularInertia {
+    *i + Mat1::new(m * na::sqnorm(d))
+}
@@ -0,0 +1,68 @@
+//! Approximate convex decomposition of concave meshes.
+
+use na::Vec3;
+use na;
+use ncollide::shape::{Compound3, CompoundData, Convex, Mesh3};
+use ncollide::procedural::{TriMesh, IndexBuffer};
+use ncollide::procedural;
+use ncollide::volumetric::Volumetric;
+use math::{Scalar, Point, AngularInertia};
+
+/// Decomposes a triangle mesh into a compound of convex pieces.
+///
+/// This uses the HACD algorithm from **ncollide**. The result may be used directly to create a
+/// dynamic rigid body with `RigidBody::new`.
+///
+/// # Arguments:
+/// * `vertices`       - the vertex buffer of the mesh.
+/// * `indices`        - the index buffer of the mesh. Each consecutive triplet of indices is a
+///                      triangle.
+/// * `error`          - the maximum concavity allowed for each convex piece. The smaller, the more
+///                      accurate (and the more numerous) the pieces are.
+/// * `min_components` - the minimum number of convex pieces to generate.
+/// * `density`        - the density used to compute the mass properties of each convex piece.
+///
+/// # Returns:
+/// The compound shape and its mass properties: its mass, center of mass and angular inertia.
+pub fn convex_decomposition(vertices:       &[Point],
+                            indices:        &[uint],
+                            error:          Scalar,
+                            min_components: uint,
+                            density:        Scalar)
+                            -> (Compound3<Scalar>, (Scalar, Point, AngularInertia)) {
+    assert!(indices.len() % 3 == 0, "The index buffer length must be a multiple of 3.");
+
+    let mut faces = Vec::with_capacity(indices.len() / 3);
+
+    for t in indices.chunks(3) {
+        faces.push(Vec3::new(t[0] as u32, t[1] as u32, t[2] as u32));
+    }
+
+    let mut trimesh = TriMesh::new(vertices.to_vec(), None, None, Some(IndexBuffer::Unified(faces)));
+    trimesh.split_index_buffer(true);
+
+    let (decomp, _) = procedural::hacd(trimesh, error, min_components);
+
+    let mut data = CompoundData::new();
+
+    for mesh in decomp.into_iter() {
+        data.push_shape(na::one(), Convex::new(mesh.coords), density.clone());
+    }
+
+    let compound = Compound3::new(data);
+    let props    = compound.mass_properties(density);
+
+    (compound, props)
+}
+
+/// Decomposes a `Mesh` shape into a compound of convex pieces.
+///
+/// A mesh can only be the shape of a static body. Use this to make a dynamic body out of it
+/// instead. See `convex_decomposition` for a description of the arguments and of the result.
+pub fn mesh_convex_decomposition(mesh:           &Mesh3<Scalar>,
+                                 error:          Scalar,
+                                 min_components: uint,
+                                 density:        Scalar)
+                                 -> (Compound3<Scalar>, (Scalar, Point, AngularInertia)) {
+    convex_decomposition(mesh.vertices().as_slice(), mesh.indices().as_slice(), error, min_components, density)
+}
@@ -0,0 +1,120 @@
+//! Trigonometric functions giving the same results on every platform.
+//!
+//! The functions of the standard library call the `libm` of the platform, whose results differ by
+//! a few ulps from one implementation to another. Those only use additions, multiplications and
+//! divisions, which are exactly rounded by IEEE 754, and are evaluated in a fixed order.
+
+use std::num::Float;
+use na;
+use math::Scalar;
+
+// The number of terms of the series of `atan` on [-tan(pi / 8), tan(pi / 8)].
+static ATAN_TERMS: uint = 16;
+
+/// Computes the sine and the cosine of `x`.
+///
+/// The approximation error is smaller than `1.0e-11` for angles up to a few turns, i.e.,
+/// negligible compared to the rounding errors of `f32`.
+pub fn sin_cos(x: Scalar) -> (Scalar, Scalar) {
+    let half_pi: Scalar = na::cast(::std::f64::consts::FRAC_PI_2);
+    let four: Scalar    = na::cast(4.0f64);
+
+    // Reduction to [-pi / 4, pi / 4].
+    let q = (x / half_pi).round();
+    let r = x - q * half_pi;
+    let k = ((q % four) + four) % four;
+
+    let (s, c) = (sin_series(r), cos_series(r));
+
+    if k == na::zero() {
+        (s, c)
+    }
+    else if k == na::one() {
+        (c, -s)
+    }
+    else if k == na::cast(2.0f64) {
+        (-s, -c)
+    }
+    else {
+        (-c, s)
+    }
+}
+
+/// Computes the angle of the point `(x, y)` wrt. the `x` axis, in `[-pi, pi]`.
+///
+/// This is the same as `y.atan2(x)`, up to an approximation error smaller than `1.0e-11`.
+pub fn atan2(y: Scalar, x: Scalar) -> Scalar {
+    let pi: Scalar = na::cast(::std::f64::consts::PI);
+
+    if x > na::zero() {
+        atan(y / x)
+    }
+    else if x < na::zero() {
+        if y >= na::zero() { atan(y / x) + pi } else { atan(y / x) - pi }
+    }
+    else if y > na::zero() {
+        pi / na::cast(2.0f64)
+    }
+    else if y < na::zero() {
+        -pi / na::cast(2.0f64)
+    }
+    else {
+        na::zero()
+    }
+}
+
+fn atan(x: Scalar) -> Scalar {
+    let quarter_pi: Scalar = na::cast(::std::f64::consts::FRAC_PI_4);
+    let tan_eighth: Scalar = na::cast(0.41421356237309503f64);
+
+    if x < na::zero() {
+        -atan(-x)
+    }
+    else if x > na::one() {
+        quarter_pi + quarter_pi - atan(na::one::<Scalar>() / x)
+    }
+    else if x > tan_eighth {
+        quarter_pi + atan_series((x - na::one()) / (x + na::one()))
+    }
+    else {
+        atan_series(x)
+    }
+}
+
+// Taylor series, accurate on [-pi / 4, pi / 4].
+fn sin_series(x: Scalar) -> Scalar {
+    let x2 = x * x;
+    let mut res: Scalar = na::zero();
+
+    for n in [ 39916800.0f64, 362880.0, 5040.0, 120.0, 6.0 ].iter() {
+        res = (res - na::cast(1.0 / *n)) * -x2;
+    }
+
+    // `res` is now x^2 / 6 - x^4 / 120 + ..., with alternating signs.
+    x - x * res
+}
+
+fn cos_series(x: Scalar) -> Scalar {
+    let x2 = x * x;
+    let mut res: Scalar = na::zero();
+
+    for n in [ 479001600.0f64, 3628800.0, 40320.0, 720.0, 24.0, 2.0 ].iter() {
+        res = (res - na::cast(1.0 / *n)) * -x2;
+    }
+
+    na::one::<Scalar>() - res
+}
+
+// Taylor series, accurate on [-tan(pi / 8), tan(pi / 8)].
+fn atan_series(x: Scalar) -> Scalar {
+    let x2 = x * x;
+    let mut res: Scalar = na::zero();
+
+    for i in range(0, ATAN_TERMS).rev() {
+        let coeff: Scalar = na::cast(1.0 / (2 * i + 1) as f64);
+
+        res = coeff - x2 * res;
+    }
+
+    x * res
+}
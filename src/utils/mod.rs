@@ -1,3 +1,29 @@
 //! Miscellaneous utilities.
 
+#[cfg(feature = "3d")]
+pub use utils::decomposition::{convex_decomposition, mesh_convex_decomposition};
+#[cfg(any(feature = "2d", feature = "3d"))]
+pub use utils::compound_builder::CompoundBuilder;
+#[cfg(any(feature = "2d", feature = "3d"))]
+pub use utils::scaling::scaled_shape;
+pub use utils::static_geometry_builder::StaticGeometryBuilder;
+pub use utils::ragdoll_builder::{RagdollBuilder, Ragdoll};
+#[cfg(feature = "3d")]
+pub use utils::bridge_builder::{build_bridge, Bridge, Hinge};
+#[cfg(feature = "3d")]
+pub use utils::pressure_ball::{build_pressure_ball, PressureBall};
+
 pub mod union_find;
+pub mod deterministic;
+#[cfg(feature = "3d")]
+mod decomposition;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod compound_builder;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod scaling;
+mod static_geometry_builder;
+mod ragdoll_builder;
+#[cfg(feature = "3d")]
+mod bridge_builder;
+#[cfg(feature = "3d")]
+mod pressure_ball;
@@ -0,0 +1,195 @@
+//! Construction of pressure balls, made of small balls linked by soft distance joints.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use na::{Vec3, Translation};
+use na;
+use ncollide::shape::Ball;
+use math::{Scalar, Point, Vect};
+use object::{RigidBody, RigidBodyHandle};
+use detection::joint::{Anchor, Distance};
+use integration::Pressure;
+use resolution::SpringDamper;
+use world::World;
+
+// The golden ratio, giving the vertices of an icosahedron.
+static GOLDEN_RATIO: f64 = 1.618033988749895;
+
+static ICOSAHEDRON_TRIANGLES: [(uint, uint, uint), ..20] = [
+    (0, 11, 5), (0, 5, 1), (0, 1, 7), (0, 7, 10), (0, 10, 11),
+    (1, 5, 9), (5, 11, 4), (11, 10, 2), (10, 7, 6), (7, 1, 8),
+    (3, 9, 4), (3, 4, 2), (3, 2, 6), (3, 6, 8), (3, 8, 9),
+    (4, 9, 5), (2, 4, 11), (6, 2, 10), (8, 6, 7), (9, 8, 1)
+];
+
+/// Builds a pressure ball, and adds it to `world`.
+///
+/// The surface of the ball is an icosahedron which triangles are split in four `subdivisions`
+/// times, with a small ball at each vertex. The vertices adjacent on the surface are linked by
+/// distance joints with a spring of stiffness `stiffness`, and a `Pressure` force generator pushes
+/// them outward by `pressure` times the relative volume the ball lost. Zero subdivisions give 12
+/// vertices, and each subdivision multiplies their number by about four.
+///
+/// The vertices are just big enough to touch their neighbours, with a density of one. They do not
+/// collide with the vertices they are linked to.
+pub fn build_pressure_ball(world:        &mut World,
+                           center:       Point,
+                           radius:       Scalar,
+                           subdivisions: uint,
+                           stiffness:    Scalar,
+                           pressure:     Scalar)
+                           -> PressureBall {
+    assert!(radius > na::zero(), "The radius of a pressure ball must be positive.");
+
+    let (directions, triangles) = icosphere(subdivisions);
+
+    // The edges of the surface, each with the indices of its vertices.
+    let mut seen  = HashSet::new();
+    let mut edges = Vec::new();
+
+    for &(a, b, c) in triangles.iter() {
+        for &(i, j) in [(a, b), (b, c), (c, a)].iter() {
+            let edge = if i < j { (i, j) } else { (j, i) };
+
+            if seen.insert(edge) {
+                edges.push(edge)
+            }
+        }
+    }
+
+    let min_length = edges.iter()
+                          .map(|&(i, j)| na::norm(&(directions[i] - directions[j])) * radius)
+                          .fold(radius, |a, b| if b < a { b } else { a });
+    let half: Scalar = na::cast(0.5f64);
+
+    let vertices: Vec<RigidBodyHandle> = directions.iter().map(|d| {
+        let mut rb = RigidBody::new_dynamic(Ball::new(min_length * half), na::one(), na::cast(0.3f64), na::cast(0.5f64));
+
+        rb.append_translation(&(*center.as_vec() + *d * radius));
+
+        world.add_body(rb)
+    }).collect();
+
+    let joints = edges.iter().map(|&(i, j)| {
+        let length    = na::norm(&(directions[i] - directions[j])) * radius;
+        let mut joint = Distance::new(Anchor::new(Some(vertices[i].clone()), na::orig()),
+                                      Anchor::new(Some(vertices[j].clone()), na::orig()),
+                                      length);
+
+        joint.set_spring(Some(SpringDamper::new(stiffness, na::zero())));
+
+        world.add_distance(joint)
+    }).collect();
+
+    let generator = world.add_force_generator(Pressure::new(vertices.as_slice(), triangles, pressure));
+
+    PressureBall {
+        vertices:  vertices,
+        joints:    joints,
+        generator: generator
+    }
+}
+
+// The unit directions of the vertices of an icosahedron subdivided `subdivisions` times, and its
+// triangles, oriented outward.
+fn icosphere(subdivisions: uint) -> (Vec<Vect>, Vec<(uint, uint, uint)>) {
+    let (o, z): (Scalar, Scalar) = (na::one(), na::zero());
+    let t: Scalar = na::cast(GOLDEN_RATIO);
+
+    let mut directions: Vec<Vect> = [
+        Vec3::new(-o, t, z), Vec3::new(o, t, z), Vec3::new(-o, -t, z), Vec3::new(o, -t, z),
+        Vec3::new(z, -o, t), Vec3::new(z, o, t), Vec3::new(z, -o, -t), Vec3::new(z, o, -t),
+        Vec3::new(t, z, -o), Vec3::new(t, z, o), Vec3::new(-t, z, -o), Vec3::new(-t, z, o)
+    ].iter().map(|d| na::normalize(d)).collect();
+
+    let mut triangles = ICOSAHEDRON_TRIANGLES.as_slice().to_vec();
+
+    for _ in range(0, subdivisions) {
+        let mut middles: HashMap<(uint, uint), uint> = HashMap::new();
+        let mut split = Vec::with_capacity(triangles.len() * 4);
+
+        // The index of the vertex at the middle of the edge between `i` and `j`, pushed onto the
+        // sphere.
+        let middle = |i: uint, j: uint, directions: &mut Vec<Vect>| -> uint {
+            let edge = if i < j { (i, j) } else { (j, i) };
+
+            match middles.find(&edge) {
+                Some(m) => return *m,
+                None    => { }
+            }
+
+            let m = na::normalize(&(directions[i] + directions[j]));
+            directions.push(m);
+            let _ = middles.insert(edge, directions.len() - 1);
+
+            directions.len() - 1
+        };
+
+        for &(a, b, c) in triangles.iter() {
+            let ab = middle(a, b, &mut directions);
+            let bc = middle(b, c, &mut directions);
+            let ca = middle(c, a, &mut directions);
+
+            split.push((a, ab, ca));
+            split.push((b, bc, ab));
+            split.push((c, ca, bc));
+            split.push((ab, bc, ca));
+        }
+
+        triangles = split;
+    }
+
+    // The triangles of the icosahedron are not all listed in the same order.
+    for tri in triangles.iter_mut() {
+        let (a, b, c) = *tri;
+        let normal    = na::cross(&(directions[b] - directions[a]), &(directions[c] - directions[a]));
+
+        if na::dot(&normal, &directions[a]) < na::zero() {
+            *tri = (a, c, b)
+        }
+    }
+
+    (directions, triangles)
+}
+
+/// The vertices, the joints, and the pressure of a ball added to the world by
+/// `build_pressure_ball`.
+pub struct PressureBall {
+    vertices:  Vec<RigidBodyHandle>,
+    joints:    Vec<Rc<RefCell<Distance>>>,
+    generator: Rc<RefCell<Pressure>>
+}
+
+impl PressureBall {
+    /// The small balls at the vertices of the surface of this pressure ball.
+    #[inline]
+    pub fn vertices(&self) -> &[RigidBodyHandle] {
+        self.vertices.as_slice()
+    }
+
+    /// The joints along the edges of the surface of this pressure ball.
+    #[inline]
+    pub fn joints(&self) -> &[Rc<RefCell<Distance>>] {
+        self.joints.as_slice()
+    }
+
+    /// The force generator inflating this pressure ball, e.g., to change its pressure.
+    #[inline]
+    pub fn generator(&self) -> &Rc<RefCell<Pressure>> {
+        &self.generator
+    }
+
+    /// The position of the center of this pressure ball, i.e., the centroid of its vertices.
+    pub fn center(&self) -> Point {
+        let mut sum: Vect = na::zero();
+
+        for v in self.vertices.iter() {
+            sum = sum + *v.borrow().center_of_mass().as_vec();
+        }
+
+        let n: Scalar = na::cast(self.vertices.len() as f64);
+
+        na::orig::<Point>() + sum / n
+    }
+}
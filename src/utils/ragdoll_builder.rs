@@ -0,0 +1,139 @@
+//! Construction of articulated bodies, e.g., ragdolls, with a per-pair self-collision.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use math::Point;
+use object::{RigidBody, RigidBodyHandle};
+use detection::joint::{Anchor, BallInSocket};
+use world::World;
+
+/// A set of bones linked by ball-in-socket joints, added to the world all at once.
+///
+/// The shapes of adjacent bones usually overlap around their joint, so the collisions between the
+/// pairs of bones given to `set_disabled_pairs` are disabled, as are those between bones linked by
+/// a joint by default. The other pairs of bones collide, e.g., a hand with the torso.
+pub struct RagdollBuilder {
+    bones:    Vec<RigidBody>,
+    // The parent of each bone, with the anchors of their joint on the parent and on the bone.
+    parents:  Vec<Option<(uint, Point, Point)>>,
+    disabled: Option<Vec<(uint, uint)>>
+}
+
+impl RagdollBuilder {
+    /// Creates a builder without any bone.
+    pub fn new() -> RagdollBuilder {
+        RagdollBuilder {
+            bones:    Vec::new(),
+            parents:  Vec::new(),
+            disabled: None
+        }
+    }
+
+    /// Adds a bone attached to no other, e.g., the torso, and returns its index.
+    pub fn push_bone(&mut self, bone: RigidBody) -> uint {
+        self.bones.push(bone);
+        self.parents.push(None);
+
+        self.bones.len() - 1
+    }
+
+    /// Adds a bone attached to the bone `parent`, and returns its index.
+    ///
+    /// The two bones are linked by a ball-in-socket joint between `parent_anchor`, in the local
+    /// coordinates of the parent, and `anchor`, in the local coordinates of the new bone.
+    pub fn push_child_bone(&mut self, bone: RigidBody, parent: uint, parent_anchor: Point, anchor: Point) -> uint {
+        assert!(parent < self.bones.len(), "Parent bone index out of bounds.");
+
+        self.bones.push(bone);
+        self.parents.push(Some((parent, parent_anchor, anchor)));
+
+        self.bones.len() - 1
+    }
+
+    /// Sets the pairs of bones which do not collide with each other.
+    ///
+    /// This replaces the default pairs, made of each bone and its parent.
+    pub fn set_disabled_pairs(&mut self, pairs: Vec<(uint, uint)>) {
+        assert!(pairs.iter().all(|&(a, b)| a < self.bones.len() && b < self.bones.len()),
+                "Bone index out of bounds.");
+
+        self.disabled = Some(pairs)
+    }
+
+    /// The pairs of bones which do not collide with each other.
+    pub fn disabled_pairs(&self) -> Vec<(uint, uint)> {
+        match self.disabled {
+            Some(ref pairs) => pairs.clone(),
+            None            => {
+                self.parents.iter().enumerate()
+                    .filter_map(|(i, p)| p.as_ref().map(|&(parent, _, _)| (parent, i)))
+                    .collect()
+            }
+        }
+    }
+
+    /// Adds the bones and their joints to `world`.
+    pub fn build(self, world: &mut World) -> Ragdoll {
+        let disabled = self.disabled_pairs();
+        let mut bones: Vec<RigidBodyHandle> = Vec::with_capacity(self.bones.len());
+        let mut joints = Vec::new();
+
+        for (bone, parent) in self.bones.into_iter().zip(self.parents.into_iter()) {
+            let handle = world.add_body(bone);
+
+            match parent {
+                Some((parent, parent_anchor, anchor)) => {
+                    let mut joint = BallInSocket::new(Anchor::new(Some(bones[parent].clone()), parent_anchor),
+                                                      Anchor::new(Some(handle.clone()), anchor));
+
+                    // The collisions are filtered by pairs instead.
+                    joint.set_collision_enabled(true);
+
+                    joints.push(world.add_ball_in_socket(joint));
+                },
+                None => { }
+            }
+
+            bones.push(handle);
+        }
+
+        for &(a, b) in disabled.iter() {
+            world.set_collision_between(&bones[a], &bones[b], false);
+        }
+
+        Ragdoll {
+            bones:  bones,
+            joints: joints
+        }
+    }
+}
+
+/// The bones and the joints of a ragdoll added to the world by a `RagdollBuilder`.
+pub struct Ragdoll {
+    bones:  Vec<RigidBodyHandle>,
+    joints: Vec<Rc<RefCell<BallInSocket>>>
+}
+
+impl Ragdoll {
+    /// The bones of this ragdoll, in the order they were pushed to the builder.
+    #[inline]
+    pub fn bones(&self) -> &[RigidBodyHandle] {
+        self.bones.as_slice()
+    }
+
+    /// The joints linking the bones of this ragdoll to their parent.
+    #[inline]
+    pub fn joints(&self) -> &[Rc<RefCell<BallInSocket>>] {
+        self.joints.as_slice()
+    }
+
+    /// Whether the bones `a` and `b` collide with each other.
+    pub fn self_collision(&self, world: &World, a: uint, b: uint) -> bool {
+        world.collision_between(&self.bones[a], &self.bones[b])
+    }
+
+    /// Enables or disables the collisions between the bones `a` and `b`.
+    pub fn set_self_collision(&self, world: &mut World, a: uint, b: uint, enabled: bool) {
+        world.set_collision_between(&self.bones[a], &self.bones[b], enabled)
+    }
+}
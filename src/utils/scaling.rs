@@ -0,0 +1,114 @@
+//! Uniform scaling of shapes.
+
+use std::sync::Arc;
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use na::Translation;
+use na;
+use ncollide::shape::{Shape, Ball, Cuboid, Cone, Cylinder, Convex, Plane, Mesh, Compound, CompoundData};
+use ncollide::volumetric::Volumetric;
+use math::{Scalar, Point, Vect, Matrix, AngularInertia};
+
+type BodyShape    = Arc<Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>>;
+type BodyCompound = Compound<Scalar, Point, Vect, Matrix, AngularInertia>;
+type BodyMesh     = Mesh<Scalar, Point, Vect, Matrix>;
+
+/// A copy of `shape` scaled uniformly by the factor `s`, with respect to its local origin.
+///
+/// Balls, cuboids, cones, cylinders, convex polyhedra, meshes, and compounds of those are
+/// supported. The parts of a compound are scaled recursively, and so are their positions. Planes
+/// are returned unchanged. An error is returned for any other shape, e.g., a user-defined one, or
+/// if `s` is not strictly positive.
+pub fn scaled_shape(shape: &Shape<Scalar, Point, Vect, Matrix>, s: Scalar) -> Result<BodyShape, String> {
+    if !(s > na::zero()) {
+        return Err("the scaling factor must be strictly positive.".to_string())
+    }
+
+    let type_id = shape.get_type_id();
+
+    if type_id == TypeId::of::<Plane<Vect>>() {
+        let plane = shape.downcast_ref::<Plane<Vect>>().unwrap();
+
+        return Ok(Arc::new(box Plane::new(plane.normal().clone()) as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>))
+    }
+
+    if type_id == TypeId::of::<BodyMesh>() {
+        let mesh     = shape.downcast_ref::<BodyMesh>().unwrap();
+        let vertices = mesh.vertices().iter().map(|v| *v * s).collect();
+        let new_mesh = Mesh::new(Arc::new(vertices),
+                                 mesh.indices().clone(),
+                                 mesh.uvs().clone(),
+                                 mesh.normals().clone());
+
+        return Ok(Arc::new(box new_mesh as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>))
+    }
+
+    // The other shapes can only be shared once boxed as part of a compound, so the scaled shape is
+    // taken back from a compound made of it only.
+    let mut data = CompoundData::new();
+
+    try!(push_scaled(&mut data, na::one(), shape, s));
+
+    let compound = Compound::new(data);
+
+    Ok(compound.shapes()[0].ref1().clone())
+}
+
+fn push_scaled(data:  &mut CompoundData<Scalar, Point, Vect, Matrix, AngularInertia>,
+               delta: Matrix,
+               shape: &Shape<Scalar, Point, Vect, Matrix>,
+               s:     Scalar)
+               -> Result<(), String> {
+    let type_id = shape.get_type_id();
+
+    if type_id == TypeId::of::<Ball<Scalar>>() {
+        let ball = shape.downcast_ref::<Ball<Scalar>>().unwrap();
+
+        push(data, delta, Ball::new(ball.radius() * s))
+    }
+    else if type_id == TypeId::of::<Cuboid<Vect>>() {
+        let cuboid = shape.downcast_ref::<Cuboid<Vect>>().unwrap();
+
+        push(data, delta, Cuboid::new(*cuboid.half_extents() * s))
+    }
+    else if type_id == TypeId::of::<Cone<Scalar>>() {
+        let cone = shape.downcast_ref::<Cone<Scalar>>().unwrap();
+
+        push(data, delta, Cone::new(cone.half_height() * s, cone.radius() * s))
+    }
+    else if type_id == TypeId::of::<Cylinder<Scalar>>() {
+        let cylinder = shape.downcast_ref::<Cylinder<Scalar>>().unwrap();
+
+        push(data, delta, Cylinder::new(cylinder.half_height() * s, cylinder.radius() * s))
+    }
+    else if type_id == TypeId::of::<Convex<Point>>() {
+        let convex = shape.downcast_ref::<Convex<Point>>().unwrap();
+
+        push(data, delta, Convex::new(convex.points().iter().map(|p| *p * s).collect()))
+    }
+    else if type_id == TypeId::of::<BodyCompound>() {
+        let compound  = shape.downcast_ref::<BodyCompound>().unwrap();
+        let mut parts = CompoundData::new();
+
+        for &(ref m, ref part) in compound.shapes().iter() {
+            let mut part_delta = m.clone();
+
+            part_delta.set_translation(m.translation() * s);
+
+            try!(push_scaled(&mut parts, part_delta, &***part, s));
+        }
+
+        push(data, delta, Compound::new(parts))
+    }
+    else {
+        Err("a shape of this type cannot be scaled.".to_string())
+    }
+}
+
+fn push<S>(data: &mut CompoundData<Scalar, Point, Vect, Matrix, AngularInertia>, delta: Matrix, shape: S) -> Result<(), String>
+    where S: Send + Sync + Shape<Scalar, Point, Vect, Matrix> + Volumetric<Scalar, Point, AngularInertia> {
+    // The density does not matter: the mass properties of the body are scaled separately.
+    data.push_shape(delta, shape, na::one());
+
+    Ok(())
+}
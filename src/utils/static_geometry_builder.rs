@@ -0,0 +1,59 @@
+//! Merging of many static shapes into a single static body.
+
+use na;
+use ncollide::shape::{Shape, Compound, CompoundData};
+use ncollide::volumetric::Volumetric;
+use object::RigidBody;
+use math::{Scalar, Point, Vect, Matrix, AngularInertia};
+
+/// Builds a single static body from many static shapes.
+///
+/// Each body added to the world has a cost for the broad phase and the pair management, even if it
+/// never moves. Scenes made of thousands of small static pieces (e.g. a level made of voxels)
+/// should instead merge them into one body. Its shape is a compound which organizes its parts in
+/// a bounding volume tree, so only the parts close to the other bodies are tested for contacts.
+///
+/// All the parts share the same restitution, friction and margin. Planes cannot be merged since
+/// they are infinite, and many triangles are better merged into a single `Mesh`.
+pub struct StaticGeometryBuilder {
+    data: CompoundData<Scalar, Point, Vect, Matrix, AngularInertia>,
+    len:  uint
+}
+
+impl StaticGeometryBuilder {
+    /// Creates a builder without any shape.
+    pub fn new() -> StaticGeometryBuilder {
+        StaticGeometryBuilder {
+            data: CompoundData::new(),
+            len:  0
+        }
+    }
+
+    /// Adds a shape located at `delta`.
+    pub fn push_shape<S>(&mut self, delta: Matrix, shape: S)
+        where S: Send + Sync + Shape<Scalar, Point, Vect, Matrix> + Volumetric<Scalar, Point, AngularInertia> {
+        // The density does not matter since the body is static.
+        self.data.push_shape(delta, shape, na::one());
+        self.len = self.len + 1;
+    }
+
+    /// The number of shapes added so far.
+    pub fn len(&self) -> uint {
+        self.len
+    }
+
+    /// Whether no shape was added yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Creates the static body made of all the shapes added.
+    ///
+    /// The shapes are located with respect to the origin of the body, which may be moved
+    /// afterward.
+    pub fn build(self, restitution: Scalar, friction: Scalar) -> RigidBody {
+        assert!(!self.is_empty(), "Cannot build a static body without any shape.");
+
+        RigidBody::new_static(Compound::new(self.data), restitution, friction)
+    }
+}
@@ -0,0 +1,78 @@
+//! Contacts solved by the world.
+
+use std::slice::Items;
+use na;
+use ncollide::geometry::Contact;
+use object::RigidBodyHandle;
+use math::{Scalar, Point, Vect};
+
+/// A contact between two bodies solved during the last step, as returned by `World::contacts`.
+#[deriving(Clone)]
+pub struct ContactInfo {
+    /// The first body in contact.
+    pub body1:            RigidBodyHandle,
+    /// The second body in contact.
+    pub body2:            RigidBodyHandle,
+    /// The contact geometry. Its normal is oriented toward the second body, and its depth
+    /// includes the margins of both bodies.
+    pub contact:          Contact<Scalar, Point, Vect>,
+    /// The normal impulse applied by the solver to keep the bodies apart.
+    pub normal_impulse:   Scalar,
+    /// The friction impulse applied by the solver to the second body, the opposite being applied
+    /// to the first one.
+    ///
+    /// This is orthogonal to the contact normal, and accumulated over all the solver iterations,
+    /// including the impulse used for warm-starting. It is zero, as the normal impulse, if the
+    /// contact was not solved, e.g., because another contact between the same bodies was found at
+    /// nearly the same place.
+    pub friction_impulse: Vect
+}
+
+impl ContactInfo {
+    /// The friction directions used by the solver for this contact.
+    ///
+    /// They form an orthonormal basis of the subspace orthogonal to the contact normal.
+    pub fn tangents(&self) -> Vec<Vect> {
+        let mut res = Vec::new();
+
+        na::orthonormal_subspace_basis(&self.contact.normal, |t| {
+            res.push(t);
+            true
+        });
+
+        res
+    }
+
+    /// The friction impulse applied along each direction returned by `tangents`.
+    pub fn friction_impulses(&self) -> Vec<Scalar> {
+        self.tangents().iter().map(|t| na::dot(&self.friction_impulse, t)).collect()
+    }
+}
+
+/// An iterator visiting the contacts of a single body, as returned by `World::contacts_with`.
+pub struct ContactsWith<'a> {
+    infos: &'a [ContactInfo],
+    ids:   Items<'a, uint>
+}
+
+impl<'a> ContactsWith<'a> {
+    #[doc(hidden)]
+    pub fn new(infos: &'a [ContactInfo], ids: &'a [uint]) -> ContactsWith<'a> {
+        ContactsWith {
+            infos: infos,
+            ids:   ids.iter()
+        }
+    }
+}
+
+impl<'a> Iterator<&'a ContactInfo> for ContactsWith<'a> {
+    #[inline]
+    fn next(&mut self) -> Option<&'a ContactInfo> {
+        self.ids.next().map(|i| &self.infos[*i])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (uint, Option<uint>) {
+        self.ids.size_hint()
+    }
+}
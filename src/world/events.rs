@@ -0,0 +1,92 @@
+use object::{RigidBodyHandle, ModifierVolumeHandle};
+use math::Scalar;
+use resolution::Impact;
+
+/// An event emitted by the physics world.
+///
+/// Events are accumulated by the world until they are retrieved with `World::drain_events`, or
+/// with `World::drain_timed_events` to know when they occurred.
+/// Within one step, activation events are emitted before sensor events, and sensor events before
+/// impact events. Each overlap with a sensor is reported exactly once by a `SensorEntered` event
+/// and, when it ends, exactly once by a `SensorLeft` event.
+///
+/// When several steps are performed before the events are drained, e.g., to split a frame into
+/// smaller steps, the events of each step follow those of the previous one. Thus, a body woken up
+/// and put back to sleep within a frame reports both events, in this order. The overlaps with the
+/// sensors are tested at the end of each step, so a body crossing a thin sensor during a single
+/// step does not emit any sensor event, unless continuous collision detection is enabled for it,
+/// see `World::add_ccd_to`. Its motion is then tested against the sensors, and each sensor it
+/// traversed is reported by a `SensorEntered` event immediately followed by a `SensorLeft` event.
+pub enum WorldEvent {
+    /// A body has been added to the world.
+    BodyAdded(RigidBodyHandle),
+    /// A body has been removed from the world.
+    BodyRemoved(RigidBodyHandle),
+    /// A sleeping body has been woken up.
+    BodyActivated(RigidBodyHandle),
+    /// A body has been put to sleep.
+    BodyDeactivated(RigidBodyHandle),
+    /// The shape of a body has been scaled by the given factor with `World::scale_body`.
+    BodyScaled(RigidBodyHandle, Scalar),
+    /// The shape of a body has been replaced with `World::update_mesh_vertices` or
+    /// `World::update_bezier_control_points`.
+    BodyShapeUpdated(RigidBodyHandle),
+    /// A body has been teleported with `World::teleport`, or to the other side of the wrap bounds.
+    BodyTeleported(RigidBodyHandle),
+    /// The speed of a body at rest exceeded the start speed of `World::set_motion_thresholds`.
+    BodyStartedMoving(RigidBodyHandle),
+    /// The speed of a moving body went under the stop speed of `World::set_motion_thresholds`, or
+    /// it fell asleep.
+    BodyStoppedMoving(RigidBodyHandle),
+    /// The angular speed of a body driven by a torque went under its `RigidBody::stall_speed`.
+    BodyStalled(RigidBodyHandle),
+    /// A stalled body spins faster than its stall speed again, or is no longer driven by a torque.
+    BodyUnstalled(RigidBodyHandle),
+    /// Two bodies collided with an impulse larger than the world impact event threshold.
+    Impact(Impact),
+    /// A dynamic body has entered a modifier volume.
+    VolumeEntered(ModifierVolumeHandle, RigidBodyHandle),
+    /// A dynamic body has left a modifier volume, or the volume has been removed.
+    VolumeLeft(ModifierVolumeHandle, RigidBodyHandle),
+    /// A body has started overlapping a sensor, given first.
+    SensorEntered(RigidBodyHandle, RigidBodyHandle),
+    /// A body has stopped overlapping a sensor, given first, or one of them has been removed.
+    SensorLeft(RigidBodyHandle, RigidBodyHandle)
+}
+
+/// An event, together with the simulation time at which it was emitted.
+///
+/// Events emitted during a step carry the step count and the time at the end of this step. Events
+/// emitted between two steps, e.g., when a body is added, carry those of the last step.
+#[deriving(Clone)]
+pub struct TimedEvent {
+    /// The value of `World::step_count` when the event was emitted.
+    pub step:  u64,
+    /// The value of `World::time` when the event was emitted.
+    pub time:  Scalar,
+    /// The event.
+    pub event: WorldEvent
+}
+
+impl Clone for WorldEvent {
+    fn clone(&self) -> WorldEvent {
+        match *self {
+            WorldEvent::BodyAdded(ref b)       => WorldEvent::BodyAdded(b.clone()),
+            WorldEvent::BodyRemoved(ref b)     => WorldEvent::BodyRemoved(b.clone()),
+            WorldEvent::BodyActivated(ref b)   => WorldEvent::BodyActivated(b.clone()),
+            WorldEvent::BodyDeactivated(ref b) => WorldEvent::BodyDeactivated(b.clone()),
+            WorldEvent::BodyScaled(ref b, s)   => WorldEvent::BodyScaled(b.clone(), s),
+            WorldEvent::BodyShapeUpdated(ref b) => WorldEvent::BodyShapeUpdated(b.clone()),
+            WorldEvent::BodyTeleported(ref b)   => WorldEvent::BodyTeleported(b.clone()),
+            WorldEvent::BodyStartedMoving(ref b) => WorldEvent::BodyStartedMoving(b.clone()),
+            WorldEvent::BodyStoppedMoving(ref b) => WorldEvent::BodyStoppedMoving(b.clone()),
+            WorldEvent::BodyStalled(ref b)       => WorldEvent::BodyStalled(b.clone()),
+            WorldEvent::BodyUnstalled(ref b)     => WorldEvent::BodyUnstalled(b.clone()),
+            WorldEvent::Impact(ref i)          => WorldEvent::Impact(i.clone()),
+            WorldEvent::VolumeEntered(ref v, ref b) => WorldEvent::VolumeEntered(v.clone(), b.clone()),
+            WorldEvent::VolumeLeft(ref v, ref b)    => WorldEvent::VolumeLeft(v.clone(), b.clone()),
+            WorldEvent::SensorEntered(ref s, ref b) => WorldEvent::SensorEntered(s.clone(), b.clone()),
+            WorldEvent::SensorLeft(ref s, ref b)    => WorldEvent::SensorLeft(s.clone(), b.clone())
+        }
+    }
+}
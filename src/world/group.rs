@@ -0,0 +1,26 @@
+//! Named groups of rigid bodies.
+
+/// A handle to a group of bodies created by `World::create_group`.
+///
+/// Groups let operations be applied to many bodies at once. A body can be part of several groups,
+/// and stays in them while it sleeps.
+#[deriving(Clone, PartialEq, Eq, Hash, Show)]
+pub struct GroupHandle {
+    id: uint
+}
+
+impl GroupHandle {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new(id: uint) -> GroupHandle {
+        GroupHandle {
+            id: id
+        }
+    }
+
+    /// The index of this group in the world that created it.
+    #[inline]
+    pub fn id(&self) -> uint {
+        self.id
+    }
+}
@@ -1,5 +1,18 @@
 //! The physics world.
 
-pub use world::world::{World, WorldBroadPhase, RigidBodyCollisionWorld, RigidBodies};
+pub use world::world::{World, WorldBroadPhase, RigidBodyCollisionWorld, RigidBodies, Joints};
+pub use world::events::{WorldEvent, TimedEvent};
+pub use world::group::GroupHandle;
+pub use world::ray_hit::RayHit;
+pub use world::contact_info::{ContactInfo, ContactsWith};
+pub use world::step_statistics::StepStatistics;
+pub use world::world_commands::{WorldCommands, WorldCommand};
 
 mod world;
+mod events;
+mod group;
+mod ray_hit;
+mod contact_info;
+mod step_statistics;
+mod time_of_impact;
+mod world_commands;
@@ -0,0 +1,17 @@
+//! Hits of rays cast on the world.
+
+use object::{RigidBodyHandle, SubShapeId};
+use math::{Scalar, Vect};
+
+/// A surface of a body hit by a ray, as returned by `World::ray_hits`.
+#[deriving(Clone)]
+pub struct RayHit {
+    /// The body hit.
+    pub body:      RigidBodyHandle,
+    /// The time of impact of the ray with the surface, in units of the ray direction.
+    pub toi:       Scalar,
+    /// The normal of the surface at the hit point.
+    pub normal:    Vect,
+    /// The part of the body's shape hit.
+    pub sub_shape: SubShapeId
+}
@@ -0,0 +1,76 @@
+use na;
+use math::Scalar;
+
+/// The time spent in each phase of the last step of a world, in seconds.
+///
+/// The durations are only measured when the `step_stats` feature is enabled. They are all zero
+/// otherwise.
+#[deriving(Clone, Show)]
+pub struct StepStatistics {
+    /// The time spent applying the forces and integrating the bodies motion.
+    pub integration:              f64,
+    /// The time spent in the broad phase, the continuous collision detection, and the narrow
+    /// phase.
+    pub collision_detection:      f64,
+    /// The time spent updating the joints and the activation states of the bodies.
+    pub activation:               f64,
+    /// The time spent collecting and filtering the contacts given to the solver.
+    pub contacts:                 f64,
+    /// The time spent in the constraints solver.
+    pub resolution:               f64,
+    /// The total duration of the step.
+    pub total:                    f64,
+    /// The number of contacts given to the solver.
+    pub num_contacts:             uint,
+    /// The number of pairs which contacts were moved with the bodies instead of being generated
+    /// again, see `World::set_contact_caching`. The pairs handled by the default narrow phase are
+    /// never counted.
+    pub num_cached_pairs:         uint,
+    /// The number of bodies moved back by the continuous collision detection.
+    pub num_ccd_clamped:          uint,
+    /// The number of bodies which angular velocity was clamped.
+    pub num_velocity_clamped:     uint,
+    /// The number of bodies with a contact which penetration correction was capped.
+    ///
+    /// Those three counts aggregate the `RigidBody::last_step_flags` of the bodies.
+    pub num_depenetration_capped: uint,
+    /// The largest approach velocity left on a contact by the solver.
+    ///
+    /// This and `joint_residual` are only computed when the solver residuals are enabled with
+    /// `World::set_solver_residuals_enabled`. They are zero otherwise.
+    pub contact_residual:         Scalar,
+    /// The largest velocity error left on a joint by the solver.
+    pub joint_residual:           Scalar
+}
+
+impl StepStatistics {
+    /// Statistics of a step that took no time.
+    pub fn new() -> StepStatistics {
+        StepStatistics {
+            integration:              0.0,
+            collision_detection:      0.0,
+            activation:               0.0,
+            contacts:                 0.0,
+            resolution:               0.0,
+            total:                    0.0,
+            num_contacts:             0,
+            num_cached_pairs:         0,
+            num_ccd_clamped:          0,
+            num_velocity_clamped:     0,
+            num_depenetration_capped: 0,
+            contact_residual:         na::zero(),
+            joint_residual:           na::zero()
+        }
+    }
+}
+
+// The current time in seconds, used to measure the durations.
+#[cfg(feature = "step_stats")]
+pub fn now() -> f64 {
+    ::time::precise_time_s()
+}
+
+#[cfg(not(feature = "step_stats"))]
+pub fn now() -> f64 {
+    0.0
+}
@@ -0,0 +1,155 @@
+use std::num::Float;
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use na;
+use ncollide::shape::{Shape, Compound, Mesh};
+use ncollide::geometry;
+use integration::euler;
+use object::RigidBody;
+use math::{Scalar, Point, Vect, Matrix, AngularInertia};
+
+type BodyCompound = Compound<Scalar, Point, Vect, Matrix, AngularInertia>;
+type BodyMesh     = Mesh<Scalar, Point, Vect, Matrix>;
+
+// The distance between the dilated shapes below which they are considered touching.
+static TOI_TOLERANCE: f64 = 1.0e-4;
+// The maximum number of advancements done for each pair of parts.
+static MAX_ITERATIONS: uint = 100;
+
+// The earliest time of impact smaller than `max_t` between two bodies moving with their current
+// velocities.
+//
+// This uses conservative advancement: the time is advanced by the distance between the shapes
+// divided by an upper bound of the speed at which any of their points may approach the other
+// shape. The parts of compound shapes are handled pairwise, each with its own margin.
+pub fn time_of_impact(rb1: &RigidBody, rb2: &RigidBody, max_t: Scalar) -> Option<Scalar> {
+    let parts1 = parts(rb1);
+    let parts2 = parts(rb2);
+    let speed  = na::norm(&(rb2.lin_vel() - rb1.lin_vel())) +
+                 na::norm(&rb1.ang_vel()) * max_radius(rb1, parts1.as_slice()) +
+                 na::norm(&rb2.ang_vel()) * max_radius(rb2, parts2.as_slice());
+
+    let mut res = None;
+
+    for &(ref delta1, s1, m1) in parts1.iter() {
+        for &(ref delta2, s2, m2) in parts2.iter() {
+            let limit = res.unwrap_or(max_t);
+
+            match advance(rb1, delta1, s1, rb2, delta2, s2, m1 + m2, speed, limit) {
+                Some(t) => res = Some(t),
+                None    => { }
+            }
+        }
+    }
+
+    res
+}
+
+fn advance(rb1:    &RigidBody,
+           delta1: &Matrix,
+           s1:     &Shape<Scalar, Point, Vect, Matrix>,
+           rb2:    &RigidBody,
+           delta2: &Matrix,
+           s2:     &Shape<Scalar, Point, Vect, Matrix>,
+           margin: Scalar,
+           speed:  Scalar,
+           max_t:  Scalar)
+           -> Option<Scalar> {
+    let tolerance: Scalar = na::cast(TOI_TOLERANCE);
+    let mut t: Scalar     = na::zero();
+
+    for _ in range(0, MAX_ITERATIONS) {
+        let m1   = position_at(rb1, t) * *delta1;
+        let m2   = position_at(rb2, t) * *delta2;
+        let dist = distance(&m1, s1, &m2, s2) - margin;
+
+        if dist <= tolerance {
+            return Some(t);
+        }
+
+        if speed <= na::zero() {
+            return None;
+        }
+
+        t = t + dist / speed;
+
+        if t > max_t {
+            return None;
+        }
+    }
+
+    // Grazing motions may not converge: no impact is reported rather than a wrong one.
+    None
+}
+
+// The distance between two shapes, where meshes and nested compounds are handled element-wise.
+fn distance(m1: &Matrix, s1: &Shape<Scalar, Point, Vect, Matrix>,
+            m2: &Matrix, s2: &Shape<Scalar, Point, Vect, Matrix>) -> Scalar {
+    let mut res: Scalar = Float::max_value();
+
+    if is_aggregate(s1) {
+        for_each_element(m1, s1, |m, s| res = res.min(distance(m, s, m2, s2)));
+    }
+    else if is_aggregate(s2) {
+        for_each_element(m2, s2, |m, s| res = res.min(distance(m1, s1, m, s)));
+    }
+    else {
+        res = geometry::distance_internal::shape_against_shape(m1, s1, m2, s2);
+    }
+
+    res
+}
+
+fn is_aggregate(s: &Shape<Scalar, Point, Vect, Matrix>) -> bool {
+    let type_id = s.get_type_id();
+
+    type_id == TypeId::of::<BodyCompound>() || type_id == TypeId::of::<BodyMesh>()
+}
+
+fn for_each_element(m: &Matrix, s: &Shape<Scalar, Point, Vect, Matrix>, f: |&Matrix, &Shape<Scalar, Point, Vect, Matrix>| -> ()) {
+    if s.get_type_id() == TypeId::of::<BodyCompound>() {
+        for &(ref delta, ref part) in s.downcast_ref::<BodyCompound>().unwrap().shapes().iter() {
+            f(&(*m * *delta), &***part)
+        }
+    }
+    else {
+        let mesh = s.downcast_ref::<BodyMesh>().unwrap();
+
+        for i in range(0, mesh.len()) {
+            f(m, &mesh.element_at(i))
+        }
+    }
+}
+
+// The top-level parts of the shape of `rb`, with their margins.
+fn parts(rb: &RigidBody) -> Vec<(Matrix, &Shape<Scalar, Point, Vect, Matrix>, Scalar)> {
+    let shape = rb.shape_ref();
+
+    if shape.get_type_id() == TypeId::of::<BodyCompound>() {
+        shape.downcast_ref::<BodyCompound>().unwrap().shapes().iter().enumerate().map(|(i, &(ref delta, ref part))| {
+            (delta.clone(), &***part, rb.part_margin(i))
+        }).collect()
+    }
+    else {
+        vec!((na::one(), shape, rb.margin()))
+    }
+}
+
+// The position of `rb` after moving with its current velocities during `t`.
+fn position_at(rb: &RigidBody, t: Scalar) -> Matrix {
+    euler::displacement(t, rb.position(), rb.center_of_mass(), &rb.lin_vel(), &rb.ang_vel()) * *rb.position()
+}
+
+// An upper bound of the distance between the center of mass of `rb` and the points of its
+// dilated shape. It does not change when the body rotates around its center of mass.
+fn max_radius(rb: &RigidBody, parts: &[(Matrix, &Shape<Scalar, Point, Vect, Matrix>, Scalar)]) -> Scalar {
+    let aabb          = rb.shape_ref().aabb(rb.position());
+    let com           = rb.center_of_mass();
+    let mut ext: Vect = na::zero();
+
+    for i in range(0, na::dim::<Vect>()) {
+        ext[i] = (aabb.mins()[i] - com[i]).abs().max((aabb.maxs()[i] - com[i]).abs());
+    }
+
+    na::norm(&ext) + parts.iter().fold(na::zero(), |m: Scalar, p| m.max(*p.ref2()))
+}
@@ -1,44 +1,128 @@
 use std::rc::Rc;
+use std::mem;
+use std::num::Float;
+use std::collections::HashSet;
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::iter::Map;
 use std::slice::Items;
+use std::any::AnyRefExt;
+use std::intrinsics::TypeId;
+use std::sync::Arc;
+use std::io::Writer;
+use na::Translation;
 use na;
-use ncollide::bounding_volume::AABB;
-use ncollide::ray::{Ray, RayIntersection};
+use ncollide::bounding_volume::{AABB, HasBoundingVolume, BoundingVolume};
+use ncollide::shape::{Shape, Mesh, BezierSurface};
+use ncollide::ray::{Ray, RayIntersection, RayCast};
+use ncollide::geometry::Contact;
 use ncollide::narrow_phase::ShapeShapeCollisionDetector;
 use ncollide::utils::data::hash_map::{HashMap, Entry};
 use ncollide::utils::data::hash::UintTWHash;
 use ncollide::broad_phase::{BroadPhase, DBVTBroadPhase, ProximitySignalHandler};
 use ncollide::narrow_phase::ContactSignalHandler;
 use ncollide::world::{CollisionWorld, CollisionObject};
-use integration::{Integrator, BodySmpEulerIntegrator, BodyForceGenerator,
-                  TranslationalCCDMotionClamping};
-use detection::ActivationManager;
+use integration::{Integrator, IntegratorKind, BodySmpEulerIntegrator, BodyVerletIntegrator,
+                  BodyForceGenerator, TranslationalCCDMotionClamping, ForceGenerator, GravityField};
+use detection::{ActivationManager, ContactAccumulator, ManifoldReducer, BroadPhasePairs,
+                BroadPhasePairsUpdater, ContactGenerator, ContactGenerators};
 use detection::Detector;
 use detection::constraint::Constraint;
-use detection::joint::{JointManager, BallInSocket, Fixed};
-use resolution::{Solver, AccumulatedImpulseSolver, CorrectionMode};
-use object::{RigidBody, RigidBodyHandle};
+use detection::joint::{JointManager, JointSignalHandler, BallInSocket, Fixed, Distance, Grab, Articulation};
+use resolution::{Solver, AccumulatedImpulseSolver, CorrectionMode, SpringDamper};
+use object::{RigidBody, RigidBodyHandle, ModifierVolume, ModifierVolumeHandle, Modifier, StepFlags};
+use world::{WorldEvent, TimedEvent, GroupHandle, RayHit, ContactInfo, ContactsWith, StepStatistics,
+            WorldCommand, WorldCommands};
+use world::step_statistics;
+use world::time_of_impact;
+use utils::scaled_shape;
 use math::{Scalar, Point, Vect, Orientation, Matrix};
 
 /// The default broad phase.
 pub type WorldBroadPhase = DBVTBroadPhase<Scalar, Point, Rc<RefCell<RigidBody>>, AABB<Point>>;
 /// An iterator visiting rigid bodies.
 pub type RigidBodies<'a> = Map<'a, &'a Entry<uint, Rc<RefCell<RigidBody>>>, &'a Rc<RefCell<RigidBody>>, Items<'a, Entry<uint, Rc<RefCell<RigidBody>>>>>;
+/// An iterator visiting joints.
+pub type Joints<'a> = Map<'a, &'a Entry<uint, Constraint>, &'a Constraint, Items<'a, Entry<uint, Constraint>>>;
 pub type RigidBodyCollisionWorld = CollisionWorld<Scalar, Point, Vect, Matrix, Rc<RefCell<RigidBody>>>;
 
 /// The physics world.
 ///
 /// This is the main structure of the physics engine.
 pub struct World {
-    cworld:      RigidBodyCollisionWorld,
-    bodies:      HashMap<uint, RigidBodyHandle, UintTWHash>,
-    forces:      BodyForceGenerator,
-    integrator:  BodySmpEulerIntegrator,
-    sleep:       ActivationManager,
-    ccd:         TranslationalCCDMotionClamping,
-    joints:      JointManager,
-    solver:      AccumulatedImpulseSolver,
+    cworld:                RigidBodyCollisionWorld,
+    bodies:                HashMap<uint, RigidBodyHandle, UintTWHash>,
+    forces:                BodyForceGenerator,
+    generators:            Vec<(uint, Box<ForceGenerator + 'static>)>,
+    gravity:               Option<Box<GravityField + 'static>>,
+    integrator:            BodySmpEulerIntegrator,
+    verlet:                BodyVerletIntegrator,
+    integrator_kind:       IntegratorKind,
+    sleep:                 ActivationManager,
+    ccd:                   TranslationalCCDMotionClamping,
+    joints:                JointManager,
+    articulations:         Vec<Rc<RefCell<Articulation>>>,
+    // The gravity compensation torques of the articulations to apply to each body at this step.
+    compensations:         HashMap<uint, Orientation, UintTWHash>,
+    solver:                AccumulatedImpulseSolver,
+    events:                Vec<WorldEvent>,
+    // The step count and time at which each event of `events` was emitted, for those already known.
+    event_stamps:          Vec<(u64, Scalar)>,
+    one_way:               HashSet<(uint, uint)>,
+    prev_one_way:          HashSet<(uint, uint)>,
+    // The keys of the pairs of bodies which collisions are disabled.
+    disabled_pairs:        HashSet<(uint, uint)>,
+    // The keys of the pairs of bodies overlapping a sensor, and the pairs themselves with the
+    // sensor first, at this step and at the previous one.
+    sensor_overlaps:       HashSet<(uint, uint)>,
+    prev_sensor_overlaps:  HashSet<(uint, uint)>,
+    sensor_pairs:          Vec<(RigidBodyHandle, RigidBodyHandle)>,
+    prev_sensor_pairs:     Vec<(RigidBodyHandle, RigidBodyHandle)>,
+    contacts:              Vec<(RigidBodyHandle, RigidBodyHandle, Contact<Scalar, Point, Vect>)>,
+    collector:             Vec<Constraint>,
+    accumulator:           ContactAccumulator,
+    reducer:               ManifoldReducer,
+    timestep:              Scalar,
+    time:                  Scalar,
+    step_count:            u64,
+    motion_prediction:     Scalar,
+    max_motion_prediction: Scalar,
+    warm_start_reset:      Option<Scalar>,
+    // Removed groups are kept as `None` so that their handles are never reused.
+    groups:                Vec<Option<(String, Vec<RigidBodyHandle>)>>,
+    pairs:                 Rc<RefCell<BroadPhasePairs>>,
+    contact_generators:    ContactGenerators,
+    moved:                 Vec<RigidBodyHandle>,
+    contact_infos:         Vec<ContactInfo>,
+    // The indices in `contact_infos` of the contacts of each body.
+    body_contacts:         HashMap<uint, Vec<uint>, UintTWHash>,
+    stats:                 StepStatistics,
+    volumes:               Vec<ModifierVolumeHandle>,
+    commands:              Rc<RefCell<WorldCommands>>,
+    wrap_bounds:           Option<(Scalar, Scalar)>,
+    drift_threshold:       Option<Scalar>,
+    // The speeds below which a body stops moving and above which it starts moving.
+    motion_thresholds:     Option<(Scalar, Scalar)>,
+    // The keys of the bodies currently considered moving.
+    moving:                HashSet<uint>,
+    // The keys of the bodies driven by a torque which reached their stall speed.
+    spinning:              HashSet<uint>,
+    // The keys of the bodies currently stalled.
+    stalled:               HashSet<uint>,
+    // The unique identifiers of the bodies, and the next one to be given.
+    uids:                  HashSet<u64>,
+    next_uid:              u64,
+    min_timestep:          Scalar,
+    paused:                bool,
+    refresh_when_paused:   bool,
+    broad_phase_padding:   Scalar,
+    // The linear and angular damping given to the dynamic bodies added to the world.
+    default_damping:       Option<(Scalar, Scalar)>,
+    // The solver residual above which a warning is logged, and the number of consecutive steps it
+    // must be exceeded for.
+    residual_warning:      Option<(Scalar, uint)>,
+    residual_streak:       uint,
+    last_residual_warning: Option<u64>
 }
 
 impl World {
@@ -56,7 +140,12 @@ impl World {
          * For the collision detection
          */
         // Collision world
-        let cworld = CollisionWorld::new(na::cast(0.10f64), na::cast(0.10f64));
+        let prediction: Scalar = na::cast(0.10f64);
+        let mut cworld = CollisionWorld::new(na::cast(0.10f64), prediction.clone());
+
+        // Broad phase pairs tracking
+        let pairs = Rc::new(RefCell::new(BroadPhasePairs::new()));
+        cworld.register_proximity_signal_handler("__nphysics_internal_broad_phase_pairs", BroadPhasePairsUpdater::new(pairs.clone()));
 
         // CCD handler
         let ccd = TranslationalCCDMotionClamping::new();
@@ -70,7 +159,7 @@ impl World {
         /*
          * For constraints resolution
          */
-        let solver = AccumulatedImpulseSolver::new(
+        let mut solver = AccumulatedImpulseSolver::new(
             na::cast(0.1f64),
             CorrectionMode::VelocityAndPosition(na::cast(0.2f64), na::cast(0.2f64), na::cast(0.08f64)),
             na::cast(0.4f64),
@@ -79,119 +168,1861 @@ impl World {
             10);
 
         World {
-            cworld:     cworld,
-            bodies:     HashMap::new(UintTWHash::new()),
-            forces:     forces,
-            integrator: integrator,
-            sleep:      sleep,
-            ccd:        ccd,
-            joints:     joints,
-            solver:     solver,
+            cworld:                cworld,
+            bodies:                HashMap::new(UintTWHash::new()),
+            forces:                forces,
+            generators:            Vec::new(),
+            gravity:               None,
+            integrator:            integrator,
+            verlet:                BodyVerletIntegrator::new(),
+            integrator_kind:       IntegratorKind::SymplecticEuler,
+            sleep:                 sleep,
+            ccd:                   ccd,
+            joints:                joints,
+            articulations:         Vec::new(),
+            compensations:         HashMap::new(UintTWHash::new()),
+            solver:                solver,
+            events:                Vec::new(),
+            event_stamps:          Vec::new(),
+            one_way:               HashSet::new(),
+            prev_one_way:          HashSet::new(),
+            disabled_pairs:        HashSet::new(),
+            sensor_overlaps:       HashSet::new(),
+            prev_sensor_overlaps:  HashSet::new(),
+            sensor_pairs:          Vec::new(),
+            prev_sensor_pairs:     Vec::new(),
+            contacts:              Vec::new(),
+            collector:             Vec::new(),
+            accumulator:           ContactAccumulator::new(),
+            reducer:               ManifoldReducer::new(2 * na::dim::<Vect>() - 2),
+            timestep:              na::cast(0.016f64),
+            time:                  na::zero(),
+            step_count:            0,
+            motion_prediction:     na::one(),
+            max_motion_prediction: na::one(),
+            warm_start_reset:      Some(na::zero()),
+            groups:                Vec::new(),
+            pairs:                 pairs,
+            contact_generators:    ContactGenerators::new(prediction),
+            moved:                 Vec::new(),
+            contact_infos:         Vec::new(),
+            body_contacts:         HashMap::new(UintTWHash::new()),
+            stats:                 StepStatistics::new(),
+            volumes:               Vec::new(),
+            commands:              Rc::new(RefCell::new(WorldCommands::new())),
+            wrap_bounds:           None,
+            drift_threshold:       None,
+            motion_thresholds:     None,
+            moving:                HashSet::new(),
+            spinning:              HashSet::new(),
+            stalled:               HashSet::new(),
+            uids:                  HashSet::new(),
+            next_uid:              1,
+            min_timestep:          na::cast(1.0e-4f64),
+            paused:                false,
+            refresh_when_paused:   false,
+            broad_phase_padding:   na::zero(),
+            default_damping:       None,
+            residual_warning:      None,
+            residual_streak:       0,
+            last_residual_warning: None
+        }
+    }
+
+    /// The nominal timestep used by `step_fixed`.
+    #[inline]
+    pub fn timestep(&self) -> Scalar {
+        self.timestep.clone()
+    }
+
+    /// Sets the nominal timestep used by `step_fixed`.
+    ///
+    /// This may be changed between two steps: the impulses cached for warm-starting are rescaled
+    /// accordingly.
+    #[inline]
+    pub fn set_timestep(&mut self, dt: Scalar) {
+        assert!(dt > na::zero(), "The timestep must be strictly positive.");
+        self.timestep = dt
+    }
+
+    /// The timestep under which `step` does nothing.
+    #[inline]
+    pub fn min_timestep(&self) -> Scalar {
+        self.min_timestep.clone()
+    }
+
+    /// Sets the timestep under which `step` does nothing.
+    ///
+    /// The solver divides by the timestep to correct the penetrations and to detect the resting
+    /// contacts, so a tiny timestep gives huge velocities lasting for a tiny time, which rounding
+    /// errors may turn into NaNs. Defaults to `1.0e-4`.
+    #[inline]
+    pub fn set_min_timestep(&mut self, min: Scalar) {
+        assert!(min > na::zero(), "The minimum timestep must be strictly positive.");
+        self.min_timestep = min
+    }
+
+    /// Whether the simulation is paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pauses or resumes the simulation.
+    ///
+    /// While paused, `step` does not simulate anything: the bodies are neither integrated nor
+    /// solved, the force generators are not applied, and neither the simulated time nor the step
+    /// count change. The events emitted before the pause are kept until they are drained, and the
+    /// queries and the commands still work. The pending commands are applied on the next step
+    /// after the world is resumed.
+    #[inline]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused
+    }
+
+    /// Whether `step` updates the structures used by the geometric queries while the simulation
+    /// is paused.
+    #[inline]
+    pub fn refresh_when_paused(&self) -> bool {
+        self.refresh_when_paused
+    }
+
+    /// Sets whether `step` calls `update_query_structures` while the simulation is paused.
+    ///
+    /// This keeps the ray casts used for picking up to date with the bodies moved by hand during
+    /// the pause. Defaults to `false`.
+    #[inline]
+    pub fn set_refresh_when_paused(&mut self, refresh: bool) {
+        self.refresh_when_paused = refresh
+    }
+
+    /// Updates the physics world using the nominal timestep.
+    pub fn step_fixed(&mut self) {
+        let dt = self.timestep.clone();
+
+        self.step(dt)
+    }
+
+    /// The simulated time.
+    ///
+    /// This is the sum of the timesteps of every step performed since the creation of the world, or
+    /// since the last call to `reset_time`.
+    #[inline]
+    pub fn time(&self) -> Scalar {
+        self.time.clone()
+    }
+
+    /// The number of steps performed since the creation of the world, or since the last call to
+    /// `reset_time`.
+    #[inline]
+    pub fn step_count(&self) -> u64 {
+        self.step_count
+    }
+
+    /// Resets the simulated time and the step counter to zero.
+    ///
+    /// The events emitted before this call keep the time at which they were emitted.
+    pub fn reset_time(&mut self) {
+        self.stamp_events();
+        self.time       = na::zero();
+        self.step_count = 0;
+    }
+
+    /// Updates only the islands of bodies selected by `filter`, using an explicit timestep.
+    ///
+    /// `filter` is called with each island given by `islands`, its first body being its
+    /// representative. The dynamic bodies of the other islands are frozen during this step: they
+    /// are neither integrated nor solved, and keep their position and velocities bit for bit. They
+    /// stay in the broad phase so that the queries still find them, and the simulated bodies
+    /// colliding with them handle them as bodies that cannot move. If `wake_frozen` is `true`, the
+    /// sleeping frozen bodies touched by a simulated one are woken up by the next step, with their
+    /// island.
+    ///
+    /// The time and the step count of the world advance as for `step`.
+    pub fn step_islands<F: FnMut(&[RigidBodyHandle]) -> bool>(&mut self, dt: Scalar, wake_frozen: bool, mut filter: F) {
+        if self.paused {
+            return self.step(dt);
+        }
+
+        let mut frozen = Vec::new();
+
+        for island in self.islands().into_iter() {
+            if !filter(island.as_slice()) {
+                frozen.push_all(island.as_slice());
+            }
+        }
+
+        for b in frozen.iter() {
+            b.borrow_mut().set_frozen(true);
+        }
+
+        self.step(dt);
+
+        let mut touched = Vec::new();
+
+        if wake_frozen {
+            for c in self.contact_infos.iter() {
+                let frozen1 = c.body1.borrow().is_frozen();
+                let frozen2 = c.body2.borrow().is_frozen();
+
+                if frozen1 && !frozen2 {
+                    touched.push(c.body1.clone())
+                }
+                else if frozen2 && !frozen1 {
+                    touched.push(c.body2.clone())
+                }
+            }
+        }
+
+        for b in frozen.iter() {
+            b.borrow_mut().set_frozen(false);
+        }
+
+        for b in touched.iter() {
+            self.sleep.will_activate(b);
+        }
+    }
+
+    // Records the current step count and time for the events emitted since the last call.
+    fn stamp_events(&mut self) {
+        while self.event_stamps.len() < self.events.len() {
+            self.event_stamps.push((self.step_count, self.time.clone()))
         }
     }
 
-    /// Updates the physics world.
+    /// Updates the physics world using an explicit timestep.
+    ///
+    /// A timestep smaller than `min_timestep`, e.g., zero, is ignored: nothing moves, and neither
+    /// the simulated time nor the step count change. This happens when a fixed-timestep
+    /// accumulator is updated by a very short frame. Nothing moves either while the world is
+    /// paused, see `set_paused`.
     pub fn step(&mut self, dt: Scalar) {
+        assert!(dt >= na::zero(), "The timestep must not be negative.");
+
+        if self.paused {
+            if self.refresh_when_paused {
+                self.update_query_structures();
+            }
+
+            return;
+        }
+
+        if dt < self.min_timestep {
+            return;
+        }
+
+        let start = step_statistics::now();
+
+        // The events emitted since the last step belong to it, and those emitted from now on
+        // belong to this step.
+        self.stamp_events();
+        self.time       = self.time + dt;
+        self.step_count = self.step_count + 1;
+
+        // The bodies moved, or which velocity or material was changed, since the last step are
+        // woken up, and the broad phase is told of their new position even if they cannot move.
+        for e in self.bodies.elements().iter() {
+            let change = e.value.borrow().external_change();
+
+            match change {
+                Some(change) => {
+                    if !e.value.borrow().can_move() {
+                        self.sleep.will_activate_neighbors(&mut self.cworld, &e.value);
+                    }
+
+                    self.sleep.activate_now(&e.value, &mut self.events);
+                    self.cworld.set_next_position(&e.value, e.value.borrow().position().clone());
+
+                    match self.warm_start_reset {
+                        Some(ref threshold) if change > *threshold => self.solver.reset_warm_start_of(&e.value),
+                        _ => { }
+                    }
+                },
+                None => { }
+            }
+        }
+
+        self.moved.clear();
+        self.update_volumes();
+
+        self.update_compensations();
+
         for e in self.bodies.elements_mut().iter_mut() {
             let mut rb = e.value.borrow_mut();
 
+            *rb.last_step_flags_mut() = StepFlags::empty();
+
+            // Left out of a partial step, see `step_islands`.
+            if rb.is_frozen() {
+                continue;
+            }
+
+            // Saved for every body so that those falling asleep do not keep a stale one.
+            rb.save_previous_position();
+
+            let volume_scale = if rb.can_move() && !self.volumes.is_empty() {
+                volume_time_scale(self.volumes.as_slice(), &e.value)
+            }
+            else {
+                na::one()
+            };
+
+            rb.set_volume_time_scale(volume_scale);
+
+            // The body is simulated in its own time, see `RigidBody::set_time_scale`.
+            let dt = dt * rb.effective_time_scale();
+
             if rb.is_active() {
                 self.forces.update(dt.clone(), rb.deref_mut());
-                self.integrator.update(dt.clone(), rb.deref_mut());
+
+                if rb.can_move() {
+                    match self.gravity {
+                        Some(ref field) => {
+                            let g = field.gravity_at(rb.center_of_mass());
+                            rb.set_lin_acc(g)
+                        },
+                        None => { }
+                    }
+
+                    if !self.volumes.is_empty() {
+                        apply_volumes(dt.clone(), self.volumes.as_slice(), &self.gravity, &self.forces, &e.value, rb.deref_mut());
+                    }
+
+                    // The feedforward of the articulations is applied before the servos driving their joints.
+                    match self.compensations.find(&(e.value.deref() as *const RefCell<RigidBody> as uint)) {
+                        Some(torque) => rb.append_ang_force(torque.clone()),
+                        None         => { }
+                    }
+
+                    for generator in self.generators.iter_mut() {
+                        let (_, ref mut generator) = *generator;
+                        generator.apply(dt.clone(), &e.value, rb.deref_mut());
+                    }
+
+                    apply_damping(dt.clone(), rb.deref_mut());
+                }
+
+                match self.integrator_kind {
+                    IntegratorKind::SymplecticEuler => self.integrator.update(dt.clone(), rb.deref_mut()),
+                    IntegratorKind::VelocityVerlet  => self.verlet.update(dt.clone(), rb.deref_mut())
+                }
+
+                if rb.can_move() {
+                    let mut motion = rb.lin_vel() * dt * self.motion_prediction;
+                    let sqlen      = na::sqnorm(&motion);
+
+                    if sqlen > self.max_motion_prediction * self.max_motion_prediction {
+                        motion = motion * (self.max_motion_prediction / sqlen.sqrt());
+                    }
+
+                    rb.set_predicted_motion(motion);
+                }
+
                 self.cworld.set_next_position(&e.value, rb.position().clone());
             }
         }
 
-        self.cworld.perform_position_update();
-        self.cworld.perform_broad_phase();
-        self.ccd.update(&mut self.cworld);
-        self.cworld.perform_narrow_phase();
+        let integrated = step_statistics::now();
+
+        self.cworld.perform_position_update();
+        self.cworld.perform_broad_phase();
+        self.ccd.update(&mut self.cworld);
+        self.cworld.perform_narrow_phase();
+
+        let detected = step_statistics::now();
+
+        self.joints.update(&mut self.sleep);
+        self.sleep.update(&mut self.cworld, &self.joints, &self.bodies, &mut self.events);
+
+        let activated = step_statistics::now();
+
+        // The buffers are only cleared so that their memory is reused by the next steps.
+        mem::swap(&mut self.one_way, &mut self.prev_one_way);
+        self.one_way.clear();
+        mem::swap(&mut self.sensor_overlaps, &mut self.prev_sensor_overlaps);
+        self.sensor_overlaps.clear();
+        mem::swap(&mut self.sensor_pairs, &mut self.prev_sensor_pairs);
+        self.sensor_pairs.clear();
+
+        {
+            let contacts        = &mut self.contacts;
+            let prev_one_way    = &self.prev_one_way;
+            let one_way         = &mut self.one_way;
+            let sensor_overlaps = &mut self.sensor_overlaps;
+            let sensor_pairs    = &mut self.sensor_pairs;
+            let generators      = &mut self.contact_generators;
+            let joints          = &self.joints;
+            let disabled        = &self.disabled_pairs;
+
+            self.cworld.contacts(|b1, b2, c| {
+                if disabled.contains(&pair_key(b1, b2)) {
+                    return;
+                }
+
+                if is_sensor_pair(b1, b2) {
+                    if !generators.overrides(b1, b2) && !joints.collision_disabled(b1, b2) {
+                        record_sensor_overlap(b1, b2, c, sensor_overlaps, sensor_pairs)
+                    }
+                }
+                else if (b1.borrow().is_active() || b2.borrow().is_active()) &&
+                   !generators.overrides(b1, b2) &&
+                   !joints.collision_disabled(b1, b2) &&
+                   accept_one_way_contact(b1, b2, c, prev_one_way, one_way) {
+                    contacts.push((b1.clone(), b2.clone(), c.clone()));
+                }
+            });
+
+            generators.generate(self.pairs.borrow().deref(), |b1, b2, c| {
+                if disabled.contains(&pair_key(b1, b2)) {
+                    return;
+                }
+
+                if is_sensor_pair(b1, b2) {
+                    if !joints.collision_disabled(b1, b2) {
+                        record_sensor_overlap(b1, b2, c, sensor_overlaps, sensor_pairs)
+                    }
+                }
+                else if (b1.borrow().is_active() || b2.borrow().is_active()) &&
+                   !joints.collision_disabled(b1, b2) &&
+                   accept_one_way_contact(b1, b2, c, prev_one_way, one_way) {
+                    contacts.push((b1.clone(), b2.clone(), c.clone()));
+                }
+            });
+        }
+
+        for &(ref sensor, ref b) in self.sensor_pairs.iter() {
+            if !self.prev_sensor_overlaps.contains(&pair_key(sensor, b)) {
+                self.events.push(WorldEvent::SensorEntered(sensor.clone(), b.clone()))
+            }
+        }
+
+        for &(ref sensor, ref b) in self.prev_sensor_pairs.iter() {
+            if !self.sensor_overlaps.contains(&pair_key(sensor, b)) {
+                self.events.push(WorldEvent::SensorLeft(sensor.clone(), b.clone()))
+            }
+        }
+
+        // A CCD-enabled body may have traversed a sensor it overlaps neither before nor after this
+        // step.
+        for &(ref sensor, ref b) in self.ccd.crossed_sensors().iter() {
+            let key = pair_key(sensor, b);
+
+            if !self.prev_sensor_overlaps.contains(&key) && !self.sensor_overlaps.contains(&key) &&
+               !self.disabled_pairs.contains(&key) && !self.joints.collision_disabled(sensor, b) {
+                self.events.push(WorldEvent::SensorEntered(sensor.clone(), b.clone()));
+                self.events.push(WorldEvent::SensorLeft(sensor.clone(), b.clone()));
+            }
+        }
+
+        self.accumulator.update(&mut self.contacts);
+        self.reducer.update(&mut self.contacts);
+
+        for &(ref b1, ref b2, ref c) in self.contacts.iter() {
+            let m1 = b1.borrow().margin_at(&c.world1);
+            let m2 = b2.borrow().margin_at(&c.world2);
+
+            let mut c = c.clone();
+            c.depth = c.depth + m1 + m2;
+
+            self.collector.push(Constraint::RBRB(b1.clone(), b2.clone(), c));
+        }
+
+        self.contacts.clear();
+        self.joints.interferences(&mut self.collector);
+
+        let collected = step_statistics::now();
+
+        self.solver.solve(dt, self.collector.as_slice());
+        self.check_residuals();
+
+        let solved = step_statistics::now();
+
+        for impact in self.solver.impacts().iter() {
+            self.events.push(WorldEvent::Impact(impact.clone()))
+        }
+
+        for b in self.solver.capped_bodies().iter() {
+            b.borrow_mut().last_step_flags_mut().set_depenetration_capped()
+        }
+
+        self.contact_infos.clear();
+
+        let impulses = self.solver.normal_impulses().iter().zip(self.solver.friction_impulses().iter());
+
+        for (constraint, (impulse, friction)) in self.collector.iter().zip(impulses) {
+            match *constraint {
+                Constraint::RBRB(ref b1, ref b2, ref c) => {
+                    self.contact_infos.push(ContactInfo {
+                        body1:            b1.clone(),
+                        body2:            b2.clone(),
+                        contact:          c.clone(),
+                        normal_impulse:   *impulse,
+                        friction_impulse: friction.clone()
+                    })
+                },
+                _ => { }
+            }
+        }
+
+        self.index_contacts();
+        self.collector.clear();
+
+        // Sleeping bodies are checked too since they may have been teleported.
+        let eps: Scalar = na::cast(MOVED_EPSILON);
+        let (mut num_ccd_clamped, mut num_velocity_clamped, mut num_depenetration_capped) = (0u, 0u, 0u);
+
+        for e in self.bodies.elements().iter() {
+            let drifting = self.drift_threshold.is_some() && self.is_free(&e.value);
+            let mut rb   = e.value.borrow_mut();
+
+            if rb.is_frozen() {
+                continue;
+            }
+
+            if drifting {
+                cancel_drift(rb.deref_mut(), self.drift_threshold.as_ref().unwrap());
+            }
+
+            match rb.max_angular_velocity() {
+                Some(max) => clamp_spin(rb.deref_mut(), &max),
+                None      => { }
+            }
+
+            let flags = rb.last_step_flags();
+
+            if flags.ccd_clamped() {
+                num_ccd_clamped = num_ccd_clamped + 1;
+            }
+
+            if flags.velocity_clamped() {
+                num_velocity_clamped = num_velocity_clamped + 1;
+            }
+
+            if flags.depenetration_capped() {
+                num_depenetration_capped = num_depenetration_capped + 1;
+            }
+
+            // Checked here so that the first body to diverge is reported before it spreads NaNs
+            // to the bodies in contact with it at the next step.
+            debug_assert!(rb.is_finite(), "The rigid body {:x} diverged at the step {}: position {}, linear velocity {}, angular velocity {}.",
+                          e.key, self.step_count, rb.position(), rb.lin_vel(), rb.ang_vel());
+
+            if rb.update_reported_position(&eps) {
+                self.moved.push(e.value.clone())
+            }
+
+            rb.save_simulated_state();
+        }
+
+        self.update_motion_states();
+        self.update_stalls();
+
+        // Applied last so that the changes are seen as external ones by the next step.
+        self.wrap_bodies();
+        self.apply_commands();
+
+        self.stats = StepStatistics {
+            integration:              integrated - start,
+            collision_detection:      detected - integrated,
+            activation:               activated - detected,
+            contacts:                 collected - activated,
+            resolution:               solved - collected,
+            total:                    step_statistics::now() - start,
+            num_contacts:             self.contact_infos.len(),
+            num_cached_pairs:         self.contact_generators.num_reused(),
+            num_ccd_clamped:          num_ccd_clamped,
+            num_velocity_clamped:     num_velocity_clamped,
+            num_depenetration_capped: num_depenetration_capped,
+            contact_residual:         self.solver.contact_residual(),
+            joint_residual:           self.solver.joint_residual()
+        };
+    }
+
+    // Logs the constraints the solver failed to satisfy for too many consecutive steps. Only done
+    // in debug mode, at most once every `RESIDUAL_WARNING_INTERVAL` steps.
+    fn check_residuals(&mut self) {
+        let (threshold, num_steps) = match self.residual_warning {
+            Some(warning) => warning,
+            None          => return
+        };
+
+        let contact = self.solver.contact_residual();
+        let joint   = self.solver.joint_residual();
+
+        if contact <= threshold && joint <= threshold {
+            self.residual_streak = 0;
+            return;
+        }
+
+        self.residual_streak = self.residual_streak + 1;
+
+        let rate_limited = match self.last_residual_warning {
+            Some(step) => self.step_count < step + RESIDUAL_WARNING_INTERVAL,
+            None       => false
+        };
+
+        if !cfg!(not(ndebug)) || self.residual_streak < num_steps || rate_limited {
+            return;
+        }
+
+        self.last_residual_warning = Some(self.step_count);
+
+        let worst = if contact >= joint { self.solver.worst_contact() } else { self.solver.worst_joint() };
+        let culprit = match worst {
+            Some(i) => describe_constraint(&self.collector[i]),
+            None    => "unknown constraint".to_string()
+        };
+
+        let _ = writeln!(&mut ::std::io::stderr(),
+                         "nphysics: the solver residual exceeded {} for {} steps at step {} (contacts: {}, joints: {}), worst: {}.",
+                         threshold, self.residual_streak, self.step_count, contact, joint, culprit);
+    }
+
+    // Whether `body` is an active dynamic body left alone by the last step: no contact, joint, nor
+    // acceleration act on it.
+    fn is_free(&self, body: &RigidBodyHandle) -> bool {
+        {
+            let rb = body.borrow();
+
+            if !rb.can_move() || !rb.is_active() || !na::is_zero(&rb.lin_acc()) || !na::is_zero(&rb.ang_acc()) {
+                return false;
+            }
+        }
+
+        let key = body.deref() as *const RefCell<RigidBody> as uint;
+
+        self.body_contacts.find(&key).is_none() &&
+        self.joints.joints_with_body(body).map(|js| js.is_empty()).unwrap_or(true)
+    }
+
+    // Emits the events of the bodies which speed crossed the motion thresholds.
+    fn update_motion_states(&mut self) {
+        let (stop, start) = match self.motion_thresholds {
+            Some(thresholds) => thresholds,
+            None             => return
+        };
+
+        for e in self.bodies.elements().iter() {
+            let rb = e.value.borrow();
+
+            if !rb.can_move() {
+                continue;
+            }
+
+            let sqspeed = if rb.is_active() { na::sqnorm(&rb.lin_vel()) } else { na::zero() };
+
+            if !self.moving.contains(&e.key) {
+                if sqspeed > start * start {
+                    let _ = self.moving.insert(e.key);
+                    self.events.push(WorldEvent::BodyStartedMoving(e.value.clone()))
+                }
+            }
+            else if sqspeed < stop * stop {
+                let _ = self.moving.remove(&e.key);
+                self.events.push(WorldEvent::BodyStoppedMoving(e.value.clone()))
+            }
+        }
+    }
+
+    // Emits the events of the bodies driven by a torque which angular speed dropped under their
+    // stall speed, or went back over it.
+    fn update_stalls(&mut self) {
+        for e in self.bodies.elements().iter() {
+            let rb = e.value.borrow();
+
+            let (driven, fast) = match rb.stall_speed() {
+                // The accelerations applied during the step are kept until the next one, and
+                // while the body sleeps, e.g., wedged by another one.
+                Some(speed) => {
+                    let sqspeed = if rb.is_active() { na::sqnorm(&rb.ang_vel()) } else { na::zero() };
+
+                    (!na::is_zero(&rb.ang_acc()), sqspeed >= speed * speed)
+                },
+                None => (false, false)
+            };
+
+            if self.stalled.contains(&e.key) {
+                if fast || !driven {
+                    let _ = self.stalled.remove(&e.key);
+                    self.events.push(WorldEvent::BodyUnstalled(e.value.clone()))
+                }
+            }
+            else if !fast && driven && self.spinning.contains(&e.key) {
+                let _ = self.stalled.insert(e.key);
+                self.events.push(WorldEvent::BodyStalled(e.value.clone()))
+            }
+
+            // A rotor slower than its stall speed is only stalled after it reached that speed, not
+            // while it starts up.
+            if fast && driven {
+                let _ = self.spinning.insert(e.key);
+            }
+            else if !driven || self.stalled.contains(&e.key) {
+                let _ = self.spinning.remove(&e.key);
+            }
+        }
+    }
+
+    // Teleports the dynamic bodies beyond the wrap bounds to the other side.
+    fn wrap_bodies(&mut self) {
+        let (x_min, x_max) = match self.wrap_bounds {
+            Some(bounds) => bounds,
+            None         => return
+        };
+
+        let mut wrapped = Vec::new();
+
+        for e in self.bodies.elements().iter() {
+            let rb = e.value.borrow();
+
+            if rb.can_move() {
+                let x = rb.position().translation().x;
+
+                if x > x_max {
+                    wrapped.push((e.value.clone(), x_min - x_max))
+                }
+                else if x < x_min {
+                    wrapped.push((e.value.clone(), x_max - x_min))
+                }
+            }
+        }
+
+        for (body, dx) in wrapped.into_iter() {
+            let mut shift: Vect = na::zero();
+            shift.x = dx;
+
+            let m = na::append_translation(body.borrow().position(), &shift);
+            self.teleport(&body, m);
+        }
+    }
+
+    // Finds the dynamic bodies inside each modifier volume, and wakes up those which entered or
+    // left it, or are inside a volume which changed.
+    fn update_volumes(&mut self) {
+        for volume in self.volumes.iter() {
+            let mut bodies = Vec::new();
+
+            {
+                let v = volume.borrow();
+
+                self.cworld.interferences_with_aabb(&v.aabb(), |b| {
+                    if b.borrow().can_move() && v.accepts(b.borrow().deref()) {
+                        bodies.push(b.clone())
+                    }
+                });
+            }
+
+            let changed         = volume.borrow_mut().take_changed();
+            let (entered, left) = volume.borrow_mut().update_inside(bodies);
+
+            if changed {
+                for b in volume.borrow().bodies().iter() {
+                    self.sleep.activate_now(b, &mut self.events);
+                }
+            }
+
+            for b in left.into_iter() {
+                self.sleep.activate_now(&b, &mut self.events);
+                self.events.push(WorldEvent::VolumeLeft(volume.clone(), b));
+            }
+
+            for b in entered.into_iter() {
+                self.sleep.activate_now(&b, &mut self.events);
+                self.events.push(WorldEvent::VolumeEntered(volume.clone(), b));
+            }
+        }
+    }
+
+    /// The time spent in each phase of the last step.
+    ///
+    /// The durations are only measured if the `step_stats` feature is enabled.
+    #[inline]
+    pub fn step_statistics(&self) -> &StepStatistics {
+        &self.stats
+    }
+
+    /// Updates the structures used by the geometric queries, without stepping the simulation.
+    ///
+    /// Call this after moving bodies with `set_transformation` for the ray, point and AABB queries
+    /// to take their new positions into account. Unlike `step`, this includes static and sleeping
+    /// bodies. This costs a position update of every body and a broad phase update: nothing is
+    /// integrated, the narrow phase is not performed, and the contacts and impulses cached for
+    /// warm-starting are kept as they are until the next step. Thus, this can be freely called
+    /// between two steps, even several times.
+    pub fn update_query_structures(&mut self) {
+        for e in self.bodies.elements().iter() {
+            self.cworld.set_next_position(&e.value, e.value.borrow().position().clone());
+        }
+
+        self.cworld.perform_position_update();
+        self.cworld.perform_broad_phase();
+    }
+
+    /// The contacts solved during the last step, with the impulses applied to them.
+    ///
+    /// At most `max_contacts_per_pair` contacts are reported for each pair of bodies.
+    #[inline]
+    pub fn contacts(&self) -> &[ContactInfo] {
+        self.contact_infos.as_slice()
+    }
+
+    /// The contacts of `body` solved during the last step.
+    ///
+    /// This includes the contacts where `body` is either the first or the second body, and has the
+    /// same cost as iterating through the result: the contacts are indexed by body at each step.
+    /// Like with `contacts`, only the contacts actually solved are reported, so the pairs filtered
+    /// out by a joint, a one-way body or the collision groups are not.
+    pub fn contacts_with(&self, body: &RigidBodyHandle) -> ContactsWith {
+        let empty: &[uint] = &[];
+        let ids = match self.body_contacts.find(&(body.deref() as *const RefCell<RigidBody> as uint)) {
+            Some(ids) => ids.as_slice(),
+            None      => empty
+        };
+
+        ContactsWith::new(self.contact_infos.as_slice(), ids)
+    }
+
+    /// The distinct bodies `body` had contacts with during the last step.
+    pub fn bodies_in_contact_with(&self, body: &RigidBodyHandle) -> Vec<RigidBodyHandle> {
+        let key     = body.deref() as *const RefCell<RigidBody> as uint;
+        let mut res = Vec::new();
+
+        for info in self.contacts_with(body) {
+            let other = if info.body1.deref() as *const RefCell<RigidBody> as uint == key {
+                &info.body2
+            }
+            else {
+                &info.body1
+            };
+
+            if !res.iter().any(|b: &RigidBodyHandle| b.deref() as *const RefCell<RigidBody> == other.deref() as *const RefCell<RigidBody>) {
+                res.push(other.clone())
+            }
+        }
+
+        res
+    }
+
+    /// Whether `body` is connected to a body that cannot move through contacts and joints.
+    ///
+    /// The contacts are those found by the last collision detection, and a joint attached to the
+    /// ground grounds its bodies. A body that cannot move is always grounded. See
+    /// `ungrounded_islands`.
+    pub fn is_grounded(&mut self, body: &RigidBodyHandle) -> bool {
+        if !body.borrow().can_move() {
+            return true;
+        }
+
+        let key = body.deref() as *const RefCell<RigidBody> as uint;
+
+        !self.ungrounded_islands().iter().any(|island| {
+            island.iter().any(|b| b.deref() as *const RefCell<RigidBody> as uint == key)
+        })
+    }
+
+    /// The groups of dynamic bodies which are not connected to any body that cannot move.
+    ///
+    /// Two bodies are connected if they touch each other or are attached by a joint, as for the
+    /// islands put to sleep. This is computed on demand from the contacts found by the last
+    /// collision detection, without the sensors and the contacts added by the contact generators.
+    /// The groups and their bodies are in the order the bodies were added to the world.
+    pub fn ungrounded_islands(&mut self) -> Vec<Vec<RigidBodyHandle>> {
+        self.sleep.ungrounded_islands(&mut self.cworld, &self.joints, &self.bodies)
+    }
+
+    /// The groups of dynamic bodies connected to each other.
+    ///
+    /// Two bodies are connected as for `ungrounded_islands`, but the groups touching a body that
+    /// cannot move are kept too. The first body of each group is its representative.
+    pub fn islands(&mut self) -> Vec<Vec<RigidBodyHandle>> {
+        self.sleep.islands(&mut self.cworld, &self.joints, &self.bodies)
+    }
+
+    // Computes the gravity compensation torques of the articulations, from the positions of the
+    // bodies at the beginning of the step.
+    fn update_compensations(&mut self) {
+        if self.articulations.is_empty() && self.compensations.elements().is_empty() {
+            return;
+        }
+
+        self.compensations = HashMap::new(UintTWHash::new());
+
+        let gravity       = &self.gravity;
+        let uniform       = self.forces.lin_acc();
+        let compensations = &mut self.compensations;
+
+        for articulation in self.articulations.iter() {
+            articulation.borrow().compensation_torques(|at| {
+                match *gravity {
+                    Some(ref field) => field.gravity_at(at),
+                    None            => uniform.clone()
+                }
+            }, compensations);
+        }
+    }
+
+    // Rebuilds the index of the contacts of each body from `contact_infos`.
+    //
+    // The lists of the bodies still in contact are reused, and those of the others are removed.
+    fn index_contacts(&mut self) {
+        for e in self.body_contacts.elements_mut().iter_mut() {
+            e.value.clear()
+        }
+
+        for (i, info) in self.contact_infos.iter().enumerate() {
+            let k1 = info.body1.deref() as *const RefCell<RigidBody> as uint;
+            let k2 = info.body2.deref() as *const RefCell<RigidBody> as uint;
+
+            for k in [ k1, k2 ].iter() {
+                let ids = self.body_contacts.find_or_insert_lazy(*k, || Some(Vec::new()));
+                ids.unwrap().push(i);
+            }
+        }
+
+        let separated: Vec<uint> = self.body_contacts.elements().iter().filter(|e| e.value.is_empty()).map(|e| e.key).collect();
+
+        for k in separated.iter() {
+            let _ = self.body_contacts.remove(k);
+        }
+    }
+
+    /// The bodies that moved during the last step.
+    ///
+    /// This includes the bodies moved by the solver, woken up, or teleported since the previous
+    /// step. A body moving very slowly is reported once its motion since it was last reported
+    /// becomes noticeable. A body just added to the world is reported by the next step if it is
+    /// not at the origin.
+    #[inline]
+    pub fn moved_bodies(&self) -> &[RigidBodyHandle] {
+        self.moved.as_slice()
+    }
+
+    /// The total capacity of the buffers reused from one step to the next.
+    ///
+    /// Once the simulation reaches a steady state, this should not change anymore: the steps do not
+    /// need to reallocate those buffers.
+    #[cfg(feature = "alloc_stats")]
+    pub fn buffers_capacity(&self) -> uint {
+        self.one_way.capacity() + self.prev_one_way.capacity() + self.contacts.capacity() +
+        self.collector.capacity() + self.contact_infos.capacity() + self.sleep.buffers_capacity() +
+        self.solver.buffers_capacity()
+    }
+
+    /// Adds a rigid body to the physics world.
+    ///
+    /// This fails if `rb` can move and has a `Mesh` shape: the narrow phase cannot compute the
+    /// contacts of a moving mesh. Decompose it into convex pieces with
+    /// `utils::mesh_convex_decomposition` instead. Static meshes are supported.
+    pub fn add_body(&mut self, rb: RigidBody) -> RigidBodyHandle {
+        let handle = Rc::new(RefCell::new(rb));
+
+        self.insert_body(handle.clone());
+
+        handle
+    }
+
+    fn insert_body(&mut self, handle: RigidBodyHandle) {
+        handle.borrow_mut().set_default_broad_phase_padding(self.broad_phase_padding.clone());
+
+        match self.default_damping {
+            Some((lin, ang)) => {
+                let mut rb = handle.borrow_mut();
+
+                if rb.can_move() && na::is_zero(&rb.linear_damping()) && na::is_zero(&rb.angular_damping()) {
+                    rb.set_linear_damping(lin);
+                    rb.set_angular_damping(ang);
+                }
+            },
+            None => { }
+        }
+
+        // XXX: dont create the collision object here.
+        let co = {
+            let rb = handle.borrow();
+
+            if rb.can_move() && rb.shape_ref().get_type_id() == TypeId::of::<Mesh<Scalar, Point, Vect, Matrix>>() {
+                panic!("A dynamic body cannot have a mesh shape. It should be decomposed into a compound of convex pieces instead.")
+            }
+
+            CollisionObject::new_shared(rb.position().clone(), rb.shape().clone(), rb.collision_groups().clone())
+        };
+
+        {
+            let mut rb = handle.borrow_mut();
+            let uid    = rb.uid();
+
+            if uid == 0 || self.uids.contains(&uid) {
+                rb.set_uid(self.next_uid);
+                self.next_uid = self.next_uid + 1;
+            }
+            else if uid >= self.next_uid {
+                // A restored body: the identifiers given from now on must not collide with it.
+                self.next_uid = uid + 1;
+            }
+
+            let _ = self.uids.insert(rb.uid());
+        }
+
+        self.bodies.insert(handle.deref() as *const RefCell<RigidBody> as uint, handle.clone());
+        self.cworld.add(handle.clone(), co);
+        self.events.push(WorldEvent::BodyAdded(handle));
+    }
+
+    /// The buffer of the changes applied at the end of the current step.
+    ///
+    /// The world must not be modified from the signal handlers since they are called while it is
+    /// being stepped. They should keep a clone of this buffer instead, and record their changes
+    /// into it. See `WorldCommands` for the operations which are deferred this way.
+    pub fn commands(&self) -> Rc<RefCell<WorldCommands>> {
+        self.commands.clone()
+    }
+
+    /// Applies right away the changes recorded by the buffer returned by `commands`.
+    ///
+    /// This is done automatically at the end of each step. The commands are applied in the order
+    /// they were recorded.
+    pub fn apply_commands(&mut self) {
+        let commands = self.commands.borrow_mut().take();
+
+        for command in commands.into_iter() {
+            match command {
+                WorldCommand::AddBody(handle) => self.insert_body(handle),
+                WorldCommand::RemoveBody(handle) => {
+                    // The body may have been removed by an earlier command.
+                    if self.bodies.find(&(handle.deref() as *const RefCell<RigidBody> as uint)).is_some() {
+                        self.remove_body(&handle)
+                    }
+                },
+                WorldCommand::AddJoint(joint) => {
+                    match joint {
+                        Constraint::BallInSocket(j) => self.joints.add_ball_in_socket(j, &mut self.sleep),
+                        Constraint::Fixed(j)        => self.joints.add_fixed(j, &mut self.sleep),
+                        Constraint::Distance(j)     => self.joints.add_distance(j, &mut self.sleep),
+                        Constraint::Grab(j)         => self.joints.add_grab(j, &mut self.sleep),
+                        Constraint::RBRB(_, _, _)   => panic!("A contact cannot be added as a joint.")
+                    }
+                },
+                WorldCommand::RemoveJoint(joint) => self.remove_joint(&joint),
+                WorldCommand::ApplyImpulse(handle, impulse) => {
+                    let mut rb = handle.borrow_mut();
+
+                    if rb.can_move() {
+                        let lv = rb.lin_vel() + impulse * rb.inv_mass();
+
+                        rb.set_lin_vel(lv)
+                    }
+                },
+                WorldCommand::ApplyAngularImpulse(handle, impulse) => {
+                    let mut rb = handle.borrow_mut();
+
+                    if rb.can_move() {
+                        let av = rb.ang_vel() + *rb.inv_inertia() * impulse;
+
+                        rb.set_ang_vel(av)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replaces the vertices of a static body with a `Mesh` shape.
+    ///
+    /// The mesh topology (the index buffer) is kept and `vertices` must have as many elements as
+    /// the original vertex buffer. The new shape replaces the old one atomically: contacts with the
+    /// old geometry are discarded and every body touching the mesh is woken up. A
+    /// `BodyShapeUpdated` event is then emitted.
+    pub fn update_mesh_vertices(&mut self, body: &RigidBodyHandle, vertices: &[Point]) {
+        let new_shape = {
+            let rb = body.borrow();
+
+            assert!(!rb.can_move(), "Only the vertices of a static mesh can be updated.");
+
+            let shape = rb.shape_ref();
+
+            if shape.get_type_id() != TypeId::of::<Mesh<Scalar, Point, Vect, Matrix>>() {
+                panic!("The shape of the body to update must be a mesh.")
+            }
+
+            let mesh = shape.downcast_ref::<Mesh<Scalar, Point, Vect, Matrix>>().unwrap();
+
+            assert!(mesh.vertices().len() == vertices.len(),
+                    "The number of vertices of a mesh cannot be modified.");
+
+            let new_mesh = Mesh::new(Arc::new(vertices.to_vec()),
+                                     mesh.indices().clone(),
+                                     mesh.uvs().clone(),
+                                     mesh.normals().clone());
+
+            Arc::new(box new_mesh as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>)
+        };
+
+        self.set_body_shape(body, new_shape);
+        self.contact_generators.forget(body);
+        self.events.push(WorldEvent::BodyShapeUpdated(body.clone()));
+    }
+
+    /// Replaces the control points of a static body with a `BezierSurface` shape.
+    ///
+    /// `control_points` must have as many elements as the original control points, with the same
+    /// layout. As for `update_mesh_vertices`, the contacts with the old surface are discarded and
+    /// the bodies touching the old or the new surface are woken up. A `BodyShapeUpdated` event is
+    /// then emitted so that the graphics can tessellate the new surface. This is cheap enough to
+    /// be done at each step for small patches, e.g., to animate a flag.
+    pub fn update_bezier_control_points(&mut self, body: &RigidBodyHandle, control_points: &[Point]) {
+        let new_shape = {
+            let rb = body.borrow();
+
+            assert!(!rb.can_move(), "Only the control points of a static Bezier surface can be updated.");
+
+            let shape = rb.shape_ref();
+
+            if shape.get_type_id() != TypeId::of::<BezierSurface<Point>>() {
+                panic!("The shape of the body to update must be a Bezier surface.")
+            }
+
+            let surface = shape.downcast_ref::<BezierSurface<Point>>().unwrap();
+
+            assert!(surface.control_points().len() == control_points.len(),
+                    "The number of control points of a Bezier surface cannot be modified.");
+
+            let new_surface = BezierSurface::new(control_points.to_vec(), surface.nupoints(), surface.nvpoints());
+
+            Arc::new(box new_surface as Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>)
+        };
+
+        self.set_body_shape(body, new_shape);
+        self.contact_generators.forget(body);
+        self.events.push(WorldEvent::BodyShapeUpdated(body.clone()));
+    }
+
+    /// Scales uniformly the shape of `body` by the factor `s`, together with its mass properties.
+    ///
+    /// The shape is scaled wrt. the origin of the body, which does not move. The density of the
+    /// body is kept: its mass and angular inertia are scaled accordingly. The margins and the
+    /// velocities are not modified. As when a mesh is updated, contacts are discarded and the
+    /// bodies touching the old or the new shape are woken up. A `BodyScaled` event is then
+    /// emitted so that the graphics can be rescaled as well.
+    ///
+    /// An error is returned, and the body left unchanged, if `s` is not strictly positive or if
+    /// some part of the shape cannot be scaled. See `utils::scaled_shape` for the supported
+    /// shapes.
+    pub fn scale_body(&mut self, body: &RigidBodyHandle, s: Scalar) -> Result<(), String> {
+        let shape = try!(scaled_shape(body.borrow().shape_ref(), s.clone()));
+
+        body.borrow_mut().scale_mass_properties(s.clone());
+        self.set_body_shape(body, shape);
+        self.contact_generators.forget(body);
+        self.events.push(WorldEvent::BodyScaled(body.clone(), s));
+
+        Ok(())
+    }
+
+    /// Replaces the shape of `body` and updates the collision world accordingly.
+    ///
+    /// Bodies touching either the old or the new shape are woken up.
+    fn set_body_shape(&mut self,
+                      body:  &RigidBodyHandle,
+                      shape: Arc<Box<Shape<Scalar, Point, Vect, Matrix> + Send + Sync>>) {
+        let old_aabb = body.bounding_volume();
+
+        body.borrow_mut().set_shape(shape);
+
+        let new_aabb = body.bounding_volume();
+
+        // Removing the collision object discards every contact with the old geometry.
+        self.cworld.remove(body);
+
+        let co = {
+            let rb = body.borrow();
+
+            CollisionObject::new_shared(rb.position().clone(), rb.shape().clone(), rb.collision_groups().clone())
+        };
+
+        self.cworld.add(body.clone(), co);
+
+        let sleep = &mut self.sleep;
+        self.cworld.interferences_with_aabb(&old_aabb, |b| sleep.will_activate(b));
+        self.cworld.interferences_with_aabb(&new_aabb, |b| sleep.will_activate(b));
+    }
+
+    /// Removes all the rigid bodies of the physics world, together with their joints.
+    ///
+    /// A `BodyRemoved` event is emitted for each body. The settings of the world, its force
+    /// generators and its modifier volumes are kept, and the unique identifiers of the removed
+    /// bodies are not given again.
+    pub fn clear(&mut self) {
+        let bodies: Vec<RigidBodyHandle> = self.bodies().map(|b| b.clone()).collect();
+
+        for b in bodies.iter() {
+            self.remove_body(b)
+        }
+    }
+
+    /// Remove a rigid body from the physics world.
+    ///
+    /// Every joint attached to `b` is removed as well. Use `detach_joints` before removing the body
+    /// to keep them attached to the ground instead.
+    pub fn remove_body(&mut self, b: &RigidBodyHandle) {
+        // The bodies resting on `b` must fall once it is gone, even if it was static.
+        self.sleep.will_activate_neighbors(&mut self.cworld, b);
+        self.cworld.remove(b);
+        self.joints.remove(b, &mut self.sleep);
+
+        for articulation in self.articulations.iter() {
+            articulation.borrow_mut().remove_joints_of(b);
+        }
+
+        self.ccd.remove_ccd_from(b);
+        self.pairs.borrow_mut().remove(b);
+        self.contact_generators.forget(b);
+        remove_handle(&mut self.moved, b);
+        self.contact_infos.retain(|c| {
+            c.body1.deref() as *const RefCell<RigidBody> != b.deref() as *const RefCell<RigidBody> &&
+            c.body2.deref() as *const RefCell<RigidBody> != b.deref() as *const RefCell<RigidBody>
+        });
+        self.index_contacts();
+        if self.bodies.remove(&(b.deref() as *const RefCell<RigidBody> as uint)) {
+            let _ = self.uids.remove(&b.borrow().uid());
+            self.events.push(WorldEvent::BodyRemoved(b.clone()));
+        }
+
+        let _ = self.moving.remove(&(b.deref() as *const RefCell<RigidBody> as uint));
+        let _ = self.spinning.remove(&(b.deref() as *const RefCell<RigidBody> as uint));
+        let _ = self.stalled.remove(&(b.deref() as *const RefCell<RigidBody> as uint));
+
+        for group in self.groups.iter_mut() {
+            match *group {
+                Some((_, ref mut bodies)) => remove_handle(bodies, b),
+                None                      => { }
+            }
+        }
+
+        for volume in self.volumes.iter() {
+            let _ = volume.borrow_mut().remove_body(b);
+        }
+
+        // The overlaps with `b` end now, so that they are not reported again by the next step.
+        let key = b.deref() as *const RefCell<RigidBody>;
+
+        for &(ref sensor, ref other) in self.sensor_pairs.iter() {
+            if sensor.deref() as *const RefCell<RigidBody> == key || other.deref() as *const RefCell<RigidBody> == key {
+                let _ = self.sensor_overlaps.remove(&pair_key(sensor, other));
+                self.events.push(WorldEvent::SensorLeft(sensor.clone(), other.clone()));
+            }
+        }
+
+        self.sensor_pairs.retain(|&(ref sensor, ref other)| {
+            sensor.deref() as *const RefCell<RigidBody> != key && other.deref() as *const RefCell<RigidBody> != key
+        });
+
+        // The address of `b` may be reused by a body added later.
+        remove_pairs_with(&mut self.disabled_pairs, key as uint);
+        remove_pairs_with(&mut self.one_way, key as uint);
+        remove_pairs_with(&mut self.prev_one_way, key as uint);
+
+        b.borrow_mut().delete();
+    }
+
+    /// Creates an empty group of bodies named `name`.
+    ///
+    /// Several groups may have the same name, `find_group` then returns the first one created.
+    pub fn create_group(&mut self, name: &str) -> GroupHandle {
+        self.groups.push(Some((name.to_string(), Vec::new())));
+
+        GroupHandle::new(self.groups.len() - 1)
+    }
+
+    /// The group named `name`, if any.
+    pub fn find_group(&self, name: &str) -> Option<GroupHandle> {
+        for (i, group) in self.groups.iter().enumerate() {
+            match *group {
+                Some((ref n, _)) if n.as_slice() == name => return Some(GroupHandle::new(i)),
+                _ => { }
+            }
+        }
+
+        None
+    }
+
+    /// The name of `group`.
+    pub fn group_name(&self, group: &GroupHandle) -> &str {
+        self.group(group).ref0().as_slice()
+    }
+
+    /// The bodies of `group`, in the order they were added.
+    pub fn group_bodies(&self, group: &GroupHandle) -> &[RigidBodyHandle] {
+        self.group(group).ref1().as_slice()
+    }
+
+    /// Adds `body` to `group`. Nothing happens if it is already part of it.
+    ///
+    /// The body is removed from all its groups when it is removed from the world.
+    pub fn add_to_group(&mut self, group: &GroupHandle, body: &RigidBodyHandle) {
+        let bodies = self.group_mut(group).mut1();
+
+        if !bodies.iter().any(|b| b.deref() as *const RefCell<RigidBody> == body.deref() as *const RefCell<RigidBody>) {
+            bodies.push(body.clone())
+        }
+    }
+
+    /// Removes `body` from `group`, without removing it from the world.
+    pub fn remove_from_group(&mut self, group: &GroupHandle, body: &RigidBodyHandle) {
+        remove_handle(self.group_mut(group).mut1(), body)
+    }
+
+    /// Calls `f` on every body of `group`.
+    pub fn for_group(&mut self, group: &GroupHandle, f: |&RigidBodyHandle| -> ()) {
+        for b in self.group(group).ref1().iter() {
+            f(b)
+        }
+    }
+
+    /// Wakes up every body of `group`.
+    pub fn wake_group(&mut self, group: &GroupHandle) {
+        let sleep = &mut self.sleep;
+
+        match self.groups[group.id()] {
+            Some((_, ref bodies)) => {
+                for b in bodies.iter() {
+                    sleep.will_activate(b)
+                }
+            },
+            None => panic!("This group has been removed.")
+        }
+    }
+
+    /// Removes from the world every body of `group`, and the group itself.
+    pub fn remove_group(&mut self, group: &GroupHandle) {
+        let (_, bodies) = self.groups[group.id()].take().expect("This group has already been removed.");
+
+        for b in bodies.iter() {
+            self.remove_body(b)
+        }
+    }
+
+    fn group(&self, group: &GroupHandle) -> &(String, Vec<RigidBodyHandle>) {
+        self.groups[group.id()].as_ref().expect("This group has been removed.")
+    }
+
+    fn group_mut(&mut self, group: &GroupHandle) -> &mut (String, Vec<RigidBodyHandle>) {
+        self.groups[group.id()].as_mut().expect("This group has been removed.")
+    }
+
+    /// Gets a mutable reference to the force generator.
+    pub fn forces_generator(&mut self) -> &mut BodyForceGenerator {
+        &mut self.forces
+    }
+
+    /// Adds a force generator to the world.
+    ///
+    /// The returned handle can be used to modify the force generator between two steps.
+    pub fn add_force_generator<G: ForceGenerator + 'static>(&mut self, generator: G) -> Rc<RefCell<G>> {
+        let res = Rc::new(RefCell::new(generator));
+        let key = res.deref() as *const RefCell<G> as uint;
+
+        self.generators.push((key, box res.clone() as Box<ForceGenerator + 'static>));
+
+        res
+    }
+
+    /// Removes a force generator from the world.
+    pub fn remove_force_generator<G: ForceGenerator + 'static>(&mut self, generator: &Rc<RefCell<G>>) {
+        let key = generator.deref() as *const RefCell<G> as uint;
+
+        self.generators.retain(|&(k, _)| k != key)
+    }
+
+    /// The pairs of bodies overlapping at the end of the last step, where the first body of each
+    /// pair is a sensor.
+    #[inline]
+    pub fn sensor_overlaps(&self) -> &[(RigidBodyHandle, RigidBodyHandle)] {
+        self.sensor_pairs.as_slice()
+    }
+
+    /// Adds a modifier volume to the world.
+    ///
+    /// The bodies inside of it are found, and modified, from the next step on.
+    pub fn add_modifier_volume(&mut self, volume: ModifierVolume) -> ModifierVolumeHandle {
+        let handle = Rc::new(RefCell::new(volume));
+
+        self.volumes.push(handle.clone());
+
+        handle
+    }
+
+    /// Removes a modifier volume from the world.
+    ///
+    /// The bodies inside of it are woken up, and a `VolumeLeft` event is emitted for each of them.
+    pub fn remove_modifier_volume(&mut self, volume: &ModifierVolumeHandle) {
+        let key = volume.deref() as *const RefCell<ModifierVolume>;
+
+        self.volumes.retain(|v| v.deref() as *const RefCell<ModifierVolume> != key);
+
+        let (_, left) = volume.borrow_mut().update_inside(Vec::new());
+
+        for b in left.into_iter() {
+            self.sleep.activate_now(&b, &mut self.events);
+            self.events.push(WorldEvent::VolumeLeft(volume.clone(), b));
+        }
+    }
+
+    /// The modifier volumes of this world.
+    #[inline]
+    pub fn modifier_volumes(&self) -> &[ModifierVolumeHandle] {
+        self.volumes.as_slice()
+    }
+
+    /// The maximum angle, in radians, a body can rotate by during one step.
+    pub fn max_angular_step(&self) -> Option<Scalar> {
+        self.integrator.max_angular_step()
+    }
+
+    /// Sets the maximum angle, in radians, a body can rotate by during one step.
+    ///
+    /// The angular velocity of the bodies spinning faster is clamped, which keeps the simulation
+    /// stable when some bodies get extreme angular velocities. Disabled by default.
+    pub fn set_max_angular_step(&mut self, max: Option<Scalar>) {
+        self.integrator.set_max_angular_step(max.clone());
+        self.verlet.set_max_angular_step(max)
+    }
+
+    /// The integration scheme used to update the bodies.
+    pub fn integrator_kind(&self) -> IntegratorKind {
+        self.integrator_kind.clone()
+    }
+
+    /// Sets the integration scheme used to update the bodies.
+    ///
+    /// Defaults to `IntegratorKind::SymplecticEuler`. Both the linear and the angular motions of
+    /// the bodies use the same scheme.
+    pub fn set_integrator(&mut self, kind: IntegratorKind) {
+        if kind != self.integrator_kind {
+            // The accelerations remembered by the Verlet integrator are stale.
+            for b in self.bodies.elements().iter() {
+                b.value.borrow_mut().set_integrated_acc(None);
+            }
+        }
+
+        self.integrator_kind = kind
+    }
+
+    /// Gets a mutable reference to the semi-implicit Euler integrator.
+    ///
+    /// It is used by the steps unless another one is selected with `set_integrator`.
+    pub fn integrator(&mut self) -> &mut BodySmpEulerIntegrator {
+        &mut self.integrator
+    }
+
+    /// Gets a mutable reference to the collision detector.
+    pub fn collision_world(&mut self) -> &mut RigidBodyCollisionWorld {
+        &mut self.cworld
+    }
+
+    /// Gets a mutable reference to the CCD manager.
+    pub fn ccd_manager(&mut self) -> &mut TranslationalCCDMotionClamping {
+        &mut self.ccd
+    }
+
+    /// Gets a mutable reference to the joint manager.
+    pub fn joint_manager(&mut self) -> &mut JointManager {
+        &mut self.joints
+    }
+
+    /// Gets a mutable reference to the constraint solver.
+    pub fn constraints_solver(&mut self) -> &mut AccumulatedImpulseSolver {
+        &mut self.solver
+    }
+
+    /// Sets the minimum normal impulse a contact must receive to emit an impact event.
+    ///
+    /// Only contacts with bodies approaching each other emit impact events, so resting contacts never
+    /// do. Impact events are disabled by default.
+    pub fn set_impact_event_threshold(&mut self, threshold: Scalar) {
+        self.solver.set_impact_threshold(threshold)
+    }
+
+    /// The maximum velocity the penetration correction may inject into a contact.
+    #[inline]
+    pub fn max_correction_velocity(&self) -> Scalar {
+        self.solver.max_correction_velocity()
+    }
+
+    /// Sets the maximum velocity the penetration correction may inject into a contact.
+    ///
+    /// This keeps the bodies created deeply overlapping from being catapulted apart, e.g., with a
+    /// limit of one unit per second. Their remaining penetration is resolved by the position
+    /// correction over the next steps. The velocity is not limited by default.
+    #[inline]
+    pub fn set_max_correction_velocity(&mut self, max_vel: Scalar) {
+        self.solver.set_max_correction_velocity(max_vel)
+    }
+
+    /// Whether the solver computes the residuals reported by `step_statistics`.
+    pub fn solver_residuals_enabled(&self) -> bool {
+        self.solver.residuals_enabled()
+    }
+
+    /// Enables or disables the computation of the solver residuals reported by `step_statistics`.
+    ///
+    /// A large residual left on the contacts makes the stacks spongy. If it decreases when the
+    /// number of iterations of the solver is increased, more iterations are needed. Otherwise, some
+    /// constraints conflict, e.g., two joints pulling a body toward different places. This costs
+    /// one more pass over the constraints and is disabled by default.
+    pub fn set_solver_residuals_enabled(&mut self, enabled: bool) {
+        self.solver.set_residuals_enabled(enabled)
+    }
+
+    /// The solver residual above which a warning is logged, and the number of consecutive steps it
+    /// must be exceeded for, if any.
+    pub fn residual_warning(&self) -> Option<(Scalar, uint)> {
+        self.residual_warning.clone()
+    }
+
+    /// Logs a warning when the solver residual exceeds `threshold` for `num_steps` consecutive
+    /// steps.
+    ///
+    /// The warning names the step and the worst contact or joint, by the addresses of its bodies
+    /// or of its joint. It is written to the standard error in debug mode only, and at most once
+    /// every 100 steps. This enables the solver residuals, see `set_solver_residuals_enabled`.
+    pub fn set_residual_warning(&mut self, threshold: Scalar, num_steps: uint) {
+        assert!(threshold >= na::zero(), "The residual threshold must not be negative.");
+        assert!(num_steps > 0, "The residual warning needs at least one step.");
+
+        self.residual_warning = Some((threshold, num_steps));
+        self.residual_streak  = 0;
+        self.solver.set_residuals_enabled(true)
+    }
+
+    /// Disables the residual warnings.
+    ///
+    /// The solver residuals are still computed if they were enabled.
+    pub fn clear_residual_warning(&mut self) {
+        self.residual_warning = None
+    }
+
+    /// The fraction of the motion of the bodies during one step used to extend their bounding volumes.
+    pub fn aabb_motion_prediction(&self) -> Scalar {
+        self.motion_prediction.clone()
+    }
+
+    /// Sets the fraction of the motion of the bodies during one step used to extend their bounding
+    /// volumes.
+    ///
+    /// The bounding volume of each moving body is extended along `factor * velocity * dt` so that
+    /// the broad phase finds the pairs before the shapes actually touch. Set this to zero to
+    /// disable the extension. Defaults to one.
+    pub fn set_aabb_motion_prediction(&mut self, factor: Scalar) {
+        assert!(factor >= na::zero(), "The motion prediction factor must be positive.");
+        self.motion_prediction = factor
+    }
+
+    /// The maximum length of the extension of the bounding volumes due to the motion prediction.
+    pub fn max_aabb_motion_prediction(&self) -> Scalar {
+        self.max_motion_prediction.clone()
+    }
+
+    /// Sets the maximum length of the extension of the bounding volumes due to the motion
+    /// prediction.
+    ///
+    /// This prevents a very fast body from being paired with most of the scene. Such bodies should
+    /// use continuous collision detection instead, see `add_ccd_to`. Defaults to one.
+    pub fn set_max_aabb_motion_prediction(&mut self, max: Scalar) {
+        assert!(max >= na::zero(), "The maximum motion prediction must be positive.");
+        self.max_motion_prediction = max
+    }
+
+    /// The padding added to the bounding volumes of the bodies for the broad phase.
+    pub fn broad_phase_padding(&self) -> Scalar {
+        self.broad_phase_padding.clone()
+    }
+
+    /// Sets the padding added to the bounding volumes of the bodies for the broad phase.
+    ///
+    /// The broad phase creates the pairs of bodies which padded bounding volumes overlap, so a
+    /// larger padding finds the pairs earlier at the cost of more pairs given to the narrow phase.
+    /// This does not change the contacts, which depend on the margins of the bodies only, see
+    /// `RigidBody::set_margin`. The bodies overriding it with `RigidBody::set_broad_phase_padding`
+    /// keep their own padding, the others are woken up for their bounding volume to be updated.
+    /// Defaults to zero: the bounding volumes are then only enlarged by the margins and the motion
+    /// prediction.
+    pub fn set_broad_phase_padding(&mut self, padding: Scalar) {
+        assert!(padding >= na::zero(), "The broad phase padding must not be negative.");
+
+        self.broad_phase_padding = padding;
+
+        for b in self.bodies.elements().iter() {
+            b.value.borrow_mut().set_default_broad_phase_padding(padding.clone())
+        }
+    }
+
+    /// The change of velocity above which the warm-starting of the contacts of a body is reset.
+    pub fn warm_start_reset_threshold(&self) -> Option<Scalar> {
+        self.warm_start_reset.clone()
+    }
+
+    /// Sets the change of velocity above which the warm-starting of the contacts of a body is
+    /// reset.
+    ///
+    /// The contacts are solved starting from the impulses of the previous step. When the velocity
+    /// of a body is set between two steps, those impulses no longer match its motion and may make
+    /// the bodies around it pop. If the sum of the norms of the changes of its linear and angular
+    /// velocities exceeds `threshold`, the cached impulses of its contacts are discarded instead.
+    /// Moving a body always exceeds the threshold. Set this to `None` to never discard them.
+    /// Defaults to zero: any change resets the warm-starting.
+    pub fn set_warm_start_reset_threshold(&mut self, threshold: Option<Scalar>) {
+        self.warm_start_reset = threshold
+    }
+
+    /// The speed under which the velocities of a body left alone are set to zero.
+    pub fn drift_threshold(&self) -> Option<Scalar> {
+        self.drift_threshold.clone()
+    }
+
+    /// Sets the speed under which the velocities of a body left alone are set to zero.
+    ///
+    /// Without gravity, nothing damps the tiny velocities left by the solver, so the bodies drift
+    /// slowly forever. At the end of each step, the linear and angular velocities of an active
+    /// body without contacts, joints nor acceleration are zeroed if their norms are smaller than
+    /// `threshold`, e.g., `1.0e-5`. Set this to `None` to keep them untouched, e.g., to check the
+    /// conservation of the momentum exactly. Defaults to `None`.
+    pub fn set_drift_threshold(&mut self, threshold: Option<Scalar>) {
+        match threshold {
+            Some(ref t) => assert!(*t >= na::zero(), "The drift threshold must not be negative."),
+            None        => { }
+        }
+
+        self.drift_threshold = threshold
+    }
+
+    /// The speeds under which a body stops moving and over which it starts moving, if the motion
+    /// events are enabled.
+    pub fn motion_thresholds(&self) -> Option<(Scalar, Scalar)> {
+        self.motion_thresholds.clone()
+    }
+
+    /// Enables the `BodyStartedMoving` and `BodyStoppedMoving` events.
+    ///
+    /// At the end of each step, a dynamic body at rest is reported as moving once the speed of its
+    /// center of mass exceeds `start_speed`, and a moving body is reported at rest once its speed
+    /// goes under `stop_speed`, or when it falls asleep. The gap between both speeds prevents a
+    /// body hovering around one of them from emitting an event at each step. Those events are
+    /// unrelated to the activation of the bodies by the solver: a body can be awake but at rest.
+    /// Every dynamic body is initially considered at rest.
+    pub fn set_motion_thresholds(&mut self, stop_speed: Scalar, start_speed: Scalar) {
+        assert!(stop_speed >= na::zero(), "The stop speed must not be negative.");
+        assert!(stop_speed < start_speed, "The stop speed must be smaller than the start speed.");
 
-        self.joints.update(&mut self.sleep);
-        self.sleep.update(&mut self.cworld, &self.joints, &self.bodies);
+        self.motion_thresholds = Some((stop_speed, start_speed))
+    }
 
-        // XXX: use `self.collector` instead to avoid allocation.
-        let mut collector = Vec::new();
+    /// Disables the motion events. Every body is considered at rest again.
+    pub fn clear_motion_thresholds(&mut self) {
+        self.motion_thresholds = None;
+        self.moving.clear();
+    }
 
-        self.cworld.contacts(|b1, b2, c| {
-            if b1.borrow().is_active() || b2.borrow().is_active() {
-                let m1 = b1.borrow().margin();
-                let m2 = b2.borrow().margin();
+    /// Moves a body to the position `m` right away, keeping its velocities.
+    ///
+    /// Unlike `RigidBody::set_transformation`, this also discards the impulses cached for its
+    /// contacts, which belong to its previous position, and its continuous collision detection
+    /// does not sweep it along the way. Its contacts are computed again at its new position by the
+    /// next step. A `BodyTeleported` event is pushed.
+    pub fn teleport(&mut self, body: &RigidBodyHandle, m: Matrix) {
+        body.borrow_mut().set_transformation(m);
+        self.solver.reset_warm_start_of(body);
+        self.ccd.reset_motion_of(body);
+        self.events.push(WorldEvent::BodyTeleported(body.clone()));
+    }
 
-                let mut c = c.clone();
-                c.depth = c.depth + m1 + m2;
+    /// The bounds of the `x` coordinate the dynamic bodies are wrapped around, if any.
+    pub fn wrap_bounds(&self) -> Option<(Scalar, Scalar)> {
+        self.wrap_bounds.clone()
+    }
 
-                collector.push(Constraint::RBRB(b1.clone(), b2.clone(), c));
-            }
-        });
+    /// Wraps the dynamic bodies around the given bounds of the `x` coordinate.
+    ///
+    /// At the end of each step, a dynamic body which origin went beyond `x_max` is teleported by
+    /// `x_min - x_max` along the `x` axis, and conversely for `x_min`, as with `teleport`. Its
+    /// contacts on the old side are dropped, and it keeps its velocities.
+    pub fn set_wrap_bounds(&mut self, x_min: Scalar, x_max: Scalar) {
+        assert!(x_min < x_max, "The lower wrap bound must be smaller than the upper one.");
 
-        self.joints.interferences(&mut collector);
+        self.wrap_bounds = Some((x_min, x_max))
+    }
 
-        self.solver.solve(dt, collector.as_slice());
+    /// Stops wrapping the dynamic bodies.
+    pub fn clear_wrap_bounds(&mut self) {
+        self.wrap_bounds = None
+    }
 
-        collector.clear();
+    /// The maximum number of contact points solved between two bodies.
+    pub fn max_contacts_per_pair(&self) -> uint {
+        self.reducer.max_contacts()
     }
 
-    /// Adds a rigid body to the physics world.
-    pub fn add_body(&mut self, rb: RigidBody) -> RigidBodyHandle {
-        // XXX: dont create the collision object here.
-        let co = CollisionObject::new_shared(
-            rb.position().clone(),
-            rb.shape().clone(),
-            rb.collision_groups().clone());
+    /// Sets the maximum number of contact points solved between two bodies.
+    ///
+    /// When a pair has more contacts, e.g., a box lying on a finely tessellated mesh, only the
+    /// deepest one and the ones most spread out around it are kept. This makes the solver faster
+    /// without changing the resting behavior much. Defaults to 4 in 3D, and 2 in 2D. `n` must not
+    /// be zero.
+    pub fn set_max_contacts_per_pair(&mut self, n: uint) {
+        self.reducer.set_max_contacts(n)
+    }
 
-        let handle = Rc::new(RefCell::new(rb));
+    /// Whether the shock propagation is enabled.
+    pub fn shock_propagation(&self) -> bool {
+        self.solver.shock_propagation()
+    }
 
-        self.bodies.insert(handle.deref() as *const RefCell<RigidBody> as uint, handle.clone());
-        self.cworld.add(handle.clone(), co);
+    /// Enables or disables the shock propagation.
+    ///
+    /// This makes stacks stable: a heavy body resting on a light one no longer sinks into it, and
+    /// tall stacks and pyramids do not sag even with few solver iterations. Stacks are detected
+    /// from the contacts with the static bodies, so this works with any gravity. It trades the
+    /// accuracy of the momentum transfers for stability, and is disabled by default. The contact
+    /// impulses reported by the world include the impulses of this pass.
+    pub fn set_shock_propagation(&mut self, enabled: bool) {
+        self.solver.set_shock_propagation(enabled)
+    }
 
-        handle
+    /// The seed of the constraint shuffling, if it is enabled.
+    pub fn constraint_shuffling(&self) -> Option<u64> {
+        self.solver.constraint_shuffling()
     }
 
-    /// Remove a rigid body from the physics world.
-    pub fn remove_body(&mut self, b: &RigidBodyHandle) {
-        self.cworld.remove(b);
-        self.joints.remove(b, &mut self.sleep);
-        self.ccd.remove_ccd_from(b);
-        self.bodies.remove(&(b.deref() as *const RefCell<RigidBody> as uint));
-        b.borrow_mut().delete();
+    /// Enables or disables the shuffling of the constraint ordering between solver iterations.
+    ///
+    /// This removes the drift of symmetric scenes toward one side, e.g., when identical bodies are
+    /// dropped at mirrored positions. The orderings are drawn from a generator seeded by `seed`, so
+    /// the simulation stays deterministic. It is disabled by default.
+    pub fn set_constraint_shuffling(&mut self, enabled: bool, seed: u64) {
+        self.solver.set_constraint_shuffling(enabled, seed)
     }
 
-    /// Gets a mutable reference to the force generator.
-    pub fn forces_generator(&mut self) -> &mut BodyForceGenerator {
-        &mut self.forces
+    /// The spring-damper of the contacts between bodies without their own, if any.
+    pub fn default_contact_spring(&self) -> Option<SpringDamper> {
+        self.solver.default_contact_spring()
     }
 
-    /// Gets a mutable reference to the position and orientation integrator.
-    pub fn integrator(&mut self) -> &mut BodySmpEulerIntegrator {
-        &mut self.integrator
+    /// Sets the spring-damper of the contacts between bodies without their own.
+    ///
+    /// See `RigidBody::set_contact_spring`. Defaults to `None`: the contacts are rigid.
+    pub fn set_default_contact_spring(&mut self, spring: Option<SpringDamper>) {
+        self.solver.set_default_contact_spring(spring)
     }
 
-    /// Gets a mutable reference to the collision detector.
-    pub fn collision_world(&mut self) -> &mut RigidBodyCollisionWorld {
-        &mut self.cworld
+    /// The spring-damper of the joints without their own, if any.
+    pub fn default_joint_spring(&self) -> Option<SpringDamper> {
+        self.solver.default_joint_spring()
     }
 
-    /// Gets a mutable reference to the CCD manager.
-    pub fn ccd_manager(&mut self) -> &mut TranslationalCCDMotionClamping {
-        &mut self.ccd
+    /// Sets the spring-damper of the joints without their own.
+    ///
+    /// Defaults to `None`: the joints are rigid, and their errors are corrected by the joint
+    /// correction factor of the solver.
+    pub fn set_default_joint_spring(&mut self, spring: Option<SpringDamper>) {
+        self.solver.set_default_joint_spring(spring)
     }
 
-    /// Gets a mutable reference to the joint manager.
-    pub fn joint_manager(&mut self) -> &mut JointManager {
-        &mut self.joints
+    /// The distance the bodies may move from the points where they started touching before their
+    /// friction anchors are reset.
+    pub fn friction_anchor_max_drift(&self) -> Option<Scalar> {
+        self.solver.friction_anchor_max_drift()
     }
 
-    /// Gets a mutable reference to the constraint solver.
-    pub fn constraints_solver(&mut self) -> &mut AccumulatedImpulseSolver {
-        &mut self.solver
+    /// Sets the distance the bodies may move from the points where they started touching before
+    /// their friction anchors are reset.
+    ///
+    /// Without anchors, a body resting on a slope slowly creeps downhill even when its friction
+    /// should hold it, because each step only cancels the current sliding velocity. The anchors
+    /// keep the contact points found when the contact appeared, and the friction pulls the drift
+    /// back toward them. The distance should be small compared to the size of the bodies, e.g., a
+    /// tenth of their contact margin. The anchors are disabled with `None`, the default.
+    pub fn set_friction_anchor_max_drift(&mut self, max_drift: Option<Scalar>) {
+        self.solver.set_friction_anchor_max_drift(max_drift)
+    }
+
+    /// Moves every event emitted since the last call to this method, or to `drain_timed_events`,
+    /// to `out`.
+    ///
+    /// Events are kept by the world until they are drained, in the order they were emitted. Body
+    /// events are always emitted, so this should be called regularly even if they are ignored.
+    pub fn drain_events(&mut self, out: &mut Vec<WorldEvent>) {
+        let events = mem::replace(&mut self.events, Vec::new());
+        self.event_stamps.clear();
+        out.extend(events.into_iter())
+    }
+
+    /// Moves every event emitted since the last call to this method, or to `drain_events`, to
+    /// `out`, together with the time at which they were emitted.
+    pub fn drain_timed_events(&mut self, out: &mut Vec<TimedEvent>) {
+        self.stamp_events();
+
+        let events = mem::replace(&mut self.events, Vec::new());
+        let stamps = mem::replace(&mut self.event_stamps, Vec::new());
+
+        for (event, (step, time)) in events.into_iter().zip(stamps.into_iter()) {
+            out.push(TimedEvent { step: step, time: time, event: event })
+        }
     }
 
     /// Sets the linear acceleration afecting every dynamic rigid body.
+    ///
+    /// This is ignored if a gravity field has been set with `set_gravity_field`.
     pub fn set_gravity(&mut self, gravity: Vect) {
         self.forces.set_lin_acc(gravity)
     }
 
+    /// Sets up the world for a game seen from above, e.g., pucks sliding on a table.
+    ///
+    /// This removes the gravity, and gives the linear damping `lin_damping` and the angular damping
+    /// `ang_damping` to the dynamic bodies added from now on, unless they already have their own,
+    /// see `RigidBody::set_linear_damping`. The friction with the ground can be simulated by adding
+    /// a `GroundFriction` force generator.
+    #[cfg(feature = "2d")]
+    pub fn set_top_down_defaults(&mut self, lin_damping: Scalar, ang_damping: Scalar) {
+        assert!(lin_damping >= na::zero() && ang_damping >= na::zero(), "The damping must not be negative.");
+
+        self.set_gravity(na::zero());
+        self.default_damping = Some((lin_damping, ang_damping))
+    }
+
+    /// The linear and angular damping given to the dynamic bodies added to the world, if any.
+    pub fn default_damping(&self) -> Option<(Scalar, Scalar)> {
+        self.default_damping.clone()
+    }
+
+    /// Stops giving a damping to the dynamic bodies added to the world.
+    ///
+    /// The bodies already added keep their damping.
+    pub fn clear_default_damping(&mut self) {
+        self.default_damping = None
+    }
+
+    /// Replaces the constant gravity by a gravity depending on the position.
+    ///
+    /// The field is evaluated at the center of mass of every active body at each step. Sleeping
+    /// bodies are not affected until they are woken up, so every body is woken up by this method.
+    pub fn set_gravity_field<F: GravityField + 'static>(&mut self, field: F) {
+        self.gravity = Some(box field as Box<GravityField + 'static>);
+        self.wake_up_all();
+    }
+
+    /// Removes the gravity field set by `set_gravity_field`.
+    ///
+    /// The constant gravity set by `set_gravity` is used again.
+    pub fn clear_gravity_field(&mut self) {
+        self.gravity = None;
+        self.wake_up_all();
+    }
+
+    fn wake_up_all(&mut self) {
+        for e in self.bodies.elements().iter() {
+            self.sleep.will_activate(&e.value)
+        }
+    }
+
     /// Sets the angular acceleration afecting every dynamic rigid body.
     pub fn set_angular_acceleration(&mut self, accel: Orientation) {
         self.forces.set_ang_acc(accel)
@@ -208,12 +2039,69 @@ impl World {
     }
 
     /// Gets every body intersected by a given ray.
+    ///
+    /// Use `RigidBody::sub_shape_hit_by_ray` to find which part of a compound or mesh was hit.
     pub fn interferences_with_ray(&mut self,
                                   ray: &Ray<Point, Vect>,
                                   f: |&RigidBodyHandle, RayIntersection<Scalar, Vect>| -> ()) {
         self.cworld.interferences_with_ray(ray, f)
     }
 
+    /// Every surface hit by `ray`, sorted by increasing time of impact.
+    ///
+    /// Hits farther than `max_toi` are ignored, and at most `max_hits` hits are returned. If
+    /// `one_per_body` is `true`, only the first surface of each body is reported. Otherwise, every
+    /// surface crossed by the ray is, e.g., both the entry and the exit of a box, or of every part
+    /// of a compound.
+    pub fn ray_hits(&mut self,
+                    ray:          &Ray<Point, Vect>,
+                    max_toi:      Option<Scalar>,
+                    max_hits:     Option<uint>,
+                    one_per_body: bool)
+                    -> Vec<RayHit> {
+        let mut hits = Vec::new();
+
+        self.cworld.interferences_with_ray(ray, |b, inter| {
+            if one_per_body {
+                let sub_shape = b.borrow().sub_shape_hit_by_ray(ray, inter.toi);
+
+                hits.push(RayHit {
+                    body:      b.clone(),
+                    toi:       inter.toi,
+                    normal:    inter.normal,
+                    sub_shape: sub_shape
+                })
+            }
+            else {
+                push_surfaces_hit_by_ray(b, ray, &inter, &mut hits)
+            }
+        });
+
+        match max_toi {
+            Some(max) => hits.retain(|h| h.toi <= max),
+            None      => { }
+        }
+
+        hits.sort_by(|a, b| a.toi.partial_cmp(&b.toi).unwrap_or(Ordering::Equal));
+
+        match max_hits {
+            Some(max) => hits.truncate(max),
+            None      => { }
+        }
+
+        hits
+    }
+
+    /// The earliest time of impact between two bodies, from now and up to `max_t`.
+    ///
+    /// Both bodies are assumed to keep their current linear and angular velocities, and their
+    /// shapes are dilated by their margins. This returns zero if they already touch each other, and
+    /// `None` if they do not touch before `max_t`. Nothing is modified, so this can be called at
+    /// any time between two steps.
+    pub fn time_of_impact(&self, b1: &RigidBodyHandle, b2: &RigidBodyHandle, max_t: Scalar) -> Option<Scalar> {
+        time_of_impact::time_of_impact(b1.borrow().deref(), b2.borrow().deref(), max_t)
+    }
+
     /// Gets every body that contain a specific point.
     pub fn interferences_with_point(&mut self, p: &Point, f: |&RigidBodyHandle| -> ()) {
         self.cworld.interferences_with_point(p, f)
@@ -225,11 +2113,17 @@ impl World {
     }
 
     /// Adds continuous collision detection to the given rigid body.
+    ///
+    /// Its motion is also tested against the sensors, so that it cannot traverse one unnoticed,
+    /// see `WorldEvent`.
     pub fn add_ccd_to(&mut self, body: &RigidBodyHandle, motion_thresold: Scalar) {
         self.ccd.add_ccd_to(body.clone(), motion_thresold)
     }
 
     /// Adds a ball-in-socket joint to the world.
+    ///
+    /// The two bodies attached to the joint do not collide with each other, unless its collisions
+    /// are enabled with `set_collision_enabled`.
     pub fn add_ball_in_socket(&mut self, joint: BallInSocket) -> Rc<RefCell<BallInSocket>> {
         let res = Rc::new(RefCell::new(joint));
 
@@ -238,12 +2132,55 @@ impl World {
         res
     }
 
-    /// Removes a ball-in-socket joint from the world.
+    /// Removes a ball-in-socket joint from the world, and from the articulations it is part of.
     pub fn remove_ball_in_socket(&mut self, joint: &Rc<RefCell<BallInSocket>>) {
+        for articulation in self.articulations.iter() {
+            articulation.borrow_mut().remove_joint(joint);
+        }
+
         self.joints.remove_ball_in_socket(joint, &mut self.sleep)
     }
 
+    /// Adds an articulation to the world, for the gravity compensation of its joints.
+    ///
+    /// The joints of the articulation must be added to the world as well, e.g., with
+    /// `add_ball_in_socket_to`.
+    pub fn add_articulation(&mut self, articulation: Articulation) -> Rc<RefCell<Articulation>> {
+        let res = Rc::new(RefCell::new(articulation));
+
+        self.articulations.push(res.clone());
+
+        res
+    }
+
+    /// Removes an articulation from the world.
+    ///
+    /// Its joints are kept, but do not compensate gravity anymore.
+    pub fn remove_articulation(&mut self, articulation: &Rc<RefCell<Articulation>>) {
+        let key = articulation.deref() as *const RefCell<Articulation>;
+
+        self.articulations.retain(|a| a.deref() as *const RefCell<Articulation> != key)
+    }
+
+    /// Adds a ball-in-socket joint to the world, as part of `articulation`.
+    ///
+    /// The first anchor of the joint is the parent side, and the second anchor the child side,
+    /// see `Articulation`.
+    pub fn add_ball_in_socket_to(&mut self,
+                                 articulation: &Rc<RefCell<Articulation>>,
+                                 joint:        BallInSocket)
+                                 -> Rc<RefCell<BallInSocket>> {
+        let res = self.add_ball_in_socket(joint);
+
+        articulation.borrow_mut().add_joint(&res);
+
+        res
+    }
+
     /// Adds a fixed joint to the world.
+    ///
+    /// The two bodies attached to the joint do not collide with each other, unless its collisions
+    /// are enabled with `set_collision_enabled`.
     pub fn add_fixed(&mut self, joint: Fixed) -> Rc<RefCell<Fixed>> {
         let res = Rc::new(RefCell::new(joint));
 
@@ -257,17 +2194,83 @@ impl World {
         self.joints.remove_joint(joint, &mut self.sleep)
     }
 
+    /// Adds a distance joint to the world.
+    ///
+    /// The two bodies attached to the joint do not collide with each other, unless its collisions
+    /// are enabled with `set_collision_enabled`.
+    pub fn add_distance(&mut self, joint: Distance) -> Rc<RefCell<Distance>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_distance(res.clone(), &mut self.sleep);
+
+        res
+    }
+
+    /// Removes a distance joint from the world.
+    pub fn remove_distance(&mut self, joint: &Rc<RefCell<Distance>>) {
+        self.joints.remove_joint(joint, &mut self.sleep)
+    }
+
+    /// Adds a grab joint to the world.
+    ///
+    /// The grabbed body keeps colliding with the others while it is pulled toward the target.
+    pub fn add_grab(&mut self, joint: Grab) -> Rc<RefCell<Grab>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.joints.add_grab(res.clone(), &mut self.sleep);
+
+        res
+    }
+
+    /// Removes a grab joint from the world.
+    pub fn remove_grab(&mut self, joint: &Rc<RefCell<Grab>>) {
+        self.joints.remove_joint(joint, &mut self.sleep)
+    }
+
+    /// Removes a joint from the world.
+    ///
+    /// `joint` must be one of the joint variants of `Constraint`, e.g. as returned by `joints()`.
+    pub fn remove_joint(&mut self, joint: &Constraint) {
+        self.joints.remove_constraint(joint, &mut self.sleep)
+    }
+
+    /// The joints attached to a given rigid body.
+    pub fn joints_attached_to(&self, body: &RigidBodyHandle) -> &[Constraint] {
+        self.joints.joints_with_body(body).unwrap_or(&[])
+    }
+
+    /// Attaches to the ground every joint anchor attached to a given rigid body.
+    ///
+    /// The anchors keep their current global position. This is useful to keep the joints of a body
+    /// that is about to be removed.
+    pub fn detach_joints(&mut self, body: &RigidBodyHandle) {
+        self.joints.detach(body, &mut self.sleep)
+    }
+
+    /// An iterator visiting all joints on this world.
+    pub fn joints(&self) -> Joints {
+        self.joints.joints().elements().iter().map(|e| &e.value)
+    }
+
     /// Collects every interferences detected since the last update.
     pub fn interferences(&mut self, out: &mut Vec<Constraint>) {
         // FIXME: ugly.
-        self.cworld.contacts(|b1, b2, c| {
-            let m1 = b1.borrow().margin();
-            let m2 = b2.borrow().margin();
+        let one_way    = &self.one_way;
+        let generators = &mut self.contact_generators;
+        let joints     = &self.joints;
+        let disabled   = &self.disabled_pairs;
 
-            let mut c = c.clone();
-            c.depth = c.depth + m1 + m2;
+        self.cworld.contacts(|b1, b2, c| {
+            if !generators.overrides(b1, b2) && !joints.collision_disabled(b1, b2) &&
+               !disabled.contains(&pair_key(b1, b2)) {
+                push_interference(b1, b2, c, one_way, out)
+            }
+        });
 
-            out.push(Constraint::RBRB(b1.clone(), b2.clone(), c));
+        generators.generate(self.pairs.borrow().deref(), |b1, b2, c| {
+            if !joints.collision_disabled(b1, b2) && !disabled.contains(&pair_key(b1, b2)) {
+                push_interference(b1, b2, c, one_way, out)
+            }
         });
 
         self.joints.interferences(out);
@@ -278,6 +2281,119 @@ impl World {
         self.bodies.elements().iter().map(|e| &e.value)
     }
 
+    /// The body of this world with the unique identifier `uid`, if any.
+    ///
+    /// See `RigidBody::uid`. This visits all the bodies.
+    pub fn body_with_uid(&self, uid: u64) -> Option<RigidBodyHandle> {
+        self.bodies().find(|b| b.borrow().uid() == uid).map(|b| b.clone())
+    }
+
+    /// The union of the bounding boxes of the bodies with a bounded shape.
+    ///
+    /// The bounding boxes are those given by `RigidBody::aabb`, and the unbounded ones, e.g. of the
+    /// planes, are skipped. This is `None` if there is no body with a bounded shape.
+    pub fn aabb(&self) -> Option<AABB<Point>> {
+        let mut res: Option<AABB<Point>> = None;
+
+        for b in self.bodies() {
+            let aabb = b.borrow().aabb();
+
+            if is_bounded(&aabb) {
+                res = Some(match res {
+                    Some(res) => res.merged(&aabb),
+                    None      => aabb
+                })
+            }
+        }
+
+        res
+    }
+
+    /// Registers a contact generator for the pairs of shapes of types `t1` and `t2`.
+    ///
+    /// It replaces the default narrow phase for those pairs, in any order: when the first body of a
+    /// pair has a shape of type `t2`, the generator is given the shapes swapped and the contacts it
+    /// produces are flipped back. It replaces any generator previously registered for `t1` and
+    /// `t2`.
+    pub fn register_contact_generator(&mut self,
+                                      t1:        TypeId,
+                                      t2:        TypeId,
+                                      generator: Box<ContactGenerator + 'static>) {
+        self.contact_generators.register(t1, t2, generator)
+    }
+
+    /// The tolerances of the contact caching, if it is enabled.
+    pub fn contact_caching(&self) -> Option<(Scalar, Scalar)> {
+        self.contact_generators.caching()
+    }
+
+    /// Enables or disables the reuse of the contacts of the pairs handled by contact generators.
+    ///
+    /// When the pose of a body relative to the other one moved by less than `max_translation` and
+    /// rotated by less than `max_angle` since the contacts of a pair were generated, e.g., for a
+    /// platform moved slowly over some static geometry, those contacts are moved with the bodies
+    /// and their depth updated instead of calling the generator again. The generator is called
+    /// once this drift exceeds the tolerances. It is disabled by default.
+    ///
+    /// Only the pairs handled by the generators registered with `register_contact_generator` are
+    /// cached. The default narrow phase belongs to ncollide and cannot reuse the contacts of a
+    /// pair, so the other pairs, e.g., those of the bodies lying on a `Mesh` without any
+    /// generator for its shape type, are always detected from scratch. Registering a generator for
+    /// such pairs makes them cacheable, as done by the `platforms_cached` benchmark scene.
+    pub fn set_contact_caching(&mut self, caching: Option<(Scalar, Scalar)>) {
+        self.contact_generators.set_caching(caching)
+    }
+
+    /// Unregisters the contact generator for the pairs of shapes of types `t1` and `t2`.
+    ///
+    /// Those pairs are handled by the default narrow phase again.
+    pub fn unregister_contact_generator(&mut self, t1: TypeId, t2: TypeId) {
+        self.contact_generators.unregister(t1, t2)
+    }
+
+    /// Appends to `out` all the pairs of bodies currently tracked by the broad phase.
+    ///
+    /// Those are the candidates given to the narrow phase: their enlarged bounding volumes overlap
+    /// and they were not filtered out. This does not query the broad phase, so it is cheap enough
+    /// to be called at each frame for debugging.
+    pub fn broad_phase_pairs(&self, out: &mut Vec<(RigidBodyHandle, RigidBodyHandle)>) {
+        self.pairs.borrow().pairs(out)
+    }
+
+    /// Whether the broad phase currently tracks the pair made of `b1` and `b2`.
+    ///
+    /// If the bounding volumes of two bodies overlap while this returns `false`, the pair was
+    /// filtered out. If this returns `true` but the bodies have no contact, the narrow phase found
+    /// them apart.
+    pub fn pair_exists(&self, b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> bool {
+        self.pairs.borrow().contains(b1, b2)
+    }
+
+    /// Whether the contacts between `b1` and `b2` are solved, unless disabled by a joint.
+    pub fn collision_between(&self, b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> bool {
+        !self.disabled_pairs.contains(&pair_key(b1, b2))
+    }
+
+    /// Enables or disables the contacts between `b1` and `b2`.
+    ///
+    /// Unlike the collision groups, this filters a single pair of bodies, e.g., two limbs of a
+    /// ragdoll which shapes overlap around their joint. The pair is still tracked by the broad
+    /// phase, but its contacts are neither solved nor reported. Both bodies are woken up. The
+    /// collisions between two bodies are enabled by default.
+    pub fn set_collision_between(&mut self, b1: &RigidBodyHandle, b2: &RigidBodyHandle, enabled: bool) {
+        let key = pair_key(b1, b2);
+
+        if enabled {
+            let _ = self.disabled_pairs.remove(&key);
+        }
+        else {
+            let _ = self.disabled_pairs.insert(key);
+        }
+
+        self.sleep.will_activate(b1);
+        self.sleep.will_activate(b2);
+    }
+
     /// Registers a handler for proximity start/stop events.
     pub fn register_proximity_signal_handler<H>(&mut self, name: &str, handler: H)
         where H: ProximitySignalHandler<RigidBodyHandle> + 'static {
@@ -299,4 +2415,315 @@ impl World {
     pub fn unregister_contact_signal_handler(&mut self, name: &str) {
         self.cworld.unregister_contact_signal_handler(name)
     }
+
+    /// Registers a handler for joint removal events.
+    pub fn register_joint_signal_handler<H>(&mut self, name: &str, handler: H)
+        where H: JointSignalHandler + 'static {
+        self.joints.register_joint_signal_handler(name, handler)
+    }
+
+    /// Unregisters a handler for joint removal events.
+    pub fn unregister_joint_signal_handler(&mut self, name: &str) {
+        self.joints.unregister_joint_signal_handler(name)
+    }
+}
+
+// Displacements smaller than this are not reported by `World::moved_bodies`.
+static MOVED_EPSILON: f64 = 1.0e-5;
+
+/*
+ * One-way bodies.
+ */
+// Minimal dot product between a contact normal and the one-way normal for a contact to start.
+static ONE_WAY_TOLERANCE: f64 = 0.1;
+// Maximal penetration depth of a contact to start.
+static ONE_WAY_MAX_DEPTH: f64 = 0.25;
+
+fn pair_key(b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> (uint, uint) {
+    let k1 = b1.deref() as *const RefCell<RigidBody> as uint;
+    let k2 = b2.deref() as *const RefCell<RigidBody> as uint;
+
+    if k1 < k2 { (k1, k2) } else { (k2, k1) }
+}
+
+// The maximum number of surfaces of a single body reported by `World::ray_hits`.
+static MAX_SURFACES_PER_BODY: uint = 64;
+
+// Pushes to `out` every surface of `body` crossed by `ray`, starting with the hit `first`.
+fn push_surfaces_hit_by_ray(body:  &RigidBodyHandle,
+                            ray:   &Ray<Point, Vect>,
+                            first: &RayIntersection<Scalar, Vect>,
+                            out:   &mut Vec<RayHit>) {
+    let rb = body.borrow();
+    // Each surface is left by this distance before the next one is searched.
+    let eps: Scalar = na::cast::<f64, Scalar>(1.0e-4) / na::norm(&ray.dir);
+
+    let mut toi    = first.toi;
+    let mut normal = first.normal;
+
+    for _ in range(0, MAX_SURFACES_PER_BODY) {
+        out.push(RayHit {
+            body:      body.clone(),
+            toi:       toi,
+            normal:    normal,
+            sub_shape: rb.sub_shape_hit_by_ray(ray, toi)
+        });
+
+        // Not solid, so that the exit of the surface is found when starting inside.
+        let next = Ray::new(ray.orig + ray.dir * (toi + eps), ray.dir.clone());
+
+        match rb.shape_ref().toi_and_normal_with_transform_and_ray(rb.position(), &next, false) {
+            Some(inter) => {
+                toi    = toi + eps + inter.toi;
+                normal = inter.normal;
+            },
+            None => break
+        }
+    }
+}
+
+// Adds the contact `c` to `out` unless it is discarded by a one-way body.
+fn push_interference(b1:      &RigidBodyHandle,
+                     b2:      &RigidBodyHandle,
+                     c:       &Contact<Scalar, Point, Vect>,
+                     one_way: &HashSet<(uint, uint)>,
+                     out:     &mut Vec<Constraint>) {
+    if is_sensor_pair(b1, b2) {
+        return;
+    }
+
+    if (b1.borrow().one_way_normal().is_some() || b2.borrow().one_way_normal().is_some()) &&
+       !one_way.contains(&pair_key(b1, b2)) {
+        return;
+    }
+
+    let m1 = b1.borrow().margin_at(&c.world1);
+    let m2 = b2.borrow().margin_at(&c.world2);
+
+    let mut c = c.clone();
+    c.depth = c.depth + m1 + m2;
+
+    out.push(Constraint::RBRB(b1.clone(), b2.clone(), c));
+}
+
+fn is_sensor_pair(b1: &RigidBodyHandle, b2: &RigidBodyHandle) -> bool {
+    b1.borrow().is_sensor() || b2.borrow().is_sensor()
+}
+
+// Records that the bodies of a sensor pair overlap if the contact `c` is penetrating.
+fn record_sensor_overlap(b1:    &RigidBodyHandle,
+                         b2:    &RigidBodyHandle,
+                         c:     &Contact<Scalar, Point, Vect>,
+                         keys:  &mut HashSet<(uint, uint)>,
+                         pairs: &mut Vec<(RigidBodyHandle, RigidBodyHandle)>) {
+    let m1 = b1.borrow().margin_at(&c.world1);
+    let m2 = b2.borrow().margin_at(&c.world2);
+
+    // The contacts within the prediction distance are not overlaps yet.
+    if c.depth + m1 + m2 > na::zero() && keys.insert(pair_key(b1, b2)) {
+        if b1.borrow().is_sensor() {
+            pairs.push((b1.clone(), b2.clone()))
+        }
+        else {
+            pairs.push((b2.clone(), b1.clone()))
+        }
+    }
+}
+
+// Tells whether a contact must be kept given the one-way normals of its bodies.
+//
+// A new contact is accepted only if its normal agrees with the one-way normal, if it is not too
+// deep, and if the other body is not moving away through the one-way body. Once accepted, the
+// contact is kept as long as its normal does not oppose the one-way normal. This hysteresis
+// prevents bodies standing at the edge of a platform to fall through it.
+fn accept_one_way_contact(b1:   &RigidBodyHandle,
+                          b2:   &RigidBodyHandle,
+                          c:    &Contact<Scalar, Point, Vect>,
+                          prev: &HashSet<(uint, uint)>,
+                          next: &mut HashSet<(uint, uint)>)
+                          -> bool {
+    let rb1 = b1.borrow();
+    let rb2 = b2.borrow();
+
+    if rb1.one_way_normal().is_none() && rb2.one_way_normal().is_none() {
+        return true;
+    }
+
+    let key      = pair_key(b1, b2);
+    let accepted = prev.contains(&key);
+
+    let accept = |n: Option<Vect>, normal: Vect, rel_vel: Vect| -> bool {
+        match n {
+            Some(n) => {
+                let d = na::dot(&n, &normal);
+
+                if accepted {
+                    d > -na::cast::<f64, Scalar>(ONE_WAY_TOLERANCE)
+                }
+                else {
+                    d > na::cast(ONE_WAY_TOLERANCE) &&
+                    c.depth <= na::cast(ONE_WAY_MAX_DEPTH) &&
+                    na::dot(&n, &rel_vel) <= na::zero()
+                }
+            },
+            None => true
+        }
+    };
+
+    // The contact normal points toward the second body.
+    if accept(rb1.one_way_normal(), c.normal, rb2.lin_vel() - rb1.lin_vel()) &&
+       accept(rb2.one_way_normal(), -c.normal, rb1.lin_vel() - rb2.lin_vel()) {
+        let _ = next.insert(key);
+
+        true
+    }
+    else {
+        false
+    }
+}
+
+// Applies the modifiers of the volumes containing `rb`, after the gravity.
+fn apply_volumes(dt:       Scalar,
+                 volumes:  &[ModifierVolumeHandle],
+                 gravity:  &Option<Box<GravityField + 'static>>,
+                 forces:   &BodyForceGenerator,
+                 handle:   &RigidBodyHandle,
+                 rb:       &mut RigidBody) {
+    let _1: Scalar = na::one();
+    let mut gravity_scale = _1;
+
+    for volume in volumes.iter() {
+        let mut volume = volume.borrow_mut();
+
+        if volume.contains(handle) {
+            match *volume.modifier_mut() {
+                Modifier::GravityScale(s) => gravity_scale = gravity_scale * s,
+                Modifier::Damping(lin, ang) => {
+                    let lv = rb.lin_vel();
+                    let av = rb.ang_vel();
+
+                    rb.set_lin_vel(lv / (_1 + lin * dt));
+                    rb.set_ang_vel(av / (_1 + ang * dt));
+                },
+                Modifier::Force(ref f) => rb.append_lin_force(f.clone()),
+                // Already applied to the timestep of the body.
+                Modifier::TimeScale(_) => { },
+                Modifier::Custom(ref mut generator) => generator.apply(dt.clone(), handle, rb)
+            }
+        }
+    }
+
+    if gravity_scale != _1 {
+        // The other forces have been appended to the gravity: only the scaled part is added.
+        let g = match *gravity {
+            Some(ref field) => field.gravity_at(rb.center_of_mass()),
+            None            => forces.lin_acc()
+        };
+
+        rb.append_lin_force(g * ((gravity_scale - _1) / rb.inv_mass()));
+    }
+}
+
+// The product of the time scales of the volumes containing `handle`.
+fn volume_time_scale(volumes: &[ModifierVolumeHandle], handle: &RigidBodyHandle) -> Scalar {
+    let mut res: Scalar = na::one();
+
+    for volume in volumes.iter() {
+        let volume = volume.borrow();
+
+        match *volume.modifier() {
+            Modifier::TimeScale(s) if volume.contains(handle) => res = res * s,
+            _ => { }
+        }
+    }
+
+    res
+}
+
+fn remove_handle(bodies: &mut Vec<RigidBodyHandle>, body: &RigidBodyHandle) {
+    let key = body.deref() as *const RefCell<RigidBody>;
+
+    bodies.retain(|b| b.deref() as *const RefCell<RigidBody> != key)
+}
+
+// Removes from `pairs` the pairs of body keys containing `key`.
+fn remove_pairs_with(pairs: &mut HashSet<(uint, uint)>, key: uint) {
+    let removed: Vec<(uint, uint)> = pairs.iter()
+                                          .filter(|&&(k1, k2)| k1 == key || k2 == key)
+                                          .map(|k| *k)
+                                          .collect();
+
+    for k in removed.iter() {
+        let _ = pairs.remove(k);
+    }
+}
+
+// Whether `aabb` is finite. The unbounded shapes use infinite or maximal bounds.
+fn is_bounded(aabb: &AABB<Point>) -> bool {
+    let max: Scalar = Float::max_value();
+
+    range(0, na::dim::<Point>()).all(|i| aabb.mins()[i].abs() < max && aabb.maxs()[i].abs() < max)
+}
+
+// Applies the linear and angular damping of `rb`.
+fn apply_damping(dt: Scalar, rb: &mut RigidBody) {
+    let _1: Scalar = na::one();
+    let lin        = rb.linear_damping();
+    let ang        = rb.angular_damping();
+
+    if !na::is_zero(&lin) {
+        let lv = rb.lin_vel();
+        rb.set_lin_vel(lv / (_1 + lin * dt));
+    }
+
+    if !na::is_zero(&ang) {
+        let av = rb.ang_vel();
+        rb.set_ang_vel(av / (_1 + ang * dt));
+    }
+}
+
+// Scales the angular velocity of `rb` down to the norm `max`.
+fn clamp_spin(rb: &mut RigidBody, max: &Scalar) {
+    let av     = rb.ang_vel();
+    let sqnorm = na::sqnorm(&av);
+
+    if sqnorm > *max * *max {
+        rb.set_ang_vel(av * (*max / sqnorm.sqrt()));
+        rb.last_step_flags_mut().set_velocity_clamped();
+    }
+}
+
+// Zeroes the velocities of `rb` smaller than `threshold`.
+fn cancel_drift(rb: &mut RigidBody, threshold: &Scalar) {
+    let sqthreshold = *threshold * *threshold;
+
+    if na::sqnorm(&rb.lin_vel()) < sqthreshold {
+        rb.set_lin_vel(na::zero())
+    }
+
+    if na::sqnorm(&rb.ang_vel()) < sqthreshold {
+        rb.set_ang_vel(na::zero())
+    }
+}
+
+// The minimum number of steps between two residual warnings.
+static RESIDUAL_WARNING_INTERVAL: u64 = 100;
+
+// Names a constraint by the addresses of its bodies or of its joint, for the logs.
+fn describe_constraint(constraint: &Constraint) -> String {
+    match *constraint {
+        Constraint::RBRB(ref b1, ref b2, _) => {
+            format!("contact between the bodies {:x} and {:x}",
+                    b1.deref() as *const RefCell<RigidBody> as uint,
+                    b2.deref() as *const RefCell<RigidBody> as uint)
+        },
+        Constraint::BallInSocket(ref j) =>
+            format!("ball-in-socket joint {:x}", j.deref() as *const RefCell<BallInSocket> as uint),
+        Constraint::Fixed(ref j) =>
+            format!("fixed joint {:x}", j.deref() as *const RefCell<Fixed> as uint),
+        Constraint::Distance(ref j) =>
+            format!("distance joint {:x}", j.deref() as *const RefCell<Distance> as uint),
+        Constraint::Grab(ref j) =>
+            format!("grab joint {:x}", j.deref() as *const RefCell<Grab> as uint)
+    }
 }
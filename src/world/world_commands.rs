@@ -0,0 +1,131 @@
+use std::rc::Rc;
+use std::mem;
+use std::cell::RefCell;
+use object::{RigidBody, RigidBodyHandle};
+use detection::constraint::Constraint;
+use detection::joint::{BallInSocket, Fixed, Distance, Grab};
+use math::{Vect, Orientation};
+
+/// A structural change of the world recorded by `WorldCommands`.
+pub enum WorldCommand {
+    /// Adds the body to the world.
+    AddBody(RigidBodyHandle),
+    /// Removes the body from the world.
+    RemoveBody(RigidBodyHandle),
+    /// Adds the joint to the world. This is never an `RBRB` constraint.
+    AddJoint(Constraint),
+    /// Removes the joint from the world.
+    RemoveJoint(Constraint),
+    /// Changes the linear velocity of the body as if the impulse was applied at its center of
+    /// mass.
+    ApplyImpulse(RigidBodyHandle, Vect),
+    /// Changes the angular velocity of the body as if the angular impulse was applied to it.
+    ApplyAngularImpulse(RigidBodyHandle, Orientation)
+}
+
+/// A buffer of changes of the world applied at the end of the current step.
+///
+/// The world cannot be modified while it is being stepped, e.g., by the contact or proximity
+/// signal handlers. Those can instead keep the buffer returned by `World::commands`, and record
+/// their changes into it. They are applied by the world at the end of the step, after the
+/// velocities and positions have been updated, in the order they were recorded. Thus, the
+/// changes are seen by the events drained after this step, and by the simulation from the next
+/// step on.
+///
+/// Only the creation of the handles is immediate: the bodies and joints added here can be used
+/// and configured right away, but they are not part of the world until the commands are applied.
+/// A body removed here is still simulated until the end of the step. Between two steps, the
+/// methods of `World` can be used directly instead, and `World::apply_commands` applies the
+/// pending commands right away.
+pub struct WorldCommands {
+    commands: Vec<WorldCommand>
+}
+
+impl WorldCommands {
+    /// Creates an empty buffer.
+    pub fn new() -> WorldCommands {
+        WorldCommands {
+            commands: Vec::new()
+        }
+    }
+
+    /// Adds a body to the world, and returns its handle.
+    pub fn add_body(&mut self, rb: RigidBody) -> RigidBodyHandle {
+        let handle = Rc::new(RefCell::new(rb));
+
+        self.commands.push(WorldCommand::AddBody(handle.clone()));
+
+        handle
+    }
+
+    /// Removes a body from the world.
+    ///
+    /// Nothing happens if the body is not part of the world when the commands are applied.
+    pub fn remove_body(&mut self, body: &RigidBodyHandle) {
+        self.commands.push(WorldCommand::RemoveBody(body.clone()))
+    }
+
+    /// Adds a ball-in-socket joint to the world, and returns its handle.
+    pub fn add_ball_in_socket(&mut self, joint: BallInSocket) -> Rc<RefCell<BallInSocket>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.commands.push(WorldCommand::AddJoint(Constraint::BallInSocket(res.clone())));
+
+        res
+    }
+
+    /// Adds a fixed joint to the world, and returns its handle.
+    pub fn add_fixed(&mut self, joint: Fixed) -> Rc<RefCell<Fixed>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.commands.push(WorldCommand::AddJoint(Constraint::Fixed(res.clone())));
+
+        res
+    }
+
+    /// Adds a distance joint to the world, and returns its handle.
+    pub fn add_distance(&mut self, joint: Distance) -> Rc<RefCell<Distance>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.commands.push(WorldCommand::AddJoint(Constraint::Distance(res.clone())));
+
+        res
+    }
+
+    /// Adds a grab joint to the world, and returns its handle.
+    pub fn add_grab(&mut self, joint: Grab) -> Rc<RefCell<Grab>> {
+        let res = Rc::new(RefCell::new(joint));
+
+        self.commands.push(WorldCommand::AddJoint(Constraint::Grab(res.clone())));
+
+        res
+    }
+
+    /// Removes a joint from the world.
+    ///
+    /// `joint` must be one of the joint variants of `Constraint`.
+    pub fn remove_joint(&mut self, joint: &Constraint) {
+        self.commands.push(WorldCommand::RemoveJoint(joint.clone()))
+    }
+
+    /// Applies an impulse at the center of mass of a dynamic body.
+    pub fn apply_impulse(&mut self, body: &RigidBodyHandle, impulse: Vect) {
+        self.commands.push(WorldCommand::ApplyImpulse(body.clone(), impulse))
+    }
+
+    /// Applies an angular impulse to a dynamic body.
+    pub fn apply_angular_impulse(&mut self, body: &RigidBodyHandle, impulse: Orientation) {
+        self.commands.push(WorldCommand::ApplyAngularImpulse(body.clone(), impulse))
+    }
+
+    /// The commands recorded and not applied yet.
+    #[inline]
+    pub fn pending(&self) -> &[WorldCommand] {
+        self.commands.as_slice()
+    }
+
+    /// Removes the pending commands, in the order they were recorded.
+    pub fn take(&mut self) -> Vec<WorldCommand> {
+        mem::replace(&mut self.commands, Vec::new())
+    }
+}